@@ -1,15 +1,783 @@
 use crate::db::{self, AppDb};
-use crate::http::client::{self, HttpConfig, HttpRequest};
+use crate::graphql::{self, schema::Schema, GraphqlError};
+use crate::grpc::call::{self, GrpcTarget};
+use crate::grpc::codec::{self, Status};
+use crate::grpc::{
+    self, reflection, CallHandle, DescriptorSource, GrpcCalls, GrpcDescriptors, GrpcMessage,
+    ServiceInfo,
+};
+use crate::http::body::RequestBody;
+use crate::http::client::{
+    self, Delivery, HeaderEntry, HeaderMode, HttpConfig, HttpRequest, HttpVersionPref,
+    ResponseHeader,
+};
+use crate::http::cookies::CookieJars;
+use crate::http::dns::{self, DnsOverrides};
+use crate::http::download::{Download, DownloadProgress};
+use crate::http::sse::{self, EventStream, SseUpdate};
+use crate::http::tls::TlsSettings;
+use crate::http::websocket::{self, Handshake, Outgoing, WsClose, WsMessage, WsRequest, WsSessions};
+use crate::http::{decode, ClientPool, ClientSource, InFlight};
+use crate::keychain;
+use prost_reflect::DescriptorPool;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
 // ── HTTP ──
 
+#[derive(Debug, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum SendOutcome {
+    Completed(Box<client::HttpResponse>),
+    Cancelled,
+}
+
 #[tauri::command]
 pub async fn send_request(
+    window: tauri::Window,
+    db: tauri::State<'_, AppDb>,
+    inflight: tauri::State<'_, InFlight>,
+    pool: tauri::State<'_, ClientPool>,
+    jars: tauri::State<'_, CookieJars>,
+    request_id: String,
     request: HttpRequest,
     config: Option<HttpConfig>,
-) -> Result<client::HttpResponse, String> {
-    client::execute_request(request, config).await
+    environment_id: Option<String>,
+    save_to: Option<String>,
+    stream_events: Option<bool>,
+) -> Result<SendOutcome, String> {
+    let stream_events = stream_events.unwrap_or(false);
+    let environment_id = environment_id.as_deref().unwrap_or("global");
+    let (jar, tls, dns) = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        (
+            jars.get_or_load(&conn, environment_id)?,
+            TlsSettings::load(&conn)?,
+            DnsOverrides::load(&conn, environment_id)?,
+        )
+    };
+    let mut config = config.unwrap_or_default();
+    let redirect = config.redirect_policy();
+    let retry = config.retry_policy();
+    let reconnect = config.reconnect_policy();
+    let decompress = config.decompress.unwrap_or(true);
+    // An event stream stays open until it is cancelled, and a download may
+    // take as long as it keeps making progress
+    if stream_events {
+        config.timeout_ms = None;
+    } else if save_to.is_some() {
+        config.read_timeout_ms = config.timeout_ms.take();
+    }
+    let clients = ClientSource {
+        pool: pool.inner().clone(),
+        config: config.effective(),
+        jar: Some(jar.clone()),
+        tls,
+        dns,
+    };
+
+    let task = {
+        let mut tasks = inflight.0.lock().map_err(|e| e.to_string())?;
+        if tasks.contains_key(&request_id) {
+            return Err(format!("Request '{}' is already in flight", request_id));
+        }
+        let task = if stream_events {
+            let request_id = request_id.clone();
+            let stream = EventStream::new(Box::new(move |update| {
+                let _ = window.emit(
+                    "sse-update",
+                    SseUpdate {
+                        request_id: request_id.clone(),
+                        update,
+                    },
+                );
+            }));
+            tokio::spawn(sse::run(
+                clients,
+                request,
+                redirect,
+                retry,
+                reconnect,
+                std::sync::Arc::new(stream),
+            ))
+        } else {
+            let delivery = match save_to {
+                Some(path) => {
+                    let request_id = request_id.clone();
+                    Delivery::Download(Download {
+                        path,
+                        on_progress: Box::new(move |downloaded_bytes, total_bytes| {
+                            let _ = window.emit(
+                                "download-progress",
+                                DownloadProgress {
+                                    request_id: request_id.clone(),
+                                    downloaded_bytes,
+                                    total_bytes,
+                                },
+                            );
+                        }),
+                    })
+                }
+                None => Delivery::Buffer,
+            };
+            tokio::spawn(client::execute_request(
+                clients, request, redirect, retry, decompress, delivery,
+            ))
+        };
+        tasks.insert(request_id.clone(), task.abort_handle());
+        task
+    };
+
+    let result = task.await;
+    inflight.0.lock().map_err(|e| e.to_string())?.remove(&request_id);
+
+    // Cancelled sends may still have stored cookies from earlier redirect hops
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        jar.persist(&conn)?;
+    }
+
+    match result {
+        Ok(response) => response.map(|r| SendOutcome::Completed(Box::new(r))),
+        Err(e) if e.is_cancelled() => Ok(SendOutcome::Cancelled),
+        Err(e) => Err(format!("Request task failed: {}", e)),
+    }
+}
+
+#[tauri::command]
+pub fn cancel_request(
+    inflight: tauri::State<'_, InFlight>,
+    request_id: String,
+) -> Result<bool, String> {
+    let handle = inflight.0.lock().map_err(|e| e.to_string())?.remove(&request_id);
+    match handle {
+        Some(handle) => {
+            handle.abort();
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+#[tauri::command]
+pub fn flush_client_pool(pool: tauri::State<'_, ClientPool>) -> Result<(), String> {
+    pool.flush()
+}
+
+// ── WebSocket ──
+
+#[derive(Debug, Serialize)]
+pub struct WsConnected {
+    pub session: db::ws_sessions::WsSession,
+    pub handshake: Handshake,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WsMessageEvent {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub message: WsMessage,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WsClosedEvent {
+    pub session_id: String,
+    #[serde(flatten)]
+    pub close: WsClose,
+}
+
+/// Opens a session, then streams its frames as `ws-message` events until a
+/// final `ws-closed`. Every frame is also stored in the session's transcript.
+#[tauri::command]
+pub async fn ws_connect(
+    window: tauri::Window,
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    jars: tauri::State<'_, CookieJars>,
+    sessions: tauri::State<'_, WsSessions>,
+    request: WsRequest,
+    config: Option<HttpConfig>,
+    environment_id: Option<String>,
+    saved_request_id: Option<String>,
+) -> Result<WsConnected, String> {
+    let environment_id = environment_id.as_deref().unwrap_or("global");
+    let (jar, tls, dns) = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        (
+            jars.get_or_load(&conn, environment_id)?,
+            TlsSettings::load(&conn)?,
+            DnsOverrides::load(&conn, environment_id)?,
+        )
+    };
+    let mut config = config.unwrap_or_default();
+    // The upgrade handshake only exists in HTTP/1.1
+    config.http_version = Some(HttpVersionPref::Http1);
+    let clients = ClientSource {
+        pool: pool.inner().clone(),
+        config: config.effective(),
+        jar: Some(jar.clone()),
+        tls,
+        dns,
+    };
+
+    let connected = websocket::connect(&clients, &request).await;
+    let session = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        jar.persist(&conn)?;
+        db::ws_sessions::create(
+            &conn,
+            &request.url,
+            &request.subprotocols,
+            connected.as_ref().ok().and_then(|(_, h)| h.protocol.as_deref()),
+            saved_request_id.as_deref(),
+            connected.as_ref().err().map(String::as_str),
+        )?
+    };
+    let (stream, handshake) = connected?;
+
+    let (queue, outgoing) = mpsc::unbounded_channel();
+    sessions
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(session.id.clone(), queue.clone());
+
+    let session_id = session.id.clone();
+    tokio::spawn(async move {
+        let db = window.state::<AppDb>();
+        let record = |message: WsMessage| {
+            if let Ok(conn) = db.0.lock() {
+                let _ = db::ws_sessions::add_message(&conn, &session_id, &message);
+            }
+            let _ = window.emit(
+                "ws-message",
+                WsMessageEvent {
+                    session_id: session_id.clone(),
+                    message,
+                },
+            );
+        };
+        let close = websocket::run(stream, queue, outgoing, record).await;
+
+        if let Ok(mut sessions) = window.state::<WsSessions>().0.lock() {
+            sessions.remove(&session_id);
+        }
+        if let Ok(conn) = db.0.lock() {
+            let _ = db::ws_sessions::close(&conn, &session_id, &close);
+        }
+        let _ = window.emit("ws-closed", WsClosedEvent { session_id, close });
+    });
+
+    Ok(WsConnected { session, handshake })
+}
+
+/// Queues a text message, or a binary one when `data` is base64 and `binary` is set.
+#[tauri::command]
+pub fn ws_send(
+    sessions: tauri::State<'_, WsSessions>,
+    session_id: String,
+    data: String,
+    binary: Option<bool>,
+) -> Result<(), String> {
+    let message = if binary.unwrap_or(false) {
+        Outgoing::Binary(decode::base64_decode(&data)?)
+    } else {
+        Outgoing::Text(data)
+    };
+    sessions.send(&session_id, message)
+}
+
+/// Starts the closing handshake; `ws-closed` follows once the server answers.
+#[tauri::command]
+pub fn ws_close(
+    sessions: tauri::State<'_, WsSessions>,
+    session_id: String,
+    code: Option<u16>,
+    reason: Option<String>,
+) -> Result<(), String> {
+    let code = code.unwrap_or(1000);
+    // Other codes are reserved for the protocol itself (RFC 6455 §7.4)
+    if code != 1000 && !(3000..5000).contains(&code) {
+        return Err(format!("Close code {} is reserved; use 1000 or 3000-4999", code));
+    }
+    sessions.send(
+        &session_id,
+        Outgoing::Close(Some(code), reason.unwrap_or_default()),
+    )
+}
+
+#[tauri::command]
+pub fn list_ws_sessions(
+    db: tauri::State<'_, AppDb>,
+    saved_request_id: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<db::ws_sessions::WsSession>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ws_sessions::list(&conn, saved_request_id.as_deref(), limit)
+}
+
+#[tauri::command]
+pub fn get_ws_messages(
+    db: tauri::State<'_, AppDb>,
+    session_id: String,
+) -> Result<Vec<db::ws_sessions::StoredWsMessage>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ws_sessions::get_messages(&conn, &session_id)
+}
+
+#[tauri::command]
+pub fn delete_ws_session(
+    db: tauri::State<'_, AppDb>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ws_sessions::delete(&conn, &id)
+}
+
+// ── GraphQL ──
+
+/// A cached introspection schema, summarised for the frontend.
+#[derive(Debug, Serialize)]
+pub struct GraphqlSchemaInfo {
+    pub endpoint: String,
+    pub fetched_at: String,
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub type_count: usize,
+}
+
+impl GraphqlSchemaInfo {
+    fn new(cached: &db::graphql_schemas::CachedSchema, schema: &Schema) -> Self {
+        GraphqlSchemaInfo {
+            endpoint: cached.endpoint.clone(),
+            fetched_at: cached.fetched_at.clone(),
+            query_type: schema.query_type.clone(),
+            mutation_type: schema.mutation_type.clone(),
+            subscription_type: schema.subscription_type.clone(),
+            type_count: schema.types.len(),
+        }
+    }
+}
+
+fn load_graphql_schema(
+    conn: &rusqlite::Connection,
+    endpoint: &str,
+) -> Result<Option<(db::graphql_schemas::CachedSchema, Schema)>, String> {
+    let Some(cached) = db::graphql_schemas::get(conn, endpoint)? else {
+        return Ok(None);
+    };
+    let value: serde_json::Value =
+        serde_json::from_str(&cached.schema).map_err(|e| e.to_string())?;
+    let schema = Schema::from_introspection(&value)?;
+    Ok(Some((cached, schema)))
+}
+
+/// Runs the introspection query with the request's URL and headers, and
+/// caches the schema under `endpoint`: the URL as written, without any
+/// credentials auth added to it.
+#[tauri::command]
+pub async fn introspect_graphql(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    jars: tauri::State<'_, CookieJars>,
+    endpoint: String,
+    request: HttpRequest,
+    config: Option<HttpConfig>,
+    environment_id: Option<String>,
+) -> Result<GraphqlSchemaInfo, String> {
+    let environment_id = environment_id.as_deref().unwrap_or("global");
+    let (jar, tls, dns) = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        (
+            jars.get_or_load(&conn, environment_id)?,
+            TlsSettings::load(&conn)?,
+            DnsOverrides::load(&conn, environment_id)?,
+        )
+    };
+    let config = config.unwrap_or_default();
+    let redirect = config.redirect_policy();
+    let retry = config.retry_policy();
+    let clients = ClientSource {
+        pool: pool.inner().clone(),
+        config: config.effective(),
+        jar: Some(jar.clone()),
+        tls,
+        dns,
+    };
+
+    let mut request = request;
+    request.method = "POST".to_string();
+    request.headers.push(HeaderEntry {
+        key: "Content-Type".to_string(),
+        value: "application/json".to_string(),
+        enabled: true,
+        mode: Some(HeaderMode::Replace),
+    });
+    request.body = Some(RequestBody::Text {
+        content: serde_json::json!({
+            "query": graphql::INTROSPECTION_QUERY,
+            "operationName": "IntrospectionQuery",
+        })
+        .to_string(),
+    });
+
+    let response =
+        client::execute_request(clients, request, redirect, retry, true, Delivery::Buffer).await;
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        jar.persist(&conn)?;
+    }
+    let response = response?;
+
+    let body: serde_json::Value = match response.body_encoding {
+        decode::BodyEncoding::Text => serde_json::from_str(&response.body).ok(),
+        decode::BodyEncoding::Base64 => None,
+    }
+    .ok_or_else(|| {
+        format!(
+            "Introspection returned {} {} without a JSON body",
+            response.status, response.status_text
+        )
+    })?;
+    let introspected = body.get("data").and_then(|d| d.get("__schema"));
+    let Some(introspected) = introspected.filter(|s| !s.is_null()) else {
+        let message = body
+            .get("errors")
+            .and_then(|e| e.get(0))
+            .and_then(|e| e.get("message"))
+            .and_then(|m| m.as_str())
+            .unwrap_or("the response has no data.__schema");
+        return Err(format!("Introspection failed: {}", message));
+    };
+    let schema = Schema::from_introspection(introspected)?;
+
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let cached = db::graphql_schemas::upsert(&conn, &endpoint, &introspected.to_string())?;
+    Ok(GraphqlSchemaInfo::new(&cached, &schema))
+}
+
+#[tauri::command]
+pub fn get_graphql_schema(
+    db: tauri::State<'_, AppDb>,
+    endpoint: String,
+) -> Result<Option<GraphqlSchemaInfo>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    Ok(load_graphql_schema(&conn, &endpoint)?
+        .map(|(cached, schema)| GraphqlSchemaInfo::new(&cached, &schema)))
+}
+
+#[tauri::command]
+pub fn delete_graphql_schema(
+    db: tauri::State<'_, AppDb>,
+    endpoint: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::graphql_schemas::delete(&conn, &endpoint)
+}
+
+#[derive(Debug, Serialize)]
+pub struct GraphqlValidation {
+    /// Whether a schema was cached for the endpoint; without one only syntax is checked.
+    pub schema_cached: bool,
+    pub errors: Vec<GraphqlError>,
+}
+
+#[tauri::command]
+pub fn validate_graphql(
+    db: tauri::State<'_, AppDb>,
+    endpoint: String,
+    query: String,
+    operation_name: Option<String>,
+) -> Result<GraphqlValidation, String> {
+    let schema = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        load_graphql_schema(&conn, &endpoint)?.map(|(_, schema)| schema)
+    };
+    Ok(GraphqlValidation {
+        schema_cached: schema.is_some(),
+        errors: graphql::check(&query, operation_name.as_deref(), schema.as_ref()),
+    })
+}
+
+// ── gRPC ──
+
+/// A saved request's call: where to, which method, and the JSON message.
+#[derive(Debug, Deserialize)]
+pub struct GrpcRequest {
+    #[serde(flatten)]
+    pub target: GrpcTarget,
+    pub source: DescriptorSource,
+    pub service: String,
+    pub method: String,
+    /// The single request message of unary and server-streaming calls.
+    #[serde(default)]
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GrpcHeadersEvent {
+    pub call_id: String,
+    pub headers: Vec<ResponseHeader>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GrpcMessageEvent {
+    pub call_id: String,
+    #[serde(flatten)]
+    pub message: GrpcMessage,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GrpcClosedEvent {
+    pub call_id: String,
+    pub status: Status,
+    pub trailers: Vec<ResponseHeader>,
+    pub time_ms: u64,
+}
+
+/// Clients for calls, which always use HTTP/2. The request timeout becomes
+/// the call's deadline rather than a client timeout, which would also cut
+/// long-lived streams short.
+fn grpc_clients(
+    db: &AppDb,
+    pool: &ClientPool,
+    jars: &CookieJars,
+    config: Option<HttpConfig>,
+    environment_id: Option<String>,
+) -> Result<(ClientSource, Option<Duration>), String> {
+    let environment_id = environment_id.as_deref().unwrap_or("global");
+    let (jar, tls, dns) = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        (
+            jars.get_or_load(&conn, environment_id)?,
+            TlsSettings::load(&conn)?,
+            DnsOverrides::load(&conn, environment_id)?,
+        )
+    };
+    let mut config = config.unwrap_or_default();
+    config.http_version = Some(HttpVersionPref::Http2);
+    let deadline = config.timeout_ms.take().map(Duration::from_millis);
+    let clients = ClientSource {
+        pool: pool.clone(),
+        config: config.effective(),
+        jar: Some(jar),
+        tls,
+        dns,
+    };
+    Ok((clients, deadline))
+}
+
+async fn grpc_pool(
+    descriptors: &GrpcDescriptors,
+    clients: &ClientSource,
+    target: &GrpcTarget,
+    source: &DescriptorSource,
+    deadline: Option<Duration>,
+    refresh: bool,
+) -> Result<DescriptorPool, String> {
+    let key = source.cache_key(&target.url);
+    if !refresh {
+        if let Some(pool) = descriptors.get(&key) {
+            return Ok(pool);
+        }
+    }
+    let pool = match source {
+        DescriptorSource::Reflection => {
+            grpc::build_pool(reflection::fetch(clients, target, deadline).await?)?
+        }
+        DescriptorSource::ProtoFiles {
+            files,
+            import_paths,
+        } => grpc::load_files(files, import_paths)?,
+    };
+    descriptors.insert(key, pool.clone());
+    Ok(pool)
+}
+
+/// Lists the services the source defines, loading it unless already cached
+/// or `refresh` is set.
+#[tauri::command]
+pub async fn grpc_describe(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    jars: tauri::State<'_, CookieJars>,
+    descriptors: tauri::State<'_, GrpcDescriptors>,
+    request: GrpcTarget,
+    source: DescriptorSource,
+    config: Option<HttpConfig>,
+    environment_id: Option<String>,
+    refresh: Option<bool>,
+) -> Result<Vec<ServiceInfo>, String> {
+    let (clients, deadline) = grpc_clients(&db, &pool, &jars, config, environment_id)?;
+    let descriptor_pool = grpc_pool(
+        &descriptors,
+        &clients,
+        &request,
+        &source,
+        deadline,
+        refresh.unwrap_or(false),
+    )
+    .await?;
+    if let Some(jar) = clients.jar {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        jar.persist(&conn)?;
+    }
+    Ok(grpc::describe(&descriptor_pool))
+}
+
+/// Starts a call under the caller-supplied `call_id`, then streams it as
+/// `grpc-headers`, `grpc-message` (both directions) and a final
+/// `grpc-closed`. Unary and server-streaming calls send `message` and
+/// half-close at once; streaming requests are fed with `grpc_send`.
+#[tauri::command]
+pub async fn grpc_start(
+    window: tauri::Window,
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    jars: tauri::State<'_, CookieJars>,
+    descriptors: tauri::State<'_, GrpcDescriptors>,
+    calls: tauri::State<'_, GrpcCalls>,
+    call_id: String,
+    request: GrpcRequest,
+    config: Option<HttpConfig>,
+    environment_id: Option<String>,
+) -> Result<(), String> {
+    let (clients, deadline) = grpc_clients(&db, &pool, &jars, config, environment_id)?;
+    let target = request.target;
+    let descriptor_pool =
+        grpc_pool(&descriptors, &clients, &target, &request.source, deadline, false).await?;
+    let method = grpc::find_method(&descriptor_pool, &request.service, &request.method)?;
+    let url = target.method_url(&request.service, &request.method)?;
+
+    let (queue, outgoing) = mpsc::unbounded_channel();
+    let mut handle = CallHandle {
+        input: method.input(),
+        queue: Some(queue),
+        cancel: CancellationToken::new(),
+        started: Instant::now(),
+    };
+    let first = if method.is_client_streaming() {
+        None
+    } else {
+        let sent = handle.send(&request.message)?;
+        handle.queue = None;
+        Some(sent)
+    };
+    let cancel = handle.cancel.clone();
+    let started = handle.started;
+    calls
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .insert(call_id.clone(), handle);
+
+    tokio::spawn(async move {
+        let emit_message = |message: GrpcMessage| {
+            let _ = window.emit(
+                "grpc-message",
+                GrpcMessageEvent {
+                    call_id: call_id.clone(),
+                    message,
+                },
+            );
+        };
+        if let Some(message) = first {
+            emit_message(message);
+        }
+
+        let output = method.output();
+        let run = async {
+            let mut call = match call::open(&clients, &target, url, deadline, outgoing).await {
+                Ok(call) => call,
+                Err(status) => return (status, Vec::new()),
+            };
+            let headers = call
+                .headers
+                .iter()
+                .map(|(name, value)| ResponseHeader::new(name, value))
+                .collect();
+            let _ = window.emit(
+                "grpc-headers",
+                GrpcHeadersEvent {
+                    call_id: call_id.clone(),
+                    headers,
+                },
+            );
+            loop {
+                match call.message().await {
+                    Ok(Some(bytes)) => match grpc::decode_json(&output, &bytes) {
+                        Ok(data) => emit_message(GrpcMessage {
+                            direction: "received",
+                            data,
+                            size_bytes: bytes.len(),
+                            at_ms: started.elapsed().as_millis() as u64,
+                        }),
+                        Err(e) => return (Status::new(codec::INTERNAL, e), call.trailers()),
+                    },
+                    Ok(None) => return (call.status(), call.trailers()),
+                    Err(status) => return (status, call.trailers()),
+                }
+            }
+        };
+        let expired = async {
+            match deadline {
+                Some(deadline) => tokio::time::sleep(deadline).await,
+                None => std::future::pending().await,
+            }
+        };
+        // Dropping the call resets its stream, which tells the server too
+        let (status, trailers) = tokio::select! {
+            outcome = run => outcome,
+            _ = cancel.cancelled() => (Status::new(codec::CANCELLED, "Cancelled"), Vec::new()),
+            _ = expired => (Status::new(codec::DEADLINE_EXCEEDED, "Deadline exceeded"), Vec::new()),
+        };
+
+        if let Ok(mut calls) = window.state::<GrpcCalls>().0.lock() {
+            calls.remove(&call_id);
+        }
+        if let (Some(jar), Ok(conn)) = (clients.jar.as_ref(), window.state::<AppDb>().0.lock()) {
+            let _ = jar.persist(&conn);
+        }
+        let _ = window.emit(
+            "grpc-closed",
+            GrpcClosedEvent {
+                call_id,
+                status,
+                trailers,
+                time_ms: started.elapsed().as_millis() as u64,
+            },
+        );
+    });
+    Ok(())
+}
+
+/// Queues a JSON message on a streaming request.
+#[tauri::command]
+pub fn grpc_send(
+    window: tauri::Window,
+    calls: tauri::State<'_, GrpcCalls>,
+    call_id: String,
+    message: String,
+) -> Result<(), String> {
+    let message = calls.send(&call_id, &message)?;
+    let _ = window.emit("grpc-message", GrpcMessageEvent { call_id, message });
+    Ok(())
+}
+
+/// Half-closes the request stream; responses keep arriving until the server ends the call.
+#[tauri::command]
+pub fn grpc_close_send(calls: tauri::State<'_, GrpcCalls>, call_id: String) -> Result<(), String> {
+    calls.close_send(&call_id)
+}
+
+/// Ends the call at once; `grpc-closed` follows with `CANCELLED`.
+#[tauri::command]
+pub fn grpc_cancel(calls: tauri::State<'_, GrpcCalls>, call_id: String) -> Result<(), String> {
+    calls.cancel(&call_id)
 }
 
 // ── Workspace ──
@@ -173,6 +941,9 @@ pub fn update_environment(
     id: String,
     data: db::environments::UpdateEnvironment,
 ) -> Result<(), String> {
+    if let Some(ref dns_overrides) = data.dns_overrides {
+        dns::parse_overrides(dns_overrides)?;
+    }
     let conn = db.0.lock().map_err(|e| e.to_string())?;
     db::environments::update(&conn, &id, &data)
 }
@@ -180,10 +951,231 @@ pub fn update_environment(
 #[tauri::command]
 pub fn delete_environment(
     db: tauri::State<'_, AppDb>,
+    jars: tauri::State<'_, CookieJars>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::environments::delete(&conn, &id)?;
+    jars.remove(&id)
+}
+
+// ── Cookies ──
+
+#[tauri::command]
+pub fn list_cookies(
+    db: tauri::State<'_, AppDb>,
+    environment_id: String,
+    domain: Option<String>,
+) -> Result<Vec<db::cookies::Cookie>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::cookies::get_by_environment(&conn, &environment_id, domain.as_deref())
+}
+
+#[tauri::command]
+pub fn create_cookie(
+    db: tauri::State<'_, AppDb>,
+    jars: tauri::State<'_, CookieJars>,
+    data: db::cookies::CreateCookie,
+) -> Result<db::cookies::Cookie, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let cookie = db::cookies::create(&conn, &data)?;
+    jars.reload(&conn, &cookie.environment_id)?;
+    Ok(cookie)
+}
+
+#[tauri::command]
+pub fn update_cookie(
+    db: tauri::State<'_, AppDb>,
+    jars: tauri::State<'_, CookieJars>,
+    id: String,
+    data: db::cookies::UpdateCookie,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::cookies::update(&conn, &id, &data)?;
+    let cookie = db::cookies::get_by_id(&conn, &id)?;
+    jars.reload(&conn, &cookie.environment_id)
+}
+
+#[tauri::command]
+pub fn delete_cookie(
+    db: tauri::State<'_, AppDb>,
+    jars: tauri::State<'_, CookieJars>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let cookie = db::cookies::get_by_id(&conn, &id)?;
+    db::cookies::delete(&conn, &id)?;
+    jars.reload(&conn, &cookie.environment_id)
+}
+
+#[tauri::command]
+pub fn clear_cookies(
+    db: tauri::State<'_, AppDb>,
+    jars: tauri::State<'_, CookieJars>,
+    environment_id: String,
+    domain: Option<String>,
+) -> Result<u64, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let deleted = db::cookies::clear(&conn, &environment_id, domain.as_deref())?;
+    jars.reload(&conn, &environment_id)?;
+    Ok(deleted)
+}
+
+// ── Client Certificates ──
+
+#[tauri::command]
+pub fn list_client_certificates(
+    db: tauri::State<'_, AppDb>,
+) -> Result<Vec<db::client_certificates::ClientCertificate>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::client_certificates::get_all(&conn)
+}
+
+#[tauri::command]
+pub fn create_client_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    data: db::client_certificates::CreateClientCertificate,
+) -> Result<db::client_certificates::ClientCertificate, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let certificate = db::client_certificates::create(&conn, &data)?;
+    pool.flush()?;
+    Ok(certificate)
+}
+
+#[tauri::command]
+pub fn update_client_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    id: String,
+    data: db::client_certificates::UpdateClientCertificate,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::client_certificates::update(&conn, &id, &data)?;
+    pool.flush()
+}
+
+#[tauri::command]
+pub fn delete_client_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::client_certificates::delete(&conn, &id)?;
+    pool.flush()
+}
+
+/// Loads a certificate without saving it, so the UI can report bad files early.
+#[tauri::command]
+pub fn test_client_certificate(
+    data: db::client_certificates::CreateClientCertificate,
+) -> Result<(), String> {
+    let certificate = db::client_certificates::ClientCertificate {
+        id: String::new(),
+        host_pattern: data.host_pattern,
+        kind: data.kind,
+        cert_path: data.cert_path,
+        key_path: data.key_path,
+        passphrase: data.passphrase,
+        enabled: true,
+        created_at: String::new(),
+    };
+    crate::http::tls::load_identity(&certificate).map(|_| ())
+}
+
+#[tauri::command]
+pub fn list_ca_certificates(
+    db: tauri::State<'_, AppDb>,
+) -> Result<Vec<db::ca_certificates::CaCertificate>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ca_certificates::get_all(&conn)
+}
+
+#[tauri::command]
+pub fn create_ca_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    data: db::ca_certificates::CreateCaCertificate,
+) -> Result<db::ca_certificates::CaCertificate, String> {
+    // Reject unreadable files now rather than on the next send
+    crate::http::tls::load_ca_certificates(&db::ca_certificates::CaCertificate {
+        id: String::new(),
+        host_pattern: None,
+        path: data.path.clone(),
+        enabled: true,
+        created_at: String::new(),
+    })?;
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let ca = db::ca_certificates::create(&conn, &data)?;
+    pool.flush()?;
+    Ok(ca)
+}
+
+#[tauri::command]
+pub fn update_ca_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    id: String,
+    data: db::ca_certificates::UpdateCaCertificate,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ca_certificates::update(&conn, &id, &data)?;
+    pool.flush()
+}
+
+#[tauri::command]
+pub fn delete_ca_certificate(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::ca_certificates::delete(&conn, &id)?;
+    pool.flush()
+}
+
+#[tauri::command]
+pub fn list_certificate_pins(
+    db: tauri::State<'_, AppDb>,
+) -> Result<Vec<db::certificate_pins::CertificatePin>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::certificate_pins::get_all(&conn)
+}
+
+#[tauri::command]
+pub fn create_certificate_pin(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    data: db::certificate_pins::CreateCertificatePin,
+) -> Result<db::certificate_pins::CertificatePin, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let pin = db::certificate_pins::create(&conn, &data)?;
+    pool.flush()?;
+    Ok(pin)
+}
+
+#[tauri::command]
+pub fn update_certificate_pin(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
     id: String,
+    data: db::certificate_pins::UpdateCertificatePin,
 ) -> Result<(), String> {
     let conn = db.0.lock().map_err(|e| e.to_string())?;
-    db::environments::delete(&conn, &id)
+    db::certificate_pins::update(&conn, &id, &data)?;
+    pool.flush()
+}
+
+#[tauri::command]
+pub fn delete_certificate_pin(
+    db: tauri::State<'_, AppDb>,
+    pool: tauri::State<'_, ClientPool>,
+    id: String,
+) -> Result<(), String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::certificate_pins::delete(&conn, &id)?;
+    pool.flush()
 }
 
 // ── Settings ──
@@ -207,6 +1199,51 @@ pub fn set_setting(
     db::settings::set(&conn, &key, &value)
 }
 
+/// What the `proxy_password` setting holds once the password itself is in
+/// the system keychain.
+const PROXY_PASSWORD_IN_KEYCHAIN: &str = "keychain";
+
+/// The proxy password, from the system keychain. One that an older version
+/// saved in the settings table is moved to the keychain first.
+#[tauri::command]
+pub async fn get_proxy_password(db: tauri::State<'_, AppDb>) -> Result<String, String> {
+    let stored = {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        db::settings::get(&conn, "proxy_password")?
+    };
+    match stored.as_deref() {
+        None | Some("") => Ok(String::new()),
+        Some(PROXY_PASSWORD_IN_KEYCHAIN) => {
+            Ok(keychain::get("proxy_password").await?.unwrap_or_default())
+        }
+        Some(password) => {
+            // Left where it is if there is no keychain to move it to
+            if keychain::set("proxy_password", password.to_string()).await.is_ok() {
+                let conn = db.0.lock().map_err(|e| e.to_string())?;
+                db::settings::set(&conn, "proxy_password", PROXY_PASSWORD_IN_KEYCHAIN)?;
+            }
+            Ok(password.to_string())
+        }
+    }
+}
+
+/// Saves the proxy password to the system keychain, leaving only a marker
+/// in the settings table.
+#[tauri::command]
+pub async fn set_proxy_password(
+    db: tauri::State<'_, AppDb>,
+    password: String,
+) -> Result<(), String> {
+    let marker = if password.is_empty() {
+        ""
+    } else {
+        PROXY_PASSWORD_IN_KEYCHAIN
+    };
+    keychain::set("proxy_password", password).await?;
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    db::settings::set(&conn, "proxy_password", marker)
+}
+
 // ── Reorder ──
 
 #[derive(Debug, Deserialize)]
@@ -278,7 +1315,8 @@ pub fn delete_history_entry(
 #[tauri::command]
 pub fn clear_history(db: tauri::State<'_, AppDb>) -> Result<(), String> {
     let conn = db.0.lock().map_err(|e| e.to_string())?;
-    db::history::clear(&conn)
+    db::history::clear(&conn)?;
+    db::ws_sessions::clear(&conn)
 }
 
 #[tauri::command]
@@ -287,7 +1325,8 @@ pub fn cleanup_old_history(
     retention_days: i32,
 ) -> Result<u64, String> {
     let conn = db.0.lock().map_err(|e| e.to_string())?;
-    db::history::cleanup_old(&conn, retention_days)
+    let deleted = db::history::cleanup_old(&conn, retention_days)?;
+    Ok(deleted + db::ws_sessions::cleanup_old(&conn, retention_days)?)
 }
 
 // ── Sync ──
@@ -385,6 +1424,8 @@ pub enum UpsertFromCloud {
         auth: String,
         variables: String,
         sort_order: i32,
+        #[serde(flatten)]
+        columns: Box<db::requests::KindColumns>,
     },
 }
 
@@ -412,10 +1453,10 @@ pub fn upsert_from_cloud(
             Ok(f.id)
         }
         UpsertFromCloud::Request {
-            cloud_id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order,
+            cloud_id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order, columns,
         } => {
             let r = db::requests::upsert_from_cloud(
-                &conn, &cloud_id, &collection_id, folder_id.as_deref(), &name, &method, &url, &headers, &params, &body, &auth, &variables, sort_order,
+                &conn, &cloud_id, &collection_id, folder_id.as_deref(), &name, &method, &url, &headers, &params, &body, &auth, &variables, sort_order, &columns,
             )?;
             Ok(r.id)
         }
@@ -497,3 +1538,12 @@ pub async fn write_file(path: String, content: String) -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to write file: {}", e))
 }
+
+/// Writes base64-encoded bytes, e.g. a binary response body.
+#[tauri::command]
+pub async fn write_binary_file(path: String, content: String) -> Result<(), String> {
+    let bytes = crate::http::decode::base64_decode(&content)?;
+    tokio::fs::write(&path, bytes)
+        .await
+        .map_err(|e| format!("Failed to write file: {}", e))
+}
@@ -0,0 +1,98 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct CaCertificate {
+    pub id: String,
+    /// `None` trusts the CA for every host; otherwise a pattern as for
+    /// client certificates.
+    pub host_pattern: Option<String>,
+    /// PEM bundle (one or more certificates) or a single DER certificate.
+    pub path: String,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+fn row_to_ca(row: &rusqlite::Row) -> rusqlite::Result<CaCertificate> {
+    Ok(CaCertificate {
+        id: row.get(0)?,
+        host_pattern: row.get(1)?,
+        path: row.get(2)?,
+        enabled: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, host_pattern, path, enabled, created_at";
+
+fn normalize_pattern(pattern: &Option<String>) -> Option<String> {
+    pattern
+        .as_ref()
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+}
+
+pub fn get_all(conn: &Connection) -> Result<Vec<CaCertificate>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ca_certificates ORDER BY host_pattern, created_at",
+            SELECT_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| row_to_ca(row))
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<CaCertificate, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM ca_certificates WHERE id = ?1", SELECT_COLS),
+        params![id],
+        |row| row_to_ca(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCaCertificate {
+    pub host_pattern: Option<String>,
+    pub path: String,
+}
+
+pub fn create(conn: &Connection, data: &CreateCaCertificate) -> Result<CaCertificate, String> {
+    let id = Uuid::new_v4().to_string();
+
+    conn.execute(
+        "INSERT INTO ca_certificates (id, host_pattern, path) VALUES (?1, ?2, ?3)",
+        params![id, normalize_pattern(&data.host_pattern), data.path],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_by_id(conn, &id)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCaCertificate {
+    pub enabled: Option<bool>,
+}
+
+pub fn update(conn: &Connection, id: &str, data: &UpdateCaCertificate) -> Result<(), String> {
+    if let Some(enabled) = data.enabled {
+        conn.execute(
+            "UPDATE ca_certificates SET enabled = ?1 WHERE id = ?2",
+            params![enabled, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM ca_certificates WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct CertificatePin {
+    pub id: String,
+    pub host_pattern: String,
+    /// Base64 SHA-256 of the server's SubjectPublicKeyInfo, as printed by
+    /// `openssl x509 -pubkey | openssl pkey -pubin -outform der | openssl dgst -sha256 -binary | base64`.
+    pub spki_sha256: String,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+fn row_to_pin(row: &rusqlite::Row) -> rusqlite::Result<CertificatePin> {
+    Ok(CertificatePin {
+        id: row.get(0)?,
+        host_pattern: row.get(1)?,
+        spki_sha256: row.get(2)?,
+        enabled: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, host_pattern, spki_sha256, enabled, created_at";
+
+/// Accepts the hash with or without curl's `sha256//` / HPKP's `sha256/` prefix.
+fn normalize_hash(hash: &str) -> Result<String, String> {
+    let hash = hash.trim();
+    let hash = hash
+        .strip_prefix("sha256//")
+        .or_else(|| hash.strip_prefix("sha256/"))
+        .unwrap_or(hash);
+    let bytes = crate::http::decode::base64_decode(hash)
+        .map_err(|_| format!("Pin '{}' is not a base64 SHA-256 hash", hash))?;
+    if bytes.len() != 32 {
+        return Err(format!(
+            "Pin '{}' is {} bytes; a SHA-256 hash is 32",
+            hash,
+            bytes.len()
+        ));
+    }
+    Ok(hash.to_string())
+}
+
+pub fn get_all(conn: &Connection) -> Result<Vec<CertificatePin>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM certificate_pins ORDER BY host_pattern, created_at",
+            SELECT_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| row_to_pin(row))
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<CertificatePin, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM certificate_pins WHERE id = ?1", SELECT_COLS),
+        params![id],
+        |row| row_to_pin(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCertificatePin {
+    pub host_pattern: String,
+    pub spki_sha256: String,
+}
+
+pub fn create(conn: &Connection, data: &CreateCertificatePin) -> Result<CertificatePin, String> {
+    let host_pattern = data.host_pattern.trim().to_lowercase();
+    if host_pattern.is_empty() {
+        return Err("A pin needs a host pattern".to_string());
+    }
+    let hash = normalize_hash(&data.spki_sha256)?;
+    let id = Uuid::new_v4().to_string();
+
+    conn.execute(
+        "INSERT INTO certificate_pins (id, host_pattern, spki_sha256) VALUES (?1, ?2, ?3)",
+        params![id, host_pattern, hash],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_by_id(conn, &id)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCertificatePin {
+    pub enabled: Option<bool>,
+}
+
+pub fn update(conn: &Connection, id: &str, data: &UpdateCertificatePin) -> Result<(), String> {
+    if let Some(enabled) = data.enabled {
+        conn.execute(
+            "UPDATE certificate_pins SET enabled = ?1 WHERE id = ?2",
+            params![enabled, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM certificate_pins WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
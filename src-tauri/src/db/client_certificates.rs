@@ -0,0 +1,161 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ClientCertificate {
+    pub id: String,
+    /// `api.example.com`, `*.example.com` or either with a `:port`.
+    pub host_pattern: String,
+    /// `pem` (certificate + PKCS#8 key files) or `pkcs12` (single bundle).
+    pub kind: String,
+    pub cert_path: String,
+    pub key_path: Option<String>,
+    pub passphrase: Option<String>,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+fn row_to_cert(row: &rusqlite::Row) -> rusqlite::Result<ClientCertificate> {
+    Ok(ClientCertificate {
+        id: row.get(0)?,
+        host_pattern: row.get(1)?,
+        kind: row.get(2)?,
+        cert_path: row.get(3)?,
+        key_path: row.get(4)?,
+        passphrase: row.get(5)?,
+        enabled: row.get(6)?,
+        created_at: row.get(7)?,
+    })
+}
+
+const SELECT_COLS: &str =
+    "id, host_pattern, kind, cert_path, key_path, passphrase, enabled, created_at";
+
+pub fn get_all(conn: &Connection) -> Result<Vec<ClientCertificate>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM client_certificates ORDER BY host_pattern, created_at",
+            SELECT_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map([], |row| row_to_cert(row))
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<ClientCertificate, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM client_certificates WHERE id = ?1", SELECT_COLS),
+        params![id],
+        |row| row_to_cert(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn validate_kind(kind: &str) -> Result<(), String> {
+    match kind {
+        "pem" | "pkcs12" => Ok(()),
+        other => Err(format!(
+            "Unknown certificate kind '{}' (expected 'pem' or 'pkcs12')",
+            other
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateClientCertificate {
+    pub host_pattern: String,
+    pub kind: String,
+    pub cert_path: String,
+    pub key_path: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+pub fn create(
+    conn: &Connection,
+    data: &CreateClientCertificate,
+) -> Result<ClientCertificate, String> {
+    validate_kind(&data.kind)?;
+    let id = Uuid::new_v4().to_string();
+
+    conn.execute(
+        "INSERT INTO client_certificates (id, host_pattern, kind, cert_path, key_path, passphrase) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            id,
+            data.host_pattern.trim().to_lowercase(),
+            data.kind,
+            data.cert_path,
+            data.key_path,
+            data.passphrase,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_by_id(conn, &id)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateClientCertificate {
+    pub host_pattern: Option<String>,
+    pub kind: Option<String>,
+    pub cert_path: Option<String>,
+    pub key_path: Option<String>,
+    pub passphrase: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+pub fn update(conn: &Connection, id: &str, data: &UpdateClientCertificate) -> Result<(), String> {
+    if let Some(ref kind) = data.kind {
+        validate_kind(kind)?;
+    }
+
+    let mut sets: Vec<String> = vec![];
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
+
+    macro_rules! add_field {
+        ($field:expr, $col:literal) => {
+            if let Some(ref val) = $field {
+                sets.push(format!("{} = ?{}", $col, values.len() + 1));
+                values.push(Box::new(val.clone()));
+            }
+        };
+    }
+
+    let host_pattern = data.host_pattern.as_ref().map(|p| p.trim().to_lowercase());
+    add_field!(host_pattern, "host_pattern");
+    add_field!(data.kind, "kind");
+    add_field!(data.cert_path, "cert_path");
+    add_field!(data.key_path, "key_path");
+    add_field!(data.passphrase, "passphrase");
+    add_field!(data.enabled, "enabled");
+
+    if sets.is_empty() {
+        return Ok(());
+    }
+
+    let sql = format!(
+        "UPDATE client_certificates SET {} WHERE id = ?{}",
+        sets.join(", "),
+        values.len() + 1
+    );
+    values.push(Box::new(id.to_string()));
+
+    conn.execute(
+        &sql,
+        rusqlite::params_from_iter(values.iter().map(|v| v.as_ref())),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM client_certificates WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
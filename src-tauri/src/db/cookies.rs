@@ -0,0 +1,250 @@
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Cookie {
+    pub id: String,
+    pub environment_id: String,
+    pub domain: String,
+    pub path: String,
+    pub name: String,
+    pub value: String,
+    /// Unix seconds; `None` for session cookies.
+    pub expires_at: Option<i64>,
+    pub secure: bool,
+    pub http_only: bool,
+    /// Set without a `Domain` attribute, so only sent to exactly `domain`.
+    pub host_only: bool,
+    pub same_site: Option<String>,
+    pub created_at: String,
+}
+
+fn row_to_cookie(row: &rusqlite::Row) -> rusqlite::Result<Cookie> {
+    Ok(Cookie {
+        id: row.get(0)?,
+        environment_id: row.get(1)?,
+        domain: row.get(2)?,
+        path: row.get(3)?,
+        name: row.get(4)?,
+        value: row.get(5)?,
+        expires_at: row.get(6)?,
+        secure: row.get(7)?,
+        http_only: row.get(8)?,
+        host_only: row.get(9)?,
+        same_site: row.get(10)?,
+        created_at: row.get(11)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, environment_id, domain, path, name, value, expires_at, \
+    secure, http_only, host_only, same_site, created_at";
+
+/// Cookies for an environment, optionally limited to a domain and its subdomains.
+pub fn get_by_environment(
+    conn: &Connection,
+    environment_id: &str,
+    domain: Option<&str>,
+) -> Result<Vec<Cookie>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM cookies WHERE environment_id = ?1 \
+             AND (?2 IS NULL OR domain = ?2 OR domain LIKE '%.' || ?2) \
+             ORDER BY domain, path, name",
+            SELECT_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![environment_id, domain], |row| row_to_cookie(row))
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+}
+
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<Cookie, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM cookies WHERE id = ?1", SELECT_COLS),
+        params![id],
+        |row| row_to_cookie(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateCookie {
+    pub environment_id: String,
+    pub domain: String,
+    pub path: Option<String>,
+    pub name: String,
+    pub value: String,
+    pub expires_at: Option<i64>,
+    #[serde(default)]
+    pub secure: bool,
+    #[serde(default)]
+    pub http_only: bool,
+    #[serde(default)]
+    pub host_only: bool,
+    pub same_site: Option<String>,
+}
+
+pub fn create(conn: &Connection, data: &CreateCookie) -> Result<Cookie, String> {
+    let id = Uuid::new_v4().to_string();
+    let domain = data.domain.trim_start_matches('.').to_lowercase();
+    let path = data.path.clone().unwrap_or_else(|| "/".to_string());
+
+    conn.execute(
+        "INSERT INTO cookies (id, environment_id, domain, path, name, value, expires_at, \
+         secure, http_only, host_only, same_site) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11) \
+         ON CONFLICT(environment_id, domain, path, name) DO UPDATE SET \
+         value = excluded.value, expires_at = excluded.expires_at, secure = excluded.secure, \
+         http_only = excluded.http_only, host_only = excluded.host_only, \
+         same_site = excluded.same_site",
+        params![
+            id,
+            data.environment_id,
+            domain,
+            path,
+            data.name,
+            data.value,
+            data.expires_at,
+            data.secure,
+            data.http_only,
+            data.host_only,
+            data.same_site,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM cookies WHERE environment_id = ?1 AND domain = ?2 AND path = ?3 AND name = ?4",
+            SELECT_COLS
+        ),
+        params![data.environment_id, domain, path, data.name],
+        |row| row_to_cookie(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCookie {
+    pub value: Option<String>,
+    pub path: Option<String>,
+    /// `Some(None)` turns the cookie into a session cookie.
+    #[serde(default, with = "double_option")]
+    pub expires_at: Option<Option<i64>>,
+    pub secure: Option<bool>,
+    pub http_only: Option<bool>,
+    pub same_site: Option<String>,
+}
+
+mod double_option {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Option<i64>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<i64>::deserialize(deserializer).map(Some)
+    }
+}
+
+pub fn update(conn: &Connection, id: &str, data: &UpdateCookie) -> Result<(), String> {
+    let mut sets: Vec<String> = vec![];
+    let mut values: Vec<Box<dyn rusqlite::types::ToSql>> = vec![];
+
+    macro_rules! add_field {
+        ($field:expr, $col:literal) => {
+            if let Some(ref val) = $field {
+                sets.push(format!("{} = ?{}", $col, values.len() + 1));
+                values.push(Box::new(val.clone()));
+            }
+        };
+    }
+
+    add_field!(data.value, "value");
+    add_field!(data.path, "path");
+    add_field!(data.expires_at, "expires_at");
+    add_field!(data.secure, "secure");
+    add_field!(data.http_only, "http_only");
+    add_field!(data.same_site, "same_site");
+
+    if sets.is_empty() {
+        return Ok(());
+    }
+
+    let sql = format!(
+        "UPDATE cookies SET {} WHERE id = ?{}",
+        sets.join(", "),
+        values.len() + 1
+    );
+    values.push(Box::new(id.to_string()));
+
+    conn.execute(
+        &sql,
+        rusqlite::params_from_iter(values.iter().map(|v| v.as_ref())),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM cookies WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Clears an environment's cookies, optionally only for a domain and its subdomains.
+pub fn clear(conn: &Connection, environment_id: &str, domain: Option<&str>) -> Result<u64, String> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM cookies WHERE environment_id = ?1 \
+             AND (?2 IS NULL OR domain = ?2 OR domain LIKE '%.' || ?2)",
+            params![environment_id, domain],
+        )
+        .map_err(|e| e.to_string())?;
+    Ok(deleted as u64)
+}
+
+/// Overwrites an environment's stored cookies with the in-memory jar contents.
+pub fn replace_for_environment(
+    conn: &Connection,
+    environment_id: &str,
+    cookies: &[Cookie],
+) -> Result<(), String> {
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+
+    tx.execute(
+        "DELETE FROM cookies WHERE environment_id = ?1",
+        params![environment_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for c in cookies {
+        tx.execute(
+            "INSERT INTO cookies (id, environment_id, domain, path, name, value, expires_at, \
+             secure, http_only, host_only, same_site, created_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+            params![
+                c.id,
+                environment_id,
+                c.domain,
+                c.path,
+                c.name,
+                c.value,
+                c.expires_at,
+                c.secure,
+                c.http_only,
+                c.host_only,
+                c.same_site,
+                c.created_at,
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    tx.commit().map_err(|e| e.to_string())
+}
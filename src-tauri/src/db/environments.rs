@@ -7,6 +7,8 @@ pub struct Environment {
     pub id: String,
     pub name: String,
     pub variables: String,
+    /// JSON `[{host, address}]`, applied through `http::dns`.
+    pub dns_overrides: String,
     pub is_global: bool,
     pub sort_order: i32,
     pub updated_at: String,
@@ -22,11 +24,12 @@ fn row_to_env(row: &rusqlite::Row) -> rusqlite::Result<Environment> {
         sort_order: row.get(4)?,
         updated_at: row.get(5)?,
         created_at: row.get(6)?,
+        dns_overrides: row.get(7)?,
     })
 }
 
 const SELECT_COLS: &str =
-    "id, name, variables, is_global, sort_order, updated_at, created_at";
+    "id, name, variables, is_global, sort_order, updated_at, created_at, dns_overrides";
 
 pub fn get_all(conn: &Connection) -> Result<Vec<Environment>, String> {
     let mut stmt = conn
@@ -75,6 +78,7 @@ pub fn create(conn: &Connection, name: &str) -> Result<Environment, String> {
 pub struct UpdateEnvironment {
     pub name: Option<String>,
     pub variables: Option<String>,
+    pub dns_overrides: Option<String>,
 }
 
 pub fn update(conn: &Connection, id: &str, data: &UpdateEnvironment) -> Result<(), String> {
@@ -89,6 +93,10 @@ pub fn update(conn: &Connection, id: &str, data: &UpdateEnvironment) -> Result<(
         sets.push(format!("variables = ?{}", values.len() + 1));
         values.push(Box::new(variables.clone()));
     }
+    if let Some(ref dns_overrides) = data.dns_overrides {
+        sets.push(format!("dns_overrides = ?{}", values.len() + 1));
+        values.push(Box::new(dns_overrides.clone()));
+    }
 
     let sql = format!(
         "UPDATE environments SET {} WHERE id = ?{}",
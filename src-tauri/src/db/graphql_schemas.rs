@@ -0,0 +1,50 @@
+use rusqlite::{params, Connection};
+
+/// An introspection result cached for one endpoint.
+pub struct CachedSchema {
+    pub endpoint: String,
+    /// The introspection response's `__schema` object, as JSON.
+    pub schema: String,
+    pub fetched_at: String,
+}
+
+pub fn get(conn: &Connection, endpoint: &str) -> Result<Option<CachedSchema>, String> {
+    let result = conn.query_row(
+        "SELECT endpoint, schema, fetched_at FROM graphql_schemas WHERE endpoint = ?1",
+        params![endpoint],
+        |row| {
+            Ok(CachedSchema {
+                endpoint: row.get(0)?,
+                schema: row.get(1)?,
+                fetched_at: row.get(2)?,
+            })
+        },
+    );
+
+    match result {
+        Ok(schema) => Ok(Some(schema)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Stores a freshly fetched schema, replacing any older one for the endpoint.
+pub fn upsert(conn: &Connection, endpoint: &str, schema: &str) -> Result<CachedSchema, String> {
+    conn.execute(
+        "INSERT INTO graphql_schemas (endpoint, schema) VALUES (?1, ?2)
+         ON CONFLICT(endpoint) DO UPDATE SET schema = excluded.schema, fetched_at = datetime('now')",
+        params![endpoint, schema],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get(conn, endpoint)?.ok_or_else(|| "Schema was not stored".to_string())
+}
+
+pub fn delete(conn: &Connection, endpoint: &str) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM graphql_schemas WHERE endpoint = ?1",
+        params![endpoint],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
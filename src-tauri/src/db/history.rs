@@ -1,3 +1,4 @@
+use base64::Engine;
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -19,8 +20,42 @@ pub struct HistoryEntry {
     pub response_body: Option<String>,
     pub response_time_ms: Option<i64>,
     pub response_size_bytes: Option<i64>,
+    /// Size before decompression; equal to `response_size_bytes` for uncompressed bodies.
+    pub response_wire_size_bytes: Option<i64>,
     pub response_body_truncated: bool,
     pub error: Option<String>,
+    pub cancelled: bool,
+    pub timing_dns_ms: Option<f64>,
+    pub timing_connect_ms: Option<f64>,
+    pub timing_tls_ms: Option<f64>,
+    pub timing_ttfb_ms: Option<f64>,
+    pub timing_download_ms: Option<f64>,
+    /// `text`, or `base64` when `response_body` holds the raw bytes encoded.
+    pub response_body_encoding: String,
+    /// Base64 of the original bytes of a text body that was transcoded from
+    /// another charset or had invalid bytes replaced.
+    pub response_body_raw: Option<String>,
+    pub response_mime_type: Option<String>,
+    pub response_charset: Option<String>,
+    pub response_url: Option<String>,
+    /// JSON array of the redirect hops followed before the response.
+    pub redirect_chain: Option<String>,
+    pub http_version: Option<String>,
+    pub remote_addr: Option<String>,
+    /// JSON summary of the server's leaf certificate.
+    pub peer_certificate: Option<String>,
+    pub tls_version: Option<String>,
+    /// Whether the TLS session was resumed, so no chain was presented.
+    pub tls_resumed: bool,
+    /// JSON summaries of the rest of the chain, nearest issuer first.
+    pub certificate_chain: Option<String>,
+    /// The certificate's `not_after`, kept in its own column so expiring
+    /// certificates can be searched for.
+    pub certificate_expires_at: Option<String>,
+    /// JSON array of the failed attempts retried before the response.
+    pub retry_attempts: Option<String>,
+    /// JSON array of the events received from an event stream response.
+    pub sse_events: Option<String>,
     pub saved_request_id: Option<String>,
     pub timestamp: String,
 }
@@ -40,6 +75,48 @@ pub struct CreateHistoryEntry {
     pub response_time_ms: Option<i64>,
     pub response_size_bytes: Option<i64>,
     pub error: Option<String>,
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(default)]
+    pub timing_dns_ms: Option<f64>,
+    #[serde(default)]
+    pub timing_connect_ms: Option<f64>,
+    #[serde(default)]
+    pub timing_tls_ms: Option<f64>,
+    #[serde(default)]
+    pub timing_ttfb_ms: Option<f64>,
+    #[serde(default)]
+    pub timing_download_ms: Option<f64>,
+    #[serde(default)]
+    pub response_body_encoding: Option<String>,
+    #[serde(default)]
+    pub response_body_raw: Option<String>,
+    #[serde(default)]
+    pub response_mime_type: Option<String>,
+    #[serde(default)]
+    pub response_charset: Option<String>,
+    #[serde(default)]
+    pub response_url: Option<String>,
+    #[serde(default)]
+    pub redirect_chain: Option<String>,
+    #[serde(default)]
+    pub http_version: Option<String>,
+    #[serde(default)]
+    pub remote_addr: Option<String>,
+    #[serde(default)]
+    pub peer_certificate: Option<String>,
+    #[serde(default)]
+    pub tls_version: Option<String>,
+    #[serde(default)]
+    pub tls_resumed: bool,
+    #[serde(default)]
+    pub certificate_chain: Option<String>,
+    #[serde(default)]
+    pub retry_attempts: Option<String>,
+    #[serde(default)]
+    pub response_wire_size_bytes: Option<i64>,
+    #[serde(default)]
+    pub sse_events: Option<String>,
     pub saved_request_id: Option<String>,
 }
 
@@ -51,12 +128,25 @@ pub struct HistorySearchParams {
     pub status_max: Option<i32>,
     pub from_date: Option<String>,
     pub to_date: Option<String>,
+    /// Only entries whose server certificate expires before this RFC 3339 time.
+    #[serde(default)]
+    pub certificate_expires_before: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
 fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
     let truncated: i32 = row.get(13)?;
+    let encoding: String = row.get(22)?;
+    let raw = row
+        .get::<_, Option<Vec<u8>>>(23)?
+        .map(|bytes| BASE64.encode(bytes));
+    // Binary bodies live only in the blob column
+    let (response_body, response_body_raw) = if encoding == "base64" {
+        (raw, None)
+    } else {
+        (row.get(10)?, raw)
+    };
     Ok(HistoryEntry {
         id: row.get(0)?,
         method: row.get(1)?,
@@ -68,37 +158,109 @@ fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
         response_status: row.get(7)?,
         response_status_text: row.get(8)?,
         response_headers: row.get(9)?,
-        response_body: row.get(10)?,
+        response_body,
         response_time_ms: row.get(11)?,
         response_size_bytes: row.get(12)?,
         response_body_truncated: truncated != 0,
         error: row.get(14)?,
         saved_request_id: row.get(15)?,
         timestamp: row.get(16)?,
+        cancelled: row.get::<_, i32>(17)? != 0,
+        timing_dns_ms: row.get(18)?,
+        timing_connect_ms: row.get(19)?,
+        timing_ttfb_ms: row.get(20)?,
+        timing_download_ms: row.get(21)?,
+        response_body_encoding: encoding,
+        response_body_raw,
+        response_mime_type: row.get(24)?,
+        response_charset: row.get(25)?,
+        response_url: row.get(26)?,
+        redirect_chain: row.get(27)?,
+        http_version: row.get(28)?,
+        remote_addr: row.get(29)?,
+        peer_certificate: row.get(30)?,
+        certificate_expires_at: row.get(31)?,
+        retry_attempts: row.get(32)?,
+        response_wire_size_bytes: row.get(33)?,
+        sse_events: row.get(34)?,
+        tls_version: row.get(35)?,
+        tls_resumed: row.get::<_, i32>(36)? != 0,
+        certificate_chain: row.get(37)?,
+        timing_tls_ms: row.get(38)?,
     })
 }
 
 const SELECT_COLS: &str = "id, method, url, headers, params, body, auth, \
     response_status, response_status_text, response_headers, response_body, \
     response_time_ms, response_size_bytes, response_body_truncated, \
-    error, saved_request_id, timestamp";
+    error, saved_request_id, timestamp, cancelled, \
+    timing_dns_ms, timing_connect_ms, timing_ttfb_ms, timing_download_ms, \
+    response_body_encoding, response_body_raw, response_mime_type, response_charset, \
+    response_url, redirect_chain, http_version, remote_addr, peer_certificate, \
+    certificate_expires_at, retry_attempts, response_wire_size_bytes, sse_events, \
+    tls_version, tls_resumed, certificate_chain, timing_tls_ms";
+
+const BASE64: base64::engine::GeneralPurpose = base64::engine::general_purpose::STANDARD;
 
 pub fn create(conn: &Connection, data: &CreateHistoryEntry) -> Result<HistoryEntry, String> {
     let id = Uuid::new_v4().to_string();
 
-    let (response_body, truncated) = match &data.response_body {
+    let encoding = data.response_body_encoding.as_deref().unwrap_or("text");
+    let is_base64 = encoding == "base64";
+
+    let raw_source = if is_base64 {
+        data.response_body.as_deref()
+    } else {
+        data.response_body_raw.as_deref()
+    };
+    let mut raw = raw_source
+        .map(|b| BASE64.decode(b))
+        .transpose()
+        .map_err(|e| format!("Invalid base64 response body: {}", e))?;
+    let raw_truncated = match raw.as_mut() {
+        Some(bytes) if bytes.len() > MAX_BODY_BYTES => {
+            bytes.truncate(MAX_BODY_BYTES);
+            true
+        }
+        _ => false,
+    };
+
+    let (response_body, text_truncated) = match &data.response_body {
+        _ if is_base64 => (None, false),
         Some(body) if body.len() > MAX_BODY_BYTES => {
-            (Some(body[..MAX_BODY_BYTES].to_string()), 1i32)
+            let end = (0..=MAX_BODY_BYTES)
+                .rev()
+                .find(|&i| body.is_char_boundary(i))
+                .unwrap_or(0);
+            (Some(body[..end].to_string()), true)
         }
-        other => (other.clone(), 0i32),
+        other => (other.clone(), false),
+    };
+    let (sse_events, events_truncated) = match data.sse_events.as_deref() {
+        Some(events) => cap_events(events)?,
+        None => (None, false),
     };
+    let truncated = (raw_truncated || text_truncated || events_truncated) as i32;
+
+    let certificate_expires_at = data
+        .peer_certificate
+        .as_deref()
+        .and_then(|c| serde_json::from_str::<serde_json::Value>(c).ok())
+        .and_then(|c| c.get("not_after")?.as_str().map(String::from));
 
     conn.execute(
         "INSERT INTO history (id, method, url, headers, params, body, auth, \
          response_status, response_status_text, response_headers, response_body, \
          response_time_ms, response_size_bytes, response_body_truncated, \
-         error, saved_request_id) \
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+         error, saved_request_id, cancelled, \
+         timing_dns_ms, timing_connect_ms, timing_ttfb_ms, timing_download_ms, \
+         response_body_encoding, response_body_raw, response_mime_type, response_charset, \
+         response_url, redirect_chain, http_version, remote_addr, peer_certificate, \
+         certificate_expires_at, retry_attempts, response_wire_size_bytes, sse_events, \
+         tls_version, tls_resumed, certificate_chain, timing_tls_ms) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, \
+         ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30, ?31, ?32, ?33, ?34, \
+         ?35, ?36, ?37, ?38)",
         params![
             id,
             data.method,
@@ -116,6 +278,28 @@ pub fn create(conn: &Connection, data: &CreateHistoryEntry) -> Result<HistoryEnt
             truncated,
             data.error,
             data.saved_request_id,
+            data.cancelled as i32,
+            data.timing_dns_ms,
+            data.timing_connect_ms,
+            data.timing_ttfb_ms,
+            data.timing_download_ms,
+            encoding,
+            raw,
+            data.response_mime_type,
+            data.response_charset,
+            data.response_url,
+            data.redirect_chain,
+            data.http_version,
+            data.remote_addr,
+            data.peer_certificate,
+            certificate_expires_at,
+            data.retry_attempts,
+            data.response_wire_size_bytes,
+            sse_events,
+            data.tls_version,
+            data.tls_resumed as i32,
+            data.certificate_chain,
+            data.timing_tls_ms,
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -123,6 +307,26 @@ pub fn create(conn: &Connection, data: &CreateHistoryEntry) -> Result<HistoryEnt
     get_by_id(conn, &id)
 }
 
+/// Keeps the newest events of a JSON event log that fit in `MAX_BODY_BYTES`.
+fn cap_events(events: &str) -> Result<(Option<String>, bool), String> {
+    if events.len() <= MAX_BODY_BYTES {
+        return Ok((Some(events.to_string()), false));
+    }
+    let events: Vec<serde_json::Value> =
+        serde_json::from_str(events).map_err(|e| format!("Invalid event log: {}", e))?;
+    let mut size = 2;
+    let keep = events
+        .iter()
+        .rev()
+        .take_while(|event| {
+            size += event.to_string().len() + 1;
+            size <= MAX_BODY_BYTES
+        })
+        .count();
+    let kept = serde_json::to_string(&events[events.len() - keep..]).map_err(|e| e.to_string())?;
+    Ok((Some(kept), true))
+}
+
 pub fn get_by_id(conn: &Connection, id: &str) -> Result<HistoryEntry, String> {
     conn.query_row(
         &format!("SELECT {} FROM history WHERE id = ?1", SELECT_COLS),
@@ -166,6 +370,11 @@ pub fn search(conn: &Connection, params_filter: &HistorySearchParams) -> Result<
         conditions.push(format!("timestamp <= ?{}", values.len()));
     }
 
+    if let Some(ref before) = params_filter.certificate_expires_before {
+        values.push(Box::new(before.clone()));
+        conditions.push(format!("certificate_expires_at < ?{}", values.len()));
+    }
+
     let where_clause = if conditions.is_empty() {
         String::new()
     } else {
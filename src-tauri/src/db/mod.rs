@@ -1,10 +1,16 @@
+pub mod ca_certificates;
+pub mod certificate_pins;
+pub mod client_certificates;
 pub mod collections;
+pub mod cookies;
 pub mod environments;
 pub mod folders;
+pub mod graphql_schemas;
 pub mod history;
 pub mod requests;
 pub mod settings;
 pub mod sync;
+pub mod ws_sessions;
 
 use rusqlite::Connection;
 use std::path::Path;
@@ -64,6 +70,86 @@ fn run_migrations(conn: &Connection) -> Result<(), String> {
         migrate_v3(conn)?;
     }
 
+    if current < 5 {
+        migrate_v4(conn)?;
+    }
+
+    if current < 6 {
+        migrate_v5(conn)?;
+    }
+
+    if current < 7 {
+        migrate_v6(conn)?;
+    }
+
+    if current < 8 {
+        migrate_v7(conn)?;
+    }
+
+    if current < 9 {
+        migrate_v8(conn)?;
+    }
+
+    if current < 10 {
+        migrate_v9(conn)?;
+    }
+
+    if current < 11 {
+        migrate_v10(conn)?;
+    }
+
+    if current < 12 {
+        migrate_v11(conn)?;
+    }
+
+    if current < 13 {
+        migrate_v12(conn)?;
+    }
+
+    if current < 14 {
+        migrate_v13(conn)?;
+    }
+
+    if current < 15 {
+        migrate_v14(conn)?;
+    }
+
+    if current < 16 {
+        migrate_v15(conn)?;
+    }
+
+    if current < 17 {
+        migrate_v16(conn)?;
+    }
+
+    if current < 18 {
+        migrate_v17(conn)?;
+    }
+
+    if current < 19 {
+        migrate_v18(conn)?;
+    }
+
+    if current < 20 {
+        migrate_v19(conn)?;
+    }
+
+    if current < 21 {
+        migrate_v20(conn)?;
+    }
+
+    if current < 22 {
+        migrate_v21(conn)?;
+    }
+
+    if current < 23 {
+        migrate_v22(conn)?;
+    }
+
+    if current < 24 {
+        migrate_v23(conn)?;
+    }
+
     Ok(())
 }
 
@@ -247,3 +333,467 @@ fn migrate_v3(conn: &Connection) -> Result<(), String> {
 
     Ok(())
 }
+
+/// v4: record cancelled sends in history
+fn migrate_v4(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN cancelled INTEGER NOT NULL DEFAULT 0;
+
+        INSERT INTO schema_version (version) VALUES (5);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v4 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v5: per-phase response timings on history
+fn migrate_v5(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN timing_dns_ms REAL;
+        ALTER TABLE history ADD COLUMN timing_connect_ms REAL;
+        ALTER TABLE history ADD COLUMN timing_ttfb_ms REAL;
+        ALTER TABLE history ADD COLUMN timing_download_ms REAL;
+
+        INSERT INTO schema_version (version) VALUES (6);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v5 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v6: response headers stored as an ordered [{name, value}] list instead of an object
+fn migrate_v6(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        UPDATE history
+        SET response_headers = (
+            SELECT json_group_array(json_object('name', key, 'value', value))
+            FROM json_each(history.response_headers)
+        )
+        WHERE response_headers IS NOT NULL
+          AND json_valid(response_headers)
+          AND json_type(response_headers) = 'object';
+
+        INSERT INTO schema_version (version) VALUES (7);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v6 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v7: persistent cookie jar, scoped per environment
+fn migrate_v7(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        CREATE TABLE IF NOT EXISTS cookies (
+            id TEXT PRIMARY KEY,
+            environment_id TEXT NOT NULL REFERENCES environments(id) ON DELETE CASCADE,
+            domain TEXT NOT NULL,
+            path TEXT NOT NULL DEFAULT '/',
+            name TEXT NOT NULL,
+            value TEXT NOT NULL,
+            expires_at INTEGER,
+            secure INTEGER NOT NULL DEFAULT 0,
+            http_only INTEGER NOT NULL DEFAULT 0,
+            host_only INTEGER NOT NULL DEFAULT 0,
+            same_site TEXT,
+            created_at TEXT NOT NULL DEFAULT (datetime('now')),
+            UNIQUE (environment_id, domain, path, name)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_cookies_env_domain ON cookies(environment_id, domain);
+
+        INSERT INTO schema_version (version) VALUES (8);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v7 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v8: binary-safe response bodies in history
+fn migrate_v8(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN response_body_encoding TEXT NOT NULL DEFAULT 'text';
+        ALTER TABLE history ADD COLUMN response_body_raw BLOB;
+        ALTER TABLE history ADD COLUMN response_mime_type TEXT;
+        ALTER TABLE history ADD COLUMN response_charset TEXT;
+
+        INSERT INTO schema_version (version) VALUES (9);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v8 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v9: final URL and redirect chain in history
+fn migrate_v9(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN response_url TEXT;
+        ALTER TABLE history ADD COLUMN redirect_chain TEXT;
+
+        INSERT INTO schema_version (version) VALUES (10);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v9 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v10: per-host client certificates for mutual TLS
+fn migrate_v10(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        CREATE TABLE IF NOT EXISTS client_certificates (
+            id TEXT PRIMARY KEY,
+            host_pattern TEXT NOT NULL,
+            kind TEXT NOT NULL DEFAULT 'pem',
+            cert_path TEXT NOT NULL,
+            key_path TEXT,
+            passphrase TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        INSERT INTO schema_version (version) VALUES (11);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v10 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v11: trusted CA certificates and server key pins
+fn migrate_v11(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        CREATE TABLE IF NOT EXISTS ca_certificates (
+            id TEXT PRIMARY KEY,
+            host_pattern TEXT,
+            path TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        CREATE TABLE IF NOT EXISTS certificate_pins (
+            id TEXT PRIMARY KEY,
+            host_pattern TEXT NOT NULL,
+            spki_sha256 TEXT NOT NULL,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        INSERT INTO schema_version (version) VALUES (12);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v11 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v12: connection details and server certificate in history
+fn migrate_v12(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN http_version TEXT;
+        ALTER TABLE history ADD COLUMN remote_addr TEXT;
+        ALTER TABLE history ADD COLUMN peer_certificate TEXT;
+        ALTER TABLE history ADD COLUMN certificate_expires_at TEXT;
+
+        CREATE INDEX IF NOT EXISTS idx_history_cert_expiry ON history(certificate_expires_at);
+
+        INSERT INTO schema_version (version) VALUES (13);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v12 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v13: per-environment DNS overrides
+fn migrate_v13(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE environments ADD COLUMN dns_overrides TEXT NOT NULL DEFAULT '[]';
+
+        INSERT INTO schema_version (version) VALUES (14);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v13 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v14: retried attempts per history entry
+fn migrate_v14(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN retry_attempts TEXT;
+
+        INSERT INTO schema_version (version) VALUES (15);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v14 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v15: on-the-wire response size alongside the decoded one
+fn migrate_v15(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN response_wire_size_bytes INTEGER;
+
+        INSERT INTO schema_version (version) VALUES (16);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v15 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v16: request kinds, WebSocket subprotocols, and WebSocket session transcripts
+fn migrate_v16(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE requests ADD COLUMN kind TEXT NOT NULL DEFAULT 'http';
+        ALTER TABLE requests ADD COLUMN subprotocols TEXT NOT NULL DEFAULT '[]';
+
+        CREATE TABLE IF NOT EXISTS ws_sessions (
+            id TEXT PRIMARY KEY,
+            url TEXT NOT NULL,
+            subprotocols TEXT NOT NULL DEFAULT '[]',
+            protocol TEXT,
+            saved_request_id TEXT,
+            error TEXT,
+            close_code INTEGER,
+            close_reason TEXT,
+            opened_at TEXT NOT NULL DEFAULT (datetime('now')),
+            closed_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ws_sessions_opened_at ON ws_sessions(opened_at DESC);
+        CREATE INDEX IF NOT EXISTS idx_ws_sessions_saved_request ON ws_sessions(saved_request_id);
+
+        CREATE TABLE IF NOT EXISTS ws_messages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES ws_sessions(id) ON DELETE CASCADE,
+            direction TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            data TEXT NOT NULL,
+            size_bytes INTEGER NOT NULL,
+            truncated INTEGER NOT NULL DEFAULT 0,
+            at_ms INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_ws_messages_session ON ws_messages(session_id, id);
+
+        INSERT INTO schema_version (version) VALUES (17);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v16 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v17: event log of server-sent event responses
+fn migrate_v17(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN sse_events TEXT;
+
+        INSERT INTO schema_version (version) VALUES (18);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v17 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v18: structured GraphQL requests and cached introspection schemas
+fn migrate_v18(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE requests ADD COLUMN graphql_query TEXT NOT NULL DEFAULT '';
+        ALTER TABLE requests ADD COLUMN graphql_variables TEXT NOT NULL DEFAULT '';
+        ALTER TABLE requests ADD COLUMN graphql_operation_name TEXT NOT NULL DEFAULT '';
+
+        CREATE TABLE IF NOT EXISTS graphql_schemas (
+            endpoint TEXT PRIMARY KEY,
+            schema TEXT NOT NULL,
+            fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+        );
+
+        INSERT INTO schema_version (version) VALUES (19);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v18 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v19: gRPC method, message and descriptor source on requests
+fn migrate_v19(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE requests ADD COLUMN grpc_service TEXT NOT NULL DEFAULT '';
+        ALTER TABLE requests ADD COLUMN grpc_method TEXT NOT NULL DEFAULT '';
+        ALTER TABLE requests ADD COLUMN grpc_message TEXT NOT NULL DEFAULT '';
+        ALTER TABLE requests ADD COLUMN grpc_descriptor_source TEXT NOT NULL DEFAULT 'reflection';
+        ALTER TABLE requests ADD COLUMN grpc_proto_files TEXT NOT NULL DEFAULT '[]';
+        ALTER TABLE requests ADD COLUMN grpc_import_paths TEXT NOT NULL DEFAULT '[]';
+
+        INSERT INTO schema_version (version) VALUES (20);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v19 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v20: JSON-RPC calls on requests
+fn migrate_v20(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE requests ADD COLUMN jsonrpc_calls TEXT NOT NULL DEFAULT '[]';
+
+        INSERT INTO schema_version (version) VALUES (21);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v20 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v21: negotiated TLS version, cipher suite and certificate chain in history
+fn migrate_v21(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN tls_version TEXT;
+        ALTER TABLE history ADD COLUMN cipher_suite TEXT;
+        ALTER TABLE history ADD COLUMN certificate_chain TEXT;
+
+        INSERT INTO schema_version (version) VALUES (22);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v21 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v22: TLS handshake time, split out of the connect time
+fn migrate_v22(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history ADD COLUMN timing_tls_ms REAL;
+
+        INSERT INTO schema_version (version) VALUES (23);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v22 failed: {}", e))?;
+
+    Ok(())
+}
+
+/// v23: resumed TLS sessions in history, replacing the cipher suite
+fn migrate_v23(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "
+        BEGIN;
+
+        ALTER TABLE history DROP COLUMN cipher_suite;
+        ALTER TABLE history ADD COLUMN tls_resumed INTEGER NOT NULL DEFAULT 0;
+
+        INSERT INTO schema_version (version) VALUES (24);
+
+        COMMIT;
+        ",
+    )
+    .map_err(|e| format!("Migration v23 failed: {}", e))?;
+
+    Ok(())
+}
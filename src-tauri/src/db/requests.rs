@@ -21,6 +21,26 @@ pub struct SavedRequest {
     pub cloud_id: Option<String>,
     pub synced_at: Option<String>,
     pub dirty: i32,
+    /// `http`, `sse`, `websocket`, `graphql`, `grpc` or `jsonrpc`.
+    pub kind: String,
+    /// JSON array of WebSocket subprotocols to offer.
+    pub subprotocols: String,
+    pub graphql_query: String,
+    /// GraphQL variables as JSON text, which may reference environment variables.
+    pub graphql_variables: String,
+    pub graphql_operation_name: String,
+    /// Fully qualified, e.g. `package.Service`.
+    pub grpc_service: String,
+    pub grpc_method: String,
+    /// The request message as JSON text, which may reference environment variables.
+    pub grpc_message: String,
+    /// `reflection` or `proto_files`.
+    pub grpc_descriptor_source: String,
+    /// JSON arrays of paths.
+    pub grpc_proto_files: String,
+    pub grpc_import_paths: String,
+    /// JSON array of the calls a JSON-RPC request makes; several are sent as a batch.
+    pub jsonrpc_calls: String,
 }
 
 fn row_to_request(row: &rusqlite::Row) -> rusqlite::Result<SavedRequest> {
@@ -42,11 +62,23 @@ fn row_to_request(row: &rusqlite::Row) -> rusqlite::Result<SavedRequest> {
         cloud_id: row.get(14)?,
         synced_at: row.get(15)?,
         dirty: row.get::<_, Option<i32>>(16)?.unwrap_or(0),
+        kind: row.get(17)?,
+        subprotocols: row.get(18)?,
+        graphql_query: row.get(19)?,
+        graphql_variables: row.get(20)?,
+        graphql_operation_name: row.get(21)?,
+        grpc_service: row.get(22)?,
+        grpc_method: row.get(23)?,
+        grpc_message: row.get(24)?,
+        grpc_descriptor_source: row.get(25)?,
+        grpc_proto_files: row.get(26)?,
+        grpc_import_paths: row.get(27)?,
+        jsonrpc_calls: row.get(28)?,
     })
 }
 
 const SELECT_COLS: &str =
-    "id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order, updated_at, created_at, cloud_id, synced_at, dirty";
+    "id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order, updated_at, created_at, cloud_id, synced_at, dirty, kind, subprotocols, graphql_query, graphql_variables, graphql_operation_name, grpc_service, grpc_method, grpc_message, grpc_descriptor_source, grpc_proto_files, grpc_import_paths, jsonrpc_calls";
 
 pub fn get_all(conn: &Connection) -> Result<Vec<SavedRequest>, String> {
     let mut stmt = conn
@@ -74,6 +106,30 @@ pub struct CreateRequest {
     pub params: String,
     pub body: String,
     pub auth: String,
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub subprotocols: Option<String>,
+    #[serde(default)]
+    pub graphql_query: Option<String>,
+    #[serde(default)]
+    pub graphql_variables: Option<String>,
+    #[serde(default)]
+    pub graphql_operation_name: Option<String>,
+    #[serde(default)]
+    pub grpc_service: Option<String>,
+    #[serde(default)]
+    pub grpc_method: Option<String>,
+    #[serde(default)]
+    pub grpc_message: Option<String>,
+    #[serde(default)]
+    pub grpc_descriptor_source: Option<String>,
+    #[serde(default)]
+    pub grpc_proto_files: Option<String>,
+    #[serde(default)]
+    pub grpc_import_paths: Option<String>,
+    #[serde(default)]
+    pub jsonrpc_calls: Option<String>,
 }
 
 pub fn create(conn: &Connection, data: &CreateRequest) -> Result<SavedRequest, String> {
@@ -87,8 +143,8 @@ pub fn create(conn: &Connection, data: &CreateRequest) -> Result<SavedRequest, S
         .map_err(|e| e.to_string())?;
 
     conn.execute(
-        "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, sort_order)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, sort_order, kind, subprotocols, graphql_query, graphql_variables, graphql_operation_name, grpc_service, grpc_method, grpc_message, grpc_descriptor_source, grpc_proto_files, grpc_import_paths, jsonrpc_calls)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, COALESCE(?12, 'http'), COALESCE(?13, '[]'), COALESCE(?14, ''), COALESCE(?15, ''), COALESCE(?16, ''), COALESCE(?17, ''), COALESCE(?18, ''), COALESCE(?19, ''), COALESCE(?20, 'reflection'), COALESCE(?21, '[]'), COALESCE(?22, '[]'), COALESCE(?23, '[]'))",
         params![
             id,
             data.collection_id,
@@ -100,7 +156,19 @@ pub fn create(conn: &Connection, data: &CreateRequest) -> Result<SavedRequest, S
             data.params,
             data.body,
             data.auth,
-            max_order + 1
+            max_order + 1,
+            data.kind,
+            data.subprotocols,
+            data.graphql_query,
+            data.graphql_variables,
+            data.graphql_operation_name,
+            data.grpc_service,
+            data.grpc_method,
+            data.grpc_message,
+            data.grpc_descriptor_source,
+            data.grpc_proto_files,
+            data.grpc_import_paths,
+            data.jsonrpc_calls
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -127,6 +195,18 @@ pub struct UpdateRequest {
     pub body: Option<String>,
     pub auth: Option<String>,
     pub variables: Option<String>,
+    pub kind: Option<String>,
+    pub subprotocols: Option<String>,
+    pub graphql_query: Option<String>,
+    pub graphql_variables: Option<String>,
+    pub graphql_operation_name: Option<String>,
+    pub grpc_service: Option<String>,
+    pub grpc_method: Option<String>,
+    pub grpc_message: Option<String>,
+    pub grpc_descriptor_source: Option<String>,
+    pub grpc_proto_files: Option<String>,
+    pub grpc_import_paths: Option<String>,
+    pub jsonrpc_calls: Option<String>,
 }
 
 pub fn update(conn: &Connection, id: &str, data: &UpdateRequest) -> Result<(), String> {
@@ -150,6 +230,18 @@ pub fn update(conn: &Connection, id: &str, data: &UpdateRequest) -> Result<(), S
     add_field!(data.body, "body");
     add_field!(data.auth, "auth");
     add_field!(data.variables, "variables");
+    add_field!(data.kind, "kind");
+    add_field!(data.subprotocols, "subprotocols");
+    add_field!(data.graphql_query, "graphql_query");
+    add_field!(data.graphql_variables, "graphql_variables");
+    add_field!(data.graphql_operation_name, "graphql_operation_name");
+    add_field!(data.grpc_service, "grpc_service");
+    add_field!(data.grpc_method, "grpc_method");
+    add_field!(data.grpc_message, "grpc_message");
+    add_field!(data.grpc_descriptor_source, "grpc_descriptor_source");
+    add_field!(data.grpc_proto_files, "grpc_proto_files");
+    add_field!(data.grpc_import_paths, "grpc_import_paths");
+    add_field!(data.jsonrpc_calls, "jsonrpc_calls");
 
     // Auto-mark dirty if this is a synced request
     sets.push("dirty = CASE WHEN cloud_id IS NOT NULL THEN 1 ELSE dirty END".to_string());
@@ -189,8 +281,8 @@ pub fn duplicate(conn: &Connection, id: &str) -> Result<SavedRequest, String> {
         .map_err(|e| e.to_string())?;
 
     conn.execute(
-        "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, sort_order)
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+        "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, sort_order, kind, subprotocols, graphql_query, graphql_variables, graphql_operation_name, grpc_service, grpc_method, grpc_message, grpc_descriptor_source, grpc_proto_files, grpc_import_paths, jsonrpc_calls)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23)",
         params![
             new_id,
             original.collection_id,
@@ -202,7 +294,19 @@ pub fn duplicate(conn: &Connection, id: &str) -> Result<SavedRequest, String> {
             original.params,
             original.body,
             original.auth,
-            max_order + 1
+            max_order + 1,
+            original.kind,
+            original.subprotocols,
+            original.graphql_query,
+            original.graphql_variables,
+            original.graphql_operation_name,
+            original.grpc_service,
+            original.grpc_method,
+            original.grpc_message,
+            original.grpc_descriptor_source,
+            original.grpc_proto_files,
+            original.grpc_import_paths,
+            original.jsonrpc_calls
         ],
     )
     .map_err(|e| e.to_string())?;
@@ -257,6 +361,38 @@ pub fn mark_dirty(conn: &Connection, id: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// The columns only some request kinds use, from a cloud row's `kind_config`.
+/// Rows without one, shared before it existed or from a project that lacks
+/// it, leave a local request's columns alone; new requests get the defaults
+/// `create` uses.
+#[derive(Debug, Deserialize)]
+pub struct KindColumns {
+    #[serde(default)]
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub subprotocols: Option<String>,
+    #[serde(default)]
+    pub graphql_query: Option<String>,
+    #[serde(default)]
+    pub graphql_variables: Option<String>,
+    #[serde(default)]
+    pub graphql_operation_name: Option<String>,
+    #[serde(default)]
+    pub grpc_service: Option<String>,
+    #[serde(default)]
+    pub grpc_method: Option<String>,
+    #[serde(default)]
+    pub grpc_message: Option<String>,
+    #[serde(default)]
+    pub grpc_descriptor_source: Option<String>,
+    #[serde(default)]
+    pub grpc_proto_files: Option<String>,
+    #[serde(default)]
+    pub grpc_import_paths: Option<String>,
+    #[serde(default)]
+    pub jsonrpc_calls: Option<String>,
+}
+
 pub fn upsert_from_cloud(
     conn: &Connection,
     cloud_id: &str,
@@ -271,6 +407,7 @@ pub fn upsert_from_cloud(
     auth: &str,
     variables: &str,
     sort_order: i32,
+    columns: &KindColumns,
 ) -> Result<SavedRequest, String> {
     let existing: Option<String> = conn
         .query_row(
@@ -282,17 +419,27 @@ pub fn upsert_from_cloud(
 
     if let Some(local_id) = existing {
         conn.execute(
-            "UPDATE requests SET collection_id = ?1, folder_id = ?2, name = ?3, method = ?4, url = ?5, headers = ?6, params = ?7, body = ?8, auth = ?9, variables = ?10, sort_order = ?11, synced_at = datetime('now'), dirty = 0 WHERE id = ?12",
-            params![collection_id, folder_id, name, method, url, headers, params_json, body, auth, variables, sort_order, local_id],
+            "UPDATE requests SET collection_id = ?1, folder_id = ?2, name = ?3, method = ?4, url = ?5, headers = ?6, params = ?7, body = ?8, auth = ?9, variables = ?10, sort_order = ?11, kind = COALESCE(?13, kind), subprotocols = COALESCE(?14, subprotocols), graphql_query = COALESCE(?15, graphql_query), graphql_variables = COALESCE(?16, graphql_variables), graphql_operation_name = COALESCE(?17, graphql_operation_name), grpc_service = COALESCE(?18, grpc_service), grpc_method = COALESCE(?19, grpc_method), grpc_message = COALESCE(?20, grpc_message), grpc_descriptor_source = COALESCE(?21, grpc_descriptor_source), grpc_proto_files = COALESCE(?22, grpc_proto_files), grpc_import_paths = COALESCE(?23, grpc_import_paths), jsonrpc_calls = COALESCE(?24, jsonrpc_calls), synced_at = datetime('now'), dirty = 0 WHERE id = ?12",
+            params![
+                collection_id, folder_id, name, method, url, headers, params_json, body, auth, variables, sort_order, local_id,
+                columns.kind, columns.subprotocols, columns.graphql_query, columns.graphql_variables, columns.graphql_operation_name,
+                columns.grpc_service, columns.grpc_method, columns.grpc_message, columns.grpc_descriptor_source,
+                columns.grpc_proto_files, columns.grpc_import_paths, columns.jsonrpc_calls
+            ],
         )
         .map_err(|e| e.to_string())?;
         get_by_id(conn, &local_id)
     } else {
         let id = Uuid::new_v4().to_string();
         conn.execute(
-            "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order, cloud_id, synced_at, dirty)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, datetime('now'), 0)",
-            params![id, collection_id, folder_id, name, method, url, headers, params_json, body, auth, variables, sort_order, cloud_id],
+            "INSERT INTO requests (id, collection_id, folder_id, name, method, url, headers, params, body, auth, variables, sort_order, cloud_id, synced_at, dirty, kind, subprotocols, graphql_query, graphql_variables, graphql_operation_name, grpc_service, grpc_method, grpc_message, grpc_descriptor_source, grpc_proto_files, grpc_import_paths, jsonrpc_calls)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, datetime('now'), 0, COALESCE(?14, 'http'), COALESCE(?15, '[]'), COALESCE(?16, ''), COALESCE(?17, ''), COALESCE(?18, ''), COALESCE(?19, ''), COALESCE(?20, ''), COALESCE(?21, ''), COALESCE(?22, 'reflection'), COALESCE(?23, '[]'), COALESCE(?24, '[]'), COALESCE(?25, '[]'))",
+            params![
+                id, collection_id, folder_id, name, method, url, headers, params_json, body, auth, variables, sort_order, cloud_id,
+                columns.kind, columns.subprotocols, columns.graphql_query, columns.graphql_variables, columns.graphql_operation_name,
+                columns.grpc_service, columns.grpc_method, columns.grpc_message, columns.grpc_descriptor_source,
+                columns.grpc_proto_files, columns.grpc_import_paths, columns.jsonrpc_calls
+            ],
         )
         .map_err(|e| e.to_string())?;
         get_by_id(conn, &id)
@@ -0,0 +1,199 @@
+use crate::http::websocket::{WsClose, WsMessage};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use uuid::Uuid;
+
+const MAX_MESSAGE_BYTES: usize = 1_048_576; // 1MB
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WsSession {
+    pub id: String,
+    pub url: String,
+    /// JSON array of the subprotocols offered.
+    pub subprotocols: String,
+    /// The subprotocol the server picked.
+    pub protocol: Option<String>,
+    pub saved_request_id: Option<String>,
+    /// Why the handshake failed or the connection dropped.
+    pub error: Option<String>,
+    pub close_code: Option<i32>,
+    pub close_reason: Option<String>,
+    pub opened_at: String,
+    /// `None` while the session is open, or if Hermes quit before it closed.
+    pub closed_at: Option<String>,
+    pub message_count: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StoredWsMessage {
+    pub id: i64,
+    pub session_id: String,
+    pub direction: String,
+    pub kind: String,
+    pub data: String,
+    pub size_bytes: i64,
+    /// `data` was cut at 1MB; `size_bytes` is still the full size.
+    pub truncated: bool,
+    pub at_ms: i64,
+}
+
+fn row_to_session(row: &rusqlite::Row) -> rusqlite::Result<WsSession> {
+    Ok(WsSession {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        subprotocols: row.get(2)?,
+        protocol: row.get(3)?,
+        saved_request_id: row.get(4)?,
+        error: row.get(5)?,
+        close_code: row.get(6)?,
+        close_reason: row.get(7)?,
+        opened_at: row.get(8)?,
+        closed_at: row.get(9)?,
+        message_count: row.get(10)?,
+    })
+}
+
+fn row_to_message(row: &rusqlite::Row) -> rusqlite::Result<StoredWsMessage> {
+    Ok(StoredWsMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        direction: row.get(2)?,
+        kind: row.get(3)?,
+        data: row.get(4)?,
+        size_bytes: row.get(5)?,
+        truncated: row.get::<_, i32>(6)? != 0,
+        at_ms: row.get(7)?,
+    })
+}
+
+const SELECT_COLS: &str = "id, url, subprotocols, protocol, saved_request_id, error, \
+    close_code, close_reason, opened_at, closed_at, \
+    (SELECT COUNT(*) FROM ws_messages m WHERE m.session_id = ws_sessions.id)";
+
+const MESSAGE_COLS: &str = "id, session_id, direction, kind, data, size_bytes, truncated, at_ms";
+
+/// Records a connection attempt; failed handshakes are stored already closed.
+pub fn create(
+    conn: &Connection,
+    url: &str,
+    subprotocols: &[String],
+    protocol: Option<&str>,
+    saved_request_id: Option<&str>,
+    error: Option<&str>,
+) -> Result<WsSession, String> {
+    let id = Uuid::new_v4().to_string();
+    let subprotocols = serde_json::to_string(subprotocols).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO ws_sessions (id, url, subprotocols, protocol, saved_request_id, error, closed_at) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, CASE WHEN ?6 IS NULL THEN NULL ELSE datetime('now') END)",
+        params![id, url, subprotocols, protocol, saved_request_id, error],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_by_id(conn, &id)
+}
+
+pub fn get_by_id(conn: &Connection, id: &str) -> Result<WsSession, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM ws_sessions WHERE id = ?1", SELECT_COLS),
+        params![id],
+        |row| row_to_session(row),
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub fn close(conn: &Connection, id: &str, close: &WsClose) -> Result<(), String> {
+    conn.execute(
+        "UPDATE ws_sessions SET closed_at = datetime('now'), close_code = ?1, close_reason = ?2, \
+         error = ?3 WHERE id = ?4",
+        params![close.code, close.reason, close.error, id],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn add_message(conn: &Connection, session_id: &str, message: &WsMessage) -> Result<(), String> {
+    // Text may be multibyte; base64 and close data are ASCII
+    let end = (0..=MAX_MESSAGE_BYTES.min(message.data.len()))
+        .rev()
+        .find(|&i| message.data.is_char_boundary(i))
+        .unwrap_or(0);
+    conn.execute(
+        "INSERT INTO ws_messages (session_id, direction, kind, data, size_bytes, truncated, at_ms) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            session_id,
+            message.direction,
+            message.kind,
+            &message.data[..end],
+            message.size_bytes as i64,
+            (end < message.data.len()) as i32,
+            message.at_ms as i64,
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Sessions newest first, optionally only those of one saved request.
+pub fn list(
+    conn: &Connection,
+    saved_request_id: Option<&str>,
+    limit: Option<i64>,
+) -> Result<Vec<WsSession>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ws_sessions WHERE ?1 IS NULL OR saved_request_id = ?1 \
+             ORDER BY opened_at DESC, rowid DESC LIMIT ?2",
+            SELECT_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![saved_request_id, limit.unwrap_or(100)], |row| {
+            row_to_session(row)
+        })
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn get_messages(conn: &Connection, session_id: &str) -> Result<Vec<StoredWsMessage>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM ws_messages WHERE session_id = ?1 ORDER BY id",
+            MESSAGE_COLS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(params![session_id], |row| row_to_message(row))
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+pub fn delete(conn: &Connection, id: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM ws_sessions WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn clear(conn: &Connection) -> Result<(), String> {
+    conn.execute("DELETE FROM ws_sessions", [])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub fn cleanup_old(conn: &Connection, retention_days: i32) -> Result<u64, String> {
+    let deleted = conn
+        .execute(
+            "DELETE FROM ws_sessions WHERE opened_at < datetime('now', ?1)",
+            params![format!("-{} days", retention_days)],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(deleted as u64)
+}
@@ -0,0 +1,113 @@
+pub mod parser;
+pub mod schema;
+pub mod validate;
+
+use parser::Pos;
+use schema::Schema;
+use serde::Serialize;
+
+/// Asks for everything validation needs. Deprecated fields, enum values and
+/// arguments are included since queries may still use them.
+pub const INTROSPECTION_QUERY: &str = r#"query IntrospectionQuery {
+  __schema {
+    queryType { name }
+    mutationType { name }
+    subscriptionType { name }
+    types { ...FullType }
+    directives {
+      name
+      locations
+      isRepeatable
+      args { ...InputValue }
+    }
+  }
+}
+
+fragment FullType on __Type {
+  kind
+  name
+  fields(includeDeprecated: true) {
+    name
+    args { ...InputValue }
+    type { ...TypeRef }
+  }
+  inputFields { ...InputValue }
+  interfaces { ...TypeRef }
+  enumValues(includeDeprecated: true) { name }
+  possibleTypes { ...TypeRef }
+}
+
+fragment InputValue on __InputValue {
+  name
+  type { ...TypeRef }
+  defaultValue
+}
+
+fragment TypeRef on __Type {
+  kind
+  name
+  ofType {
+    kind
+    name
+    ofType {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+              }
+            }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// A syntax or validation error, located in the query text.
+#[derive(Debug, Serialize, Clone)]
+pub struct GraphqlError {
+    pub message: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl GraphqlError {
+    pub fn at(pos: Pos, message: impl Into<String>) -> Self {
+        GraphqlError {
+            message: message.into(),
+            line: pos.line,
+            column: pos.column,
+        }
+    }
+}
+
+/// Parses `query` and, when a schema is at hand, validates it. Syntax errors
+/// stop at the first one.
+pub fn check(
+    query: &str,
+    operation_name: Option<&str>,
+    schema: Option<&Schema>,
+) -> Vec<GraphqlError> {
+    let document = match parser::parse(query) {
+        Ok(document) => document,
+        Err(e) => return vec![e],
+    };
+    match schema {
+        Some(schema) => validate::validate(schema, &document, operation_name),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,1142 @@
+use super::GraphqlError;
+
+/// A 1-based line and column in the query text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pos {
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Name {
+    pub value: String,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct Document {
+    pub definitions: Vec<Definition>,
+}
+
+#[derive(Debug)]
+pub enum Definition {
+    Operation(Operation),
+    Fragment(Fragment),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OperationKind::Query => "query",
+            OperationKind::Mutation => "mutation",
+            OperationKind::Subscription => "subscription",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Operation {
+    pub kind: OperationKind,
+    pub name: Option<Name>,
+    pub variables: Vec<VariableDefinition>,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct Fragment {
+    pub name: Name,
+    pub type_condition: Name,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct VariableDefinition {
+    pub name: Name,
+    pub ty: Type,
+    pub default: Option<Value>,
+    pub directives: Vec<Directive>,
+    pub pos: Pos,
+}
+
+/// A type reference as written in the query, e.g. `[ID!]!`.
+#[derive(Debug, Clone)]
+pub enum Type {
+    Named(Name),
+    List(Box<Type>, Pos),
+    NonNull(Box<Type>),
+}
+
+impl Type {
+    pub fn named(&self) -> &Name {
+        match self {
+            Type::Named(name) => name,
+            Type::List(inner, _) | Type::NonNull(inner) => inner.named(),
+        }
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Named(name) => write!(f, "{}", name.value),
+            Type::List(inner, _) => write!(f, "[{}]", inner),
+            Type::NonNull(inner) => write!(f, "{}!", inner),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SelectionSet {
+    pub items: Vec<Selection>,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub enum Selection {
+    Field(Field),
+    Spread(FragmentSpread),
+    Inline(InlineFragment),
+}
+
+#[derive(Debug)]
+pub struct Field {
+    pub alias: Option<Name>,
+    pub name: Name,
+    pub arguments: Vec<Argument>,
+    pub directives: Vec<Directive>,
+    pub selection_set: Option<SelectionSet>,
+}
+
+impl Field {
+    /// The key the field's value appears under in the response.
+    pub fn response_key(&self) -> &str {
+        &self.alias.as_ref().unwrap_or(&self.name).value
+    }
+}
+
+#[derive(Debug)]
+pub struct FragmentSpread {
+    pub name: Name,
+    pub directives: Vec<Directive>,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct InlineFragment {
+    pub type_condition: Option<Name>,
+    pub directives: Vec<Directive>,
+    pub selection_set: SelectionSet,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct Directive {
+    pub name: Name,
+    pub arguments: Vec<Argument>,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub struct Argument {
+    pub name: Name,
+    pub value: Value,
+}
+
+#[derive(Debug)]
+pub struct Value {
+    pub kind: ValueKind,
+    pub pos: Pos,
+}
+
+#[derive(Debug)]
+pub enum ValueKind {
+    Variable(String),
+    Int(String),
+    Float(String),
+    String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    List(Vec<Value>),
+    Object(Vec<(Name, Value)>),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            ValueKind::Variable(name) => write!(f, "${}", name),
+            ValueKind::Int(raw) | ValueKind::Float(raw) | ValueKind::Enum(raw) => f.write_str(raw),
+            ValueKind::String(s) => write!(f, "{:?}", s),
+            ValueKind::Boolean(b) => write!(f, "{}", b),
+            ValueKind::Null => f.write_str("null"),
+            ValueKind::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            ValueKind::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, " {}: {}", name.value, value)?;
+                }
+                f.write_str(" }")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Punct(char),
+    Spread,
+    Name(String),
+    Int(String),
+    Float(String),
+    Str(String),
+    Eof,
+}
+
+impl std::fmt::Display for Tok {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tok::Punct(c) => write!(f, "\"{}\"", c),
+            Tok::Spread => f.write_str("\"...\""),
+            Tok::Name(name) => write!(f, "Name \"{}\"", name),
+            Tok::Int(raw) => write!(f, "Int \"{}\"", raw),
+            Tok::Float(raw) => write!(f, "Float \"{}\"", raw),
+            Tok::Str(s) => write!(f, "String {:?}", s),
+            Tok::Eof => f.write_str("<EOF>"),
+        }
+    }
+}
+
+struct Token {
+    tok: Tok,
+    pos: Pos,
+}
+
+/// Splits the source into tokens, skipping whitespace, commas and comments.
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: u32,
+    column: u32,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Lexer {
+            chars: source.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn pos(&self) -> Pos {
+        Pos {
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        match c {
+            '\n' => {
+                self.line += 1;
+                self.column = 1;
+            }
+            // A lone CR ends a line; CRLF counts once, at the LF
+            '\r' if self.chars.peek() != Some(&'\n') => {
+                self.line += 1;
+                self.column = 1;
+            }
+            '\r' => {}
+            _ => self.column += 1,
+        }
+        Some(c)
+    }
+
+    fn error(&self, pos: Pos, message: impl Into<String>) -> GraphqlError {
+        GraphqlError::at(pos, format!("Syntax Error: {}", message.into()))
+    }
+
+    fn next_token(&mut self) -> Result<Token, GraphqlError> {
+        loop {
+            match self.chars.peek() {
+                Some(' ' | '\t' | '\n' | '\r' | ',' | '\u{feff}') => {
+                    self.bump();
+                }
+                Some('#') => {
+                    while !matches!(self.chars.peek(), None | Some('\n' | '\r')) {
+                        self.bump();
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let pos = self.pos();
+        let Some(&c) = self.chars.peek() else {
+            return Ok(Token { tok: Tok::Eof, pos });
+        };
+        let tok = match c {
+            '!' | '$' | '&' | '(' | ')' | ':' | '=' | '@' | '[' | ']' | '{' | '|' | '}' => {
+                self.bump();
+                Tok::Punct(c)
+            }
+            '.' => {
+                for _ in 0..3 {
+                    if self.bump() != Some('.') {
+                        return Err(self.error(pos, "Unexpected \".\", did you mean \"...\"?"));
+                    }
+                }
+                Tok::Spread
+            }
+            '"' => self.string(pos)?,
+            '-' | '0'..='9' => self.number(pos)?,
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c == '_' || c.is_ascii_alphanumeric() {
+                        name.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Tok::Name(name)
+            }
+            c => return Err(self.error(pos, format!("Unexpected character {:?}.", c))),
+        };
+        Ok(Token { tok, pos })
+    }
+
+    fn digits(&mut self, raw: &mut String) -> usize {
+        let mut count = 0;
+        while let Some(&c) = self.chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            raw.push(c);
+            self.bump();
+            count += 1;
+        }
+        count
+    }
+
+    fn number(&mut self, pos: Pos) -> Result<Tok, GraphqlError> {
+        let mut raw = String::new();
+        if self.chars.peek() == Some(&'-') {
+            raw.push('-');
+            self.bump();
+        }
+        let int_start = raw.len();
+        if self.digits(&mut raw) == 0 {
+            return Err(self.error(self.pos(), "Invalid number, expected digit."));
+        }
+        if raw[int_start..].len() > 1 && raw[int_start..].starts_with('0') {
+            return Err(self.error(pos, "Invalid number, unexpected digit after 0."));
+        }
+
+        let mut float = false;
+        if self.chars.peek() == Some(&'.') {
+            float = true;
+            raw.push('.');
+            self.bump();
+            if self.digits(&mut raw) == 0 {
+                return Err(self.error(self.pos(), "Invalid number, expected digit."));
+            }
+        }
+        if let Some(&e @ ('e' | 'E')) = self.chars.peek() {
+            float = true;
+            raw.push(e);
+            self.bump();
+            if let Some(&sign @ ('+' | '-')) = self.chars.peek() {
+                raw.push(sign);
+                self.bump();
+            }
+            if self.digits(&mut raw) == 0 {
+                return Err(self.error(self.pos(), "Invalid number, expected digit."));
+            }
+        }
+        if let Some(&c) = self.chars.peek() {
+            if c == '.' || c == '_' || c.is_ascii_alphabetic() {
+                return Err(self.error(
+                    self.pos(),
+                    format!("Invalid number, expected digit but got {:?}.", c),
+                ));
+            }
+        }
+        Ok(if float {
+            Tok::Float(raw)
+        } else {
+            Tok::Int(raw)
+        })
+    }
+
+    fn string(&mut self, pos: Pos) -> Result<Tok, GraphqlError> {
+        self.bump();
+        if self.chars.peek() == Some(&'"') {
+            self.bump();
+            if self.chars.peek() != Some(&'"') {
+                return Ok(Tok::Str(String::new()));
+            }
+            self.bump();
+            return self.block_string(pos);
+        }
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None | Some('\n' | '\r') => return Err(self.error(pos, "Unterminated string.")),
+                Some('"') => return Ok(Tok::Str(value)),
+                Some('\\') => {
+                    let escape_pos = self.pos();
+                    match self.bump() {
+                        Some('"') => value.push('"'),
+                        Some('\\') => value.push('\\'),
+                        Some('/') => value.push('/'),
+                        Some('b') => value.push('\u{8}'),
+                        Some('f') => value.push('\u{c}'),
+                        Some('n') => value.push('\n'),
+                        Some('r') => value.push('\r'),
+                        Some('t') => value.push('\t'),
+                        Some('u') => value.push(self.unicode_escape(escape_pos)?),
+                        Some(c) => {
+                            return Err(self.error(
+                                escape_pos,
+                                format!("Invalid character escape sequence: \"\\{}\".", c),
+                            ))
+                        }
+                        None => return Err(self.error(pos, "Unterminated string.")),
+                    }
+                }
+                Some(c) => value.push(c),
+            }
+        }
+    }
+
+    /// `\uXXXX`, a surrogate pair of them, or `\u{...}`.
+    fn unicode_escape(&mut self, pos: Pos) -> Result<char, GraphqlError> {
+        let invalid = |lexer: &Self| lexer.error(pos, "Invalid Unicode escape sequence.");
+        let code = if self.chars.peek() == Some(&'{') {
+            self.bump();
+            let mut hex = String::new();
+            while let Some(c) = self.bump() {
+                if c == '}' {
+                    break;
+                }
+                hex.push(c);
+            }
+            u32::from_str_radix(&hex, 16).map_err(|_| invalid(self))?
+        } else {
+            let high = self.hex4().ok_or_else(|| invalid(self))?;
+            if (0xD800..0xDC00).contains(&high) {
+                let low = (self.bump() == Some('\\') && self.bump() == Some('u'))
+                    .then(|| self.hex4())
+                    .flatten()
+                    .filter(|low| (0xDC00..0xE000).contains(low))
+                    .ok_or_else(|| invalid(self))?;
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            } else {
+                high
+            }
+        };
+        char::from_u32(code).ok_or_else(|| invalid(self))
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            code = code * 16 + self.bump()?.to_digit(16)?;
+        }
+        Some(code)
+    }
+
+    fn block_string(&mut self, pos: Pos) -> Result<Tok, GraphqlError> {
+        let mut raw = String::new();
+        loop {
+            match self.bump() {
+                None => return Err(self.error(pos, "Unterminated string.")),
+                Some('"') if self.chars.peek() == Some(&'"') => {
+                    self.bump();
+                    if self.chars.peek() == Some(&'"') {
+                        self.bump();
+                        return Ok(Tok::Str(dedent_block_string(&raw)));
+                    }
+                    raw.push_str("\"\"");
+                }
+                Some('\\') if self.chars.peek() == Some(&'"') => {
+                    // `\"""` is an escaped triple quote; anything shorter is literal
+                    let mut quotes = 0;
+                    while quotes < 3 && self.chars.peek() == Some(&'"') {
+                        self.bump();
+                        quotes += 1;
+                    }
+                    if quotes < 3 {
+                        raw.push('\\');
+                    }
+                    raw.push_str(&"\"".repeat(quotes));
+                }
+                Some(c) => raw.push(c),
+            }
+        }
+    }
+}
+
+/// Strips the common indentation and blank first/last lines of a block string.
+fn dedent_block_string(raw: &str) -> String {
+    let lines: Vec<&str> = raw
+        .split("\r\n")
+        .flat_map(|l| l.split(['\n', '\r']))
+        .collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let trimmed = line.trim_start_matches([' ', '\t']);
+            (!trimmed.is_empty()).then(|| line.len() - trimmed.len())
+        })
+        .min()
+        .unwrap_or(0);
+    let mut lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                line.get(indent..).unwrap_or("")
+            }
+        })
+        .collect();
+    let blank = |line: &&str| line.trim_matches([' ', '\t']).is_empty();
+    while lines.first().is_some_and(blank) {
+        lines.remove(0);
+    }
+    while lines.last().is_some_and(blank) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Parses an executable document: operations and fragments only.
+pub fn parse(source: &str) -> Result<Document, GraphqlError> {
+    let mut parser = Parser {
+        lexer: Lexer::new(source),
+        token: Token {
+            tok: Tok::Eof,
+            pos: Pos { line: 1, column: 1 },
+        },
+    };
+    parser.advance()?;
+    parser.document()
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    token: Token,
+}
+
+impl Parser<'_> {
+    fn advance(&mut self) -> Result<Token, GraphqlError> {
+        let next = self.lexer.next_token()?;
+        Ok(std::mem::replace(&mut self.token, next))
+    }
+
+    fn unexpected(&self) -> GraphqlError {
+        self.lexer
+            .error(self.token.pos, format!("Unexpected {}.", self.token.tok))
+    }
+
+    fn peek_punct(&self, c: char) -> bool {
+        self.token.tok == Tok::Punct(c)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.token.tok, Tok::Name(name) if name == keyword)
+    }
+
+    fn skip_punct(&mut self, c: char) -> Result<bool, GraphqlError> {
+        if self.peek_punct(c) {
+            self.advance()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<Pos, GraphqlError> {
+        if self.peek_punct(c) {
+            return Ok(self.advance()?.pos);
+        }
+        Err(self.lexer.error(
+            self.token.pos,
+            format!("Expected \"{}\", found {}.", c, self.token.tok),
+        ))
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), GraphqlError> {
+        if self.peek_keyword(keyword) {
+            self.advance()?;
+            return Ok(());
+        }
+        Err(self.lexer.error(
+            self.token.pos,
+            format!("Expected \"{}\", found {}.", keyword, self.token.tok),
+        ))
+    }
+
+    fn name(&mut self) -> Result<Name, GraphqlError> {
+        if let Tok::Name(_) = self.token.tok {
+            let token = self.advance()?;
+            if let Tok::Name(value) = token.tok {
+                return Ok(Name {
+                    value,
+                    pos: token.pos,
+                });
+            }
+        }
+        Err(self.lexer.error(
+            self.token.pos,
+            format!("Expected Name, found {}.", self.token.tok),
+        ))
+    }
+
+    fn document(&mut self) -> Result<Document, GraphqlError> {
+        let mut definitions = Vec::new();
+        loop {
+            match &self.token.tok {
+                Tok::Eof if definitions.is_empty() => {
+                    return Err(self.lexer.error(self.token.pos, "Document contains no operations."))
+                }
+                Tok::Eof => break,
+                Tok::Punct('{') => definitions.push(Definition::Operation(self.operation()?)),
+                Tok::Name(name) => match name.as_str() {
+                    "query" | "mutation" | "subscription" => {
+                        definitions.push(Definition::Operation(self.operation()?))
+                    }
+                    "fragment" => definitions.push(Definition::Fragment(self.fragment()?)),
+                    "schema" | "scalar" | "type" | "interface" | "union" | "enum" | "input"
+                    | "directive" | "extend" => {
+                        return Err(GraphqlError::at(
+                            self.token.pos,
+                            format!(
+                                "The \"{}\" definition is not executable; only operations and fragments can be sent.",
+                                name
+                            ),
+                        ))
+                    }
+                    _ => return Err(self.unexpected()),
+                },
+                _ => return Err(self.unexpected()),
+            }
+        }
+        Ok(Document { definitions })
+    }
+
+    fn operation(&mut self) -> Result<Operation, GraphqlError> {
+        let pos = self.token.pos;
+        if self.peek_punct('{') {
+            return Ok(Operation {
+                kind: OperationKind::Query,
+                name: None,
+                variables: Vec::new(),
+                directives: Vec::new(),
+                selection_set: self.selection_set()?,
+                pos,
+            });
+        }
+        let kind = match self.name()?.value.as_str() {
+            "query" => OperationKind::Query,
+            "mutation" => OperationKind::Mutation,
+            _ => OperationKind::Subscription,
+        };
+        let name = match self.token.tok {
+            Tok::Name(_) => Some(self.name()?),
+            _ => None,
+        };
+        Ok(Operation {
+            kind,
+            name,
+            variables: self.variable_definitions()?,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+            pos,
+        })
+    }
+
+    fn variable_definitions(&mut self) -> Result<Vec<VariableDefinition>, GraphqlError> {
+        let mut variables = Vec::new();
+        if !self.skip_punct('(')? {
+            return Ok(variables);
+        }
+        loop {
+            let pos = self.expect_punct('$')?;
+            let name = self.name()?;
+            self.expect_punct(':')?;
+            let ty = self.type_ref()?;
+            let default = if self.skip_punct('=')? {
+                Some(self.value(true)?)
+            } else {
+                None
+            };
+            variables.push(VariableDefinition {
+                name,
+                ty,
+                default,
+                directives: self.directives(true)?,
+                pos,
+            });
+            if self.skip_punct(')')? {
+                return Ok(variables);
+            }
+        }
+    }
+
+    fn type_ref(&mut self) -> Result<Type, GraphqlError> {
+        let ty = if self.peek_punct('[') {
+            let pos = self.advance()?.pos;
+            let inner = self.type_ref()?;
+            self.expect_punct(']')?;
+            Type::List(Box::new(inner), pos)
+        } else {
+            Type::Named(self.name()?)
+        };
+        if self.skip_punct('!')? {
+            return Ok(Type::NonNull(Box::new(ty)));
+        }
+        Ok(ty)
+    }
+
+    fn fragment(&mut self) -> Result<Fragment, GraphqlError> {
+        let pos = self.token.pos;
+        self.expect_keyword("fragment")?;
+        if self.peek_keyword("on") {
+            return Err(self.unexpected());
+        }
+        let name = self.name()?;
+        self.expect_keyword("on")?;
+        Ok(Fragment {
+            name,
+            type_condition: self.name()?,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+            pos,
+        })
+    }
+
+    fn selection_set(&mut self) -> Result<SelectionSet, GraphqlError> {
+        let pos = self.expect_punct('{')?;
+        let mut items = Vec::new();
+        loop {
+            items.push(self.selection()?);
+            if self.skip_punct('}')? {
+                return Ok(SelectionSet { items, pos });
+            }
+        }
+    }
+
+    fn selection(&mut self) -> Result<Selection, GraphqlError> {
+        if self.token.tok != Tok::Spread {
+            return Ok(Selection::Field(self.field()?));
+        }
+        let pos = self.advance()?.pos;
+        let named_spread = matches!(&self.token.tok, Tok::Name(name) if name != "on");
+        if named_spread {
+            return Ok(Selection::Spread(FragmentSpread {
+                name: self.name()?,
+                directives: self.directives(false)?,
+                pos,
+            }));
+        }
+        let type_condition = if self.peek_keyword("on") {
+            self.advance()?;
+            Some(self.name()?)
+        } else {
+            None
+        };
+        Ok(Selection::Inline(InlineFragment {
+            type_condition,
+            directives: self.directives(false)?,
+            selection_set: self.selection_set()?,
+            pos,
+        }))
+    }
+
+    fn field(&mut self) -> Result<Field, GraphqlError> {
+        let first = self.name()?;
+        let (alias, name) = if self.skip_punct(':')? {
+            (Some(first), self.name()?)
+        } else {
+            (None, first)
+        };
+        Ok(Field {
+            alias,
+            name,
+            arguments: self.arguments(false)?,
+            directives: self.directives(false)?,
+            selection_set: if self.peek_punct('{') {
+                Some(self.selection_set()?)
+            } else {
+                None
+            },
+        })
+    }
+
+    fn arguments(&mut self, constant: bool) -> Result<Vec<Argument>, GraphqlError> {
+        let mut arguments = Vec::new();
+        if !self.skip_punct('(')? {
+            return Ok(arguments);
+        }
+        loop {
+            let name = self.name()?;
+            self.expect_punct(':')?;
+            arguments.push(Argument {
+                name,
+                value: self.value(constant)?,
+            });
+            if self.skip_punct(')')? {
+                return Ok(arguments);
+            }
+        }
+    }
+
+    fn directives(&mut self, constant: bool) -> Result<Vec<Directive>, GraphqlError> {
+        let mut directives = Vec::new();
+        while self.peek_punct('@') {
+            let pos = self.advance()?.pos;
+            directives.push(Directive {
+                name: self.name()?,
+                arguments: self.arguments(constant)?,
+                pos,
+            });
+        }
+        Ok(directives)
+    }
+
+    /// A value literal; `constant` rules out variables, as in default values.
+    fn value(&mut self, constant: bool) -> Result<Value, GraphqlError> {
+        let pos = self.token.pos;
+        let kind = match &self.token.tok {
+            Tok::Punct('$') => {
+                self.advance()?;
+                let name = self.name()?.value;
+                if constant {
+                    return Err(self.lexer.error(
+                        pos,
+                        format!("Unexpected variable \"${}\" in constant value.", name),
+                    ));
+                }
+                ValueKind::Variable(name)
+            }
+            Tok::Punct('[') => {
+                self.advance()?;
+                let mut items = Vec::new();
+                while !self.skip_punct(']')? {
+                    items.push(self.value(constant)?);
+                }
+                ValueKind::List(items)
+            }
+            Tok::Punct('{') => {
+                self.advance()?;
+                let mut fields = Vec::new();
+                while !self.skip_punct('}')? {
+                    let name = self.name()?;
+                    self.expect_punct(':')?;
+                    fields.push((name, self.value(constant)?));
+                }
+                ValueKind::Object(fields)
+            }
+            Tok::Int(_) | Tok::Float(_) | Tok::Str(_) => match self.advance()?.tok {
+                Tok::Int(raw) => ValueKind::Int(raw),
+                Tok::Float(raw) => ValueKind::Float(raw),
+                Tok::Str(s) => ValueKind::String(s),
+                _ => unreachable!(),
+            },
+            Tok::Name(name) => {
+                let kind = match name.as_str() {
+                    "true" => ValueKind::Boolean(true),
+                    "false" => ValueKind::Boolean(false),
+                    "null" => ValueKind::Null,
+                    _ => ValueKind::Enum(name.clone()),
+                };
+                self.advance()?;
+                kind
+            }
+            _ => return Err(self.unexpected()),
+        };
+        Ok(Value { kind, pos })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(source: &str) -> Operation {
+        match parse(source).unwrap().definitions.remove(0) {
+            Definition::Operation(operation) => operation,
+            Definition::Fragment(_) => panic!("expected an operation in {}", source),
+        }
+    }
+
+    fn field(selection: &Selection) -> &Field {
+        match selection {
+            Selection::Field(field) => field,
+            other => panic!("expected a field, got {:?}", other),
+        }
+    }
+
+    /// The value of the first argument of the first field.
+    fn argument(source: &str) -> Value {
+        let operation = operation(source);
+        let Selection::Field(mut field) = operation.selection_set.items.into_iter().next().unwrap()
+        else {
+            panic!("expected a field in {}", source);
+        };
+        field.arguments.remove(0).value
+    }
+
+    #[test]
+    fn parses_executable_documents() {
+        let sources = [
+            "{ a }",
+            "query { a b c }",
+            "query Q { a(x: 1, y: -2.5e3, z: \"s\", w: true, v: null, u: ENUM) }",
+            "query Q($id: ID!, $ids: [ID!]! = [\"1\", 2], $f: F = { a: [1 2] }) { a(id: $id) }",
+            "query Q($x: Int = 1 @deprecated) @live { a }",
+            "mutation M { create(input: { name: \"n\", tags: [$tag] }) { id } }",
+            "subscription S { events { id } }",
+            "{ alias: a(x: 1) @include(if: $show) @skip(if: false) { b } }",
+            "{ a { ...F ... on T { b } ... @include(if: true) { c } } } fragment F on T { d }",
+            "fragment F on T @dir { a } query { ...F }",
+            "# comment\n{\n  a, # trailing\n  b,,,\n}\n",
+            "\u{feff}{ a }",
+            "{ a(s: \"\\u00e9\\u{1F600}\\uD83D\\uDE00\\n\\\"\") }",
+            "{ a(s: \"\"\"block\"\"\") }",
+            "{ on: a, fragment: b, query: c }",
+        ];
+        for source in sources {
+            if let Err(e) = parse(source) {
+                panic!("{:?} did not parse: {}", source, e.message);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_syntax() {
+        let cases = [
+            ("", "Syntax Error: Document contains no operations.", 1, 1),
+            ("{ }", "Syntax Error: Expected Name, found \"}\".", 1, 3),
+            ("{ a", "Syntax Error: Expected Name, found <EOF>.", 1, 4),
+            ("{ a ..b }", "Syntax Error: Unexpected \".\", did you mean \"...\"?", 1, 5),
+            ("{ a(x: 01) }", "Syntax Error: Invalid number, unexpected digit after 0.", 1, 8),
+            ("{ a(x: 1.) }", "Syntax Error: Invalid number, expected digit.", 1, 10),
+            ("{ a(x: 1x) }", "Syntax Error: Invalid number, expected digit but got 'x'.", 1, 9),
+            ("{ a(x: \"abc) }", "Syntax Error: Unterminated string.", 1, 8),
+            ("{ a(x: \"a\nb\") }", "Syntax Error: Unterminated string.", 1, 8),
+            ("{ a(x: \"\"\"abc) }", "Syntax Error: Unterminated string.", 1, 8),
+            (
+                "{ a(x: \"\\q\") }",
+                "Syntax Error: Invalid character escape sequence: \"\\q\".",
+                1,
+                10,
+            ),
+            ("{ a(x: \"\\uD800\") }", "Syntax Error: Invalid Unicode escape sequence.", 1, 10),
+            ("{ a(x: \"\\u12G4\") }", "Syntax Error: Invalid Unicode escape sequence.", 1, 10),
+            (
+                "query Q($id: ID = $other) { a }",
+                "Syntax Error: Unexpected variable \"$other\" in constant value.",
+                1,
+                19,
+            ),
+            ("query Q($id ID) { a }", "Syntax Error: Expected \":\", found Name \"ID\".", 1, 13),
+            ("query Q($id: [ID) { a }", "Syntax Error: Expected \"]\", found \")\".", 1, 17),
+            ("fragment on on T { a }", "Syntax Error: Unexpected Name \"on\".", 1, 10),
+            ("fragment F T { a }", "Syntax Error: Expected \"on\", found Name \"T\".", 1, 12),
+            ("{ a(x:) }", "Syntax Error: Unexpected \")\".", 1, 7),
+            ("{ a } }", "Syntax Error: Unexpected \"}\".", 1, 7),
+            ("{ a ? }", "Syntax Error: Unexpected character '?'.", 1, 5),
+            ("\n\n  { a(x: $) }", "Syntax Error: Expected Name, found \")\".", 3, 11),
+            (
+                "type Query { a: Int }",
+                "The \"type\" definition is not executable; only operations and fragments can be sent.",
+                1,
+                1,
+            ),
+        ];
+        for (source, message, line, column) in cases {
+            let error = parse(source).expect_err(source);
+            assert_eq!(error.message, message, "{:?}", source);
+            assert_eq!((error.line, error.column), (line, column), "{:?}", source);
+        }
+    }
+
+    #[test]
+    fn dedents_block_strings() {
+        let cases = [
+            (
+                "\"\"\"\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  \"\"\"",
+                "Hello,\n  World!\n\nYours,\n  GraphQL.",
+            ),
+            (
+                "\"\"\"  first\n    second\n    third\"\"\"",
+                "  first\nsecond\nthird",
+            ),
+            ("\"\"\"\r\n  a\r\n    b\r\n\"\"\"", "a\n  b"),
+            ("\"\"\"a \\\"\"\" b\"\"\"", "a \"\"\" b"),
+            (
+                "\"\"\"has \"\" and \\\"\" quotes\"\"\"",
+                "has \"\" and \\\"\" quotes",
+            ),
+            ("\"\"\"no \\n escapes\"\"\"", "no \\n escapes"),
+            ("\"\"\"\n\n  \n\"\"\"", ""),
+            ("\"\"", ""),
+        ];
+        for (literal, expected) in cases {
+            let source = format!("{{ a(s: {}) }}", literal);
+            match argument(&source).kind {
+                ValueKind::String(s) => assert_eq!(s, expected, "{:?}", literal),
+                other => panic!("expected a string from {:?}, got {:?}", literal, other),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_line_and_column_after_block_strings() {
+        let error = parse("{ a(s: \"\"\"\r\n  x\r\n\"\"\") ? }").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 6));
+    }
+
+    #[test]
+    fn parses_variable_definitions() {
+        let operation = operation(
+            "query Q($id: ID!, $ids: [[ID]!] = [[\"1\"]], $f: Filter = { a: 1, b: [X] }) { a }",
+        );
+        assert_eq!(operation.kind, OperationKind::Query);
+        assert_eq!(operation.name.unwrap().value, "Q");
+        let variables: Vec<(String, String, Option<String>)> = operation
+            .variables
+            .iter()
+            .map(|v| {
+                (
+                    v.name.value.clone(),
+                    v.ty.to_string(),
+                    v.default.as_ref().map(|d| d.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            variables,
+            [
+                ("id".to_string(), "ID!".to_string(), None),
+                (
+                    "ids".to_string(),
+                    "[[ID]!]".to_string(),
+                    Some("[[\"1\"]]".to_string())
+                ),
+                (
+                    "f".to_string(),
+                    "Filter".to_string(),
+                    Some("{ a: 1, b: [X] }".to_string())
+                ),
+            ]
+        );
+        assert_eq!(operation.variables[1].ty.named().value, "ID");
+        assert_eq!(
+            operation.variables[2].pos,
+            Pos {
+                line: 1,
+                column: 44
+            }
+        );
+    }
+
+    #[test]
+    fn parses_fragments_and_spreads() {
+        let document = parse(
+            "query { node { ...F @include(if: $x) ... on User { name } ... { id } } }\n\
+             fragment F on Node @cached { id }",
+        )
+        .unwrap();
+        let [Definition::Operation(operation), Definition::Fragment(fragment)] =
+            &document.definitions[..]
+        else {
+            panic!("expected an operation and a fragment");
+        };
+
+        let node = field(&operation.selection_set.items[0]);
+        let items = &node.selection_set.as_ref().unwrap().items;
+        let Selection::Spread(spread) = &items[0] else {
+            panic!("expected a spread, got {:?}", items[0]);
+        };
+        assert_eq!(spread.name.value, "F");
+        assert_eq!(spread.directives[0].name.value, "include");
+        assert_eq!(spread.directives[0].arguments[0].value.to_string(), "$x");
+        let Selection::Inline(typed) = &items[1] else {
+            panic!("expected an inline fragment, got {:?}", items[1]);
+        };
+        assert_eq!(typed.type_condition.as_ref().unwrap().value, "User");
+        assert_eq!(field(&typed.selection_set.items[0]).name.value, "name");
+        let Selection::Inline(untyped) = &items[2] else {
+            panic!("expected an inline fragment, got {:?}", items[2]);
+        };
+        assert!(untyped.type_condition.is_none());
+
+        assert_eq!(fragment.name.value, "F");
+        assert_eq!(fragment.type_condition.value, "Node");
+        assert_eq!(fragment.directives[0].name.value, "cached");
+        assert_eq!(fragment.pos, Pos { line: 2, column: 1 });
+    }
+
+    #[test]
+    fn parses_fields_and_directives() {
+        let operation = operation("mutation @a { x: y(n: 1, s: \"t\") @b(c: [1, 2]) @d { z } }");
+        assert_eq!(operation.kind, OperationKind::Mutation);
+        assert_eq!(operation.directives[0].name.value, "a");
+        let field = field(&operation.selection_set.items[0]);
+        assert_eq!(field.response_key(), "x");
+        assert_eq!(field.name.value, "y");
+        assert_eq!(arguments_of(&field.arguments), ["n: 1", "s: \"t\""]);
+        let directives: Vec<&str> = field
+            .directives
+            .iter()
+            .map(|d| d.name.value.as_str())
+            .collect();
+        assert_eq!(directives, ["b", "d"]);
+        assert_eq!(arguments_of(&field.directives[0].arguments), ["c: [1, 2]"]);
+        assert_eq!(
+            field.name.pos,
+            Pos {
+                line: 1,
+                column: 18
+            }
+        );
+    }
+
+    fn arguments_of(arguments: &[Argument]) -> Vec<String> {
+        arguments
+            .iter()
+            .map(|a| format!("{}: {}", a.name.value, a.value))
+            .collect()
+    }
+}
@@ -0,0 +1,295 @@
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+    List,
+    NonNull,
+}
+
+/// A type reference from introspection: `{ kind, name, ofType }`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeRef {
+    pub kind: TypeKind,
+    pub name: Option<String>,
+    pub of_type: Option<Box<TypeRef>>,
+}
+
+impl TypeRef {
+    pub fn named(name: &str, kind: TypeKind) -> Self {
+        TypeRef {
+            kind,
+            name: Some(name.to_string()),
+            of_type: None,
+        }
+    }
+
+    pub fn non_null(inner: TypeRef) -> Self {
+        TypeRef {
+            kind: TypeKind::NonNull,
+            name: None,
+            of_type: Some(Box::new(inner)),
+        }
+    }
+
+    pub fn list(inner: TypeRef) -> Self {
+        TypeRef {
+            kind: TypeKind::List,
+            name: None,
+            of_type: Some(Box::new(inner)),
+        }
+    }
+
+    /// The named type at the bottom of any list and non-null wrappers.
+    pub fn named_type(&self) -> &str {
+        match self.of_type {
+            Some(ref inner) => inner.named_type(),
+            None => self.name.as_deref().unwrap_or(""),
+        }
+    }
+
+    pub fn is_non_null(&self) -> bool {
+        self.kind == TypeKind::NonNull
+    }
+
+    /// The wrapped type of a list or non-null reference.
+    pub fn inner(&self) -> Option<&TypeRef> {
+        self.of_type.as_deref()
+    }
+}
+
+impl std::fmt::Display for TypeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.kind, self.inner()) {
+            (TypeKind::NonNull, Some(inner)) => write!(f, "{}!", inner),
+            (TypeKind::List, Some(inner)) => write!(f, "[{}]", inner),
+            _ => f.write_str(self.name.as_deref().unwrap_or("")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InputValue {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+    pub default_value: Option<String>,
+}
+
+impl InputValue {
+    /// Whether the value must be given: non-null and without a default.
+    pub fn is_required(&self) -> bool {
+        self.ty.is_non_null() && self.default_value.is_none()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct FieldDef {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
+    pub ty: TypeRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedRef {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnumValue {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IntrospectedType {
+    kind: TypeKind,
+    name: String,
+    fields: Option<Vec<FieldDef>>,
+    input_fields: Option<Vec<InputValue>>,
+    interfaces: Option<Vec<NamedRef>>,
+    enum_values: Option<Vec<EnumValue>>,
+    possible_types: Option<Vec<NamedRef>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectiveDef {
+    pub name: String,
+    #[serde(default)]
+    pub args: Vec<InputValue>,
+    #[serde(default)]
+    pub locations: Vec<String>,
+    #[serde(default)]
+    pub is_repeatable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Introspection {
+    query_type: Option<NamedRef>,
+    mutation_type: Option<NamedRef>,
+    subscription_type: Option<NamedRef>,
+    types: Vec<IntrospectedType>,
+    #[serde(default)]
+    directives: Vec<DirectiveDef>,
+}
+
+/// A named type with whatever members its kind has.
+#[derive(Debug)]
+pub struct SchemaType {
+    pub kind: TypeKind,
+    pub name: String,
+    pub fields: Vec<FieldDef>,
+    pub input_fields: Vec<InputValue>,
+    pub enum_values: HashSet<String>,
+    /// Object types a value of this type can be: itself for objects, the
+    /// implementations of an interface, or a union's members.
+    pub possible_types: HashSet<String>,
+}
+
+impl SchemaType {
+    pub fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self.kind,
+            TypeKind::Object | TypeKind::Interface | TypeKind::Union
+        )
+    }
+
+    pub fn is_input(&self) -> bool {
+        matches!(
+            self.kind,
+            TypeKind::Scalar | TypeKind::Enum | TypeKind::InputObject
+        )
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        matches!(self.kind, TypeKind::Scalar | TypeKind::Enum)
+    }
+}
+
+/// A schema rebuilt from the `__schema` object of an introspection response.
+#[derive(Debug)]
+pub struct Schema {
+    pub query_type: Option<String>,
+    pub mutation_type: Option<String>,
+    pub subscription_type: Option<String>,
+    pub types: HashMap<String, SchemaType>,
+    pub directives: HashMap<String, DirectiveDef>,
+    /// `__schema` and `__type`, which only the query root has.
+    pub meta_fields: Vec<FieldDef>,
+}
+
+impl Schema {
+    pub fn from_introspection(schema: &serde_json::Value) -> Result<Schema, String> {
+        let introspection: Introspection = serde_json::from_value(schema.clone())
+            .map_err(|e| format!("Not an introspection schema: {}", e))?;
+
+        let mut types: HashMap<String, SchemaType> = HashMap::new();
+        let mut implementations: Vec<(String, String)> = Vec::new();
+        for ty in introspection.types {
+            for interface in ty.interfaces.iter().flatten() {
+                implementations.push((interface.name.clone(), ty.name.clone()));
+            }
+            let mut possible_types: HashSet<String> = ty
+                .possible_types
+                .into_iter()
+                .flatten()
+                .map(|t| t.name)
+                .collect();
+            if ty.kind == TypeKind::Object {
+                possible_types.insert(ty.name.clone());
+            }
+            types.insert(
+                ty.name.clone(),
+                SchemaType {
+                    kind: ty.kind,
+                    name: ty.name,
+                    fields: ty.fields.unwrap_or_default(),
+                    input_fields: ty.input_fields.unwrap_or_default(),
+                    enum_values: ty
+                        .enum_values
+                        .into_iter()
+                        .flatten()
+                        .map(|v| v.name)
+                        .collect(),
+                    possible_types,
+                },
+            );
+        }
+        // Some servers leave `possibleTypes` off interfaces
+        for (interface, object) in implementations {
+            if let Some(ty) = types.get_mut(&interface) {
+                ty.possible_types.insert(object);
+            }
+        }
+
+        let mut directives: HashMap<String, DirectiveDef> = introspection
+            .directives
+            .into_iter()
+            .map(|d| (d.name.clone(), d))
+            .collect();
+        for (name, locations) in [
+            ("skip", ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+            ("include", ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"]),
+        ] {
+            directives
+                .entry(name.to_string())
+                .or_insert_with(|| DirectiveDef {
+                    name: name.to_string(),
+                    args: vec![InputValue {
+                        name: "if".to_string(),
+                        ty: TypeRef::non_null(TypeRef::named("Boolean", TypeKind::Scalar)),
+                        default_value: None,
+                    }],
+                    locations: locations.iter().map(|l| l.to_string()).collect(),
+                    is_repeatable: false,
+                });
+        }
+
+        let meta_fields = vec![
+            FieldDef {
+                name: "__schema".to_string(),
+                args: Vec::new(),
+                ty: TypeRef::non_null(TypeRef::named("__Schema", TypeKind::Object)),
+            },
+            FieldDef {
+                name: "__type".to_string(),
+                args: vec![InputValue {
+                    name: "name".to_string(),
+                    ty: TypeRef::non_null(TypeRef::named("String", TypeKind::Scalar)),
+                    default_value: None,
+                }],
+                ty: TypeRef::named("__Type", TypeKind::Object),
+            },
+        ];
+
+        Ok(Schema {
+            query_type: introspection.query_type.map(|t| t.name),
+            mutation_type: introspection.mutation_type.map(|t| t.name),
+            subscription_type: introspection.subscription_type.map(|t| t.name),
+            types,
+            directives,
+            meta_fields,
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SchemaType> {
+        self.types.get(name)
+    }
+}
@@ -0,0 +1,1225 @@
+use super::parser::{
+    Argument, Definition, Directive, Document, Field, Fragment, Operation, OperationKind, Pos,
+    Selection, SelectionSet, Type, Value, ValueKind,
+};
+use super::schema::{InputValue, Schema, SchemaType, TypeKind, TypeRef};
+use super::GraphqlError;
+use std::collections::{HashMap, HashSet};
+
+/// A variable read in an argument or input field.
+struct Usage {
+    name: String,
+    /// The type the position expects; `None` where the position itself is unknown.
+    expected: Option<TypeRef>,
+    /// The argument or input field has a default, so a nullable variable may fill a non-null slot.
+    location_default: bool,
+    pos: Pos,
+}
+
+/// What an operation or fragment body refers to, for the checks that need
+/// to follow fragment spreads.
+#[derive(Default)]
+struct Scope {
+    usages: Vec<Usage>,
+    spreads: Vec<String>,
+}
+
+/// Checks a parsed document against a schema, following the validation
+/// rules of the GraphQL spec (§5) that can be decided from introspection.
+/// `operation_name` is the one the request will ask to run.
+pub fn validate(
+    schema: &Schema,
+    document: &Document,
+    operation_name: Option<&str>,
+) -> Vec<GraphqlError> {
+    let mut validator = Validator {
+        schema,
+        fragments: HashMap::new(),
+        errors: Vec::new(),
+        scope: Scope::default(),
+    };
+    validator.document(document, operation_name);
+    validator.errors
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a Fragment>,
+    errors: Vec<GraphqlError>,
+    scope: Scope,
+}
+
+impl<'a> Validator<'a> {
+    fn error(&mut self, pos: Pos, message: String) {
+        self.errors.push(GraphqlError::at(pos, message));
+    }
+
+    fn document(&mut self, document: &'a Document, operation_name: Option<&str>) {
+        let mut operations = Vec::new();
+        for definition in &document.definitions {
+            match definition {
+                Definition::Operation(operation) => operations.push(operation),
+                Definition::Fragment(fragment) => {
+                    if self.fragments.contains_key(fragment.name.value.as_str()) {
+                        self.error(
+                            fragment.name.pos,
+                            format!(
+                                "There can be only one fragment named \"{}\".",
+                                fragment.name.value
+                            ),
+                        );
+                    } else {
+                        self.fragments.insert(&fragment.name.value, fragment);
+                    }
+                }
+            }
+        }
+
+        self.operation_names(&operations, operation_name);
+
+        let mut fragment_scopes: HashMap<&str, Scope> = HashMap::new();
+        for fragment in self.fragments.values().copied().collect::<Vec<_>>() {
+            self.fragment(fragment);
+            fragment_scopes.insert(&fragment.name.value, std::mem::take(&mut self.scope));
+        }
+
+        let mut used_fragments: HashSet<String> = HashSet::new();
+        for operation in operations {
+            self.operation(operation);
+            let scope = std::mem::take(&mut self.scope);
+            let mut usages: Vec<&Usage> = scope.usages.iter().collect();
+            let mut pending: Vec<&str> = scope.spreads.iter().map(String::as_str).collect();
+            let mut reached = HashSet::new();
+            while let Some(name) = pending.pop() {
+                if !reached.insert(name) {
+                    continue;
+                }
+                if let Some(fragment_scope) = fragment_scopes.get(name) {
+                    usages.extend(fragment_scope.usages.iter());
+                    pending.extend(fragment_scope.spreads.iter().map(String::as_str));
+                }
+            }
+            self.variables(operation, &usages);
+            used_fragments.extend(reached.into_iter().map(str::to_string));
+        }
+
+        let mut unused: Vec<&Fragment> = self
+            .fragments
+            .values()
+            .filter(|f| !used_fragments.contains(&f.name.value))
+            .copied()
+            .collect();
+        unused.sort_by_key(|f| (f.pos.line, f.pos.column));
+        for fragment in unused {
+            self.error(
+                fragment.pos,
+                format!("Fragment \"{}\" is never used.", fragment.name.value),
+            );
+        }
+
+        self.fragment_cycles(&fragment_scopes);
+    }
+
+    fn operation_names(&mut self, operations: &[&'a Operation], operation_name: Option<&str>) {
+        let mut seen = HashSet::new();
+        for operation in operations {
+            match operation.name {
+                Some(ref name) if !seen.insert(name.value.as_str()) => self.error(
+                    name.pos,
+                    format!("There can be only one operation named \"{}\".", name.value),
+                ),
+                None if operations.len() > 1 => self.error(
+                    operation.pos,
+                    "This anonymous operation must be the only defined operation.".to_string(),
+                ),
+                _ => {}
+            }
+        }
+
+        let first = operations
+            .first()
+            .map_or(Pos { line: 1, column: 1 }, |o| o.pos);
+        match operation_name.filter(|name| !name.is_empty()) {
+            Some(name) if !seen.contains(name) => {
+                self.error(first, format!("Unknown operation named \"{}\".", name))
+            }
+            None if operations.len() > 1 => self.error(
+                first,
+                "Must provide operation name if query contains multiple operations.".to_string(),
+            ),
+            _ => {}
+        }
+    }
+
+    fn operation(&mut self, operation: &'a Operation) {
+        let location = match operation.kind {
+            OperationKind::Query => "QUERY",
+            OperationKind::Mutation => "MUTATION",
+            OperationKind::Subscription => "SUBSCRIPTION",
+        };
+        self.directives(&operation.directives, location);
+        for variable in &operation.variables {
+            self.directives(&variable.directives, "VARIABLE_DEFINITION");
+        }
+
+        let schema = self.schema;
+        let root = match operation.kind {
+            OperationKind::Query => schema.query_type.as_deref(),
+            OperationKind::Mutation => schema.mutation_type.as_deref(),
+            OperationKind::Subscription => schema.subscription_type.as_deref(),
+        };
+        let Some(root) = root.and_then(|name| schema.get(name)) else {
+            self.error(
+                operation.pos,
+                format!(
+                    "Schema is not configured to execute {} operation.",
+                    operation.kind.as_str()
+                ),
+            );
+            return;
+        };
+
+        if operation.kind == OperationKind::Subscription {
+            let root_fields: HashSet<&str> = operation
+                .selection_set
+                .items
+                .iter()
+                .filter_map(|s| match s {
+                    Selection::Field(f) => Some(f.response_key()),
+                    _ => None,
+                })
+                .collect();
+            if root_fields.len() > 1 {
+                let name = operation.name.as_ref().map(|n| n.value.as_str());
+                self.error(
+                    operation.pos,
+                    match name {
+                        Some(name) => format!(
+                            "Subscription \"{}\" must select only one top level field.",
+                            name
+                        ),
+                        None => "Anonymous Subscription must select only one top level field."
+                            .to_string(),
+                    },
+                );
+            }
+        }
+
+        self.selection_set(&operation.selection_set, root);
+    }
+
+    fn fragment(&mut self, fragment: &'a Fragment) {
+        self.directives(&fragment.directives, "FRAGMENT_DEFINITION");
+        if let Some(ty) =
+            self.condition_type(&fragment.type_condition.value, fragment.type_condition.pos)
+        {
+            self.selection_set(&fragment.selection_set, ty);
+        }
+    }
+
+    /// Looks up a fragment's type condition, which must be an object, interface or union.
+    fn condition_type(&mut self, name: &str, pos: Pos) -> Option<&'a SchemaType> {
+        let schema = self.schema;
+        match schema.get(name) {
+            None => {
+                self.error(pos, format!("Unknown type \"{}\".", name));
+                None
+            }
+            Some(ty) if !ty.is_composite() => {
+                self.error(
+                    pos,
+                    format!(
+                        "Fragment cannot condition on non composite type \"{}\".",
+                        name
+                    ),
+                );
+                None
+            }
+            Some(ty) => Some(ty),
+        }
+    }
+
+    fn selection_set(&mut self, selection_set: &'a SelectionSet, parent: &'a SchemaType) {
+        self.field_conflicts(selection_set);
+        for selection in &selection_set.items {
+            match selection {
+                Selection::Field(field) => self.field(field, parent),
+                Selection::Spread(spread) => {
+                    self.directives(&spread.directives, "FRAGMENT_SPREAD");
+                    self.scope.spreads.push(spread.name.value.clone());
+                    let Some(fragment) = self.fragments.get(spread.name.value.as_str()).copied()
+                    else {
+                        self.error(
+                            spread.name.pos,
+                            format!("Unknown fragment \"{}\".", spread.name.value),
+                        );
+                        continue;
+                    };
+                    let schema = self.schema;
+                    if let Some(ty) = schema.get(&fragment.type_condition.value) {
+                        if ty.is_composite() && !overlaps(parent, ty) {
+                            self.error(
+                                spread.pos,
+                                format!(
+                                    "Fragment \"{}\" cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                                    spread.name.value, parent.name, ty.name
+                                ),
+                            );
+                        }
+                    }
+                }
+                Selection::Inline(inline) => {
+                    self.directives(&inline.directives, "INLINE_FRAGMENT");
+                    let ty = match inline.type_condition {
+                        None => parent,
+                        Some(ref condition) => {
+                            let Some(ty) = self.condition_type(&condition.value, condition.pos)
+                            else {
+                                continue;
+                            };
+                            if !overlaps(parent, ty) {
+                                self.error(
+                                    inline.pos,
+                                    format!(
+                                        "Fragment cannot be spread here as objects of type \"{}\" can never be of type \"{}\".",
+                                        parent.name, ty.name
+                                    ),
+                                );
+                            }
+                            ty
+                        }
+                    };
+                    self.selection_set(&inline.selection_set, ty);
+                }
+            }
+        }
+    }
+
+    /// Two fields at the same level that answer to one response key must be the same field.
+    fn field_conflicts(&mut self, selection_set: &SelectionSet) {
+        let mut by_key: HashMap<&str, &Field> = HashMap::new();
+        for selection in &selection_set.items {
+            let Selection::Field(field) = selection else {
+                continue;
+            };
+            let key = field.response_key();
+            let Some(other) = by_key.get(key) else {
+                by_key.insert(key, field);
+                continue;
+            };
+            let reason = if other.name.value != field.name.value {
+                format!(
+                    "\"{}\" and \"{}\" are different fields",
+                    other.name.value, field.name.value
+                )
+            } else if arguments_text(&other.arguments) != arguments_text(&field.arguments) {
+                "they have differing arguments".to_string()
+            } else {
+                continue;
+            };
+            self.error(
+                field.name.pos,
+                format!(
+                    "Fields \"{}\" conflict because {}. Use different aliases on the fields to fetch both if this was intentional.",
+                    key, reason
+                ),
+            );
+        }
+    }
+
+    fn field(&mut self, field: &'a Field, parent: &'a SchemaType) {
+        self.directives(&field.directives, "FIELD");
+        let name = field.name.value.as_str();
+
+        if name == "__typename" {
+            self.arguments(
+                &field.arguments,
+                &[],
+                &format!("field \"{}.{}\"", parent.name, name),
+                &format!("Field \"{}\"", name),
+                field.name.pos,
+            );
+            if field.selection_set.is_some() {
+                self.error(
+                    field.name.pos,
+                    format!(
+                        "Field \"{}\" must not have a selection since type \"String!\" has no subfields.",
+                        name
+                    ),
+                );
+            }
+            return;
+        }
+
+        let schema = self.schema;
+        let is_query_root = schema.query_type.as_deref() == Some(parent.name.as_str());
+        let definition = parent.field(name).or_else(|| {
+            is_query_root
+                .then(|| schema.meta_fields.iter().find(|f| f.name == name))
+                .flatten()
+        });
+        let Some(definition) = definition else {
+            let hint = if parent.kind == TypeKind::Union {
+                " Did you mean to use an inline fragment?"
+            } else {
+                ""
+            };
+            self.error(
+                field.name.pos,
+                format!(
+                    "Cannot query field \"{}\" on type \"{}\".{}",
+                    name, parent.name, hint
+                ),
+            );
+            // Variables read here still count as used
+            for argument in &field.arguments {
+                self.value(&argument.value, None, false);
+            }
+            return;
+        };
+
+        self.arguments(
+            &field.arguments,
+            &definition.args,
+            &format!("field \"{}.{}\"", parent.name, name),
+            &format!("Field \"{}\"", name),
+            field.name.pos,
+        );
+
+        let Some(ty) = schema.get(definition.ty.named_type()) else {
+            return;
+        };
+        match field.selection_set {
+            Some(ref selection_set) if ty.is_leaf() => self.error(
+                selection_set.pos,
+                format!(
+                    "Field \"{}\" must not have a selection since type \"{}\" has no subfields.",
+                    name, definition.ty
+                ),
+            ),
+            None if ty.is_composite() => self.error(
+                field.name.pos,
+                format!(
+                    "Field \"{}\" of type \"{}\" must have a selection of subfields. Did you mean \"{} {{ ... }}\"?",
+                    name, definition.ty, name
+                ),
+            ),
+            Some(ref selection_set) if ty.is_composite() => self.selection_set(selection_set, ty),
+            _ => {}
+        }
+    }
+
+    /// Checks arguments against their definitions. `owner` names the field or
+    /// directive in "unknown argument" errors, `subject` starts "missing" ones.
+    fn arguments(
+        &mut self,
+        arguments: &[Argument],
+        definitions: &[InputValue],
+        owner: &str,
+        subject: &str,
+        pos: Pos,
+    ) {
+        let mut seen = HashSet::new();
+        for argument in arguments {
+            let name = argument.name.value.as_str();
+            if !seen.insert(name) {
+                self.error(
+                    argument.name.pos,
+                    format!("There can be only one argument named \"{}\".", name),
+                );
+                continue;
+            }
+            match definitions.iter().find(|d| d.name == name) {
+                Some(definition) => self.value(
+                    &argument.value,
+                    Some(&definition.ty),
+                    definition.default_value.is_some(),
+                ),
+                None => {
+                    self.error(
+                        argument.name.pos,
+                        format!("Unknown argument \"{}\" on {}.", name, owner),
+                    );
+                    self.value(&argument.value, None, false);
+                }
+            }
+        }
+        for definition in definitions {
+            if definition.is_required() && !seen.contains(definition.name.as_str()) {
+                self.error(
+                    pos,
+                    format!(
+                        "{} argument \"{}\" of type \"{}\" is required, but it was not provided.",
+                        subject, definition.name, definition.ty
+                    ),
+                );
+            }
+        }
+    }
+
+    fn directives(&mut self, directives: &[Directive], location: &str) {
+        let schema = self.schema;
+        let mut seen = HashSet::new();
+        for directive in directives {
+            let name = directive.name.value.as_str();
+            let Some(definition) = schema.directives.get(name) else {
+                self.error(directive.pos, format!("Unknown directive \"@{}\".", name));
+                for argument in &directive.arguments {
+                    self.value(&argument.value, None, false);
+                }
+                continue;
+            };
+            if !definition.locations.iter().any(|l| l == location) {
+                self.error(
+                    directive.pos,
+                    format!("Directive \"@{}\" may not be used on {}.", name, location),
+                );
+            }
+            if !seen.insert(name) && !definition.is_repeatable {
+                self.error(
+                    directive.pos,
+                    format!(
+                        "The directive \"@{}\" can only be used once at this location.",
+                        name
+                    ),
+                );
+            }
+            self.arguments(
+                &directive.arguments,
+                &definition.args,
+                &format!("directive \"@{}\"", name),
+                &format!("Directive \"@{}\"", name),
+                directive.pos,
+            );
+        }
+    }
+
+    /// Checks a literal against the input type expected where it appears,
+    /// and records any variables it reads.
+    fn value(&mut self, value: &Value, expected: Option<&TypeRef>, location_default: bool) {
+        if let ValueKind::Variable(ref name) = value.kind {
+            self.scope.usages.push(Usage {
+                name: name.clone(),
+                expected: expected.cloned(),
+                location_default,
+                pos: value.pos,
+            });
+            return;
+        }
+        let Some(expected) = expected else {
+            // Still collect the variables nested in lists and objects
+            match value.kind {
+                ValueKind::List(ref items) => {
+                    for item in items {
+                        self.value(item, None, false);
+                    }
+                }
+                ValueKind::Object(ref fields) => {
+                    for (_, field) in fields {
+                        self.value(field, None, false);
+                    }
+                }
+                _ => {}
+            }
+            return;
+        };
+
+        let mismatch =
+            |value: &Value| format!("Expected value of type \"{}\", found {}.", expected, value);
+        match (expected.kind, expected.inner()) {
+            (TypeKind::NonNull, Some(inner)) => {
+                if let ValueKind::Null = value.kind {
+                    self.error(value.pos, mismatch(value));
+                } else {
+                    self.value(value, Some(inner), false);
+                }
+                return;
+            }
+            _ if matches!(value.kind, ValueKind::Null) => return,
+            (TypeKind::List, Some(inner)) => {
+                match value.kind {
+                    ValueKind::List(ref items) => {
+                        for item in items {
+                            self.value(item, Some(inner), false);
+                        }
+                    }
+                    // A single value is coerced to a list of one
+                    _ => self.value(value, Some(inner), false),
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        let schema = self.schema;
+        let Some(ty) = schema.get(expected.named_type()) else {
+            return;
+        };
+        match ty.kind {
+            TypeKind::Scalar => {
+                let valid = match ty.name.as_str() {
+                    "Int" => {
+                        matches!(value.kind, ValueKind::Int(ref raw) if raw.parse::<i32>().is_ok())
+                    }
+                    "Float" => matches!(value.kind, ValueKind::Int(_) | ValueKind::Float(_)),
+                    "String" => matches!(value.kind, ValueKind::String(_)),
+                    "Boolean" => matches!(value.kind, ValueKind::Boolean(_)),
+                    "ID" => matches!(value.kind, ValueKind::String(_) | ValueKind::Int(_)),
+                    // Custom scalars accept any literal
+                    _ => true,
+                };
+                if !valid {
+                    self.error(value.pos, mismatch(value));
+                }
+            }
+            TypeKind::Enum => match value.kind {
+                ValueKind::Enum(ref name) if !ty.enum_values.contains(name) => self.error(
+                    value.pos,
+                    format!("Value \"{}\" does not exist in \"{}\" enum.", name, ty.name),
+                ),
+                ValueKind::Enum(_) => {}
+                _ => self.error(
+                    value.pos,
+                    format!(
+                        "Enum \"{}\" cannot represent non-enum value: {}.",
+                        ty.name, value
+                    ),
+                ),
+            },
+            TypeKind::InputObject => {
+                let ValueKind::Object(ref fields) = value.kind else {
+                    self.error(value.pos, mismatch(value));
+                    return;
+                };
+                let mut seen = HashSet::new();
+                for (name, field) in fields {
+                    if !seen.insert(name.value.as_str()) {
+                        self.error(
+                            name.pos,
+                            format!(
+                                "There can be only one input field named \"{}\".",
+                                name.value
+                            ),
+                        );
+                        continue;
+                    }
+                    match ty.input_fields.iter().find(|f| f.name == name.value) {
+                        Some(definition) => self.value(
+                            field,
+                            Some(&definition.ty),
+                            definition.default_value.is_some(),
+                        ),
+                        None => {
+                            self.error(
+                                name.pos,
+                                format!(
+                                    "Field \"{}\" is not defined by type \"{}\".",
+                                    name.value, ty.name
+                                ),
+                            );
+                            self.value(field, None, false);
+                        }
+                    }
+                }
+                for definition in &ty.input_fields {
+                    if definition.is_required() && !seen.contains(definition.name.as_str()) {
+                        self.error(
+                            value.pos,
+                            format!(
+                                "Field \"{}.{}\" of required type \"{}\" was not provided.",
+                                ty.name, definition.name, definition.ty
+                            ),
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Checks an operation's variable definitions against the variables its
+    /// body, and the fragments it spreads, actually read.
+    fn variables(&mut self, operation: &'a Operation, usages: &[&Usage]) {
+        let schema = self.schema;
+        let operation_label = match operation.name {
+            Some(ref name) => format!(" by operation \"{}\"", name.value),
+            None => String::new(),
+        };
+        let mut defined: HashMap<&str, Option<TypeRef>> = HashMap::new();
+        for variable in &operation.variables {
+            let name = variable.name.value.as_str();
+            if defined.contains_key(name) {
+                self.error(
+                    variable.name.pos,
+                    format!("There can be only one variable named \"${}\".", name),
+                );
+                continue;
+            }
+            let named = variable.ty.named();
+            let ty = match schema.get(&named.value) {
+                None => {
+                    self.error(named.pos, format!("Unknown type \"{}\".", named.value));
+                    None
+                }
+                Some(ty) if !ty.is_input() => {
+                    self.error(
+                        named.pos,
+                        format!(
+                            "Variable \"${}\" cannot be non-input type \"{}\".",
+                            name, variable.ty
+                        ),
+                    );
+                    None
+                }
+                Some(_) => type_ref(schema, &variable.ty),
+            };
+            if let (Some(ref ty), Some(ref default)) = (&ty, &variable.default) {
+                self.value(default, Some(ty), false);
+            }
+            // A non-null default lets a nullable variable fill a non-null slot
+            let ty = ty.map(|ty| match variable.default {
+                Some(ref default)
+                    if !ty.is_non_null() && !matches!(default.kind, ValueKind::Null) =>
+                {
+                    TypeRef::non_null(ty)
+                }
+                _ => ty,
+            });
+            defined.insert(name, ty);
+        }
+
+        let mut used = HashSet::new();
+        for usage in usages {
+            used.insert(usage.name.as_str());
+            let Some(variable_type) = defined.get(usage.name.as_str()) else {
+                self.error(
+                    usage.pos,
+                    format!(
+                        "Variable \"${}\" is not defined{}.",
+                        usage.name, operation_label
+                    ),
+                );
+                continue;
+            };
+            let (Some(variable_type), Some(expected)) = (variable_type, &usage.expected) else {
+                continue;
+            };
+            let variable_type = if usage.location_default && !variable_type.is_non_null() {
+                TypeRef::non_null(variable_type.clone())
+            } else {
+                variable_type.clone()
+            };
+            if !is_subtype(&variable_type, expected) {
+                let declared = operation
+                    .variables
+                    .iter()
+                    .find(|v| v.name.value == usage.name)
+                    .map(|v| v.ty.to_string())
+                    .unwrap_or_default();
+                self.error(
+                    usage.pos,
+                    format!(
+                        "Variable \"${}\" of type \"{}\" used in position expecting type \"{}\".",
+                        usage.name, declared, expected
+                    ),
+                );
+            }
+        }
+
+        for variable in &operation.variables {
+            if !used.contains(variable.name.value.as_str()) {
+                let label = match operation.name {
+                    Some(ref name) => format!(" in operation \"{}\"", name.value),
+                    None => String::new(),
+                };
+                self.error(
+                    variable.pos,
+                    format!(
+                        "Variable \"${}\" is never used{}.",
+                        variable.name.value, label
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Reports each fragment that spreads itself, directly or through others.
+    fn fragment_cycles(&mut self, scopes: &HashMap<&str, Scope>) {
+        let mut names: Vec<&str> = scopes.keys().copied().collect();
+        names.sort();
+        let mut done = HashSet::new();
+        for name in names {
+            let mut path = Vec::new();
+            self.find_cycles(name, scopes, &mut path, &mut done);
+        }
+    }
+
+    fn find_cycles<'s>(
+        &mut self,
+        name: &'s str,
+        scopes: &'s HashMap<&str, Scope>,
+        path: &mut Vec<&'s str>,
+        done: &mut HashSet<&'s str>,
+    ) {
+        if done.contains(name) {
+            return;
+        }
+        if let Some(start) = path.iter().position(|n| *n == name) {
+            let via = &path[start + 1..];
+            let pos = self
+                .fragments
+                .get(name)
+                .map_or(Pos { line: 1, column: 1 }, |f| f.pos);
+            self.error(
+                pos,
+                if via.is_empty() {
+                    format!("Cannot spread fragment \"{}\" within itself.", name)
+                } else {
+                    format!(
+                        "Cannot spread fragment \"{}\" within itself via {}.",
+                        name,
+                        via.iter()
+                            .map(|n| format!("\"{}\"", n))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                },
+            );
+            return;
+        }
+        let Some(scope) = scopes.get(name) else {
+            return;
+        };
+        path.push(name);
+        for spread in &scope.spreads {
+            self.find_cycles(spread, scopes, path, done);
+        }
+        path.pop();
+        done.insert(name);
+    }
+}
+
+/// Whether some object could be of both types.
+fn overlaps(a: &SchemaType, b: &SchemaType) -> bool {
+    a.name == b.name
+        || a.possible_types
+            .iter()
+            .any(|t| b.possible_types.contains(t))
+}
+
+/// Resolves a type written in the query against the schema.
+fn type_ref(schema: &Schema, ty: &Type) -> Option<TypeRef> {
+    match ty {
+        Type::Named(name) => schema
+            .get(&name.value)
+            .map(|t| TypeRef::named(&t.name, t.kind)),
+        Type::List(inner, _) => Some(TypeRef::list(type_ref(schema, inner)?)),
+        Type::NonNull(inner) => Some(TypeRef::non_null(type_ref(schema, inner)?)),
+    }
+}
+
+/// Whether a variable of type `sub` can be passed where `sup` is expected.
+fn is_subtype(sub: &TypeRef, sup: &TypeRef) -> bool {
+    match (sub.kind, sub.inner(), sup.kind, sup.inner()) {
+        (TypeKind::NonNull, Some(sub), TypeKind::NonNull, Some(sup)) => is_subtype(sub, sup),
+        (_, _, TypeKind::NonNull, _) => false,
+        (TypeKind::NonNull, Some(sub), _, _) => is_subtype(sub, sup),
+        (TypeKind::List, Some(sub), TypeKind::List, Some(sup)) => is_subtype(sub, sup),
+        (TypeKind::List, _, _, _) | (_, _, TypeKind::List, _) => false,
+        _ => sub.named_type() == sup.named_type(),
+    }
+}
+
+fn arguments_text(arguments: &[Argument]) -> String {
+    let mut parts: Vec<String> = arguments
+        .iter()
+        .map(|a| format!("{}: {}", a.name.value, a.value))
+        .collect();
+    parts.sort();
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::parser::parse;
+    use serde_json::{json, Value as Json};
+
+    const KINDS: &[(&str, &str)] = &[
+        ("ID", "SCALAR"),
+        ("String", "SCALAR"),
+        ("Int", "SCALAR"),
+        ("Float", "SCALAR"),
+        ("Boolean", "SCALAR"),
+        ("Node", "INTERFACE"),
+        ("SearchResult", "UNION"),
+        ("Role", "ENUM"),
+        ("UserFilter", "INPUT_OBJECT"),
+        ("CreateUserInput", "INPUT_OBJECT"),
+    ];
+
+    /// An introspection type reference from SDL notation, e.g. `[ID!]!`.
+    fn type_json(sdl: &str) -> Json {
+        if let Some(inner) = sdl.strip_suffix('!') {
+            return json!({ "kind": "NON_NULL", "name": null, "ofType": type_json(inner) });
+        }
+        if let Some(inner) = sdl.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            return json!({ "kind": "LIST", "name": null, "ofType": type_json(inner) });
+        }
+        let kind = KINDS
+            .iter()
+            .find(|(name, _)| *name == sdl)
+            .map_or("OBJECT", |(_, kind)| kind);
+        json!({ "kind": kind, "name": sdl, "ofType": null })
+    }
+
+    /// Fields or input values as `name(arg: Type = default): Type`.
+    fn values_json(defs: &[&str]) -> Json {
+        defs.iter()
+            .map(|def| {
+                let (head, ty) = def.rsplit_once(": ").unwrap();
+                let (ty, default) = match ty.split_once(" = ") {
+                    Some((ty, default)) => (ty, Some(default)),
+                    None => (ty, None),
+                };
+                let (name, args) = match head.split_once('(') {
+                    Some((name, args)) => {
+                        let args: Vec<&str> = args.trim_end_matches(')').split(", ").collect();
+                        (name, values_json(&args))
+                    }
+                    None => (head, json!([])),
+                };
+                json!({
+                    "name": name,
+                    "args": args,
+                    "type": type_json(ty),
+                    "defaultValue": default,
+                })
+            })
+            .collect()
+    }
+
+    fn object(name: &str, interfaces: &[&str], fields: &[&str]) -> Json {
+        let interfaces: Vec<Json> = interfaces.iter().map(|i| json!({ "name": i })).collect();
+        json!({ "kind": "OBJECT", "name": name, "fields": values_json(fields), "interfaces": interfaces })
+    }
+
+    /// A small schema: users and posts behind a `Node` interface and a
+    /// `SearchResult` union, an enum, two input objects and a `@cached` directive.
+    fn schema() -> Schema {
+        let scalars = ["ID", "String", "Int", "Float", "Boolean"]
+            .map(|name| json!({ "kind": "SCALAR", "name": name }));
+        let mut types = vec![
+            object(
+                "Query",
+                &[],
+                &[
+                    "me: User",
+                    "user(id: ID!): User",
+                    "users(filter: UserFilter, first: Int = 10): [User!]!",
+                    "usersById(ids: [ID!]!): [User]",
+                    "node(id: ID!): Node",
+                    "search(term: String!): [SearchResult!]!",
+                ],
+            ),
+            object(
+                "Mutation",
+                &[],
+                &["createUser(input: CreateUserInput!): User"],
+            ),
+            object(
+                "Subscription",
+                &[],
+                &["userCreated: User", "userDeleted: ID"],
+            ),
+            object(
+                "User",
+                &["Node"],
+                &[
+                    "id: ID!",
+                    "name: String",
+                    "role: Role",
+                    "friends(first: Int): [User!]!",
+                    "posts: [Post!]!",
+                ],
+            ),
+            object("Post", &["Node"], &["id: ID!", "title: String!"]),
+            json!({ "kind": "INTERFACE", "name": "Node", "fields": values_json(&["id: ID!"]) }),
+            json!({
+                "kind": "UNION",
+                "name": "SearchResult",
+                "possibleTypes": [{ "name": "User" }, { "name": "Post" }],
+            }),
+            json!({ "kind": "ENUM", "name": "Role", "enumValues": [{ "name": "ADMIN" }, { "name": "USER" }] }),
+            json!({
+                "kind": "INPUT_OBJECT",
+                "name": "UserFilter",
+                "inputFields": values_json(&["role: Role", "nameContains: String"]),
+            }),
+            json!({
+                "kind": "INPUT_OBJECT",
+                "name": "CreateUserInput",
+                "inputFields": values_json(&["name: String!", "role: Role = USER"]),
+            }),
+        ];
+        types.extend(scalars);
+        Schema::from_introspection(&json!({
+            "queryType": { "name": "Query" },
+            "mutationType": { "name": "Mutation" },
+            "subscriptionType": { "name": "Subscription" },
+            "types": types,
+            "directives": [{
+                "name": "cached",
+                "locations": ["QUERY", "FIELD"],
+                "args": values_json(&["ttl: Int!"]),
+            }],
+        }))
+        .unwrap()
+    }
+
+    fn errors(schema: &Schema, query: &str, operation_name: Option<&str>) -> Vec<String> {
+        let document = parse(query).unwrap_or_else(|e| panic!("{:?}: {}", query, e.message));
+        validate(schema, &document, operation_name)
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_operations() {
+        let schema = schema();
+        let cases = [
+            ("{ me { id name role } }", None),
+            ("{ user(id: 1) { id } u2: user(id: \"2\") { id } }", None),
+            ("query Q($id: ID!) { user(id: $id) { ...UserFields } } fragment UserFields on User { name friends(first: 2) { id } }", None),
+            ("{ search(term: \"a\") { __typename ... on User { name } ... on Post { title } } }", None),
+            ("{ node(id: 1) { id ... on User { name } ...PostFields } } fragment PostFields on Post { title }", None),
+            ("{ me { ... { id } ... on Node { id } } }", None),
+            ("query($f: UserFilter, $n: Int) { users(filter: $f, first: $n) { id } }", None),
+            ("query($r: Role = ADMIN) { users(filter: { role: $r, nameContains: \"a\" }) { id } }", None),
+            ("query($ids: [ID!]!) { usersById(ids: $ids) { id } }", None),
+            ("{ usersById(ids: \"1\") { id } }", None),
+            ("{ users(first: null) { id } }", None),
+            ("mutation { createUser(input: { name: \"a\" }) { id } }", None),
+            ("mutation($name: String = \"n\") { createUser(input: { name: $name }) { id } }", None),
+            ("subscription { userCreated { id } }", None),
+            ("query A { me { id } } query B { me { name } }", Some("B")),
+            ("query @cached(ttl: 60) { me @include(if: true) { id @skip(if: false) } }", None),
+            ("query($show: Boolean!) { me @include(if: $show) { id } }", None),
+            ("{ me { a: id b: name id id } }", None),
+            ("{ __typename __type(name: \"User\") { name } }", None),
+        ];
+        for (query, operation_name) in cases {
+            assert_eq!(
+                errors(&schema, query, operation_name),
+                Vec::<String>::new(),
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_operations() {
+        let schema = schema();
+        let cases: &[(&str, Option<&str>, &[&str])] = &[
+            // Fields
+            ("{ me { email } }", None, &["Cannot query field \"email\" on type \"User\"."]),
+            (
+                "{ search(term: \"a\") { title } }",
+                None,
+                &["Cannot query field \"title\" on type \"SearchResult\". Did you mean to use an inline fragment?"],
+            ),
+            (
+                "{ me }",
+                None,
+                &["Field \"me\" of type \"User\" must have a selection of subfields. Did you mean \"me { ... }\"?"],
+            ),
+            (
+                "{ me { name { x } } }",
+                None,
+                &["Field \"name\" must not have a selection since type \"String\" has no subfields."],
+            ),
+            (
+                "{ me { a: id a: name } }",
+                None,
+                &["Fields \"a\" conflict because \"id\" and \"name\" are different fields. Use different aliases on the fields to fetch both if this was intentional."],
+            ),
+            (
+                "{ me { friends(first: 1) { id } friends(first: 2) { id } } }",
+                None,
+                &["Fields \"friends\" conflict because they have differing arguments. Use different aliases on the fields to fetch both if this was intentional."],
+            ),
+            // Arguments and values
+            (
+                "{ user { id } }",
+                None,
+                &["Field \"user\" argument \"id\" of type \"ID!\" is required, but it was not provided."],
+            ),
+            ("{ user(id: 1, nope: 2) { id } }", None, &["Unknown argument \"nope\" on field \"Query.user\"."]),
+            ("{ user(id: 1, id: 2) { id } }", None, &["There can be only one argument named \"id\"."]),
+            ("{ user(id: 1.5) { id } }", None, &["Expected value of type \"ID\", found 1.5."]),
+            ("{ users(first: \"ten\") { id } }", None, &["Expected value of type \"Int\", found \"ten\"."]),
+            ("{ users(first: 2147483648) { id } }", None, &["Expected value of type \"Int\", found 2147483648."]),
+            ("{ users(filter: { role: OWNER }) { id } }", None, &["Value \"OWNER\" does not exist in \"Role\" enum."]),
+            (
+                "{ users(filter: { role: \"ADMIN\" }) { id } }",
+                None,
+                &["Enum \"Role\" cannot represent non-enum value: \"ADMIN\"."],
+            ),
+            ("{ users(filter: 1) { id } }", None, &["Expected value of type \"UserFilter\", found 1."]),
+            (
+                "mutation { createUser(input: { role: ADMIN }) { id } }",
+                None,
+                &["Field \"CreateUserInput.name\" of required type \"String!\" was not provided."],
+            ),
+            (
+                "mutation { createUser(input: { name: \"a\", age: 3 }) { id } }",
+                None,
+                &["Field \"age\" is not defined by type \"CreateUserInput\"."],
+            ),
+            (
+                "mutation { createUser(input: { name: \"a\", name: \"b\" }) { id } }",
+                None,
+                &["There can be only one input field named \"name\"."],
+            ),
+            (
+                "mutation { createUser(input: null) { id } }",
+                None,
+                &["Expected value of type \"CreateUserInput!\", found null."],
+            ),
+            // Variables
+            ("{ user(id: $id) { id } }", None, &["Variable \"$id\" is not defined."]),
+            (
+                "query Q { me { ...F } } fragment F on User { friends(first: $n) { id } }",
+                None,
+                &["Variable \"$n\" is not defined by operation \"Q\"."],
+            ),
+            ("query Q($id: ID!) { me { id } }", None, &["Variable \"$id\" is never used in operation \"Q\"."]),
+            (
+                "query($id: ID) { user(id: $id) { id } }",
+                None,
+                &["Variable \"$id\" of type \"ID\" used in position expecting type \"ID!\"."],
+            ),
+            (
+                "query($ids: [ID]) { usersById(ids: $ids) { id } }",
+                None,
+                &["Variable \"$ids\" of type \"[ID]\" used in position expecting type \"[ID!]!\"."],
+            ),
+            (
+                "query($u: User) { me { id } }",
+                None,
+                &["Variable \"$u\" cannot be non-input type \"User\".", "Variable \"$u\" is never used."],
+            ),
+            ("query($x: Nope) { user(id: $x) { id } }", None, &["Unknown type \"Nope\"."]),
+            (
+                "query($id: ID!, $id: ID!) { user(id: $id) { id } }",
+                None,
+                &["There can be only one variable named \"$id\"."],
+            ),
+            (
+                "query($r: Role = \"ADMIN\") { users(filter: { role: $r }) { id } }",
+                None,
+                &["Enum \"Role\" cannot represent non-enum value: \"ADMIN\"."],
+            ),
+            // Fragments
+            ("{ me { ...F } }", None, &["Unknown fragment \"F\"."]),
+            ("{ me { id } } fragment F on User { id }", None, &["Fragment \"F\" is never used."]),
+            (
+                "{ me { ...F } } fragment F on User { id } fragment F on User { name }",
+                None,
+                &["There can be only one fragment named \"F\"."],
+            ),
+            (
+                "{ me { ...F } } fragment F on Post { id }",
+                None,
+                &["Fragment \"F\" cannot be spread here as objects of type \"User\" can never be of type \"Post\"."],
+            ),
+            (
+                "{ me { ... on Post { id } } }",
+                None,
+                &["Fragment cannot be spread here as objects of type \"User\" can never be of type \"Post\"."],
+            ),
+            (
+                "{ me { ...F } } fragment F on Role { id }",
+                None,
+                &["Fragment cannot condition on non composite type \"Role\"."],
+            ),
+            ("{ me { ... on Nope { id } } }", None, &["Unknown type \"Nope\"."]),
+            (
+                "{ me { ...A } } fragment A on User { ...B } fragment B on User { ...A }",
+                None,
+                &["Cannot spread fragment \"A\" within itself via \"B\"."],
+            ),
+            ("{ me { ...A } } fragment A on User { ...A }", None, &["Cannot spread fragment \"A\" within itself."]),
+            // Directives
+            ("{ me @unknown { id } }", None, &["Unknown directive \"@unknown\"."]),
+            ("query @skip(if: true) { me { id } }", None, &["Directive \"@skip\" may not be used on QUERY."]),
+            (
+                "{ me @skip(if: true) @skip(if: false) { id } }",
+                None,
+                &["The directive \"@skip\" can only be used once at this location."],
+            ),
+            (
+                "{ me { id @include } }",
+                None,
+                &["Directive \"@include\" argument \"if\" of type \"Boolean!\" is required, but it was not provided."],
+            ),
+            (
+                "query @cached(ttl: 1, max: 2) { me { id } }",
+                None,
+                &["Unknown argument \"max\" on directive \"@cached\"."],
+            ),
+            // Operations
+            (
+                "subscription { userCreated { id } userDeleted }",
+                None,
+                &["Anonymous Subscription must select only one top level field."],
+            ),
+            (
+                "query A { me { id } } query A { me { id } }",
+                Some("A"),
+                &["There can be only one operation named \"A\"."],
+            ),
+            (
+                "{ me { id } } query B { me { id } }",
+                Some("B"),
+                &["This anonymous operation must be the only defined operation."],
+            ),
+            (
+                "query A { me { id } } query B { me { id } }",
+                None,
+                &["Must provide operation name if query contains multiple operations."],
+            ),
+            ("query A { me { id } }", Some("C"), &["Unknown operation named \"C\"."]),
+        ];
+        for (query, operation_name, expected) in cases {
+            assert_eq!(
+                errors(&schema, query, *operation_name),
+                *expected,
+                "{}",
+                query
+            );
+        }
+    }
+
+    #[test]
+    fn locates_errors() {
+        let document = parse("query {\n  me {\n    email\n  }\n}").unwrap();
+        let errors = validate(&schema(), &document, None);
+        assert_eq!((errors[0].line, errors[0].column), (3, 5));
+    }
+
+    #[test]
+    fn needs_a_root_type_for_the_operation() {
+        let schema = Schema::from_introspection(&json!({
+            "queryType": { "name": "Query" },
+            "types": [object("Query", &[], &["a: Int"])],
+        }))
+        .unwrap();
+        assert_eq!(
+            errors(&schema, "mutation { a }", None),
+            ["Schema is not configured to execute mutation operation."]
+        );
+    }
+}
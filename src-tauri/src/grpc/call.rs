@@ -0,0 +1,247 @@
+//! Calls over HTTP/2 through the pooled clients, so proxies, TLS settings,
+//! DNS overrides and cookies apply as they do to requests.
+
+use super::codec::{self, Decoder, Status};
+use crate::http::client::{build_header_map, HeaderEntry, ResponseHeader};
+use crate::http::{tls, ClientSource};
+use http_body::Frame;
+use http_body_util::BodyExt;
+use prost::bytes::Bytes;
+use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE, TE};
+use reqwest::Url;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Compressions the decoder can undo, offered in `grpc-accept-encoding`.
+const ACCEPT_ENCODING: &str = "gzip, deflate";
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct GrpcTarget {
+    /// `grpc://` for cleartext HTTP/2, `grpcs://` for TLS; `http(s)://` also work.
+    pub url: String,
+    /// Inherited default headers, one layer per ancestor, as for HTTP requests.
+    #[serde(default)]
+    pub default_headers: Vec<Vec<HeaderEntry>>,
+    /// Sent as the call's metadata.
+    pub headers: Vec<HeaderEntry>,
+}
+
+impl GrpcTarget {
+    /// The URL a method is posted to: `/package.Service/Method` under the
+    /// target's path, which proxies sometimes need.
+    pub fn method_url(&self, service: &str, method: &str) -> Result<Url, String> {
+        let mut url =
+            Url::parse(&self.url).map_err(|e| format!("Invalid URL '{}': {}", self.url, e))?;
+        let scheme = match url.scheme() {
+            "grpc" | "http" => "http",
+            "grpcs" | "https" => "https",
+            other => {
+                return Err(format!(
+                    "Unsupported gRPC scheme '{}'; use grpc:// or grpcs://",
+                    other
+                ))
+            }
+        };
+        // The url crate refuses to switch between special and non-special schemes
+        if url.scheme() != scheme {
+            let rest = &self.url[url.scheme().len()..];
+            url = Url::parse(&format!("{}{}", scheme, rest))
+                .map_err(|e| format!("Invalid URL '{}': {}", self.url, e))?;
+        }
+        let path = format!(
+            "{}/{}/{}",
+            url.path().trim_end_matches('/'),
+            service,
+            method
+        );
+        url.set_path(&path);
+        url.set_query(None);
+        Ok(url)
+    }
+}
+
+/// A request body fed one framed message at a time; dropping the sender
+/// half-closes the call.
+struct Outgoing(mpsc::UnboundedReceiver<Vec<u8>>);
+
+impl http_body::Body for Outgoing {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        self.0
+            .poll_recv(cx)
+            .map(|frame| frame.map(|frame| Ok(Frame::data(Bytes::from(frame)))))
+    }
+}
+
+/// An open call, reading the server's messages as they arrive.
+pub struct Call {
+    pub headers: HeaderMap,
+    body: reqwest::Body,
+    encoding: Option<String>,
+    decoder: Decoder,
+    trailers: Option<HeaderMap>,
+    /// Set when the call failed before any body, e.g. an HTTP error.
+    early: Option<Status>,
+    done: bool,
+}
+
+/// `grpc-timeout` allows at most eight digits per unit.
+fn timeout_header(deadline: Duration) -> String {
+    let millis = deadline.as_millis().max(1);
+    if millis < 100_000_000 {
+        format!("{}m", millis)
+    } else {
+        format!("{}S", (millis / 1000).min(99_999_999))
+    }
+}
+
+/// Starts a call to `url`, sending whatever `outgoing` yields as the request
+/// stream. Returns once the server's headers arrive, so anything the server
+/// needs before it answers must already be queued.
+pub async fn open(
+    clients: &ClientSource,
+    target: &GrpcTarget,
+    url: Url,
+    deadline: Option<Duration>,
+    outgoing: mpsc::UnboundedReceiver<Vec<u8>>,
+) -> Result<Call, Status> {
+    let mut headers = build_header_map(&target.default_headers, &target.headers)
+        .map_err(|e| Status::new(codec::INTERNAL, e))?;
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/grpc"));
+    headers.insert(TE, HeaderValue::from_static("trailers"));
+    headers.insert(
+        "grpc-accept-encoding",
+        HeaderValue::from_static(ACCEPT_ENCODING),
+    );
+    headers.remove("grpc-encoding");
+    if let Some(deadline) = deadline {
+        if let Ok(value) = HeaderValue::from_str(&timeout_header(deadline)) {
+            headers.insert("grpc-timeout", value);
+        }
+    }
+
+    let client = clients
+        .client_for(&url, None)
+        .map_err(|e| Status::new(codec::INTERNAL, e))?;
+    let response = client
+        .post(url)
+        .headers(headers)
+        .body(reqwest::Body::wrap(Outgoing(outgoing)))
+        .send()
+        .await
+        .map_err(|e| Status::new(codec::UNAVAILABLE, tls::describe_send_error(&e)))?;
+
+    let status = response.status().as_u16();
+    let response = http::Response::from(response);
+    let (parts, body) = response.into_parts();
+    let headers = parts.headers;
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string();
+    // A status in the headers means a trailers-only response with no body
+    let early = match Status::from_headers(&headers) {
+        Some(status) => Some(status),
+        None if status != 200 => Some(Status::from_http(status)),
+        None if !content_type.starts_with("application/grpc") => Some(Status::new(
+            codec::UNKNOWN,
+            format!(
+                "Server answered with content type '{}'; it may not speak gRPC",
+                content_type
+            ),
+        )),
+        None => None,
+    };
+    let encoding = headers
+        .get("grpc-encoding")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_ascii_lowercase());
+
+    Ok(Call {
+        done: early.is_some(),
+        headers,
+        body,
+        encoding,
+        decoder: Decoder::default(),
+        trailers: None,
+        early,
+    })
+}
+
+impl Call {
+    /// The next message, or `None` once the server has finished.
+    pub async fn message(&mut self) -> Result<Option<Vec<u8>>, Status> {
+        loop {
+            if let Some(message) = self
+                .decoder
+                .next(self.encoding.as_deref())
+                .map_err(|e| Status::new(codec::INTERNAL, e))?
+            {
+                return Ok(Some(message));
+            }
+            if self.done {
+                return Ok(None);
+            }
+            match self.body.frame().await {
+                None => {
+                    self.done = true;
+                    if self.decoder.has_partial() {
+                        return Err(Status::new(
+                            codec::INTERNAL,
+                            "Response ended in the middle of a message",
+                        ));
+                    }
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Err(Status::new(
+                        codec::UNAVAILABLE,
+                        format!("Connection failed: {}", tls::error_chain(&e)),
+                    ));
+                }
+                Some(Ok(frame)) => match frame.into_data() {
+                    Ok(data) => self.decoder.push(&data),
+                    Err(frame) => {
+                        if let Ok(trailers) = frame.into_trailers() {
+                            self.trailers = Some(trailers);
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    /// How the call ended; only meaningful once `message` has returned `None`.
+    pub fn status(&self) -> Status {
+        if let Some(ref status) = self.early {
+            return status.clone();
+        }
+        self.trailers
+            .as_ref()
+            .and_then(Status::from_headers)
+            .unwrap_or_else(|| {
+                Status::new(
+                    codec::INTERNAL,
+                    "Server ended the call without a grpc-status",
+                )
+            })
+    }
+
+    pub fn trailers(&self) -> Vec<ResponseHeader> {
+        self.trailers
+            .iter()
+            .flatten()
+            .map(|(name, value)| ResponseHeader::new(name, value))
+            .collect()
+    }
+}
@@ -0,0 +1,285 @@
+//! gRPC's length-prefixed message framing and status codes.
+
+use crate::http::decode;
+use percent_encoding::percent_decode_str;
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+
+/// Compressed-flag byte plus a big-endian length.
+const PREFIX_LEN: usize = 5;
+
+/// Messages larger than this are refused rather than buffered (gRPC's
+/// usual default receive limit is 4MB; reflection answers can be bigger).
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Frames one uncompressed message.
+pub fn encode(message: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(PREFIX_LEN + message.len());
+    frame.push(0);
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(message);
+    frame
+}
+
+/// Splits a response body into messages as its chunks arrive.
+#[derive(Default)]
+pub struct Decoder {
+    buf: Vec<u8>,
+}
+
+impl Decoder {
+    pub fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// The next complete message, inflated per the response's `grpc-encoding`
+    /// if it was sent compressed.
+    pub fn next(&mut self, encoding: Option<&str>) -> Result<Option<Vec<u8>>, String> {
+        if self.buf.len() < PREFIX_LEN {
+            return Ok(None);
+        }
+        let compressed = match self.buf[0] {
+            0 => false,
+            1 => true,
+            flag => return Err(format!("Invalid message compression flag {}", flag)),
+        };
+        let len = u32::from_be_bytes([self.buf[1], self.buf[2], self.buf[3], self.buf[4]]) as usize;
+        if len > MAX_MESSAGE_BYTES {
+            return Err(format!(
+                "Message of {} bytes exceeds the {} byte limit",
+                len, MAX_MESSAGE_BYTES
+            ));
+        }
+        if self.buf.len() < PREFIX_LEN + len {
+            return Ok(None);
+        }
+        let message: Vec<u8> = self
+            .buf
+            .drain(..PREFIX_LEN + len)
+            .skip(PREFIX_LEN)
+            .collect();
+        if !compressed {
+            return Ok(Some(message));
+        }
+        match encoding {
+            Some(encoding) if encoding != "identity" => {
+                decode::decompress(&[encoding.to_string()], &message).map(Some)
+            }
+            _ => Err("Server sent a compressed message without a grpc-encoding".to_string()),
+        }
+    }
+
+    /// Whether a partial message was left over when the body ended.
+    pub fn has_partial(&self) -> bool {
+        !self.buf.is_empty()
+    }
+}
+
+pub const OK: u32 = 0;
+pub const CANCELLED: u32 = 1;
+pub const UNKNOWN: u32 = 2;
+pub const DEADLINE_EXCEEDED: u32 = 4;
+pub const PERMISSION_DENIED: u32 = 7;
+pub const UNIMPLEMENTED: u32 = 12;
+pub const INTERNAL: u32 = 13;
+pub const UNAVAILABLE: u32 = 14;
+pub const UNAUTHENTICATED: u32 = 16;
+
+const CODE_NAMES: [&str; 17] = [
+    "OK",
+    "CANCELLED",
+    "UNKNOWN",
+    "INVALID_ARGUMENT",
+    "DEADLINE_EXCEEDED",
+    "NOT_FOUND",
+    "ALREADY_EXISTS",
+    "PERMISSION_DENIED",
+    "RESOURCE_EXHAUSTED",
+    "FAILED_PRECONDITION",
+    "ABORTED",
+    "OUT_OF_RANGE",
+    "UNIMPLEMENTED",
+    "INTERNAL",
+    "UNAVAILABLE",
+    "DATA_LOSS",
+    "UNAUTHENTICATED",
+];
+
+/// How a call ended, from the server's trailers or decided locally.
+#[derive(Debug, Serialize, Clone)]
+pub struct Status {
+    pub code: u32,
+    /// `OK`, `NOT_FOUND`, ...
+    pub name: String,
+    pub message: String,
+}
+
+impl Status {
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        Status {
+            code,
+            name: CODE_NAMES
+                .get(code as usize)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("CODE_{}", code)),
+            message: message.into(),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.code == OK
+    }
+
+    /// Reads `grpc-status` and `grpc-message`, if the headers carry them.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let code = headers.get("grpc-status")?;
+        let code = code.to_str().ok().and_then(|c| c.trim().parse().ok());
+        let message = headers
+            .get("grpc-message")
+            .and_then(|m| m.to_str().ok())
+            .map(|m| percent_decode_str(m).decode_utf8_lossy().into_owned())
+            .unwrap_or_default();
+        Some(match code {
+            Some(code) => Status::new(code, message),
+            None => Status::new(UNKNOWN, "Server sent an invalid grpc-status"),
+        })
+    }
+
+    /// The status a non-gRPC HTTP error maps to, as gRPC clients report it.
+    pub fn from_http(status: u16) -> Self {
+        let code = match status {
+            400 => INTERNAL,
+            401 => UNAUTHENTICATED,
+            403 => PERMISSION_DENIED,
+            404 => UNIMPLEMENTED,
+            429 | 502 | 503 | 504 => UNAVAILABLE,
+            _ => UNKNOWN,
+        };
+        Status::new(code, format!("Server answered with HTTP {}", status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn frame(flag: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![flag];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    fn drain(decoder: &mut Decoder, encoding: Option<&str>) -> Vec<Vec<u8>> {
+        let mut messages = Vec::new();
+        while let Some(message) = decoder.next(encoding).unwrap() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    #[test]
+    fn encodes_a_length_prefix() {
+        assert_eq!(encode(b"hi"), [0, 0, 0, 0, 2, b'h', b'i']);
+        assert_eq!(encode(b""), [0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn reassembles_frames_split_across_chunks() {
+        let messages: [&[u8]; 3] = [b"first", b"", &[7; 300]];
+        let body: Vec<u8> = messages.iter().flat_map(|m| encode(m)).collect();
+        // Every split point, including ones inside the length prefix
+        for split in 0..=body.len() {
+            let mut decoder = Decoder::default();
+            let mut received = Vec::new();
+            for chunk in [&body[..split], &body[split..]] {
+                decoder.push(chunk);
+                received.extend(drain(&mut decoder, None));
+            }
+            assert_eq!(received, messages, "split at {}", split);
+            assert!(!decoder.has_partial());
+        }
+    }
+
+    #[test]
+    fn waits_for_the_rest_of_a_frame() {
+        let body = encode(b"hello");
+        let mut decoder = Decoder::default();
+        for byte in &body[..body.len() - 1] {
+            decoder.push(&[*byte]);
+            assert_eq!(decoder.next(None).unwrap(), None);
+        }
+        assert!(decoder.has_partial());
+        decoder.push(&body[body.len() - 1..]);
+        assert_eq!(decoder.next(None).unwrap().unwrap(), b"hello");
+        assert!(!decoder.has_partial());
+    }
+
+    #[test]
+    fn inflates_compressed_messages() {
+        let mut decoder = Decoder::default();
+        decoder.push(&frame(1, &gzip(b"squeezed")));
+        decoder.push(&frame(0, b"plain"));
+        assert_eq!(
+            drain(&mut decoder, Some("gzip")),
+            [&b"squeezed"[..], b"plain"]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_frames() {
+        let cases: [(Vec<u8>, Option<&str>, &str); 4] = [
+            (frame(2, b"x"), None, "Invalid message compression flag 2"),
+            (
+                frame(1, b"x"),
+                None,
+                "Server sent a compressed message without a grpc-encoding",
+            ),
+            (
+                frame(1, b"x"),
+                Some("identity"),
+                "Server sent a compressed message without a grpc-encoding",
+            ),
+            (
+                [0, 0x04, 0, 0, 1].to_vec(),
+                None,
+                "Message of 67108865 bytes exceeds the 67108864 byte limit",
+            ),
+        ];
+        for (body, encoding, message) in cases {
+            let mut decoder = Decoder::default();
+            decoder.push(&body);
+            assert_eq!(decoder.next(encoding).unwrap_err(), message);
+        }
+    }
+
+    #[test]
+    fn reads_status_from_trailers() {
+        let mut headers = HeaderMap::new();
+        assert!(Status::from_headers(&headers).is_none());
+
+        headers.insert("grpc-status", "5".parse().unwrap());
+        headers.insert(
+            "grpc-message",
+            "no%20such%20user%3A%20%C3%A9".parse().unwrap(),
+        );
+        let status = Status::from_headers(&headers).unwrap();
+        assert_eq!((status.code, status.name.as_str()), (5, "NOT_FOUND"));
+        assert_eq!(status.message, "no such user: é");
+
+        headers.insert("grpc-status", "nope".parse().unwrap());
+        let status = Status::from_headers(&headers).unwrap();
+        assert_eq!(status.code, UNKNOWN);
+
+        assert_eq!(Status::new(42, "").name, "CODE_42");
+        assert_eq!(Status::from_http(404).name, "UNIMPLEMENTED");
+        assert_eq!(Status::from_http(503).name, "UNAVAILABLE");
+    }
+}
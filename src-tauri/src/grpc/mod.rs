@@ -0,0 +1,351 @@
+pub mod call;
+pub mod codec;
+pub mod proto;
+pub mod reflection;
+
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, Kind, MessageDescriptor, MethodDescriptor, SerializeOptions,
+};
+use prost_types::FileDescriptorProto;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// Where a request's service definitions come from.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DescriptorSource {
+    /// Asked of the server itself.
+    Reflection,
+    /// Parsed from `.proto` files on disk; imports are looked up in
+    /// `import_paths`, then next to the files themselves.
+    ProtoFiles {
+        files: Vec<String>,
+        #[serde(default)]
+        import_paths: Vec<String>,
+    },
+}
+
+impl DescriptorSource {
+    /// What the loaded pool is cached under: the server for reflection, the
+    /// file set otherwise.
+    pub fn cache_key(&self, url: &str) -> String {
+        match self {
+            DescriptorSource::Reflection => format!("reflection {}", url),
+            DescriptorSource::ProtoFiles {
+                files,
+                import_paths,
+            } => format!("files {} | {}", files.join(","), import_paths.join(",")),
+        }
+    }
+}
+
+/// Loaded descriptor pools, so each call doesn't reparse or re-ask the server.
+#[derive(Default)]
+pub struct GrpcDescriptors(pub Mutex<HashMap<String, DescriptorPool>>);
+
+impl GrpcDescriptors {
+    pub fn get(&self, key: &str) -> Option<DescriptorPool> {
+        self.0.lock().ok()?.get(key).cloned()
+    }
+
+    pub fn insert(&self, key: String, pool: DescriptorPool) {
+        if let Ok(mut pools) = self.0.lock() {
+            pools.insert(key, pool);
+        }
+    }
+}
+
+/// Adds files to a pool that already holds the well-known types.
+pub fn build_pool(files: Vec<FileDescriptorProto>) -> Result<DescriptorPool, String> {
+    let mut pool = DescriptorPool::global();
+    let files: Vec<_> = files
+        .into_iter()
+        .filter(|f| pool.get_file_by_name(f.name()).is_none())
+        .collect();
+    pool.add_file_descriptor_protos(files)
+        .map_err(|e| format!("Invalid service definitions: {}", e))?;
+    Ok(pool)
+}
+
+/// The name a file is imported by: its path under an import root, or else
+/// just its file name, with its directory serving as a root.
+fn import_name(path: &Path, roots: &[PathBuf]) -> String {
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or_else(|| Path::new(path.file_name().unwrap_or_default()))
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn parse_file(path: &Path, name: &str) -> Result<FileDescriptorProto, String> {
+    let source = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read '{}': {}", path.display(), e))?;
+    proto::parse(name, &source)
+}
+
+/// Parses `files` and everything they import that the well-known types don't cover.
+pub fn load_files(files: &[String], import_paths: &[String]) -> Result<DescriptorPool, String> {
+    if files.is_empty() {
+        return Err("No .proto files selected".to_string());
+    }
+    let mut roots: Vec<PathBuf> = import_paths.iter().map(PathBuf::from).collect();
+    let explicit = roots.len();
+    for file in files {
+        if let Some(dir) = Path::new(file).parent() {
+            if !roots[..explicit]
+                .iter()
+                .any(|root| Path::new(file).starts_with(root))
+            {
+                roots.push(dir.to_path_buf());
+            }
+        }
+    }
+
+    let known = DescriptorPool::global();
+    let mut parsed = Vec::new();
+    let mut seen = HashSet::new();
+    let mut missing = VecDeque::new();
+    for file in files {
+        let path = Path::new(file);
+        let name = import_name(path, &roots[..explicit]);
+        if seen.insert(name.clone()) {
+            let proto = parse_file(path, &name)?;
+            missing.extend(proto.dependency.iter().cloned());
+            parsed.push(proto);
+        }
+    }
+    while let Some(name) = missing.pop_front() {
+        if known.get_file_by_name(&name).is_some() || !seen.insert(name.clone()) {
+            continue;
+        }
+        let path = roots
+            .iter()
+            .map(|root| root.join(&name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                format!(
+                    "Import '{}' not found; add the directory it is relative to as an import path",
+                    name
+                )
+            })?;
+        let proto = parse_file(&path, &name)?;
+        missing.extend(proto.dependency.iter().cloned());
+        parsed.push(proto);
+    }
+    build_pool(parsed)
+}
+
+#[derive(Debug, Serialize)]
+pub struct MethodInfo {
+    pub name: String,
+    /// `package.Service/Method`, as shown to users.
+    pub full_name: String,
+    pub input_type: String,
+    pub output_type: String,
+    pub client_streaming: bool,
+    pub server_streaming: bool,
+    /// A JSON input message with every field at its default.
+    pub template: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ServiceInfo {
+    pub name: String,
+    pub methods: Vec<MethodInfo>,
+}
+
+/// Every service in the pool, sorted by name.
+pub fn describe(pool: &DescriptorPool) -> Vec<ServiceInfo> {
+    let mut services: Vec<ServiceInfo> = pool
+        .services()
+        .map(|service| ServiceInfo {
+            name: service.full_name().to_string(),
+            methods: service
+                .methods()
+                .map(|method| MethodInfo {
+                    name: method.name().to_string(),
+                    full_name: format!("{}/{}", service.full_name(), method.name()),
+                    input_type: method.input().full_name().to_string(),
+                    output_type: method.output().full_name().to_string(),
+                    client_streaming: method.is_client_streaming(),
+                    server_streaming: method.is_server_streaming(),
+                    template: template(&method.input()),
+                })
+                .collect(),
+        })
+        .collect();
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    services
+}
+
+pub fn find_method(
+    pool: &DescriptorPool,
+    service: &str,
+    method: &str,
+) -> Result<MethodDescriptor, String> {
+    let found = pool
+        .get_service_by_name(service)
+        .ok_or_else(|| format!("Service '{}' is not defined", service))?;
+    let found = found.methods().find(|m| m.name() == method);
+    found.ok_or_else(|| format!("Method '{}' is not defined on '{}'", method, service))
+}
+
+/// Nesting beyond this is left empty so self-referencing types stay finite.
+const TEMPLATE_DEPTH: usize = 6;
+
+/// Types whose default has no JSON form worth showing (`Any` needs a type URL).
+const TEMPLATE_SKIPPED: [&str; 2] = ["google.protobuf.Any", "google.protobuf.Value"];
+
+fn template_value(kind: &Kind, stack: &mut Vec<String>) -> prost_reflect::Value {
+    match kind {
+        Kind::Message(m) => prost_reflect::Value::Message(template_message(m, stack)),
+        kind => prost_reflect::Value::default_value(kind),
+    }
+}
+
+fn template_message(desc: &MessageDescriptor, stack: &mut Vec<String>) -> DynamicMessage {
+    let mut message = DynamicMessage::new(desc.clone());
+    if stack.len() >= TEMPLATE_DEPTH || stack.iter().any(|name| name == desc.full_name()) {
+        return message;
+    }
+    stack.push(desc.full_name().to_string());
+    let mut oneofs = HashSet::new();
+    for field in desc.fields() {
+        // Only the first member of each oneof, since setting two is an error
+        if let Some(oneof) = field.containing_oneof().filter(|o| !o.is_synthetic()) {
+            if !oneofs.insert(oneof.name().to_string()) {
+                continue;
+            }
+        }
+        let kind = field.kind();
+        if let Kind::Message(m) = &kind {
+            if TEMPLATE_SKIPPED.contains(&m.full_name()) {
+                continue;
+            }
+        }
+        let value = if field.is_map() {
+            prost_reflect::Value::Map(HashMap::new())
+        } else if field.is_list() {
+            prost_reflect::Value::List(vec![template_value(&kind, stack)])
+        } else {
+            template_value(&kind, stack)
+        };
+        message.set_field(&field, value);
+    }
+    stack.pop();
+    message
+}
+
+/// A pretty-printed JSON message with every field filled in, to start editing from.
+pub fn template(desc: &MessageDescriptor) -> String {
+    to_json(&template_message(desc, &mut Vec::new())).unwrap_or_default()
+}
+
+/// Parses a message from its JSON mapping and encodes it; blank text is an
+/// empty message.
+pub fn encode_json(desc: &MessageDescriptor, text: &str) -> Result<Vec<u8>, String> {
+    let text = if text.trim().is_empty() { "{}" } else { text };
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    let message = DynamicMessage::deserialize(desc.clone(), &mut deserializer)
+        .and_then(|message| deserializer.end().map(|_| message))
+        .map_err(|e| format!("Invalid {} message: {}", desc.full_name(), e))?;
+    Ok(message.encode_to_vec())
+}
+
+/// Decodes a message into pretty-printed JSON, defaults included so the
+/// shape of the response is visible.
+pub fn decode_json(desc: &MessageDescriptor, bytes: &[u8]) -> Result<String, String> {
+    let message = DynamicMessage::decode(desc.clone(), bytes)
+        .map_err(|e| format!("Invalid {} message: {}", desc.full_name(), e))?;
+    to_json(&message)
+}
+
+fn to_json(message: &DynamicMessage) -> Result<String, String> {
+    let mut serializer = serde_json::Serializer::pretty(Vec::new());
+    let options = SerializeOptions::new().skip_default_fields(false);
+    message
+        .serialize_with_options(&mut serializer, &options)
+        .map_err(|e| e.to_string())?;
+    String::from_utf8(serializer.into_inner()).map_err(|e| e.to_string())
+}
+
+/// One message as sent or received.
+#[derive(Debug, Serialize, Clone)]
+pub struct GrpcMessage {
+    /// `sent` or `received`.
+    pub direction: &'static str,
+    /// The message in its JSON mapping.
+    pub data: String,
+    /// Encoded size, without the frame prefix.
+    pub size_bytes: usize,
+    /// Milliseconds since the call started.
+    pub at_ms: u64,
+}
+
+/// A running call's request side.
+pub struct CallHandle {
+    pub input: MessageDescriptor,
+    /// Dropped once the client half-closes.
+    pub queue: Option<mpsc::UnboundedSender<Vec<u8>>>,
+    pub cancel: CancellationToken,
+    pub started: Instant,
+}
+
+impl CallHandle {
+    /// Encodes and queues a message, returning it as the transcript shows it.
+    pub fn send(&self, text: &str) -> Result<GrpcMessage, String> {
+        let queue = self
+            .queue
+            .as_ref()
+            .ok_or("The request stream is already closed")?;
+        let encoded = encode_json(&self.input, text)?;
+        let message = GrpcMessage {
+            direction: "sent",
+            data: decode_json(&self.input, &encoded)?,
+            size_bytes: encoded.len(),
+            at_ms: self.started.elapsed().as_millis() as u64,
+        };
+        queue
+            .send(codec::encode(&encoded))
+            .map_err(|_| "The call has ended".to_string())?;
+        Ok(message)
+    }
+}
+
+/// Running calls, keyed by the caller-supplied call ID.
+#[derive(Default)]
+pub struct GrpcCalls(pub Mutex<HashMap<String, CallHandle>>);
+
+impl GrpcCalls {
+    pub fn send(&self, call_id: &str, text: &str) -> Result<GrpcMessage, String> {
+        let calls = self.0.lock().map_err(|e| e.to_string())?;
+        let call = calls
+            .get(call_id)
+            .ok_or_else(|| format!("gRPC call '{}' is not running", call_id))?;
+        call.send(text)
+    }
+
+    /// Ends the request stream; the server's side continues until it finishes.
+    pub fn close_send(&self, call_id: &str) -> Result<(), String> {
+        let mut calls = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(call) = calls.get_mut(call_id) {
+            call.queue = None;
+        }
+        Ok(())
+    }
+
+    pub fn cancel(&self, call_id: &str) -> Result<(), String> {
+        let calls = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(call) = calls.get(call_id) {
+            call.cancel.cancel();
+        }
+        Ok(())
+    }
+}
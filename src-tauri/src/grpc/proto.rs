@@ -0,0 +1,1356 @@
+//! Parses `.proto` source into the descriptors protoc would produce, so
+//! services can be loaded without a protoc install. Type references are left
+//! as written; the descriptor pool resolves them against the package scope.
+
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    descriptor_proto::{ExtensionRange, ReservedRange},
+    enum_descriptor_proto::EnumReservedRange,
+    DescriptorProto, EnumDescriptorProto, EnumOptions, EnumValueDescriptorProto,
+    FieldDescriptorProto, FieldOptions, FileDescriptorProto, MessageOptions, MethodDescriptorProto,
+    OneofDescriptorProto, ServiceDescriptorProto,
+};
+
+/// Field numbers above this are invalid (2^29 - 1).
+const MAX_FIELD_NUMBER: i32 = 536_870_911;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pos {
+    line: u32,
+    column: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    /// Integer or float literal as written, without a sign.
+    Number(String),
+    /// Decoded value and the text between the quotes, kept for bytes defaults.
+    Str {
+        value: String,
+        raw: String,
+    },
+    Symbol(char),
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(name) => format!("'{}'", name),
+            Token::Number(text) => format!("number {}", text),
+            Token::Str { raw, .. } => format!("string \"{}\"", raw),
+            Token::Symbol(c) => format!("'{}'", c),
+            Token::Eof => "end of file".to_string(),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    pos: Pos,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(source: &'a str) -> Self {
+        Lexer {
+            chars: source.chars().peekable(),
+            pos: Pos { line: 1, column: 1 },
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else {
+            self.pos.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), (Pos, String)> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                }
+                Some('/') => {
+                    let start = self.pos;
+                    let mut ahead = self.chars.clone();
+                    ahead.next();
+                    match ahead.peek() {
+                        Some('/') => {
+                            while !matches!(self.chars.peek(), None | Some('\n')) {
+                                self.bump();
+                            }
+                        }
+                        Some('*') => {
+                            self.bump();
+                            self.bump();
+                            loop {
+                                match self.bump() {
+                                    Some('*') if self.chars.peek() == Some(&'/') => {
+                                        self.bump();
+                                        break;
+                                    }
+                                    Some(_) => {}
+                                    None => {
+                                        return Err((start, "Unterminated comment".to_string()))
+                                    }
+                                }
+                            }
+                        }
+                        _ => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<(Pos, Token), (Pos, String)> {
+        self.skip_trivia()?;
+        let start = self.pos;
+        let Some(&c) = self.chars.peek() else {
+            return Ok((start, Token::Eof));
+        };
+        let token = if c.is_ascii_alphabetic() || c == '_' {
+            let mut name = String::new();
+            while let Some(&c) = self.chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                self.bump();
+            }
+            Token::Ident(name)
+        } else if c.is_ascii_digit() {
+            Token::Number(self.number())
+        } else if c == '"' || c == '\'' {
+            self.string(c).map_err(|message| (start, message))?
+        } else {
+            self.bump();
+            // `.5` is a float, anything else after a dot is a name separator
+            if c == '.' && self.chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                Token::Number(format!(".{}", self.number()))
+            } else {
+                Token::Symbol(c)
+            }
+        };
+        Ok((start, token))
+    }
+
+    fn number(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(&c) = self.chars.peek() {
+            let exponent_sign =
+                (c == '+' || c == '-') && matches!(text.chars().last(), Some('e' | 'E'));
+            let hex = text.starts_with("0x") || text.starts_with("0X");
+            if exponent_sign && !hex || c.is_ascii_alphanumeric() || c == '.' {
+                text.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        text
+    }
+
+    fn string(&mut self, quote: char) -> Result<Token, String> {
+        self.bump();
+        let mut value = String::new();
+        let mut raw = String::new();
+        let mut bytes = Vec::new();
+        let flush = |bytes: &mut Vec<u8>, value: &mut String| {
+            value.push_str(&String::from_utf8_lossy(bytes));
+            bytes.clear();
+        };
+        loop {
+            let c = match self.bump() {
+                None | Some('\n') => return Err("Unterminated string".to_string()),
+                Some(c) if c == quote => break,
+                Some(c) => c,
+            };
+            raw.push(c);
+            if c != '\\' {
+                flush(&mut bytes, &mut value);
+                value.push(c);
+                continue;
+            }
+            let escape = self.bump().ok_or("Unterminated string")?;
+            raw.push(escape);
+            match escape {
+                'n' => bytes.push(b'\n'),
+                'r' => bytes.push(b'\r'),
+                't' => bytes.push(b'\t'),
+                'a' => bytes.push(0x07),
+                'b' => bytes.push(0x08),
+                'f' => bytes.push(0x0c),
+                'v' => bytes.push(0x0b),
+                '\\' | '\'' | '"' | '?' => bytes.push(escape as u8),
+                'x' | 'X' => {
+                    let digits = self.take_digits(16, 2, &mut raw);
+                    if digits.is_empty() {
+                        return Err("Invalid \\x escape in string".to_string());
+                    }
+                    bytes.push(u8::from_str_radix(&digits, 16).map_err(|e| e.to_string())?);
+                }
+                '0'..='7' => {
+                    let mut digits = escape.to_string();
+                    digits.push_str(&self.take_digits(8, 2, &mut raw));
+                    let byte = u32::from_str_radix(&digits, 8).map_err(|e| e.to_string())?;
+                    bytes.push(u8::try_from(byte).map_err(|_| "Octal escape out of range")?);
+                }
+                'u' | 'U' => {
+                    let len = if escape == 'u' { 4 } else { 8 };
+                    let digits = self.take_digits(16, len, &mut raw);
+                    let ch = u32::from_str_radix(&digits, 16)
+                        .ok()
+                        .filter(|_| digits.len() == len)
+                        .and_then(char::from_u32)
+                        .ok_or("Invalid unicode escape in string")?;
+                    flush(&mut bytes, &mut value);
+                    value.push(ch);
+                }
+                other => return Err(format!("Invalid escape '\\{}' in string", other)),
+            }
+        }
+        flush(&mut bytes, &mut value);
+        Ok(Token::Str { value, raw })
+    }
+
+    fn take_digits(&mut self, radix: u32, max: usize, raw: &mut String) -> String {
+        let mut digits = String::new();
+        while digits.len() < max {
+            match self.chars.peek() {
+                Some(&c) if c.is_digit(radix) => {
+                    digits.push(c);
+                    raw.push(c);
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        digits
+    }
+}
+
+/// Options the descriptors keep; everything else is parsed and dropped.
+#[derive(Default)]
+struct FieldOpts {
+    default: Option<String>,
+    json_name: Option<String>,
+    packed: Option<bool>,
+}
+
+/// An option's value, as far as it matters here.
+enum Constant {
+    Ident(String),
+    /// With its sign, if any.
+    Number(String),
+    Str {
+        value: String,
+        raw: String,
+    },
+    Aggregate,
+}
+
+impl Constant {
+    fn as_bool(&self) -> Option<bool> {
+        match self {
+            Constant::Ident(name) if name == "true" => Some(true),
+            Constant::Ident(name) if name == "false" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    pos: Pos,
+    token: Token,
+    proto3: bool,
+}
+
+type PResult<T> = Result<T, (Pos, String)>;
+
+impl<'a> Parser<'a> {
+    fn new(source: &'a str) -> PResult<Self> {
+        let mut lexer = Lexer::new(source);
+        let (pos, token) = lexer.next_token()?;
+        Ok(Parser {
+            lexer,
+            pos,
+            token,
+            proto3: false,
+        })
+    }
+
+    fn advance(&mut self) -> PResult<Token> {
+        let (pos, token) = self.lexer.next_token()?;
+        self.pos = pos;
+        Ok(std::mem::replace(&mut self.token, token))
+    }
+
+    fn fail<T>(&self, message: impl Into<String>) -> PResult<T> {
+        Err((self.pos, message.into()))
+    }
+
+    fn unexpected<T>(&self, expected: &str) -> PResult<T> {
+        self.fail(format!(
+            "Expected {}, found {}",
+            expected,
+            self.token.describe()
+        ))
+    }
+
+    fn is_symbol(&self, c: char) -> bool {
+        self.token == Token::Symbol(c)
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(&self.token, Token::Ident(name) if name == keyword)
+    }
+
+    fn eat_symbol(&mut self, c: char) -> PResult<bool> {
+        if self.is_symbol(c) {
+            self.advance()?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn expect_symbol(&mut self, c: char) -> PResult<()> {
+        if self.eat_symbol(c)? {
+            Ok(())
+        } else {
+            self.unexpected(&format!("'{}'", c))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> PResult<()> {
+        if self.is_keyword(keyword) {
+            self.advance()?;
+            Ok(())
+        } else {
+            self.unexpected(&format!("'{}'", keyword))
+        }
+    }
+
+    fn ident(&mut self) -> PResult<String> {
+        match &self.token {
+            Token::Ident(_) => match self.advance()? {
+                Token::Ident(name) => Ok(name),
+                _ => unreachable!(),
+            },
+            _ => self.unexpected("an identifier"),
+        }
+    }
+
+    /// A dotted name, optionally fully qualified with a leading dot.
+    fn full_ident(&mut self) -> PResult<String> {
+        let mut name = String::new();
+        if self.eat_symbol('.')? {
+            name.push('.');
+        }
+        name.push_str(&self.ident()?);
+        while self.eat_symbol('.')? {
+            name.push('.');
+            name.push_str(&self.ident()?);
+        }
+        Ok(name)
+    }
+
+    fn string(&mut self) -> PResult<(String, String)> {
+        let Token::Str { .. } = self.token else {
+            return self.unexpected("a string");
+        };
+        let mut value = String::new();
+        let mut raw = String::new();
+        // Adjacent literals are concatenated, as in C
+        while let Token::Str { .. } = self.token {
+            if let Token::Str { value: v, raw: r } = self.advance()? {
+                value.push_str(&v);
+                raw.push_str(&r);
+            }
+        }
+        Ok((value, raw))
+    }
+
+    fn int(&mut self) -> PResult<i64> {
+        let negative = self.eat_symbol('-')?;
+        let Token::Number(ref text) = self.token else {
+            return self.unexpected("an integer");
+        };
+        let parsed = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X"))
+        {
+            i64::from_str_radix(hex, 16)
+        } else if text.len() > 1 && text.starts_with('0') {
+            i64::from_str_radix(&text[1..], 8)
+        } else {
+            text.parse()
+        };
+        let value = match parsed {
+            Ok(value) => value,
+            Err(_) => return self.fail(format!("Invalid integer {}", text)),
+        };
+        self.advance()?;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn field_number(&mut self) -> PResult<i32> {
+        let pos = self.pos;
+        let number = self.int()?;
+        if !(1..=MAX_FIELD_NUMBER as i64).contains(&number) {
+            return Err((pos, format!("Field number {} is out of range", number)));
+        }
+        Ok(number as i32)
+    }
+
+    fn constant(&mut self) -> PResult<Constant> {
+        if self.eat_symbol('{')? {
+            let mut depth = 1;
+            while depth > 0 {
+                match self.advance()? {
+                    Token::Symbol('{') => depth += 1,
+                    Token::Symbol('}') => depth -= 1,
+                    Token::Eof => return self.unexpected("'}'"),
+                    _ => {}
+                }
+            }
+            return Ok(Constant::Aggregate);
+        }
+        let sign = if self.eat_symbol('-')? {
+            "-"
+        } else {
+            self.eat_symbol('+')?;
+            ""
+        };
+        match self.token {
+            Token::Number(_) => match self.advance()? {
+                Token::Number(text) => Ok(Constant::Number(format!("{}{}", sign, text))),
+                _ => unreachable!(),
+            },
+            Token::Ident(_) => {
+                let name = self.full_ident()?;
+                Ok(if sign.is_empty() {
+                    Constant::Ident(name)
+                } else {
+                    Constant::Number(format!("{}{}", sign, name))
+                })
+            }
+            Token::Str { .. } if sign.is_empty() => {
+                let (value, raw) = self.string()?;
+                Ok(Constant::Str { value, raw })
+            }
+            _ => self.unexpected("a constant"),
+        }
+    }
+
+    /// `name`, `(ext.name)` or `(ext.name).field`.
+    fn option_name(&mut self) -> PResult<String> {
+        let mut name = String::new();
+        loop {
+            if self.eat_symbol('(')? {
+                name.push('(');
+                name.push_str(&self.full_ident()?);
+                self.expect_symbol(')')?;
+                name.push(')');
+            } else {
+                name.push_str(&self.ident()?);
+            }
+            if !self.eat_symbol('.')? {
+                return Ok(name);
+            }
+            name.push('.');
+        }
+    }
+
+    /// `option name = value;`, after the keyword.
+    fn option_statement(&mut self) -> PResult<(String, Constant)> {
+        let name = self.option_name()?;
+        self.expect_symbol('=')?;
+        let value = self.constant()?;
+        self.expect_symbol(';')?;
+        Ok((name, value))
+    }
+
+    /// `[name = value, ...]`, if present.
+    fn bracket_options(&mut self) -> PResult<Vec<(String, Constant)>> {
+        let mut options = Vec::new();
+        if !self.eat_symbol('[')? {
+            return Ok(options);
+        }
+        loop {
+            let name = self.option_name()?;
+            self.expect_symbol('=')?;
+            options.push((name, self.constant()?));
+            if !self.eat_symbol(',')? {
+                break;
+            }
+        }
+        self.expect_symbol(']')?;
+        Ok(options)
+    }
+
+    fn field_options(&mut self) -> PResult<FieldOpts> {
+        let mut opts = FieldOpts::default();
+        for (name, value) in self.bracket_options()? {
+            match (name.as_str(), value) {
+                ("default", Constant::Str { value, raw }) => {
+                    opts.default = Some(if value.is_ascii() { value } else { raw });
+                }
+                ("default", Constant::Number(text) | Constant::Ident(text)) => {
+                    opts.default = Some(text);
+                }
+                ("json_name", Constant::Str { value, .. }) => opts.json_name = Some(value),
+                ("packed", value) => opts.packed = value.as_bool(),
+                _ => {}
+            }
+        }
+        Ok(opts)
+    }
+
+    fn file(&mut self, name: &str) -> PResult<FileDescriptorProto> {
+        let mut file = FileDescriptorProto {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+
+        if self.is_keyword("edition") {
+            return self.fail("Protobuf editions are not supported; use proto2 or proto3 syntax");
+        }
+        if self.is_keyword("syntax") {
+            self.advance()?;
+            self.expect_symbol('=')?;
+            let pos = self.pos;
+            let (syntax, _) = self.string()?;
+            self.expect_symbol(';')?;
+            match syntax.as_str() {
+                "proto2" => {}
+                "proto3" => {
+                    self.proto3 = true;
+                    file.syntax = Some(syntax);
+                }
+                other => return Err((pos, format!("Unknown syntax \"{}\"", other))),
+            }
+        }
+
+        loop {
+            match &self.token {
+                Token::Eof => break,
+                Token::Symbol(';') => {
+                    self.advance()?;
+                }
+                Token::Ident(keyword) => match keyword.clone().as_str() {
+                    "package" => {
+                        if file.package.is_some() {
+                            return self.fail("Multiple package declarations");
+                        }
+                        self.advance()?;
+                        file.package = Some(self.full_ident()?);
+                        self.expect_symbol(';')?;
+                    }
+                    "import" => {
+                        self.advance()?;
+                        let index = file.dependency.len() as i32;
+                        if self.is_keyword("public") {
+                            self.advance()?;
+                            file.public_dependency.push(index);
+                        } else if self.is_keyword("weak") {
+                            self.advance()?;
+                            file.weak_dependency.push(index);
+                        }
+                        let (path, _) = self.string()?;
+                        self.expect_symbol(';')?;
+                        file.dependency.push(path);
+                    }
+                    "option" => {
+                        self.advance()?;
+                        self.option_statement()?;
+                    }
+                    "message" => {
+                        self.advance()?;
+                        file.message_type.push(self.message()?);
+                    }
+                    "enum" => {
+                        self.advance()?;
+                        file.enum_type.push(self.enumeration()?);
+                    }
+                    "service" => {
+                        self.advance()?;
+                        file.service.push(self.service()?);
+                    }
+                    "extend" => {
+                        self.advance()?;
+                        self.extend()?;
+                    }
+                    _ => return self.unexpected("a top-level definition"),
+                },
+                _ => return self.unexpected("a top-level definition"),
+            }
+        }
+        Ok(file)
+    }
+
+    fn message(&mut self) -> PResult<DescriptorProto> {
+        let mut message = DescriptorProto {
+            name: Some(self.ident()?),
+            ..Default::default()
+        };
+        self.expect_symbol('{')?;
+        self.message_body(&mut message)?;
+
+        // proto3 `optional` fields sit in synthetic oneofs, after the real ones
+        for index in 0..message.field.len() {
+            if message.field[index].proto3_optional == Some(true) {
+                message.field[index].oneof_index = Some(message.oneof_decl.len() as i32);
+                message.oneof_decl.push(OneofDescriptorProto {
+                    name: Some(format!("_{}", message.field[index].name())),
+                    options: None,
+                });
+            }
+        }
+        Ok(message)
+    }
+
+    fn message_body(&mut self, message: &mut DescriptorProto) -> PResult<()> {
+        loop {
+            if self.eat_symbol('}')? {
+                return Ok(());
+            }
+            if self.eat_symbol(';')? {
+                continue;
+            }
+            let Token::Ident(keyword) = self.token.clone() else {
+                return self.unexpected("a field or '}'");
+            };
+            match keyword.as_str() {
+                "option" => {
+                    self.advance()?;
+                    self.option_statement()?;
+                }
+                "message" => {
+                    self.advance()?;
+                    message.nested_type.push(self.message()?);
+                }
+                "enum" => {
+                    self.advance()?;
+                    message.enum_type.push(self.enumeration()?);
+                }
+                "extend" => {
+                    self.advance()?;
+                    self.extend()?;
+                }
+                "extensions" => {
+                    self.advance()?;
+                    for (start, end) in self.ranges()? {
+                        message.extension_range.push(ExtensionRange {
+                            start: Some(start),
+                            end: Some(end),
+                            options: None,
+                        });
+                    }
+                    self.bracket_options()?;
+                    self.expect_symbol(';')?;
+                }
+                "reserved" => {
+                    self.advance()?;
+                    if let Token::Str { .. } = self.token {
+                        message.reserved_name.extend(self.reserved_names()?);
+                    } else {
+                        for (start, end) in self.ranges()? {
+                            message.reserved_range.push(ReservedRange {
+                                start: Some(start),
+                                end: Some(end),
+                            });
+                        }
+                        self.expect_symbol(';')?;
+                    }
+                }
+                "oneof" => {
+                    self.advance()?;
+                    let index = message.oneof_decl.len() as i32;
+                    message.oneof_decl.push(OneofDescriptorProto {
+                        name: Some(self.ident()?),
+                        options: None,
+                    });
+                    self.expect_symbol('{')?;
+                    while !self.eat_symbol('}')? {
+                        if self.eat_symbol(';')? {
+                            continue;
+                        }
+                        if self.is_keyword("option") {
+                            self.advance()?;
+                            self.option_statement()?;
+                            continue;
+                        }
+                        let mut field = self.field(None, message)?;
+                        field.oneof_index = Some(index);
+                        message.field.push(field);
+                    }
+                }
+                "map" => {
+                    // A field may also be of a message type called `map`
+                    let mut ahead = self.lexer.chars.clone();
+                    while ahead.peek().is_some_and(|c| c.is_whitespace()) {
+                        ahead.next();
+                    }
+                    if ahead.peek() == Some(&'<') {
+                        self.advance()?;
+                        let field = self.map_field(message)?;
+                        message.field.push(field);
+                    } else {
+                        let field = self.field(None, message)?;
+                        message.field.push(field);
+                    }
+                }
+                "repeated" | "optional" | "required" => {
+                    let label = match keyword.as_str() {
+                        "repeated" => Label::Repeated,
+                        "optional" => Label::Optional,
+                        _ => Label::Required,
+                    };
+                    if label == Label::Required && self.proto3 {
+                        return self.fail("Required fields are not allowed in proto3");
+                    }
+                    self.advance()?;
+                    let field = self.field(Some(label), message)?;
+                    message.field.push(field);
+                }
+                _ => {
+                    if !self.proto3 {
+                        return self
+                            .fail("Fields in proto2 need a label: optional, required or repeated");
+                    }
+                    let field = self.field(None, message)?;
+                    message.field.push(field);
+                }
+            }
+        }
+    }
+
+    /// `1, 5 to 10, 20 to max`, as half-open ranges.
+    fn ranges(&mut self) -> PResult<Vec<(i32, i32)>> {
+        let mut ranges = Vec::new();
+        loop {
+            let start = self.field_number()?;
+            let end = if self.is_keyword("to") {
+                self.advance()?;
+                if self.is_keyword("max") {
+                    self.advance()?;
+                    MAX_FIELD_NUMBER
+                } else {
+                    self.field_number()?
+                }
+            } else {
+                start
+            };
+            ranges.push((start, end + 1));
+            if !self.eat_symbol(',')? {
+                return Ok(ranges);
+            }
+        }
+    }
+
+    fn reserved_names(&mut self) -> PResult<Vec<String>> {
+        let mut names = Vec::new();
+        loop {
+            names.push(self.string()?.0);
+            if !self.eat_symbol(',')? {
+                self.expect_symbol(';')?;
+                return Ok(names);
+            }
+        }
+    }
+
+    /// `type name = number [options];`, after any label.
+    fn field(
+        &mut self,
+        label: Option<Label>,
+        message: &DescriptorProto,
+    ) -> PResult<FieldDescriptorProto> {
+        if self.is_keyword("group") {
+            return self.fail("Groups are not supported");
+        }
+        let type_name = self.full_ident()?;
+        let name = self.ident()?;
+        self.expect_symbol('=')?;
+        let number = self.field_number()?;
+        let opts = self.field_options()?;
+        self.expect_symbol(';')?;
+
+        if message.field.iter().any(|f| f.name() == name) {
+            return self.fail(format!("Field \"{}\" is already defined", name));
+        }
+        let mut field = FieldDescriptorProto {
+            name: Some(name),
+            number: Some(number),
+            label: Some(label.unwrap_or(Label::Optional) as i32),
+            default_value: opts.default,
+            json_name: opts.json_name,
+            ..Default::default()
+        };
+        set_field_type(&mut field, type_name);
+        if opts.packed.is_some() {
+            field.options = Some(FieldOptions {
+                packed: opts.packed,
+                ..Default::default()
+            });
+        }
+        if label == Some(Label::Optional) && self.proto3 {
+            field.proto3_optional = Some(true);
+        }
+        Ok(field)
+    }
+
+    /// `map<K, V> name = number [options];`, after the keyword. The entry
+    /// message is nested in `message`, as protoc does.
+    fn map_field(&mut self, message: &mut DescriptorProto) -> PResult<FieldDescriptorProto> {
+        self.expect_symbol('<')?;
+        let key_type = self.full_ident()?;
+        self.expect_symbol(',')?;
+        let value_type = self.full_ident()?;
+        self.expect_symbol('>')?;
+        let name = self.ident()?;
+        self.expect_symbol('=')?;
+        let number = self.field_number()?;
+        let opts = self.field_options()?;
+        self.expect_symbol(';')?;
+
+        let entry_name = map_entry_name(&name);
+        let mut key = FieldDescriptorProto {
+            name: Some("key".to_string()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            ..Default::default()
+        };
+        set_field_type(&mut key, key_type);
+        let mut value = FieldDescriptorProto {
+            name: Some("value".to_string()),
+            number: Some(2),
+            label: Some(Label::Optional as i32),
+            ..Default::default()
+        };
+        set_field_type(&mut value, value_type);
+        message.nested_type.push(DescriptorProto {
+            name: Some(entry_name.clone()),
+            field: vec![key, value],
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+
+        Ok(FieldDescriptorProto {
+            name: Some(name),
+            number: Some(number),
+            label: Some(Label::Repeated as i32),
+            type_name: Some(entry_name),
+            json_name: opts.json_name,
+            ..Default::default()
+        })
+    }
+
+    fn enumeration(&mut self) -> PResult<EnumDescriptorProto> {
+        let mut enumeration = EnumDescriptorProto {
+            name: Some(self.ident()?),
+            ..Default::default()
+        };
+        self.expect_symbol('{')?;
+        loop {
+            if self.eat_symbol('}')? {
+                break;
+            }
+            if self.eat_symbol(';')? {
+                continue;
+            }
+            if self.is_keyword("option") {
+                self.advance()?;
+                let (name, value) = self.option_statement()?;
+                if name == "allow_alias" {
+                    enumeration.options = Some(EnumOptions {
+                        allow_alias: value.as_bool(),
+                        ..Default::default()
+                    });
+                }
+                continue;
+            }
+            if self.is_keyword("reserved") {
+                self.advance()?;
+                if let Token::Str { .. } = self.token {
+                    enumeration.reserved_name.extend(self.reserved_names()?);
+                } else {
+                    loop {
+                        let start = self.int()? as i32;
+                        let end = if self.is_keyword("to") {
+                            self.advance()?;
+                            if self.is_keyword("max") {
+                                self.advance()?;
+                                i32::MAX
+                            } else {
+                                self.int()? as i32
+                            }
+                        } else {
+                            start
+                        };
+                        // Unlike message ranges, enum ranges are inclusive
+                        enumeration.reserved_range.push(EnumReservedRange {
+                            start: Some(start),
+                            end: Some(end),
+                        });
+                        if !self.eat_symbol(',')? {
+                            break;
+                        }
+                    }
+                    self.expect_symbol(';')?;
+                }
+                continue;
+            }
+            let name = self.ident()?;
+            self.expect_symbol('=')?;
+            let pos = self.pos;
+            let number = self.int()?;
+            let number = i32::try_from(number)
+                .map_err(|_| (pos, format!("Enum value {} is out of range", number)))?;
+            self.bracket_options()?;
+            self.expect_symbol(';')?;
+            enumeration.value.push(EnumValueDescriptorProto {
+                name: Some(name),
+                number: Some(number),
+                options: None,
+            });
+        }
+        if enumeration.value.is_empty() {
+            return self.fail(format!("Enum \"{}\" has no values", enumeration.name()));
+        }
+        Ok(enumeration)
+    }
+
+    fn service(&mut self) -> PResult<ServiceDescriptorProto> {
+        let mut service = ServiceDescriptorProto {
+            name: Some(self.ident()?),
+            ..Default::default()
+        };
+        self.expect_symbol('{')?;
+        loop {
+            if self.eat_symbol('}')? {
+                break;
+            }
+            if self.eat_symbol(';')? {
+                continue;
+            }
+            if self.is_keyword("option") {
+                self.advance()?;
+                self.option_statement()?;
+                continue;
+            }
+            self.expect_keyword("rpc")?;
+            let name = self.ident()?;
+            let (client_streaming, input_type) = self.rpc_type()?;
+            self.expect_keyword("returns")?;
+            let (server_streaming, output_type) = self.rpc_type()?;
+            if self.eat_symbol('{')? {
+                while !self.eat_symbol('}')? {
+                    if self.eat_symbol(';')? {
+                        continue;
+                    }
+                    self.expect_keyword("option")?;
+                    self.option_statement()?;
+                }
+            } else {
+                self.expect_symbol(';')?;
+            }
+            service.method.push(MethodDescriptorProto {
+                name: Some(name),
+                input_type: Some(input_type),
+                output_type: Some(output_type),
+                options: None,
+                client_streaming: client_streaming.then_some(true),
+                server_streaming: server_streaming.then_some(true),
+            });
+        }
+        Ok(service)
+    }
+
+    /// `( [stream] Type )`.
+    fn rpc_type(&mut self) -> PResult<(bool, String)> {
+        self.expect_symbol('(')?;
+        let mut streaming = false;
+        if self.is_keyword("stream") {
+            self.advance()?;
+            // Unless `stream` is itself the type name
+            if self.is_symbol(')') {
+                self.advance()?;
+                return Ok((false, "stream".to_string()));
+            }
+            streaming = true;
+        }
+        let name = self.full_ident()?;
+        self.expect_symbol(')')?;
+        Ok((streaming, name))
+    }
+
+    /// Extensions don't affect how messages travel as JSON, so they are
+    /// checked for syntax and dropped.
+    fn extend(&mut self) -> PResult<()> {
+        self.full_ident()?;
+        self.expect_symbol('{')?;
+        let mut scratch = DescriptorProto::default();
+        loop {
+            if self.eat_symbol('}')? {
+                return Ok(());
+            }
+            if self.eat_symbol(';')? {
+                continue;
+            }
+            let label = match &self.token {
+                Token::Ident(k) if k == "repeated" => Some(Label::Repeated),
+                Token::Ident(k) if k == "optional" => Some(Label::Optional),
+                Token::Ident(k) if k == "required" => Some(Label::Required),
+                _ => None,
+            };
+            if label.is_some() {
+                self.advance()?;
+            }
+            let field = self.field(label, &scratch)?;
+            scratch.field.push(field);
+        }
+    }
+}
+
+fn scalar_type(name: &str) -> Option<Type> {
+    Some(match name {
+        "double" => Type::Double,
+        "float" => Type::Float,
+        "int32" => Type::Int32,
+        "int64" => Type::Int64,
+        "uint32" => Type::Uint32,
+        "uint64" => Type::Uint64,
+        "sint32" => Type::Sint32,
+        "sint64" => Type::Sint64,
+        "fixed32" => Type::Fixed32,
+        "fixed64" => Type::Fixed64,
+        "sfixed32" => Type::Sfixed32,
+        "sfixed64" => Type::Sfixed64,
+        "bool" => Type::Bool,
+        "string" => Type::String,
+        "bytes" => Type::Bytes,
+        _ => return None,
+    })
+}
+
+/// Scalars get a type; messages and enums only a name, which the pool resolves.
+fn set_field_type(field: &mut FieldDescriptorProto, type_name: String) {
+    match scalar_type(&type_name) {
+        Some(ty) => field.set_type(ty),
+        None => field.type_name = Some(type_name),
+    }
+}
+
+/// `foo_bar` → `FooBarEntry`.
+fn map_entry_name(field: &str) -> String {
+    let mut name = String::new();
+    let mut upper = true;
+    for c in field.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            name.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name.push_str("Entry");
+    name
+}
+
+/// Parses one file; `name` is the path other files import it by.
+pub fn parse(name: &str, source: &str) -> Result<FileDescriptorProto, String> {
+    Parser::new(source)
+        .and_then(|mut parser| parser.file(name))
+        .map_err(|(pos, message)| format!("{}:{}:{}: {}", name, pos.line, pos.column, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::build_pool;
+    use prost::Message;
+    use prost_reflect::{DynamicMessage, Kind, Value};
+
+    const COMMON: &str = r#"
+        syntax = "proto3";
+        package acme.common;
+
+        message Money {
+          string currency = 1;
+          int64 units = 2;
+        }
+    "#;
+
+    const SHOP: &str = r#"
+        syntax = "proto3";
+        package acme.shop;
+
+        import "acme/common.proto";
+        import public "google/protobuf/timestamp.proto";
+        import weak "acme/legacy.proto";
+
+        message Order {
+          message Line {
+            enum Kind { KIND_UNSPECIFIED = 0; ITEM = 1; FEE = 2; }
+            string sku = 1;
+            Kind kind = 2;
+            acme.common.Money price = 3;
+          }
+          repeated Line lines = 1;
+          map<string, Line> lines_by_sku = 2;
+          map<int32, string> notes = 3;
+          oneof payment {
+            string card_token = 4;
+            Line.Kind voucher_kind = 5;
+          }
+          optional string comment = 6;
+          oneof delivery {
+            string address = 7;
+          }
+          repeated int32 quantities = 8;
+          repeated sint64 deltas = 9 [packed = false];
+          google.protobuf.Timestamp placed_at = 10;
+        }
+
+        service Shop {
+          rpc Place (Order) returns (Order);
+          rpc Watch (Order) returns (stream Order) { option deprecated = true; }
+        }
+    "#;
+
+    fn pool() -> prost_reflect::DescriptorPool {
+        let files = vec![
+            parse("acme/common.proto", COMMON).unwrap(),
+            parse(
+                "acme/shop.proto",
+                &SHOP.replace("import weak \"acme/legacy.proto\";", ""),
+            )
+            .unwrap(),
+        ];
+        build_pool(files).unwrap()
+    }
+
+    #[test]
+    fn records_imports() {
+        let file = parse("acme/shop.proto", SHOP).unwrap();
+        assert_eq!(
+            file.dependency,
+            [
+                "acme/common.proto",
+                "google/protobuf/timestamp.proto",
+                "acme/legacy.proto"
+            ]
+        );
+        assert_eq!(file.public_dependency, [1]);
+        assert_eq!(file.weak_dependency, [2]);
+        assert_eq!(file.package(), "acme.shop");
+        assert_eq!(file.syntax(), "proto3");
+    }
+
+    #[test]
+    fn resolves_imported_and_nested_types() {
+        let pool = pool();
+        let order = pool.get_message_by_name("acme.shop.Order").unwrap();
+        let line = pool.get_message_by_name("acme.shop.Order.Line").unwrap();
+        assert_eq!(
+            line.parent_message().unwrap().full_name(),
+            "acme.shop.Order"
+        );
+
+        let kind = line.get_field_by_name("kind").unwrap();
+        let Kind::Enum(kind) = kind.kind() else {
+            panic!("expected an enum, got {:?}", kind.kind());
+        };
+        assert_eq!(kind.full_name(), "acme.shop.Order.Line.Kind");
+        assert_eq!(kind.get_value_by_name("FEE").unwrap().number(), 2);
+
+        let price = line.get_field_by_name("price").unwrap();
+        assert!(matches!(price.kind(), Kind::Message(m) if m.full_name() == "acme.common.Money"));
+        let placed_at = order.get_field_by_name("placed_at").unwrap();
+        assert!(
+            matches!(placed_at.kind(), Kind::Message(m) if m.full_name() == "google.protobuf.Timestamp")
+        );
+
+        let shop = pool.get_service_by_name("acme.shop.Shop").unwrap();
+        let watch = shop.methods().find(|m| m.name() == "Watch").unwrap();
+        assert!(watch.is_server_streaming() && !watch.is_client_streaming());
+        assert_eq!(watch.input().full_name(), "acme.shop.Order");
+    }
+
+    #[test]
+    fn builds_oneofs() {
+        let file = parse("acme/shop.proto", SHOP).unwrap();
+        let order = &file.message_type[0];
+        let oneofs: Vec<&str> = order.oneof_decl.iter().map(|o| o.name()).collect();
+        // proto3 `optional` adds a synthetic oneof after the declared ones
+        assert_eq!(oneofs, ["payment", "delivery", "_comment"]);
+        let index_of = |name: &str| {
+            order
+                .field
+                .iter()
+                .find(|f| f.name() == name)
+                .unwrap()
+                .oneof_index
+        };
+        assert_eq!(index_of("card_token"), Some(0));
+        assert_eq!(index_of("voucher_kind"), Some(0));
+        assert_eq!(index_of("address"), Some(1));
+        assert_eq!(index_of("comment"), Some(2));
+        assert_eq!(index_of("lines"), None);
+
+        let order = pool().get_message_by_name("acme.shop.Order").unwrap();
+        let payment = order.oneofs().find(|o| o.name() == "payment").unwrap();
+        let fields: Vec<String> = payment.fields().map(|f| f.name().to_string()).collect();
+        assert_eq!(fields, ["card_token", "voucher_kind"]);
+        let comment = order.get_field_by_name("comment").unwrap();
+        assert!(comment.containing_oneof().unwrap().is_synthetic());
+    }
+
+    #[test]
+    fn builds_map_entries() {
+        let file = parse("acme/shop.proto", SHOP).unwrap();
+        let order = &file.message_type[0];
+        let entry = order
+            .nested_type
+            .iter()
+            .find(|m| m.name() == "LinesBySkuEntry")
+            .unwrap();
+        assert_eq!(entry.options.as_ref().unwrap().map_entry, Some(true));
+        assert_eq!(entry.field[0].r#type(), Type::String);
+        assert_eq!(entry.field[1].type_name(), "Line");
+
+        let order = pool().get_message_by_name("acme.shop.Order").unwrap();
+        let lines_by_sku = order.get_field_by_name("lines_by_sku").unwrap();
+        assert!(lines_by_sku.is_map());
+        let Kind::Message(entry) = lines_by_sku.kind() else {
+            panic!("expected a map entry message");
+        };
+        assert!(
+            matches!(entry.map_entry_value_field().kind(), Kind::Message(m) if m.name() == "Line")
+        );
+        let notes = order.get_field_by_name("notes").unwrap();
+        let Kind::Message(entry) = notes.kind() else {
+            panic!("expected a map entry message");
+        };
+        assert_eq!(entry.map_entry_key_field().kind(), Kind::Int32);
+    }
+
+    #[test]
+    fn packs_repeated_scalars_unless_told_not_to() {
+        let file = parse("acme/shop.proto", SHOP).unwrap();
+        let field = |name: &str| {
+            file.message_type[0]
+                .field
+                .iter()
+                .find(|f| f.name() == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(field("quantities").options, None);
+        assert_eq!(field("deltas").options.unwrap().packed, Some(false));
+
+        let order = pool().get_message_by_name("acme.shop.Order").unwrap();
+        let mut message = DynamicMessage::new(order.clone());
+        message.set_field_by_name(
+            "quantities",
+            Value::List(vec![Value::I32(1), Value::I32(2), Value::I32(300)]),
+        );
+        message.set_field_by_name("deltas", Value::List(vec![Value::I64(-1), Value::I64(1)]));
+        assert_eq!(
+            message.encode_to_vec(),
+            [
+                0x42, 4, 1, 2, 0xac, 0x02, // field 8, packed: 1, 2, 300
+                0x48, 1, 0x48, 2, // field 9, one zigzag varint per element: -1, 1
+            ]
+        );
+
+        // Parsers must accept either encoding for any repeated scalar
+        let swapped = [0x40, 1, 0x40, 2, 0x4a, 2, 1, 2];
+        let decoded = DynamicMessage::decode(order, &swapped[..]).unwrap();
+        assert_eq!(
+            decoded
+                .get_field_by_name("quantities")
+                .unwrap()
+                .as_list()
+                .unwrap(),
+            [Value::I32(1), Value::I32(2)]
+        );
+        assert_eq!(
+            decoded
+                .get_field_by_name("deltas")
+                .unwrap()
+                .as_list()
+                .unwrap(),
+            [Value::I64(-1), Value::I64(1)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let cases = [
+            (
+                "message M { int32 a = 1; }",
+                "f.proto:1:13: Fields in proto2 need a label: optional, required or repeated",
+            ),
+            (
+                "syntax = \"proto3\";\nmessage M { required int32 a = 1; }",
+                "f.proto:2:13: Required fields are not allowed in proto3",
+            ),
+            (
+                "syntax = \"proto4\";",
+                "f.proto:1:10: Unknown syntax \"proto4\"",
+            ),
+            (
+                "edition = \"2023\";",
+                "f.proto:1:1: Protobuf editions are not supported; use proto2 or proto3 syntax",
+            ),
+            (
+                "syntax = \"proto3\";\nmessage M {\n  int32 a = 0;\n}",
+                "f.proto:3:13: Field number 0 is out of range",
+            ),
+            (
+                "syntax = \"proto3\"; message M { int32 a = 1; string a = 2; }",
+                "f.proto:1:59: Field \"a\" is already defined",
+            ),
+            (
+                "syntax = \"proto3\"; message M { map<string, int32 m = 1; }",
+                "f.proto:1:50: Expected '>', found 'm'",
+            ),
+            (
+                "syntax = \"proto3\"; enum E { }",
+                "f.proto:1:30: Enum \"E\" has no values",
+            ),
+            (
+                "package a; package b;",
+                "f.proto:1:12: Multiple package declarations",
+            ),
+            (
+                "import \"x.proto\"",
+                "f.proto:1:17: Expected ';', found end of file",
+            ),
+            (
+                "service S { rpc M (A) (B); }",
+                "f.proto:1:23: Expected 'returns', found '('",
+            ),
+            (
+                "syntax = \"proto3\"; message M { int32 a = 1 }",
+                "f.proto:1:44: Expected ';', found '}'",
+            ),
+        ];
+        for (source, message) in cases {
+            assert_eq!(parse("f.proto", source).unwrap_err(), message, "{}", source);
+        }
+    }
+}
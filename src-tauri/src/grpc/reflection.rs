@@ -0,0 +1,243 @@
+//! A client for the server reflection service, which lists a server's
+//! services and hands over the descriptors of the files defining them.
+
+use super::call::{self, GrpcTarget};
+use super::codec::{self, Status};
+use crate::http::ClientSource;
+use prost::Message;
+use prost_reflect::DescriptorPool;
+use prost_types::FileDescriptorProto;
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Newest first; older servers only implement v1alpha.
+const SERVICES: [&str; 2] = [
+    "grpc.reflection.v1.ServerReflection",
+    "grpc.reflection.v1alpha.ServerReflection",
+];
+
+/// Applies when the request sets no timeout, so an unresponsive server
+/// doesn't leave the service list loading forever.
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(30);
+
+#[derive(Clone, PartialEq, Message)]
+struct ServerReflectionRequest {
+    #[prost(string, tag = "1")]
+    host: String,
+    #[prost(oneof = "MessageRequest", tags = "3, 4, 7")]
+    message_request: Option<MessageRequest>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum MessageRequest {
+    #[prost(string, tag = "3")]
+    FileByFilename(String),
+    #[prost(string, tag = "4")]
+    FileContainingSymbol(String),
+    #[prost(string, tag = "7")]
+    ListServices(String),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ServerReflectionResponse {
+    #[prost(oneof = "MessageResponse", tags = "4, 6, 7")]
+    message_response: Option<MessageResponse>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+enum MessageResponse {
+    #[prost(message, tag = "4")]
+    Files(FileDescriptorResponse),
+    #[prost(message, tag = "6")]
+    Services(ListServiceResponse),
+    #[prost(message, tag = "7")]
+    Error(ErrorResponse),
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct FileDescriptorResponse {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    file_descriptor_proto: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ListServiceResponse {
+    #[prost(message, repeated, tag = "1")]
+    service: Vec<ServiceResponse>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ServiceResponse {
+    #[prost(string, tag = "1")]
+    name: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ErrorResponse {
+    #[prost(int32, tag = "1")]
+    error_code: i32,
+    #[prost(string, tag = "2")]
+    error_message: String,
+}
+
+/// One reflection stream, asked one question at a time.
+struct Session {
+    queue: mpsc::UnboundedSender<Vec<u8>>,
+    call: call::Call,
+}
+
+impl Session {
+    async fn ask(&mut self, request: MessageRequest) -> Result<MessageResponse, Status> {
+        let request = ServerReflectionRequest {
+            host: String::new(),
+            message_request: Some(request),
+        };
+        self.queue
+            .send(codec::encode(&request.encode_to_vec()))
+            .map_err(|_| Status::new(codec::INTERNAL, "Reflection stream closed"))?;
+        self.answer().await
+    }
+
+    async fn answer(&mut self) -> Result<MessageResponse, Status> {
+        let Some(message) = self.call.message().await? else {
+            let status = self.call.status();
+            return Err(if status.is_ok() {
+                Status::new(codec::INTERNAL, "Reflection stream ended early")
+            } else {
+                status
+            });
+        };
+        let response = ServerReflectionResponse::decode(message.as_slice()).map_err(|e| {
+            Status::new(
+                codec::INTERNAL,
+                format!("Invalid reflection response: {}", e),
+            )
+        })?;
+        match response.message_response {
+            Some(MessageResponse::Error(error)) => {
+                Err(Status::new(error.error_code as u32, error.error_message))
+            }
+            Some(response) => Ok(response),
+            None => Err(Status::new(codec::INTERNAL, "Empty reflection response")),
+        }
+    }
+
+    fn files(response: MessageResponse) -> Result<Vec<FileDescriptorProto>, Status> {
+        let MessageResponse::Files(files) = response else {
+            return Err(Status::new(
+                codec::INTERNAL,
+                "Unexpected reflection response",
+            ));
+        };
+        files
+            .file_descriptor_proto
+            .iter()
+            .map(|bytes| {
+                FileDescriptorProto::decode(bytes.as_slice()).map_err(|e| {
+                    Status::new(codec::INTERNAL, format!("Invalid file descriptor: {}", e))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Files received so far, and the dependencies still to check for.
+#[derive(Default)]
+struct Files {
+    files: Vec<FileDescriptorProto>,
+    seen: HashSet<String>,
+    missing: VecDeque<String>,
+}
+
+impl Files {
+    fn add(&mut self, batch: Vec<FileDescriptorProto>) {
+        for file in batch {
+            if self.seen.insert(file.name().to_string()) {
+                self.missing.extend(file.dependency.iter().cloned());
+                self.files.push(file);
+            }
+        }
+    }
+}
+
+async fn fetch_with(
+    clients: &ClientSource,
+    target: &GrpcTarget,
+    service: &str,
+    deadline: Option<Duration>,
+) -> Result<Vec<FileDescriptorProto>, Status> {
+    let url = target
+        .method_url(service, "ServerReflectionInfo")
+        .map_err(|e| Status::new(codec::INTERNAL, e))?;
+    let (queue, outgoing) = mpsc::unbounded_channel();
+    // The server answers the headers along with the first reply
+    let first = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let _ = queue.send(codec::encode(&first.encode_to_vec()));
+    let call = call::open(clients, target, url, deadline, outgoing).await?;
+    let mut session = Session { queue, call };
+
+    let MessageResponse::Services(list) = session.answer().await? else {
+        return Err(Status::new(
+            codec::INTERNAL,
+            "Unexpected reflection response",
+        ));
+    };
+
+    // The well-known types are built in, so servers that leave them out still work
+    let known = DescriptorPool::global();
+    let mut files = Files::default();
+
+    for service in list.service {
+        if SERVICES.contains(&service.name.as_str()) {
+            continue;
+        }
+        let response = session
+            .ask(MessageRequest::FileContainingSymbol(service.name))
+            .await?;
+        files.add(Session::files(response)?);
+    }
+    // Servers usually send dependencies unasked, but need not
+    while let Some(name) = files.missing.pop_front() {
+        if files.seen.contains(&name) || known.get_file_by_name(&name).is_some() {
+            continue;
+        }
+        let response = session.ask(MessageRequest::FileByFilename(name)).await?;
+        files.add(Session::files(response)?);
+    }
+    Ok(files.files)
+}
+
+/// The files defining every service the server offers, dependencies included.
+pub async fn fetch(
+    clients: &ClientSource,
+    target: &GrpcTarget,
+    deadline: Option<Duration>,
+) -> Result<Vec<FileDescriptorProto>, String> {
+    let deadline = deadline.unwrap_or(DEFAULT_DEADLINE);
+    let attempts = async {
+        let mut result = Err(Status::new(codec::UNIMPLEMENTED, ""));
+        for service in SERVICES {
+            result = fetch_with(clients, target, service, Some(deadline)).await;
+            match result {
+                Err(ref status) if status.code == codec::UNIMPLEMENTED => continue,
+                _ => break,
+            }
+        }
+        result
+    };
+    let result = tokio::time::timeout(deadline, attempts)
+        .await
+        .unwrap_or_else(|_| Err(Status::new(codec::DEADLINE_EXCEEDED, "")));
+    result.map_err(|status| match status.code {
+        codec::UNIMPLEMENTED => {
+            "Server does not support reflection; load its .proto files instead".to_string()
+        }
+        _ => format!("Reflection failed: {} {}", status.name, status.message)
+            .trim_end()
+            .to_string(),
+    })
+}
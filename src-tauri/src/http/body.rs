@@ -0,0 +1,172 @@
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use std::io::SeekFrom;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// Bytes read from the start of a file to sniff its type.
+const SNIFF_LEN: usize = 8192;
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestBody {
+    Text {
+        content: String,
+    },
+    /// Streamed from disk so large uploads never pass through the webview.
+    File {
+        path: String,
+        content_type: Option<String>,
+    },
+    Multipart {
+        parts: Vec<FormPart>,
+    },
+}
+
+/// One `multipart/form-data` field. File parts are streamed from disk.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FormPart {
+    Text {
+        name: String,
+        value: String,
+        content_type: Option<String>,
+    },
+    File {
+        name: String,
+        path: String,
+        /// Defaults to the file's own name.
+        filename: Option<String>,
+        /// Sniffed from the file when unset.
+        content_type: Option<String>,
+    },
+}
+
+pub enum PreparedBody {
+    Raw {
+        body: reqwest::Body,
+        /// Applied only when the request has no Content-Type header of its own.
+        content_type: Option<String>,
+        size_bytes: u64,
+    },
+    /// reqwest sets Content-Type (with the boundary) and Content-Length itself.
+    Multipart(Form),
+}
+
+struct OpenedFile {
+    file: tokio::fs::File,
+    size_bytes: u64,
+    content_type: String,
+}
+
+async fn open_file(path: &str, content_type: Option<String>) -> Result<OpenedFile, String> {
+    let mut file = tokio::fs::File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open '{}': {}", path, e))?;
+    let size_bytes = file
+        .metadata()
+        .await
+        .map_err(|e| format!("Failed to read '{}': {}", path, e))?
+        .len();
+
+    let content_type = match content_type.filter(|c| !c.is_empty()) {
+        Some(c) => c,
+        None => {
+            let mut head = vec![0; SNIFF_LEN.min(size_bytes as usize)];
+            file.read_exact(&mut head)
+                .await
+                .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+            file.seek(SeekFrom::Start(0))
+                .await
+                .map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+            sniff_content_type(path, &head)
+        }
+    };
+
+    Ok(OpenedFile {
+        file,
+        size_bytes,
+        content_type,
+    })
+}
+
+impl FormPart {
+    async fn into_part(self) -> Result<(String, Part), String> {
+        match self {
+            FormPart::Text {
+                name,
+                value,
+                content_type,
+            } => {
+                let mut part = Part::text(value);
+                if let Some(ct) = content_type.filter(|c| !c.is_empty()) {
+                    part = part
+                        .mime_str(&ct)
+                        .map_err(|e| format!("Invalid content type for '{}': {}", name, e))?;
+                }
+                Ok((name, part))
+            }
+            FormPart::File {
+                name,
+                path,
+                filename,
+                content_type,
+            } => {
+                let opened = open_file(&path, content_type).await?;
+                let filename = filename.filter(|f| !f.is_empty()).unwrap_or_else(|| {
+                    std::path::Path::new(&path)
+                        .file_name()
+                        .map(|f| f.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+                let part = Part::stream_with_length(
+                    reqwest::Body::wrap_stream(ReaderStream::new(opened.file)),
+                    opened.size_bytes,
+                )
+                .file_name(filename)
+                .mime_str(&opened.content_type)
+                .map_err(|e| format!("Invalid content type for '{}': {}", name, e))?;
+                Ok((name, part))
+            }
+        }
+    }
+}
+
+impl RequestBody {
+    pub async fn prepare(self) -> Result<PreparedBody, String> {
+        match self {
+            RequestBody::Text { content } => Ok(PreparedBody::Raw {
+                size_bytes: content.len() as u64,
+                body: content.into(),
+                content_type: None,
+            }),
+            RequestBody::File { path, content_type } => {
+                let opened = open_file(&path, content_type).await?;
+                Ok(PreparedBody::Raw {
+                    body: reqwest::Body::wrap_stream(ReaderStream::new(opened.file)),
+                    content_type: Some(opened.content_type),
+                    size_bytes: opened.size_bytes,
+                })
+            }
+            RequestBody::Multipart { parts } => {
+                let mut form = Form::new();
+                for part in parts {
+                    let (name, part) = part.into_part().await?;
+                    form = form.part(name, part);
+                }
+                Ok(PreparedBody::Multipart(form))
+            }
+        }
+    }
+}
+
+/// Magic bytes first, then the file extension, then `application/octet-stream`.
+pub fn sniff_content_type(path: &str, head: &[u8]) -> String {
+    if let Some(kind) = infer::get(head) {
+        return kind.mime_type().to_string();
+    }
+    mime_guess::from_path(path)
+        .first_raw()
+        .unwrap_or("application/octet-stream")
+        .to_string()
+}
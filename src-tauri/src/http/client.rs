@@ -1,73 +1,357 @@
-use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
-use reqwest::Method;
+use super::body::{PreparedBody, RequestBody};
+use super::cookies::CookieJar;
+use super::decode::{self, BodyEncoding};
+use super::download::{self, Download};
+use super::dns::{HostOverride, IpFamily, Resolver};
+use super::timing::{self, TimingBreakdown, TimingLayer};
+use reqwest::header::{
+    HeaderMap, HeaderName, HeaderValue, ACCEPT, ACCEPT_ENCODING, AUTHORIZATION, CACHE_CONTROL,
+    CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, LOCATION, PROXY_AUTHORIZATION, TRANSFER_ENCODING,
+    WWW_AUTHENTICATE,
+};
+use super::proxy::{self, ProxyConfig};
+use super::retry::{RetryAttempt, RetryConfig, RetryPolicy};
+use super::sse::{self, EventStream, ReconnectConfig, ReconnectPolicy, StreamEnd};
+use super::ClientSource;
+use super::tls::{self, PinLayer, TlsProfile};
+use super::unix::UnixTarget;
+use super::x509::{self, CertificateSummary};
+use reqwest::tls::TlsInfo;
+use reqwest::{Method, Url};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-#[derive(Debug, Deserialize)]
+/// How a header combines with values of the same name set before it.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HeaderMode {
+    /// Always add another value.
+    Append,
+    /// Drop every earlier value of this name, inherited or not.
+    Replace,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct HeaderEntry {
     pub key: String,
     pub value: String,
     pub enabled: bool,
+    /// `None` replaces inherited values on the first occurrence in a layer
+    /// and appends on repeats within the same layer.
+    #[serde(default)]
+    pub mode: Option<HeaderMode>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct HttpRequest {
     pub method: String,
     pub url: String,
+    /// Inherited default headers, one layer per ancestor: collection first,
+    /// then folders from outermost to innermost.
+    #[serde(default)]
+    pub default_headers: Vec<Vec<HeaderEntry>>,
     pub headers: Vec<HeaderEntry>,
-    pub body: Option<String>,
+    pub body: Option<RequestBody>,
+}
+
+/// Which HTTP version the client speaks.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpVersionPref {
+    /// HTTP/2 when the server offers it via ALPN, HTTP/1.1 otherwise.
+    #[default]
+    Auto,
+    /// HTTP/1.1 only, even if the server offers h2.
+    Http1,
+    /// HTTP/2 only: ALPN `h2` over TLS and prior-knowledge h2c over
+    /// cleartext, with no fallback to HTTP/1.1.
+    Http2,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 pub struct HttpConfig {
     pub timeout_ms: Option<u64>,
-    pub proxy_url: Option<String>,
+    /// Bounds connecting and each read rather than the whole exchange, for
+    /// transfers that may rightly run longer than `timeout_ms`.
+    pub read_timeout_ms: Option<u64>,
+    pub proxy: Option<ProxyConfig>,
     pub verify_ssl: Option<bool>,
+    pub http_version: Option<HttpVersionPref>,
+    pub ip_family: Option<IpFamily>,
+    pub follow_redirects: Option<bool>,
+    pub max_redirects: Option<u32>,
+    pub preserve_method_on_redirect: Option<bool>,
+    pub retry: Option<RetryConfig>,
+    /// Ask for and decode gzip, deflate, br and zstd bodies. Defaults to on.
+    pub decompress: Option<bool>,
+    /// Reopening event streams that end; only read in event stream mode.
+    pub reconnect: Option<ReconnectConfig>,
+}
+
+impl HttpConfig {
+    /// Normalizes settings that are equivalent on the wire so they share a pooled client.
+    pub fn effective(mut self) -> Self {
+        self.proxy = self.proxy.and_then(ProxyConfig::effective);
+        if self.verify_ssl == Some(true) {
+            self.verify_ssl = None;
+        }
+        if self.http_version == Some(HttpVersionPref::Auto) {
+            self.http_version = None;
+        }
+        if self.ip_family == Some(IpFamily::Any) {
+            self.ip_family = None;
+        }
+        // Redirects are followed per request, not by the client
+        self.follow_redirects = None;
+        self.max_redirects = None;
+        self.preserve_method_on_redirect = None;
+        // So are retries, decompression and event stream reconnects
+        self.retry = None;
+        self.decompress = None;
+        self.reconnect = None;
+        self
+    }
+
+    pub fn redirect_policy(&self) -> RedirectPolicy {
+        let default = RedirectPolicy::default();
+        RedirectPolicy {
+            follow: self.follow_redirects.unwrap_or(default.follow),
+            max_hops: self.max_redirects.unwrap_or(default.max_hops),
+            preserve_method: self.preserve_method_on_redirect.unwrap_or(default.preserve_method),
+        }
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry
+            .as_ref()
+            .map(RetryConfig::policy)
+            .unwrap_or_default()
+    }
+
+    pub fn reconnect_policy(&self) -> ReconnectPolicy {
+        self.reconnect
+            .as_ref()
+            .map(ReconnectConfig::policy)
+            .unwrap_or_default()
+    }
+}
+
+/// What `execute_request` does with the response body.
+pub enum Delivery {
+    /// Read it whole and decode it.
+    Buffer,
+    /// Stream it to a file as received.
+    Download(Download),
+    /// Parse a `text/event-stream` body as it arrives, handing each event to
+    /// the stream. Any other response is buffered.
+    Events(Arc<EventStream>),
+}
+
+/// How `execute_request` follows redirects. Hops are driven by hand rather
+/// than by reqwest so each one can be reported.
+#[derive(Debug, Clone, Copy)]
+pub struct RedirectPolicy {
+    pub follow: bool,
+    pub max_hops: u32,
+    /// Resend the method and body on 307/308; when off they are treated like 303.
+    pub preserve_method: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        RedirectPolicy {
+            follow: true,
+            max_hops: 10,
+            preserve_method: true,
+        }
+    }
+}
+
+/// An intermediate 3xx response that was followed.
+#[derive(Debug, Serialize)]
+pub struct RedirectHop {
+    pub url: String,
+    pub status: u16,
+    pub status_text: String,
+    pub headers: Vec<ResponseHeader>,
+    pub time_ms: u64,
+    pub timings: TimingBreakdown,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ResponseHeader {
+    pub name: String,
+    pub value: String,
+    /// Original bytes, present only when `value` is not valid UTF-8 and had to be decoded lossily.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<Vec<u8>>,
+}
+
+impl ResponseHeader {
+    pub fn new(name: &HeaderName, value: &HeaderValue) -> Self {
+        let bytes = value.as_bytes();
+        match std::str::from_utf8(bytes) {
+            Ok(v) => ResponseHeader {
+                name: name.to_string(),
+                value: v.to_string(),
+                raw: None,
+            },
+            Err(_) => ResponseHeader {
+                name: name.to_string(),
+                value: String::from_utf8_lossy(bytes).to_string(),
+                raw: Some(bytes.to_vec()),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
 pub struct HttpResponse {
     pub status: u16,
     pub status_text: String,
-    pub headers: HashMap<String, String>,
+    /// Final URL after any redirects.
+    pub url: String,
+    pub headers: Vec<ResponseHeader>,
+    /// Decoded text, or base64 when `body_encoding` is `base64`.
     pub body: String,
+    pub body_encoding: BodyEncoding,
+    /// Original bytes as base64, present only when `body` was transcoded from
+    /// another charset or had invalid bytes replaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_raw: Option<String>,
+    pub mime_type: Option<String>,
+    pub charset: Option<String>,
+    /// Content codings removed from the body, e.g. `gzip`; absent when it
+    /// arrived uncompressed or was left as received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    /// Exactly the bytes received, as base64, present only when they were decompressed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wire_body: Option<String>,
+    /// Why a compressed body could not be decoded and was returned as received.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decompress_error: Option<String>,
+    /// Set when the body was streamed to this path instead of returned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saved_to: Option<String>,
+    /// Set when the body was read as an event stream; its events went to the
+    /// stream rather than into `body`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_stream: Option<StreamEnd>,
     pub time_ms: u64,
+    /// Size of the body after decompression.
     pub size_bytes: u64,
+    /// Size of the body as received on the wire, before decompression.
+    pub wire_size_bytes: u64,
+    /// Size of the request body as sent (0 when there is none).
+    pub request_size_bytes: u64,
+    pub timings: TimingBreakdown,
+    /// Redirects followed before this response, in order.
+    pub redirects: Vec<RedirectHop>,
+    /// Failed attempts retried before this response, in order.
+    pub attempts: Vec<RetryAttempt>,
+    pub connection: ConnectionInfo,
 }
 
-pub async fn execute_request(
-    request: HttpRequest,
-    config: Option<HttpConfig>,
-) -> Result<HttpResponse, String> {
-    let mut builder = reqwest::Client::builder();
+/// How the final response was delivered.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionInfo {
+    /// `HTTP/1.1`, `HTTP/2`, ...; over TLS this is what ALPN settled on.
+    pub http_version: String,
+    pub remote_addr: Option<String>,
+    /// Whether an idle pooled connection carried the request.
+    pub reused: bool,
+    /// Set instead of `remote_addr` for `http+unix` requests.
+    pub unix_socket: Option<String>,
+    /// Every address the host resolved to, in the order they were tried.
+    /// Empty when the connection was reused or the host is an IP literal.
+    pub resolved_addresses: Vec<String>,
+    /// Whether those addresses came from an environment's DNS override.
+    pub dns_override: bool,
+    /// `TLSv1.3` or `TLSv1.2`; `None` over plain HTTP and when the
+    /// connection was reused or its session resumed.
+    #[serde(default)]
+    pub tls_version: Option<String>,
+    /// Whether this send's TLS handshake resumed an earlier session. The
+    /// certificate exchange is skipped then, so only the leaf is known.
+    #[serde(default)]
+    pub tls_resumed: bool,
+    /// The server's leaf certificate.
+    pub peer_certificate: Option<CertificateSummary>,
+    /// The rest of the chain the server sent, nearest issuer first.
+    #[serde(default)]
+    pub certificate_chain: Vec<CertificateSummary>,
+}
 
-    if let Some(ref cfg) = config {
-        if let Some(timeout_ms) = cfg.timeout_ms {
-            builder = builder.timeout(Duration::from_millis(timeout_ms));
-        }
-        if let Some(ref proxy_url) = cfg.proxy_url {
-            if !proxy_url.is_empty() {
-                let proxy = reqwest::Proxy::all(proxy_url)
-                    .map_err(|e| format!("Invalid proxy URL: {}", e))?;
-                builder = builder.proxy(proxy);
-            }
-        }
-        if let Some(verify_ssl) = cfg.verify_ssl {
-            builder = builder.danger_accept_invalid_certs(!verify_ssl);
+pub fn build_client(
+    config: &HttpConfig,
+    jar: Option<Arc<CookieJar>>,
+    tls_profile: &TlsProfile,
+    host_override: Option<HostOverride>,
+    unix_socket: Option<&Path>,
+) -> Result<reqwest::Client, String> {
+    let mut builder = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .dns_resolver(Arc::new(Resolver {
+            host_override,
+            family: config.ip_family.unwrap_or_default(),
+        }))
+        .connector_layer(TimingLayer)
+        .tls_info(true);
+
+    if let Some(jar) = jar {
+        builder = builder.cookie_provider(jar);
+    }
+
+    if let Some(timeout_ms) = config.timeout_ms {
+        builder = builder.timeout(Duration::from_millis(timeout_ms));
+    }
+    if let Some(read_timeout_ms) = config.read_timeout_ms {
+        let timeout = Duration::from_millis(read_timeout_ms);
+        builder = builder.connect_timeout(timeout).read_timeout(timeout);
+    }
+    builder = proxy::apply(builder, config.proxy.as_ref())?;
+    let http_version = config.http_version.unwrap_or_default();
+    match http_version {
+        HttpVersionPref::Auto => {}
+        HttpVersionPref::Http1 => builder = builder.http1_only(),
+        HttpVersionPref::Http2 => builder = builder.http2_prior_knowledge(),
+    }
+    builder = builder.use_preconfigured_tls(tls::client_config(
+        tls_profile,
+        config.verify_ssl.unwrap_or(true),
+        http_version,
+    )?);
+    if let Some(socket) = unix_socket {
+        #[cfg(unix)]
+        {
+            builder = builder.unix_socket(socket);
         }
+        #[cfg(not(unix))]
+        return Err(format!(
+            "Cannot connect to '{}': Unix domain sockets are not supported on this platform",
+            socket.display()
+        ));
+    }
+    // Pins are checked even with verification off, as curl's --pinnedpubkey is
+    if !tls_profile.pins.is_empty() {
+        builder = builder.connector_layer(PinLayer {
+            pins: Arc::new(tls::decode_pins(&tls_profile.pins)?),
+        });
     }
 
-    let client = builder
+    builder
         .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
-
-    let method = Method::from_str(&request.method.to_uppercase())
-        .map_err(|e| format!("Invalid HTTP method '{}': {}", request.method, e))?;
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
 
-    let mut header_map = HeaderMap::new();
-    for entry in &request.headers {
+fn apply_header_layer(map: &mut HeaderMap, entries: &[HeaderEntry]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for entry in entries {
         if !entry.enabled || entry.key.is_empty() {
             continue;
         }
@@ -75,48 +359,481 @@ pub async fn execute_request(
             .map_err(|e| format!("Invalid header name '{}': {}", entry.key, e))?;
         let value = HeaderValue::from_str(&entry.value)
             .map_err(|e| format!("Invalid header value for '{}': {}", entry.key, e))?;
-        header_map.insert(name, value);
+        let first_in_layer = seen.insert(name.clone());
+        match entry.mode {
+            Some(HeaderMode::Append) => {
+                map.append(name, value);
+            }
+            Some(HeaderMode::Replace) => {
+                map.insert(name, value);
+            }
+            None if first_in_layer => {
+                map.insert(name, value);
+            }
+            None => {
+                map.append(name, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Merges inherited default headers with the request's own, innermost layer last.
+pub fn build_header_map(
+    default_headers: &[Vec<HeaderEntry>],
+    headers: &[HeaderEntry],
+) -> Result<HeaderMap, String> {
+    let mut map = HeaderMap::new();
+    for layer in default_headers {
+        apply_header_layer(&mut map, layer)?;
     }
+    apply_header_layer(&mut map, headers)?;
+    Ok(map)
+}
+
+/// Builds one hop's request; file and multipart bodies are re-opened each time.
+async fn build_request(
+    client: &reqwest::Client,
+    method: Method,
+    url: Url,
+    mut header_map: HeaderMap,
+    body: Option<RequestBody>,
+) -> Result<reqwest::Request, String> {
+    let prepared = match body {
+        Some(body) => Some(body.prepare().await?),
+        None => None,
+    };
 
-    let mut req_builder = client.request(method, &request.url).headers(header_map);
+    let req_builder = client.request(method, url);
+    let req_builder = match prepared {
+        Some(PreparedBody::Raw {
+            body,
+            content_type,
+            size_bytes,
+        }) => {
+            if let Some(content_type) = content_type {
+                if !header_map.contains_key(CONTENT_TYPE) {
+                    let value = HeaderValue::from_str(&content_type)
+                        .map_err(|e| format!("Invalid content type '{}': {}", content_type, e))?;
+                    header_map.insert(CONTENT_TYPE, value);
+                }
+            }
+            // Streamed bodies would otherwise go out chunked
+            if !header_map.contains_key(CONTENT_LENGTH) {
+                header_map.insert(CONTENT_LENGTH, HeaderValue::from(size_bytes));
+            }
+            req_builder.headers(header_map).body(body)
+        }
+        Some(PreparedBody::Multipart(form)) => {
+            // A user-supplied Content-Type would lack the generated boundary
+            header_map.remove(CONTENT_TYPE);
+            header_map.remove(CONTENT_LENGTH);
+            req_builder.headers(header_map).multipart(form)
+        }
+        None => req_builder.headers(header_map),
+    };
+
+    req_builder
+        .build()
+        .map_err(|e| format!("Failed to build request: {}", e))
+}
 
-    if let Some(body) = &request.body {
-        req_builder = req_builder.body(body.clone());
+/// The next hop's URL, if `response` is a redirect this policy follows.
+fn redirect_target(response: &reqwest::Response, url: &Url) -> Result<Option<Url>, String> {
+    if !matches!(response.status().as_u16(), 301 | 302 | 303 | 307 | 308) {
+        return Ok(None);
     }
+    let Some(location) = response.headers().get(LOCATION) else {
+        return Ok(None);
+    };
+    let location = location
+        .to_str()
+        .map_err(|_| "Redirect Location header is not valid UTF-8".to_string())?;
+    url.join(location)
+        .map(Some)
+        .map_err(|e| format!("Invalid redirect Location '{}': {}", location, e))
+}
+
+pub async fn execute_request(
+    clients: ClientSource,
+    request: HttpRequest,
+    redirect: RedirectPolicy,
+    retry: RetryPolicy,
+    decompress: bool,
+    delivery: Delivery,
+) -> Result<HttpResponse, String> {
+    let mut method = Method::from_str(&request.method.to_uppercase())
+        .map_err(|e| format!("Invalid HTTP method '{}': {}", request.method, e))?;
+    let mut url = Url::parse(&request.url)
+        .map_err(|e| format!("Invalid URL '{}': {}", request.url, e))?;
+    let unix = UnixTarget::take_from(&mut url)?;
+    let unix_socket = |url: &Url| unix.as_ref().and_then(|u| u.socket_for(url));
+    let display_url = |url: &Url| match unix {
+        Some(ref unix) => unix.display(url),
+        None => url.to_string(),
+    };
+    let mut header_map = build_header_map(&request.default_headers, &request.headers)?;
+    // Saved files keep the server's bytes, so only ask for compression when decoding
+    if decompress
+        && matches!(delivery, Delivery::Buffer)
+        && !header_map.contains_key(ACCEPT_ENCODING)
+    {
+        header_map.insert(ACCEPT_ENCODING, HeaderValue::from_static(decode::ACCEPT_ENCODING));
+    }
+    if let Delivery::Events(_) = delivery {
+        if !header_map.contains_key(ACCEPT) {
+            header_map.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
+        }
+        if !header_map.contains_key(CACHE_CONTROL) {
+            header_map.insert(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        }
+    }
+    let mut body = request.body;
+
+    let send_error = |e: &reqwest::Error, url: &Url| {
+        let mut message = tls::describe_send_error(e);
+        if let (true, Some(socket)) = (e.is_connect(), unix_socket(url)) {
+            message = format!("{} (Unix socket {})", message, socket.display());
+        }
+        // HTTP/1-only servers just drop the h2 preface, leaving a bare "connection closed"
+        if clients.config.http_version == Some(HttpVersionPref::Http2)
+            && !e.is_connect()
+            && !e.is_timeout()
+        {
+            format!("{} (HTTP/2 only is selected; the server may not support HTTP/2)", message)
+        } else {
+            message
+        }
+    };
+
+    let mut redirects = Vec::new();
+    let mut attempts = Vec::new();
+    let mut start = None;
 
-    let start = Instant::now();
+    let (response, phases, hop_start, headers_at, request_size_bytes) = loop {
+        let client = clients.client_for(&url, unix_socket(&url))?;
 
-    let response = req_builder
-        .send()
-        .await
-        .map_err(|e| format!("Request failed: {}", e))?;
+        // Each hop is retried on its own; redirects don't count as attempts
+        let mut failed = 0;
+        let (response, phases, hop_start, headers_at, request_size_bytes) = loop {
+            let built = build_request(&client, method.clone(), url.clone(), header_map.clone(), body.clone())
+                .await?;
+            let request_size_bytes: u64 = built
+                .headers()
+                .get(CONTENT_LENGTH)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+
+            let hop_start = Instant::now();
+            start.get_or_insert(hop_start);
+
+            let (result, phases) = timing::scoped(client.execute(built)).await;
+            let headers_at = Instant::now();
+            let retryable = retry.allows(&method, failed + 1);
+
+            let (status, error, delay) = match result {
+                Ok(response) => {
+                    let status = response.status();
+                    let delay = if retryable && retry.retries_status(status.as_u16()) {
+                        retry.delay(failed + 1, Some(response.headers()))
+                    } else {
+                        None
+                    };
+                    let Some(delay) = delay else {
+                        break (response, phases, hop_start, headers_at, request_size_bytes);
+                    };
+                    (Some(status), None, delay)
+                }
+                Err(e) => {
+                    let message = send_error(&e, &url);
+                    let delay = if retryable && retry.retries_error(&e) {
+                        retry.delay(failed + 1, None)
+                    } else {
+                        None
+                    };
+                    let Some(delay) = delay else {
+                        return Err(if failed > 0 {
+                            format!("{} (gave up after {} attempts)", message, failed + 1)
+                        } else {
+                            message
+                        });
+                    };
+                    (None, Some(message), delay)
+                }
+            };
+
+            failed += 1;
+            attempts.push(RetryAttempt {
+                url: display_url(&url),
+                status: status.map(|s| s.as_u16()),
+                status_text: status
+                    .and_then(|s| s.canonical_reason())
+                    .unwrap_or("")
+                    .to_string(),
+                error,
+                time_ms: headers_at.duration_since(hop_start).as_millis() as u64,
+                delay_ms: delay.as_millis() as u64,
+            });
+            tokio::time::sleep(delay).await;
+        };
+
+        // A limit of zero hands back the first 3xx, as not following does
+        let next = if redirect.follow && redirect.max_hops > 0 {
+            redirect_target(&response, &url)?
+        } else {
+            None
+        };
+        let Some(next) = next else {
+            break (response, phases, hop_start, headers_at, request_size_bytes);
+        };
+
+        if redirects.len() as u32 >= redirect.max_hops {
+            return Err(format!("Too many redirects (max {})", redirect.max_hops));
+        }
+
+        let status = response.status();
+        let timings = {
+            let phases = phases.lock().map_err(|e| e.to_string())?;
+            TimingBreakdown::new(&phases, hop_start, headers_at, headers_at)
+        };
+        redirects.push(RedirectHop {
+            url: display_url(&url),
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers: response
+                .headers()
+                .iter()
+                .map(|(name, value)| ResponseHeader::new(name, value))
+                .collect(),
+            time_ms: headers_at.duration_since(hop_start).as_millis() as u64,
+            timings,
+        });
+
+        // 303 always, 301/302 from POST, and 307/308 unless preserved, become a bodiless GET
+        let to_get = match status.as_u16() {
+            303 => method != Method::HEAD,
+            301 | 302 => method == Method::POST,
+            _ => !redirect.preserve_method,
+        };
+        if to_get {
+            method = Method::GET;
+            body = None;
+            for name in [CONTENT_TYPE, CONTENT_LENGTH, CONTENT_ENCODING, TRANSFER_ENCODING] {
+                header_map.remove(name);
+            }
+        }
+
+        // Don't leak credentials to another origin
+        if next.origin() != url.origin() {
+            for name in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE] {
+                header_map.remove(name);
+            }
+        }
+
+        url = next;
+    };
+
+    let start = start.unwrap_or(hop_start);
+    let final_url = display_url(response.url());
 
-    let elapsed = start.elapsed();
     let status = response.status();
     let status_text = status.canonical_reason().unwrap_or("").to_string();
     let status_code = status.as_u16();
 
-    let mut headers = HashMap::new();
-    for (key, value) in response.headers() {
-        if let Ok(v) = value.to_str() {
-            headers.insert(key.to_string(), v.to_string());
+    let response_headers = response.headers().clone();
+    let connection = {
+        let phases = phases.lock().map_err(|e| e.to_string())?;
+        let tls_info = response.extensions().get::<TlsInfo>();
+        let leaf = tls_info.and_then(|info| info.peer_certificate());
+        let reused = phases.connected_at.is_none();
+        ConnectionInfo {
+            http_version: format!("{:?}", response.version()),
+            remote_addr: response.remote_addr().map(|addr| addr.to_string()),
+            reused,
+            unix_socket: unix_socket(response.url()).map(|s| s.display().to_string()),
+            resolved_addresses: phases.resolved.iter().map(|ip| ip.to_string()).collect(),
+            dns_override: phases.dns_override,
+            tls_version: phases.tls.as_ref().and_then(|h| h.version).map(String::from),
+            // A new TLS connection the verifier never saw skipped the full handshake
+            tls_resumed: !reused && tls_info.is_some() && phases.tls.is_none(),
+            peer_certificate: leaf.and_then(x509::summarize),
+            certificate_chain: phases
+                .tls
+                .as_ref()
+                .map(|h| h.chain.iter().skip(1).filter_map(|c| x509::summarize(c)).collect())
+                .unwrap_or_default(),
         }
-    }
+    };
+    let headers: Vec<ResponseHeader> = response_headers
+        .iter()
+        .map(|(name, value)| ResponseHeader::new(name, value))
+        .collect();
 
-    let body_bytes = response
-        .bytes()
-        .await
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
+    let mut content_encoding = None;
+    let mut wire_body = None;
+    let mut decompress_error = None;
+    let mut event_stream = None;
+    // Error pages and the like come back as ordinary responses
+    let delivery = match delivery {
+        Delivery::Events(_) if !sse::is_event_stream(status, &response_headers) => Delivery::Buffer,
+        delivery => delivery,
+    };
+    let (decoded, size_bytes, wire_size_bytes, saved_to) = match delivery {
+        Delivery::Download(download) => {
+            let written = download::stream_to_file(response, &download).await?;
+            let decoded = decode::DecodedBody {
+                body: String::new(),
+                encoding: BodyEncoding::Text,
+                raw: None,
+                mime_type: decode::declared_mime(&response_headers)
+                    .map(|m| m.essence_str().to_string()),
+                charset: None,
+            };
+            (decoded, written, written, Some(download.path))
+        }
+        Delivery::Events(stream) => {
+            let (received, end) = sse::read_events(response, &stream).await;
+            event_stream = Some(end);
+            let decoded = decode::DecodedBody {
+                body: String::new(),
+                encoding: BodyEncoding::Text,
+                raw: None,
+                mime_type: Some("text/event-stream".to_string()),
+                charset: None,
+            };
+            (decoded, received, received, None)
+        }
+        Delivery::Buffer => {
+            let received = response
+                .bytes()
+                .await
+                .map_err(|e| format!("Failed to read response body: {}", e))?;
+            let codings = decode::content_codings(&response_headers);
+            let decompressed = if decompress && !codings.is_empty() && !received.is_empty() {
+                match decode::decompress(&codings, &received) {
+                    Ok(bytes) => {
+                        content_encoding = Some(codings.join(", "));
+                        wire_body = Some(decode::base64_encode(&received));
+                        Some(bytes)
+                    }
+                    Err(e) => {
+                        decompress_error = Some(e);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            let body_bytes = decompressed.as_deref().unwrap_or(&received);
+            let compressed = !codings.is_empty() && decompressed.is_none();
+            let decoded = decode::decode_body(&response_headers, body_bytes, compressed);
+            (decoded, body_bytes.len() as u64, received.len() as u64, None)
+        }
+    };
+
+    let finished = Instant::now();
 
-    let size_bytes = body_bytes.len() as u64;
-    let body = String::from_utf8_lossy(&body_bytes).to_string();
+    let timings = {
+        let phases = phases.lock().map_err(|e| e.to_string())?;
+        TimingBreakdown::new(&phases, hop_start, headers_at, finished)
+    };
 
     Ok(HttpResponse {
         status: status_code,
         status_text,
+        url: final_url,
         headers,
-        body,
-        time_ms: elapsed.as_millis() as u64,
+        body: decoded.body,
+        body_encoding: decoded.encoding,
+        body_raw: decoded.raw,
+        mime_type: decoded.mime_type,
+        charset: decoded.charset,
+        content_encoding,
+        wire_body,
+        decompress_error,
+        saved_to,
+        event_stream,
+        time_ms: finished.duration_since(start).as_millis() as u64,
         size_bytes,
+        wire_size_bytes,
+        request_size_bytes,
+        timings,
+        redirects,
+        attempts,
+        connection,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serves a 302 to `/next` for every request, returning the base URL.
+    async fn redirecting_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    let response = concat!(
+                        "HTTP/1.1 302 Found\r\n",
+                        "Location: /next\r\n",
+                        "Content-Length: 0\r\n",
+                        "Connection: close\r\n\r\n",
+                    );
+                    let _ = socket.write_all(response.as_bytes()).await;
+                });
+            }
+        });
+        format!("http://{}/", addr)
+    }
+
+    #[tokio::test]
+    async fn unfollowed_redirects_are_returned() {
+        let url = redirecting_server().await;
+        let policies = [
+            RedirectPolicy {
+                follow: true,
+                max_hops: 0,
+                preserve_method: true,
+            },
+            RedirectPolicy {
+                follow: false,
+                ..RedirectPolicy::default()
+            },
+        ];
+        for redirect in policies {
+            let clients = ClientSource {
+                pool: Default::default(),
+                config: HttpConfig::default(),
+                jar: None,
+                tls: Default::default(),
+                dns: Default::default(),
+            };
+            let request = HttpRequest {
+                method: "GET".to_string(),
+                url: url.clone(),
+                default_headers: Vec::new(),
+                headers: Vec::new(),
+                body: None,
+            };
+            let response = execute_request(
+                clients,
+                request,
+                redirect,
+                RetryPolicy::default(),
+                true,
+                Delivery::Buffer,
+            )
+            .await
+            .unwrap_or_else(|e| panic!("{:?}: {}", redirect, e));
+            assert_eq!(response.status, 302, "{:?}", redirect);
+            assert_eq!(response.url, url, "{:?}", redirect);
+            assert!(response.redirects.is_empty(), "{:?}", redirect);
+        }
+    }
+}
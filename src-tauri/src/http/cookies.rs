@@ -0,0 +1,404 @@
+use crate::db::{self, cookies::Cookie};
+use publicsuffix::{List, Psl};
+use reqwest::header::HeaderValue;
+use reqwest::Url;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Same format as SQLite's `datetime('now')`, used for `created_at`.
+fn now_sqlite() -> String {
+    let t = time::OffsetDateTime::now_utc();
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        t.year(),
+        u8::from(t.month()),
+        t.day(),
+        t.hour(),
+        t.minute(),
+        t.second()
+    )
+}
+
+/// RFC 6265 §5.1.3 domain matching. An IP address only matches itself.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain)
+            && host[..host.len() - domain.len()].ends_with('.')
+            && !is_ip_address(host))
+}
+
+/// `host_str()` puts IPv6 addresses in brackets.
+fn is_ip_address(host: &str) -> bool {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .is_ok()
+}
+
+/// Mozilla's Public Suffix List, bundled so the check works offline.
+fn public_suffixes() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        include_str!("public_suffix_list.dat")
+            .parse()
+            .expect("bundled public suffix list")
+    })
+}
+
+/// Whether `domain` is a listed public suffix such as `com` or `co.uk`,
+/// under which no site may set cookies for its neighbours.
+fn is_public_suffix(domain: &str) -> bool {
+    public_suffixes()
+        .suffix(domain.as_bytes())
+        .is_some_and(|s| s.is_known() && s.as_bytes() == domain.as_bytes())
+}
+
+/// RFC 6265 §5.1.4 path matching.
+fn path_matches(cookie_path: &str, request_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/')
+                || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// RFC 6265 §5.1.4 default-path: the request path up to, not including, its last '/'.
+fn default_path(url: &Url) -> String {
+    let path = url.path();
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => path[..i].to_string(),
+    }
+}
+
+/// Per-environment cookie jar fed by responses (including redirect hops)
+/// and persisted to the `cookies` table after each send.
+pub struct CookieJar {
+    pub environment_id: String,
+    cookies: RwLock<Vec<Cookie>>,
+    dirty: AtomicBool,
+}
+
+impl CookieJar {
+    pub fn load(conn: &Connection, environment_id: &str) -> Result<Self, String> {
+        Ok(CookieJar {
+            environment_id: environment_id.to_string(),
+            cookies: RwLock::new(db::cookies::get_by_environment(conn, environment_id, None)?),
+            dirty: AtomicBool::new(false),
+        })
+    }
+
+    /// Picks up edits made through the cookie commands.
+    pub fn reload(&self, conn: &Connection) -> Result<(), String> {
+        let fresh = db::cookies::get_by_environment(conn, &self.environment_id, None)?;
+        *self.cookies.write().map_err(|e| e.to_string())? = fresh;
+        self.dirty.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Writes the jar back to SQLite if any response changed it.
+    pub fn persist(&self, conn: &Connection) -> Result<(), String> {
+        if !self.dirty.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        let now = now_unix();
+        let snapshot: Vec<Cookie> = self
+            .cookies
+            .read()
+            .map_err(|e| e.to_string())?
+            .iter()
+            .filter(|c| c.expires_at.is_none_or(|exp| exp > now))
+            .cloned()
+            .collect();
+        db::cookies::replace_for_environment(conn, &self.environment_id, &snapshot)
+    }
+
+    fn store(&self, header: &HeaderValue, url: &Url) {
+        let Ok(raw) = header.to_str() else { return };
+        let Ok(parsed) = cookie::Cookie::parse(raw) else { return };
+        let Some(host) = url.host_str().map(str::to_lowercase) else { return };
+
+        // RFC 6265 §5.3 steps 5-6: a public suffix or IP address may only be
+        // named by that host itself, and the cookie then stays host-only
+        let (domain, host_only) = match parsed.domain() {
+            Some(d) if !d.is_empty() => {
+                let d = d.trim_start_matches('.').to_lowercase();
+                if is_public_suffix(&d) || is_ip_address(&host) {
+                    if d != host {
+                        return;
+                    }
+                    (host, true)
+                } else if domain_matches(&host, &d) {
+                    (d, false)
+                } else {
+                    return;
+                }
+            }
+            _ => (host, true),
+        };
+
+        let path = parsed
+            .path()
+            .filter(|p| p.starts_with('/'))
+            .map(str::to_string)
+            .unwrap_or_else(|| default_path(url));
+
+        let now = now_unix();
+        let expires_at = match (parsed.max_age(), parsed.expires_datetime()) {
+            (Some(max_age), _) => Some(now.saturating_add(max_age.whole_seconds())),
+            (None, Some(expires)) => Some(expires.unix_timestamp()),
+            (None, None) => None,
+        };
+
+        let Ok(mut cookies) = self.cookies.write() else { return };
+        let existing = cookies
+            .iter()
+            .position(|c| c.domain == domain && c.path == path && c.name == parsed.name());
+
+        if expires_at.is_some_and(|exp| exp <= now) {
+            if let Some(i) = existing {
+                cookies.remove(i);
+                self.dirty.store(true, Ordering::SeqCst);
+            }
+            return;
+        }
+
+        let (id, created_at) = match existing {
+            Some(i) => (cookies[i].id.clone(), cookies[i].created_at.clone()),
+            None => (Uuid::new_v4().to_string(), now_sqlite()),
+        };
+        let cookie = Cookie {
+            id,
+            environment_id: self.environment_id.clone(),
+            domain,
+            path,
+            name: parsed.name().to_string(),
+            value: parsed.value().to_string(),
+            expires_at,
+            secure: parsed.secure().unwrap_or(false),
+            http_only: parsed.http_only().unwrap_or(false),
+            host_only,
+            same_site: parsed.same_site().map(|s| s.to_string()),
+            created_at,
+        };
+        match existing {
+            Some(i) => cookies[i] = cookie,
+            None => cookies.push(cookie),
+        }
+        self.dirty.store(true, Ordering::SeqCst);
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        for header in cookie_headers {
+            self.store(header, url);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let host = url.host_str()?.to_lowercase();
+        let secure = url.scheme() == "https";
+        let now = now_unix();
+        let cookies = self.cookies.read().ok()?;
+
+        let mut matching: Vec<&Cookie> = cookies
+            .iter()
+            .filter(|c| {
+                if c.host_only {
+                    host == c.domain
+                } else {
+                    domain_matches(&host, &c.domain)
+                }
+            })
+            .filter(|c| path_matches(&c.path, url.path()))
+            .filter(|c| secure || !c.secure)
+            .filter(|c| c.expires_at.is_none_or(|exp| exp > now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+
+        // RFC 6265 §5.4: longer paths first
+        matching.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+        let header = matching
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect::<Vec<_>>()
+            .join("; ");
+        HeaderValue::from_str(&header).ok()
+    }
+}
+
+/// Loaded jars, keyed by environment ID.
+#[derive(Default)]
+pub struct CookieJars(pub Mutex<HashMap<String, Arc<CookieJar>>>);
+
+impl CookieJars {
+    pub fn get_or_load(
+        &self,
+        conn: &Connection,
+        environment_id: &str,
+    ) -> Result<Arc<CookieJar>, String> {
+        let mut jars = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(jar) = jars.get(environment_id) {
+            return Ok(jar.clone());
+        }
+        let jar = Arc::new(CookieJar::load(conn, environment_id)?);
+        jars.insert(environment_id.to_string(), jar.clone());
+        Ok(jar)
+    }
+
+    pub fn reload(&self, conn: &Connection, environment_id: &str) -> Result<(), String> {
+        let jars = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(jar) = jars.get(environment_id) {
+            jar.reload(conn)?;
+        }
+        Ok(())
+    }
+
+    /// Forgets a deleted environment's jar; its rows go with the cascade.
+    pub fn remove(&self, environment_id: &str) -> Result<(), String> {
+        self.0.lock().map_err(|e| e.to_string())?.remove(environment_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::cookie::CookieStore;
+
+    fn jar() -> CookieJar {
+        CookieJar {
+            environment_id: "env".to_string(),
+            cookies: RwLock::new(Vec::new()),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// (domain, host_only) of what `set_cookie` from `url` left in the jar.
+    fn stored(url: &str, set_cookie: &str) -> Option<(String, bool)> {
+        let jar = jar();
+        jar.store(
+            &HeaderValue::from_str(set_cookie).unwrap(),
+            &Url::parse(url).unwrap(),
+        );
+        let cookies = jar.cookies.read().unwrap();
+        cookies.first().map(|c| (c.domain.clone(), c.host_only))
+    }
+
+    #[test]
+    fn domain_attribute() {
+        let cases = [
+            (
+                "https://a.example.com/",
+                "n=v",
+                Some(("a.example.com", true)),
+            ),
+            (
+                "https://a.example.com/",
+                "n=v; Domain=example.com",
+                Some(("example.com", false)),
+            ),
+            (
+                "https://a.example.com/",
+                "n=v; Domain=.Example.COM",
+                Some(("example.com", false)),
+            ),
+            (
+                "https://example.com/",
+                "n=v; Domain=example.com",
+                Some(("example.com", false)),
+            ),
+            ("https://a.example.com/", "n=v; Domain=other.com", None),
+            (
+                "https://a.example.com/",
+                "n=v; Domain=b.a.example.com",
+                None,
+            ),
+            // Public suffixes
+            ("https://a.example.com/", "n=v; Domain=com", None),
+            ("https://a.example.co.uk/", "n=v; Domain=co.uk", None),
+            ("https://a.github.io/", "n=v; Domain=github.io", None),
+            (
+                "https://a.github.io/",
+                "n=v; Domain=a.github.io",
+                Some(("a.github.io", false)),
+            ),
+            ("https://co.uk/", "n=v; Domain=co.uk", Some(("co.uk", true))),
+            // IP addresses
+            ("http://127.0.0.1/", "n=v", Some(("127.0.0.1", true))),
+            (
+                "http://127.0.0.1/",
+                "n=v; Domain=127.0.0.1",
+                Some(("127.0.0.1", true)),
+            ),
+            ("http://127.0.0.1/", "n=v; Domain=0.0.1", None),
+            ("http://[::1]/", "n=v; Domain=[::1]", Some(("[::1]", true))),
+        ];
+        for (url, set_cookie, expected) in cases {
+            let expected = expected.map(|(d, h)| (d.to_string(), h));
+            assert_eq!(
+                stored(url, set_cookie),
+                expected,
+                "{} from {}",
+                set_cookie,
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn bundled_public_suffix_list_parses() {
+        assert!(!public_suffixes().is_empty());
+        assert!(is_public_suffix("co.uk"));
+        assert!(is_public_suffix("com"));
+        assert!(!is_public_suffix("example.co.uk"));
+    }
+
+    #[test]
+    fn huge_max_age_does_not_overflow() {
+        let jar = jar();
+        let url = Url::parse("https://example.com/").unwrap();
+        jar.store(&HeaderValue::from_static("n=v; Max-Age=9223372036854775807"), &url);
+        let cookies = jar.cookies.read().unwrap();
+        assert!(cookies[0].expires_at.unwrap() > now_unix());
+    }
+
+    #[test]
+    fn ip_addresses_only_match_themselves() {
+        assert!(domain_matches("127.0.0.1", "127.0.0.1"));
+        assert!(!domain_matches("127.0.0.1", "0.0.1"));
+        assert!(!domain_matches("[::1]", ":1]"));
+        assert!(domain_matches("a.example.com", "example.com"));
+        assert!(!domain_matches("aexample.com", "example.com"));
+    }
+
+    #[test]
+    fn host_only_cookies_skip_subdomains() {
+        let jar = jar();
+        let origin = Url::parse("https://example.com/").unwrap();
+        jar.store(&HeaderValue::from_static("host=1"), &origin);
+        jar.store(
+            &HeaderValue::from_static("wide=1; Domain=example.com"),
+            &origin,
+        );
+
+        let sent = |url: &str| jar.cookies(&Url::parse(url).unwrap());
+        assert_eq!(sent("https://example.com/").unwrap(), "host=1; wide=1");
+        assert_eq!(sent("https://a.example.com/").unwrap(), "wide=1");
+        assert_eq!(sent("https://example.org/"), None);
+    }
+}
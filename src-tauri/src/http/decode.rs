@@ -0,0 +1,274 @@
+use base64::Engine;
+use encoding_rs::{Encoding, UTF_8};
+use reqwest::header::{HeaderMap, CONTENT_ENCODING, CONTENT_TYPE};
+use serde::Serialize;
+use std::io::Read;
+
+/// The content codings `decompress` can remove, as sent in `Accept-Encoding`.
+pub const ACCEPT_ENCODING: &str = "gzip, deflate, br, zstd";
+
+/// Largest body `decompress` will produce, so a compression bomb cannot
+/// exhaust memory.
+const MAX_DECOMPRESSED_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyEncoding {
+    Text,
+    Base64,
+}
+
+pub struct DecodedBody {
+    pub body: String,
+    pub encoding: BodyEncoding,
+    /// Base64 of the original bytes when text was transcoded from a non-UTF-8
+    /// charset, or when bytes invalid in the charset were replaced.
+    pub raw: Option<String>,
+    /// Declared type without parameters, or sniffed from magic bytes.
+    pub mime_type: Option<String>,
+    /// Charset actually used to decode a text body.
+    pub charset: Option<String>,
+}
+
+pub fn base64_encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+pub fn base64_decode(data: &str) -> Result<Vec<u8>, String> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| format!("Invalid base64: {}", e))
+}
+
+fn is_text_mime(mime: &mime::Mime) -> bool {
+    if mime.type_() == mime::TEXT {
+        return true;
+    }
+    if let Some(suffix) = mime.suffix() {
+        if suffix == mime::JSON || suffix == mime::XML {
+            return true;
+        }
+    }
+    mime.type_() == mime::APPLICATION
+        && matches!(
+            mime.subtype().as_str(),
+            "json"
+                | "xml"
+                | "javascript"
+                | "ecmascript"
+                | "x-www-form-urlencoded"
+                | "graphql"
+                | "x-ndjson"
+                | "yaml"
+                | "x-yaml"
+                | "toml"
+                | "sql"
+        )
+}
+
+pub fn declared_mime(headers: &HeaderMap) -> Option<mime::Mime> {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+/// The response's content codings in the order they were applied.
+pub fn content_codings(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty() && coding != "identity")
+        .collect()
+}
+
+fn decode_coding(coding: &str, bytes: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    // Reading one byte past the limit tells a body of exactly `limit` bytes from a larger one
+    fn read_capped(decoder: impl Read, limit: u64) -> std::io::Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        decoder.take(limit + 1).read_to_end(&mut decoded)?;
+        Ok(decoded)
+    }
+    let result = match coding {
+        "gzip" | "x-gzip" => read_capped(flate2::read::MultiGzDecoder::new(bytes), limit),
+        // Servers disagree on whether deflate is zlib-wrapped, as the RFC says, or raw
+        "deflate" => match bytes {
+            [cmf, flg, ..] if cmf & 0x0f == 8 && u16::from_be_bytes([*cmf, *flg]) % 31 == 0 => {
+                read_capped(flate2::read::ZlibDecoder::new(bytes), limit)
+            }
+            _ => read_capped(flate2::read::DeflateDecoder::new(bytes), limit),
+        },
+        "br" => read_capped(brotli::Decompressor::new(bytes, 4096), limit),
+        "zstd" => {
+            zstd::stream::read::Decoder::new(bytes).and_then(|decoder| read_capped(decoder, limit))
+        }
+        other => return Err(format!("Unsupported content coding '{}'", other)),
+    };
+    match result {
+        Ok(decoded) if decoded.len() as u64 > limit => Err(format!(
+            "Decompressed {} data is larger than {} MB",
+            coding,
+            limit / (1024 * 1024)
+        )),
+        Ok(decoded) => Ok(decoded),
+        Err(e) => Err(format!("Invalid {} data: {}", coding, e)),
+    }
+}
+
+/// Removes the codings listed in `codings`, last applied first.
+pub fn decompress(codings: &[String], bytes: &[u8]) -> Result<Vec<u8>, String> {
+    decompress_capped(codings, bytes, MAX_DECOMPRESSED_BYTES)
+}
+
+fn decompress_capped(codings: &[String], bytes: &[u8], limit: u64) -> Result<Vec<u8>, String> {
+    let mut body = bytes.to_vec();
+    for coding in codings.iter().rev() {
+        body = decode_coding(coding, &body, limit)?;
+    }
+    Ok(body)
+}
+
+/// Decodes a response body as text where the headers (or, failing that, the
+/// bytes themselves) say it is text, and as base64 otherwise. `compressed`
+/// bodies still carry a content coding and are always base64.
+pub fn decode_body(headers: &HeaderMap, bytes: &[u8], compressed: bool) -> DecodedBody {
+    let declared = declared_mime(headers);
+
+    let sniffed = infer::get(bytes).map(|kind| kind.mime_type().to_string());
+    let mime_type = declared
+        .as_ref()
+        .map(|m| m.essence_str().to_string())
+        .or_else(|| sniffed.clone());
+
+    let binary = |mime_type| DecodedBody {
+        body: base64_encode(bytes),
+        encoding: BodyEncoding::Base64,
+        raw: None,
+        mime_type,
+        charset: None,
+    };
+
+    // Compressed payloads are passed through undecoded
+    if compressed {
+        return binary(mime_type);
+    }
+
+    let declared_charset = declared
+        .as_ref()
+        .and_then(|m| m.get_param(mime::CHARSET))
+        .and_then(|c| Encoding::for_label(c.as_str().as_bytes()));
+
+    match declared.as_ref() {
+        Some(m) if is_text_mime(m) => {
+            let (text, encoding, had_errors) = declared_charset.unwrap_or(UTF_8).decode(bytes);
+            let lossy = encoding != UTF_8 || had_errors;
+            DecodedBody {
+                body: text.into_owned(),
+                encoding: BodyEncoding::Text,
+                raw: lossy.then(|| base64_encode(bytes)),
+                mime_type,
+                charset: Some(encoding.name().to_string()),
+            }
+        }
+        Some(_) => binary(mime_type),
+        // Undeclared: text only if nothing binary is recognised and it is valid UTF-8
+        None => match (sniffed, std::str::from_utf8(bytes)) {
+            (None, Ok(text)) => DecodedBody {
+                body: text.to_string(),
+                encoding: BodyEncoding::Text,
+                raw: None,
+                mime_type: None,
+                charset: Some(UTF_8.name().to_string()),
+            },
+            _ => binary(mime_type),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn brotli(bytes: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        {
+            let mut encoder = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+            encoder.write_all(bytes).unwrap();
+        }
+        encoded
+    }
+
+    fn headers(content_type: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        headers
+    }
+
+    fn codings(list: &[&str]) -> Vec<String> {
+        list.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn stacked_codings_are_removed_last_applied_first() {
+        let body = b"hello, hello, hello".as_slice();
+        let encoded = brotli(&gzip(body));
+        assert_eq!(
+            decompress(&codings(&["gzip", "br"]), &encoded).unwrap(),
+            body
+        );
+        assert!(decompress(&codings(&["br", "gzip"]), &encoded).is_err());
+    }
+
+    #[test]
+    fn unknown_coding_is_an_error() {
+        let err = decompress(&codings(&["compress"]), b"data").unwrap_err();
+        assert_eq!(err, "Unsupported content coding 'compress'");
+    }
+
+    #[test]
+    fn output_is_capped() {
+        let body = vec![0u8; 4096];
+        for (coding, encoded) in [("gzip", gzip(&body)), ("br", brotli(&body))] {
+            let list = codings(&[coding]);
+            assert_eq!(
+                decompress_capped(&list, &encoded, 4096).unwrap(),
+                body,
+                "{}",
+                coding
+            );
+            let err = decompress_capped(&list, &encoded, 4095).unwrap_err();
+            assert!(err.contains("larger than"), "{}: {}", coding, err);
+        }
+    }
+
+    #[test]
+    fn invalid_text_keeps_the_original_bytes() {
+        let cases = [
+            ("text/plain", b"caf\xc3\xa9".as_slice(), false),
+            ("text/plain", b"caf\xe9", true),
+            ("text/plain; charset=iso-8859-1", b"caf\xe9", true),
+            ("application/json; charset=utf-8", b"{\"a\":\"\xff\"}", true),
+        ];
+        for (content_type, bytes, keeps_raw) in cases {
+            let decoded = decode_body(&headers(content_type), bytes, false);
+            assert_eq!(decoded.encoding, BodyEncoding::Text, "{}", content_type);
+            assert_eq!(
+                decoded.raw,
+                keeps_raw.then(|| base64_encode(bytes)),
+                "{} {:?}",
+                content_type,
+                bytes
+            );
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use super::timing;
+use super::tls::host_matches;
+use crate::db;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Instant;
+
+/// Which address families name resolution may return.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum IpFamily {
+    #[default]
+    Any,
+    Ipv4,
+    Ipv6,
+}
+
+impl IpFamily {
+    fn allows(self, ip: &IpAddr) -> bool {
+        match self {
+            IpFamily::Any => true,
+            IpFamily::Ipv4 => ip.is_ipv4(),
+            IpFamily::Ipv6 => ip.is_ipv6(),
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            IpFamily::Any => "IP",
+            IpFamily::Ipv4 => "IPv4",
+            IpFamily::Ipv6 => "IPv6",
+        }
+    }
+}
+
+/// One entry of an environment's `dns_overrides`, the equivalent of curl's
+/// `--resolve host[:port]:address[,address...]`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsOverride {
+    /// `api.example.com`, `*.example.com` or either with a `:port`.
+    pub host: String,
+    /// One or more comma-separated IP addresses.
+    pub address: String,
+}
+
+/// Parses `10.0.0.5, [2001:db8::5]` into addresses, brackets optional.
+pub fn parse_addresses(value: &str) -> Result<Vec<IpAddr>, String> {
+    let addrs = value
+        .split(',')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .map(|a| {
+            a.trim_matches(|c| c == '[' || c == ']')
+                .parse::<IpAddr>()
+                .map_err(|_| format!("'{}' is not an IP address", a))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if addrs.is_empty() {
+        return Err("No address given".to_string());
+    }
+    Ok(addrs)
+}
+
+/// Parses and validates an environment's `dns_overrides` JSON, skipping
+/// entries with no host.
+pub fn parse_overrides(json: &str) -> Result<Vec<(String, Vec<IpAddr>)>, String> {
+    let entries: Vec<DnsOverride> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid DNS overrides: {}", e))?;
+    entries
+        .into_iter()
+        .filter(|entry| !entry.host.trim().is_empty())
+        .map(|entry| {
+            let host = entry.host.trim().to_lowercase();
+            let addrs = parse_addresses(&entry.address)
+                .map_err(|e| format!("Invalid DNS override for '{}': {}", host, e))?;
+            Ok((host, addrs))
+        })
+        .collect()
+}
+
+/// Host-to-address overrides in effect for a send: the global environment's,
+/// then the active environment's, which win over global ones just as
+/// environment variables do.
+#[derive(Debug, Clone, Default)]
+pub struct DnsOverrides(Vec<(String, Vec<IpAddr>)>);
+
+impl DnsOverrides {
+    pub fn load(conn: &Connection, environment_id: &str) -> Result<Self, String> {
+        let mut ids = vec!["global"];
+        if environment_id != "global" {
+            ids.push(environment_id);
+        }
+        let mut overrides = Vec::new();
+        for id in ids {
+            let environment = db::environments::get_by_id(conn, id)?;
+            let parsed = parse_overrides(&environment.dns_overrides)
+                .map_err(|e| format!("Environment '{}': {}", environment.name, e))?;
+            overrides.extend(parsed);
+        }
+        Ok(DnsOverrides(overrides))
+    }
+
+    /// The most specific override matching the URL's host and port.
+    pub fn for_url(&self, url: &Url) -> Option<HostOverride> {
+        let host = url.host_str()?.to_string();
+        self.0
+            .iter()
+            .filter(|(pattern, _)| host_matches(pattern, url))
+            // max_by_key keeps the last of equals, so the active environment wins ties
+            .max_by_key(|(pattern, _)| (!pattern.starts_with("*."), pattern.len()))
+            .map(|(_, addrs)| HostOverride {
+                host,
+                addrs: addrs.clone(),
+            })
+    }
+}
+
+/// The addresses one host resolves to for a pooled client.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostOverride {
+    pub host: String,
+    pub addrs: Vec<IpAddr>,
+}
+
+/// Resolver that applies a host override and the address family
+/// restriction, and reports each lookup into the send's timings.
+pub struct Resolver {
+    pub host_override: Option<HostOverride>,
+    pub family: IpFamily,
+}
+
+impl Resolve for Resolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let phases = timing::current_phases();
+        let overridden = self
+            .host_override
+            .as_ref()
+            .filter(|o| o.host == name.as_str())
+            .map(|o| o.addrs.clone());
+        let family = self.family;
+        Box::pin(async move {
+            let start = Instant::now();
+            let is_override = overridden.is_some();
+            let addrs = match overridden {
+                Some(addrs) => addrs,
+                None => tokio::net::lookup_host((name.as_str(), 0))
+                    .await?
+                    .map(|addr| addr.ip())
+                    .collect(),
+            };
+            let addrs: Vec<IpAddr> = addrs.into_iter().filter(|ip| family.allows(ip)).collect();
+            if addrs.is_empty() {
+                return Err(format!("No {} address for {}", family.label(), name.as_str()).into());
+            }
+            if let Some(phases) = phases {
+                if let Ok(mut phases) = phases.lock() {
+                    phases.dns = Some(start.elapsed());
+                    phases.resolved = addrs.clone();
+                    phases.dns_override = is_override;
+                }
+            }
+            Ok(Box::new(addrs.into_iter().map(|ip| SocketAddr::new(ip, 0))) as Addrs)
+        })
+    }
+}
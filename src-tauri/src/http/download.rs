@@ -0,0 +1,90 @@
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+
+/// Minimum gap between progress callbacks; the final one is always sent.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DownloadProgress {
+    pub request_id: String,
+    pub downloaded_bytes: u64,
+    /// From Content-Length; `None` for chunked responses.
+    pub total_bytes: Option<u64>,
+}
+
+/// Where a "send and save" request writes its body, and who to tell about progress.
+pub struct Download {
+    pub path: String,
+    pub on_progress: Box<dyn Fn(u64, Option<u64>) + Send + Sync>,
+}
+
+/// A `.part` file that is deleted when dropped unless it was moved into
+/// place, so failed and cancelled (aborted) downloads leave nothing behind.
+struct PartFile {
+    path: String,
+    renamed: bool,
+}
+
+impl Drop for PartFile {
+    fn drop(&mut self) {
+        if !self.renamed {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Streams the body into `<path>.part`, renaming it to `path` once complete so
+/// an interrupted download never looks finished. Returns the bytes written.
+pub async fn stream_to_file(
+    mut response: reqwest::Response,
+    download: &Download,
+) -> Result<u64, String> {
+    let part_path = format!("{}.part", download.path);
+    let total = response.content_length();
+
+    let file = tokio::fs::File::create(&part_path)
+        .await
+        .map_err(|e| format!("Failed to create '{}': {}", part_path, e))?;
+    // Declared before the writer so the file is closed before it is deleted
+    let mut part = PartFile {
+        path: part_path.clone(),
+        renamed: false,
+    };
+    let mut writer = tokio::io::BufWriter::new(file);
+
+    let mut written = 0u64;
+    let mut last_report = Instant::now();
+    (download.on_progress)(0, total);
+
+    async {
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Failed to read response body: {}", e))?
+        {
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|e| format!("Failed to write '{}': {}", part_path, e))?;
+            written += chunk.len() as u64;
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                (download.on_progress)(written, total);
+                last_report = Instant::now();
+            }
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write '{}': {}", part_path, e))
+    }
+    .await?;
+    drop(writer);
+
+    tokio::fs::rename(&part_path, &download.path)
+        .await
+        .map_err(|e| format!("Failed to move download to '{}': {}", download.path, e))?;
+    part.renamed = true;
+    (download.on_progress)(written, total);
+    Ok(written)
+}
@@ -1 +1,104 @@
+pub mod body;
 pub mod client;
+pub mod cookies;
+pub mod decode;
+pub mod dns;
+pub mod download;
+pub mod proxy;
+pub mod retry;
+pub mod sse;
+pub mod timing;
+pub mod tls;
+pub mod unix;
+pub mod websocket;
+pub mod ws_frame;
+pub mod x509;
+
+use client::HttpConfig;
+use cookies::CookieJar;
+use dns::{DnsOverrides, HostOverride};
+use reqwest::Url;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tls::{TlsProfile, TlsSettings};
+use std::sync::{Arc, Mutex};
+use tokio::task::AbortHandle;
+
+/// In-flight `send_request` tasks, keyed by the caller-supplied request ID.
+#[derive(Default)]
+pub struct InFlight(pub Mutex<HashMap<String, AbortHandle>>);
+
+/// Identifies one pooled client: effective config, cookie scope, the TLS
+/// settings (client certificate, extra CAs, pins), the DNS override and the
+/// Unix socket, if any, for the hosts it serves.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PoolKey {
+    config: HttpConfig,
+    cookie_scope: Option<String>,
+    tls: TlsProfile,
+    dns: Option<HostOverride>,
+    unix_socket: Option<PathBuf>,
+}
+
+/// Long-lived clients keyed by everything in a `PoolKey`, so connections and HTTP/2 streams are reused
+/// across sends.
+#[derive(Default, Clone)]
+pub struct ClientPool(Arc<Mutex<HashMap<PoolKey, reqwest::Client>>>);
+
+impl ClientPool {
+    pub fn get(
+        &self,
+        config: &HttpConfig,
+        jar: Option<Arc<CookieJar>>,
+        tls: &TlsProfile,
+        dns: Option<HostOverride>,
+        unix_socket: Option<&Path>,
+    ) -> Result<reqwest::Client, String> {
+        let key = PoolKey {
+            config: config.clone(),
+            cookie_scope: jar.as_ref().map(|j| j.environment_id.clone()),
+            tls: tls.clone(),
+            dns: dns.clone(),
+            unix_socket: unix_socket.map(Path::to_path_buf),
+        };
+        let mut clients = self.0.lock().map_err(|e| e.to_string())?;
+        if let Some(client) = clients.get(&key) {
+            return Ok(client.clone());
+        }
+        let client = client::build_client(config, jar, tls, dns, unix_socket)?;
+        clients.insert(key, client.clone());
+        Ok(client)
+    }
+
+    pub fn flush(&self) -> Result<(), String> {
+        self.0.lock().map_err(|e| e.to_string())?.clear();
+        Ok(())
+    }
+}
+
+/// Everything needed to pick a client for each hop of a send, so TLS and
+/// DNS settings configured for one host are never applied to another.
+#[derive(Clone)]
+pub struct ClientSource {
+    pub pool: ClientPool,
+    pub config: HttpConfig,
+    pub jar: Option<Arc<CookieJar>>,
+    pub tls: TlsSettings,
+    pub dns: DnsOverrides,
+}
+
+impl ClientSource {
+    pub fn client_for(
+        &self,
+        url: &Url,
+        unix_socket: Option<&Path>,
+    ) -> Result<reqwest::Client, String> {
+        self.pool.get(
+            &self.config,
+            self.jar.clone(),
+            &self.tls.for_url(url),
+            self.dns.for_url(url),
+            unix_socket,
+        )
+    }
+}
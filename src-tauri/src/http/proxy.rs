@@ -0,0 +1,169 @@
+use reqwest::{ClientBuilder, NoProxy, Proxy, Url};
+use serde::Deserialize;
+
+/// Proxies for outgoing requests. Every field is optional; with nothing set
+/// requests follow the system proxy settings unless `use_system` is off.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ProxyConfig {
+    /// Proxy for `http://` URLs.
+    pub http: Option<String>,
+    /// Proxy for `https://` URLs, tunnelled with CONNECT.
+    pub https: Option<String>,
+    /// `socks5://` or `socks5h://` proxy for URLs without a proxy of their own.
+    pub socks: Option<String>,
+    /// Credentials sent to every configured proxy, overriding any in the URLs.
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Comma-separated hosts, domains (matching subdomains too), IP addresses
+    /// and CIDR ranges that bypass the proxies, in `NO_PROXY` syntax.
+    pub no_proxy: Option<String>,
+    /// Honor `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` for
+    /// whatever the fields above leave unset. Defaults to on.
+    pub use_system: Option<bool>,
+}
+
+fn non_empty(value: &mut Option<String>) {
+    if value.as_deref().is_some_and(|v| v.trim().is_empty()) {
+        *value = None;
+    }
+}
+
+impl ProxyConfig {
+    /// Normalizes blank fields away; `None` when nothing differs from the default.
+    pub fn effective(mut self) -> Option<Self> {
+        for field in [
+            &mut self.http,
+            &mut self.https,
+            &mut self.socks,
+            &mut self.username,
+            &mut self.password,
+            &mut self.no_proxy,
+        ] {
+            non_empty(field);
+        }
+        if self.username.is_none() {
+            self.password = None;
+        }
+        if self.use_system == Some(true) {
+            self.use_system = None;
+        }
+        (self != ProxyConfig::default()).then_some(self)
+    }
+}
+
+const HTTP_SCHEMES: &[&str] = &["http", "https", "socks5", "socks5h"];
+const SOCKS_SCHEMES: &[&str] = &["socks5", "socks5h"];
+
+/// Parses a proxy address, defaulting the scheme when only `host:port` is given.
+fn parse_proxy_url(
+    label: &str,
+    value: &str,
+    default_scheme: &str,
+    schemes: &[&str],
+) -> Result<Url, String> {
+    let value = value.trim();
+    let url = if value.contains("://") {
+        Url::parse(value)
+    } else {
+        Url::parse(&format!("{}://{}", default_scheme, value))
+    }
+    .map_err(|e| format!("Invalid {} proxy '{}': {}", label, value, e))?;
+
+    if !schemes.contains(&url.scheme()) {
+        return Err(format!(
+            "Unsupported {} proxy scheme '{}' (expected {})",
+            label,
+            url.scheme(),
+            schemes.join(", ")
+        ));
+    }
+    if url.host_str().is_none() {
+        return Err(format!("Invalid {} proxy '{}': missing host", label, value));
+    }
+    Ok(url)
+}
+
+/// The first of the environment variables that is set and non-empty.
+fn env_var(names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())
+}
+
+/// Applies the proxy configuration to a client builder. With nothing
+/// configured reqwest's own system proxy lookup is left in place, which on
+/// macOS and Windows also covers the OS proxy settings.
+pub fn apply(
+    builder: ClientBuilder,
+    config: Option<&ProxyConfig>,
+) -> Result<ClientBuilder, String> {
+    let Some(config) = config else {
+        return Ok(builder);
+    };
+    let use_system = config.use_system.unwrap_or(true);
+    let from_env = |names: &[&str]| if use_system { env_var(names) } else { None };
+
+    let http = config
+        .http
+        .clone()
+        .or_else(|| from_env(&["http_proxy", "HTTP_PROXY"]));
+    let https = config
+        .https
+        .clone()
+        .or_else(|| from_env(&["https_proxy", "HTTPS_PROXY"]));
+    // ALL_PROXY may name an HTTP proxy as well as a SOCKS one
+    let (fallback, label, default_scheme, schemes) = match config.socks {
+        Some(ref socks) => (Some(socks.clone()), "SOCKS", "socks5h", SOCKS_SCHEMES),
+        None => (
+            from_env(&["all_proxy", "ALL_PROXY"]),
+            "ALL_PROXY",
+            "http",
+            HTTP_SCHEMES,
+        ),
+    };
+
+    if http.is_none() && https.is_none() && fallback.is_none() {
+        return Ok(if use_system {
+            builder
+        } else {
+            builder.no_proxy()
+        });
+    }
+
+    let bypass = [config.no_proxy.clone(), from_env(&["no_proxy", "NO_PROXY"])]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(",");
+    let bypass = NoProxy::from_string(&bypass);
+
+    let finish = |proxy: Proxy| {
+        let proxy = match config.username {
+            Some(ref username) => {
+                proxy.basic_auth(username, config.password.as_deref().unwrap_or(""))
+            }
+            None => proxy,
+        };
+        proxy.no_proxy(bypass.clone())
+    };
+
+    // reqwest uses the first proxy that matches, so scheme-specific ones go first
+    let mut builder = builder;
+    if let Some(http) = http {
+        let url = parse_proxy_url("HTTP", &http, "http", HTTP_SCHEMES)?;
+        let proxy = Proxy::http(url).map_err(|e| format!("Invalid HTTP proxy: {}", e))?;
+        builder = builder.proxy(finish(proxy));
+    }
+    if let Some(https) = https {
+        let url = parse_proxy_url("HTTPS", &https, "http", HTTP_SCHEMES)?;
+        let proxy = Proxy::https(url).map_err(|e| format!("Invalid HTTPS proxy: {}", e))?;
+        builder = builder.proxy(finish(proxy));
+    }
+    if let Some(fallback) = fallback {
+        let url = parse_proxy_url(label, &fallback, default_scheme, schemes)?;
+        let proxy = Proxy::all(url).map_err(|e| format!("Invalid {} proxy: {}", label, e))?;
+        builder = builder.proxy(finish(proxy));
+    }
+    Ok(builder)
+}
@@ -0,0 +1,153 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use time::format_description::well_known::Rfc2822;
+use time::OffsetDateTime;
+
+/// How the wait between attempts grows.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum Backoff {
+    /// The initial delay before every retry.
+    Fixed,
+    /// The initial delay times the number of failed attempts.
+    Linear,
+    /// The initial delay, doubled after each failed attempt.
+    #[default]
+    Exponential,
+}
+
+/// Retries for failed attempts. Every field is optional; with nothing set a
+/// request is sent once.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RetryConfig {
+    /// Attempts in total, the first one included.
+    pub max_attempts: Option<u32>,
+    pub backoff: Option<Backoff>,
+    pub initial_delay_ms: Option<u64>,
+    /// Upper bound on any single wait, computed or asked for by the server.
+    pub max_delay_ms: Option<u64>,
+    /// Response statuses that are retried.
+    pub statuses: Option<Vec<u16>>,
+    /// Retry connection failures, timeouts and connections dropped mid-request.
+    pub network_errors: Option<bool>,
+    /// Wait as long as a `Retry-After` header says instead of backing off.
+    pub respect_retry_after: Option<bool>,
+    /// Retry POST, PATCH and other non-idempotent methods too.
+    pub non_idempotent: Option<bool>,
+}
+
+impl RetryConfig {
+    pub fn policy(&self) -> RetryPolicy {
+        let default = RetryPolicy::default();
+        RetryPolicy {
+            max_attempts: self.max_attempts.unwrap_or(default.max_attempts).max(1),
+            backoff: self.backoff.unwrap_or(default.backoff),
+            initial_delay: self
+                .initial_delay_ms
+                .map_or(default.initial_delay, Duration::from_millis),
+            max_delay: self
+                .max_delay_ms
+                .map_or(default.max_delay, Duration::from_millis),
+            statuses: self.statuses.clone().unwrap_or(default.statuses),
+            network_errors: self.network_errors.unwrap_or(default.network_errors),
+            respect_retry_after: self
+                .respect_retry_after
+                .unwrap_or(default.respect_retry_after),
+            non_idempotent: self.non_idempotent.unwrap_or(default.non_idempotent),
+        }
+    }
+}
+
+/// How `execute_request` retries each hop.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub backoff: Backoff,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub statuses: Vec<u16>,
+    pub network_errors: bool,
+    pub respect_retry_after: bool,
+    pub non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            backoff: Backoff::default(),
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            statuses: vec![408, 429, 500, 502, 503, 504],
+            network_errors: true,
+            respect_retry_after: true,
+            non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether another attempt may follow `attempts` failed ones of `method`.
+    pub fn allows(&self, method: &Method, attempts: u32) -> bool {
+        attempts < self.max_attempts && (self.non_idempotent || method.is_idempotent())
+    }
+
+    pub fn retries_status(&self, status: u16) -> bool {
+        self.statuses.contains(&status)
+    }
+
+    pub fn retries_error(&self, error: &reqwest::Error) -> bool {
+        self.network_errors && (error.is_connect() || error.is_timeout() || error.is_request())
+    }
+
+    /// The wait after `attempts` failed attempts. `None` when the response's
+    /// `Retry-After` asks for longer than `max_delay`, in which case the
+    /// response is returned rather than retried early.
+    pub fn delay(&self, attempts: u32, headers: Option<&HeaderMap>) -> Option<Duration> {
+        if self.respect_retry_after {
+            if let Some(wait) = headers.and_then(retry_after) {
+                return (wait <= self.max_delay).then_some(wait);
+            }
+        }
+        let factor = match self.backoff {
+            Backoff::Fixed => 1,
+            Backoff::Linear => attempts,
+            Backoff::Exponential => 2u32.saturating_pow(attempts.saturating_sub(1)),
+        };
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+/// `Retry-After` as delay-seconds or an HTTP-date; dates in the past mean now.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = OffsetDateTime::parse(value, &Rfc2822).ok()?;
+    Some(
+        (date - OffsetDateTime::now_utc())
+            .try_into()
+            .unwrap_or_default(),
+    )
+}
+
+/// An attempt that failed and was retried.
+#[derive(Debug, Serialize)]
+pub struct RetryAttempt {
+    pub url: String,
+    /// The retried status, absent when no response arrived.
+    pub status: Option<u16>,
+    pub status_text: String,
+    /// Why no response arrived.
+    pub error: Option<String>,
+    pub time_ms: u64,
+    /// How long was waited before the next attempt.
+    pub delay_ms: u64,
+}
@@ -0,0 +1,515 @@
+use super::client::{
+    self, Delivery, HeaderEntry, HeaderMode, HttpRequest, HttpResponse, RedirectPolicy,
+    ResponseHeader,
+};
+use super::decode;
+use super::retry::RetryPolicy;
+use super::ClientSource;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Wait before reconnecting when the server never sent a `retry` field.
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// Reconnection after the stream ends. Every field is optional; by default a
+/// closed stream is reopened until five attempts in a row fail.
+#[derive(Debug, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ReconnectConfig {
+    pub enabled: Option<bool>,
+    /// Reconnects in a row that may fail to open a stream before giving up.
+    pub max_attempts: Option<u32>,
+}
+
+impl ReconnectConfig {
+    pub fn policy(&self) -> ReconnectPolicy {
+        let default = ReconnectPolicy::default();
+        ReconnectPolicy {
+            enabled: self.enabled.unwrap_or(default.enabled),
+            max_attempts: self.max_attempts.unwrap_or(default.max_attempts),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    pub enabled: bool,
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            enabled: true,
+            max_attempts: 5,
+        }
+    }
+}
+
+/// One dispatched event.
+#[derive(Debug, Serialize, Clone)]
+pub struct SseEvent {
+    /// The last event ID seen so far, as a browser's `lastEventId`.
+    pub id: Option<String>,
+    /// `message` unless the server named it.
+    pub event: String,
+    pub data: String,
+    /// Reconnection delay set in this event's block, if any.
+    pub retry: Option<u64>,
+    /// Milliseconds since the first connection was opened.
+    pub at_ms: u64,
+}
+
+/// What the frontend hears while a stream is running.
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamUpdate {
+    /// A connection was answered with an event stream.
+    Open {
+        status: u16,
+        status_text: String,
+        headers: Vec<ResponseHeader>,
+        http_version: String,
+    },
+    Event(SseEvent),
+    /// The stream ended and will be reopened after `delay_ms`.
+    Reconnecting {
+        attempt: u32,
+        delay_ms: u64,
+        /// Why the previous connection ended, if it did not end cleanly.
+        error: Option<String>,
+        last_event_id: Option<String>,
+    },
+}
+
+/// A `StreamUpdate` as emitted to the frontend, tagged with its send.
+#[derive(Debug, Serialize, Clone)]
+pub struct SseUpdate {
+    pub request_id: String,
+    #[serde(flatten)]
+    pub update: StreamUpdate,
+}
+
+/// How an event stream response ended.
+#[derive(Debug, Serialize)]
+pub struct StreamEnd {
+    /// Events dispatched on this connection.
+    pub events: u64,
+    /// Why the connection dropped, if the server did not close it cleanly.
+    pub error: Option<String>,
+}
+
+/// Splits the stream into lines and fields as they arrive (WHATWG HTML
+/// §9.2.6). The last event ID and retry delay outlive each connection.
+#[derive(Default)]
+pub struct SseParser {
+    line: Vec<u8>,
+    /// The previous chunk ended in CR, so a leading LF belongs to it.
+    after_cr: bool,
+    /// Set once the optional byte order mark has been dealt with.
+    started: bool,
+    data: String,
+    event: String,
+    retry: Option<u64>,
+    last_event_id: Option<String>,
+    reconnect_delay: Option<u64>,
+}
+
+/// A dispatched event, before it is timestamped.
+pub struct ParsedEvent {
+    pub id: Option<String>,
+    pub event: String,
+    pub data: String,
+    pub retry: Option<u64>,
+}
+
+impl SseParser {
+    /// Forgets any partial line or event, as a new connection starts clean.
+    pub fn reset_connection(&mut self) {
+        self.line.clear();
+        self.after_cr = false;
+        self.started = false;
+        self.data.clear();
+        self.event.clear();
+        self.retry = None;
+    }
+
+    /// The ID to send as `Last-Event-ID`; an empty `id` field clears it.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref().filter(|id| !id.is_empty())
+    }
+
+    pub fn reconnect_delay(&self) -> Option<Duration> {
+        self.reconnect_delay.map(Duration::from_millis)
+    }
+
+    /// Feeds one chunk and returns the events it completed.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<ParsedEvent> {
+        let mut bytes = chunk;
+        if !self.started {
+            // The BOM may itself be split across chunks
+            self.line.extend_from_slice(bytes);
+            const BOM: &[u8] = b"\xEF\xBB\xBF";
+            if self.line.len() < BOM.len() && BOM.starts_with(&self.line) {
+                return Vec::new();
+            }
+            self.started = true;
+            let buffered = std::mem::take(&mut self.line);
+            let rest = buffered.strip_prefix(BOM).unwrap_or(&buffered);
+            return self.push_lines(rest);
+        }
+        if self.after_cr && bytes.first() == Some(&b'\n') {
+            bytes = &bytes[1..];
+        }
+        self.after_cr = false;
+        self.push_lines(bytes)
+    }
+
+    fn push_lines(&mut self, bytes: &[u8]) -> Vec<ParsedEvent> {
+        let mut events = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\r' | b'\n' => {
+                    if bytes[i] == b'\r' {
+                        match bytes.get(i + 1) {
+                            Some(b'\n') => i += 1,
+                            Some(_) => {}
+                            None => self.after_cr = true,
+                        }
+                    }
+                    let line = std::mem::take(&mut self.line);
+                    if let Some(event) = self.process_line(&line) {
+                        events.push(event);
+                    }
+                }
+                b => self.line.push(b),
+            }
+            i += 1;
+        }
+        events
+    }
+
+    fn process_line(&mut self, line: &[u8]) -> Option<ParsedEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        let line = String::from_utf8_lossy(line);
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line.as_ref(), ""),
+        };
+        match field {
+            "event" => self.event = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_string()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(ms) = value.parse() {
+                    self.reconnect_delay = Some(ms);
+                    self.retry = Some(ms);
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ParsedEvent> {
+        let retry = self.retry.take();
+        let event = std::mem::take(&mut self.event);
+        if self.data.is_empty() {
+            return None;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        Some(ParsedEvent {
+            id: self.last_event_id().map(str::to_string),
+            event: if event.is_empty() {
+                "message".to_string()
+            } else {
+                event
+            },
+            data,
+            retry,
+        })
+    }
+}
+
+/// Where `execute_request` sends an event stream's events instead of buffering the body.
+pub struct EventStream {
+    pub on_update: Box<dyn Fn(StreamUpdate) + Send + Sync>,
+    parser: Mutex<SseParser>,
+    started: Instant,
+}
+
+impl EventStream {
+    pub fn new(on_update: Box<dyn Fn(StreamUpdate) + Send + Sync>) -> Self {
+        EventStream {
+            on_update,
+            parser: Mutex::new(SseParser::default()),
+            started: Instant::now(),
+        }
+    }
+
+    fn last_event_id(&self) -> Option<String> {
+        self.parser
+            .lock()
+            .ok()
+            .and_then(|p| p.last_event_id().map(str::to_string))
+    }
+
+    fn reconnect_delay(&self) -> Duration {
+        self.parser
+            .lock()
+            .ok()
+            .and_then(|p| p.reconnect_delay())
+            .unwrap_or(DEFAULT_RETRY)
+    }
+}
+
+/// Whether a response's body should be read as events.
+pub fn is_event_stream(status: reqwest::StatusCode, headers: &HeaderMap) -> bool {
+    status.is_success()
+        && decode::declared_mime(headers).is_some_and(|m| m.essence_str() == "text/event-stream")
+}
+
+/// Reads events until the server closes the stream or the connection drops.
+/// Returns the bytes received and how the stream ended.
+pub async fn read_events(
+    mut response: reqwest::Response,
+    stream: &EventStream,
+) -> (u64, StreamEnd) {
+    (stream.on_update)(StreamUpdate::Open {
+        status: response.status().as_u16(),
+        status_text: response
+            .status()
+            .canonical_reason()
+            .unwrap_or("")
+            .to_string(),
+        headers: response
+            .headers()
+            .iter()
+            .map(|(name, value)| ResponseHeader::new(name, value))
+            .collect(),
+        http_version: format!("{:?}", response.version()),
+    });
+    if let Ok(mut parser) = stream.parser.lock() {
+        parser.reset_connection();
+    }
+
+    let mut received = 0u64;
+    let mut end = StreamEnd {
+        events: 0,
+        error: None,
+    };
+    loop {
+        let chunk = match response.chunk().await {
+            Ok(Some(chunk)) => chunk,
+            Ok(None) => break,
+            Err(e) => {
+                end.error = Some(format!("Event stream interrupted: {}", e));
+                break;
+            }
+        };
+        received += chunk.len() as u64;
+        let events = match stream.parser.lock() {
+            Ok(mut parser) => parser.push(&chunk),
+            Err(e) => {
+                end.error = Some(e.to_string());
+                break;
+            }
+        };
+        for event in events {
+            end.events += 1;
+            (stream.on_update)(StreamUpdate::Event(SseEvent {
+                id: event.id,
+                event: event.event,
+                data: event.data,
+                retry: event.retry,
+                at_ms: stream.started.elapsed().as_millis() as u64,
+            }));
+        }
+    }
+    (received, end)
+}
+
+/// Sends `request` and follows its event stream, reopening it with
+/// `Last-Event-ID` when it ends. Returns the last response; one that is not
+/// an event stream (an error status, say) ends the run.
+pub async fn run(
+    clients: ClientSource,
+    request: HttpRequest,
+    redirect: RedirectPolicy,
+    retry: RetryPolicy,
+    reconnect: ReconnectPolicy,
+    stream: Arc<EventStream>,
+) -> Result<HttpResponse, String> {
+    let mut opened = false;
+    let mut failed = 0;
+    loop {
+        let mut request = request.clone();
+        let last_event_id = stream.last_event_id();
+        if let Some(ref id) = last_event_id {
+            request.headers.push(HeaderEntry {
+                key: "Last-Event-ID".to_string(),
+                value: id.clone(),
+                enabled: true,
+                mode: Some(HeaderMode::Replace),
+            });
+        }
+
+        // Compressed bodies can't be decoded incrementally, so none is asked for
+        let result = client::execute_request(
+            clients.clone(),
+            request,
+            redirect,
+            retry.clone(),
+            false,
+            Delivery::Events(stream.clone()),
+        )
+        .await;
+
+        let error = match result {
+            Ok(ref response) => match response.event_stream {
+                Some(ref end) => {
+                    opened = true;
+                    failed = 0;
+                    end.error.clone()
+                }
+                None => return result,
+            },
+            Err(ref e) if opened => {
+                failed += 1;
+                Some(e.clone())
+            }
+            Err(_) => return result,
+        };
+
+        if !reconnect.enabled || failed >= reconnect.max_attempts {
+            return result;
+        }
+        let delay = stream.reconnect_delay();
+        (stream.on_update)(StreamUpdate::Reconnecting {
+            attempt: failed + 1,
+            delay_ms: delay.as_millis() as u64,
+            error,
+            last_event_id: stream.last_event_id(),
+        });
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feeds `chunks` in order and returns every event as (id, event, data).
+    fn parse(parser: &mut SseParser, chunks: &[&[u8]]) -> Vec<(Option<String>, String, String)> {
+        chunks
+            .iter()
+            .flat_map(|chunk| parser.push(chunk))
+            .map(|e| (e.id, e.event, e.data))
+            .collect()
+    }
+
+    fn message(data: &str) -> (Option<String>, String, String) {
+        (None, "message".to_string(), data.to_string())
+    }
+
+    #[test]
+    fn line_endings_split_across_chunks() {
+        let cases: [&[&[u8]]; 4] = [
+            &[b"data: a\r", b"\n\r\n"],
+            &[b"data: a\r", b"\n\r", b"\n"],
+            &[b"data: a\r\r"],
+            &[b"data: a\n", b"\n"],
+        ];
+        for chunks in cases {
+            let mut parser = SseParser::default();
+            assert_eq!(parse(&mut parser, chunks), [message("a")], "{:?}", chunks);
+        }
+        // A CR ending one chunk and an LF starting the next make one line break, not two
+        let mut parser = SseParser::default();
+        let events = parse(&mut parser, &[b"data: a\r", b"\ndata: b\r\n\r\n"]);
+        assert_eq!(events, [message("a\nb")]);
+    }
+
+    #[test]
+    fn leading_bom_is_skipped() {
+        let cases: [&[&[u8]]; 3] = [
+            &[b"\xEF\xBB\xBFdata: a\n\n"],
+            &[b"\xEF", b"\xBB", b"\xBFdata: a\n\n"],
+            &[b"data: a\n\n"],
+        ];
+        for chunks in cases {
+            let mut parser = SseParser::default();
+            assert_eq!(parse(&mut parser, chunks), [message("a")], "{:?}", chunks);
+        }
+        // Only the first one is a BOM; later ones are part of the field name
+        let mut parser = SseParser::default();
+        let events = parse(&mut parser, &[b"\xEF\xBB\xBF\xEF\xBB\xBFdata: a\n\n"]);
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn data_lines_are_joined() {
+        let mut parser = SseParser::default();
+        let events = parse(
+            &mut parser,
+            &[b"data: first\ndata\ndata:  third\n: comment\nevent: update\n\n"],
+        );
+        assert_eq!(
+            events,
+            [(None, "update".to_string(), "first\n\n third".to_string())]
+        );
+    }
+
+    #[test]
+    fn id_with_nul_is_ignored() {
+        let mut parser = SseParser::default();
+        let events = parse(&mut parser, &[b"id: 1\ndata: a\n\nid: 2\0x\ndata: b\n\n"]);
+        let ids: Vec<_> = events.into_iter().map(|(id, ..)| id).collect();
+        assert_eq!(ids, [Some("1".to_string()), Some("1".to_string())]);
+        assert_eq!(parser.last_event_id(), Some("1"));
+    }
+
+    #[test]
+    fn non_numeric_retry_is_ignored() {
+        let cases = [
+            ("retry: 1500", Some(1500)),
+            ("retry: 15s", None),
+            ("retry: -1", None),
+            ("retry: 1.5", None),
+            ("retry:", None),
+        ];
+        for (line, delay) in cases {
+            let mut parser = SseParser::default();
+            let events = parser.push(format!("{}\ndata: a\n\n", line).as_bytes());
+            assert_eq!(events[0].retry, delay, "{}", line);
+            assert_eq!(
+                parser.reconnect_delay(),
+                delay.map(Duration::from_millis),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn events_dispatch_only_on_a_blank_line() {
+        let mut parser = SseParser::default();
+        assert!(parse(&mut parser, &[b"data: a\n"]).is_empty());
+        assert_eq!(parse(&mut parser, &[b"\n"]), [message("a")]);
+        // An event still open when the stream ends is dropped
+        assert!(parse(&mut parser, &[b"data: b\n", b"data: c"]).is_empty());
+        parser.reset_connection();
+        assert_eq!(parse(&mut parser, &[b"data: d\n\n"]), [message("d")]);
+    }
+}
@@ -0,0 +1,134 @@
+use super::tls::Handshake;
+use serde::Serialize;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+tokio::task_local! {
+    /// Per-send sink the resolver and connector layer report into. Connects
+    /// polled outside a scoped send are not recorded.
+    static PHASES: Arc<Mutex<ConnectPhases>>;
+}
+
+#[derive(Debug, Default)]
+pub struct ConnectPhases {
+    pub dns: Option<Duration>,
+    /// What the last lookup returned, after any override and family filter.
+    pub resolved: Vec<IpAddr>,
+    pub dns_override: bool,
+    /// TCP connect, plus any proxy tunnel.
+    pub connect: Option<Duration>,
+    /// When the TLS setup started a handshake on the new connection.
+    pub tls_started_at: Option<Instant>,
+    pub tls_handshake: Option<Duration>,
+    pub connected_at: Option<Instant>,
+    /// Set by the certificate verifier when this send performed a full handshake.
+    pub tls: Option<Handshake>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct TimingBreakdown {
+    /// Name resolution; `None` when the connection was reused or the host is an IP literal.
+    pub dns_ms: Option<f64>,
+    /// TCP connect; `None` when reused.
+    pub connect_ms: Option<f64>,
+    /// TLS handshake; `None` when reused or over plain HTTP.
+    pub tls_ms: Option<f64>,
+    /// From the connection being ready to the response headers arriving.
+    pub ttfb_ms: f64,
+    /// Reading the response body.
+    pub download_ms: f64,
+}
+
+fn as_ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+impl TimingBreakdown {
+    pub fn new(
+        phases: &ConnectPhases,
+        started: Instant,
+        headers_at: Instant,
+        finished: Instant,
+    ) -> Self {
+        let ready = phases.connected_at.unwrap_or(started);
+        TimingBreakdown {
+            dns_ms: phases.dns.map(as_ms),
+            connect_ms: phases.connect.map(as_ms),
+            tls_ms: phases.tls_handshake.map(as_ms),
+            ttfb_ms: as_ms(headers_at.saturating_duration_since(ready)),
+            download_ms: as_ms(finished.saturating_duration_since(headers_at)),
+        }
+    }
+}
+
+/// Runs `fut` with a fresh phase sink, returning the sink alongside the output.
+pub async fn scoped<F: Future>(fut: F) -> (F::Output, Arc<Mutex<ConnectPhases>>) {
+    let phases = Arc::new(Mutex::new(ConnectPhases::default()));
+    let output = PHASES.scope(phases.clone(), fut).await;
+    (output, phases)
+}
+
+/// The current send's phase sink, for resolvers and connectors to report
+/// into. Must be taken before any work is spawned off the send's task.
+pub fn current_phases() -> Option<Arc<Mutex<ConnectPhases>>> {
+    PHASES.try_with(Arc::clone).ok()
+}
+
+/// Connector layer that times connection establishment (minus DNS, which
+/// the resolver reports separately), split at the start of any TLS handshake.
+#[derive(Clone)]
+pub struct TimingLayer;
+
+impl<S> Layer<S> for TimingLayer {
+    type Service = TimingConnector<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimingConnector { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct TimingConnector<S> {
+    inner: S,
+}
+
+impl<S, R> Service<R> for TimingConnector<S>
+where
+    S: Service<R>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let phases = current_phases();
+        let start = Instant::now();
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let conn = fut.await?;
+            if let Some(phases) = phases {
+                if let Ok(mut phases) = phases.lock() {
+                    let now = Instant::now();
+                    let dns = phases.dns.unwrap_or_default();
+                    // A handshake started before this connect began belongs to another
+                    let tls_start = phases.tls_started_at.filter(|at| *at >= start);
+                    let tcp_end = tls_start.unwrap_or(now);
+                    phases.connect = Some(tcp_end.duration_since(start).saturating_sub(dns));
+                    phases.tls_handshake = tls_start.map(|at| now.duration_since(at));
+                    phases.connected_at = Some(now);
+                }
+            }
+            Ok(conn)
+        })
+    }
+}
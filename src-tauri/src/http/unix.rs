@@ -0,0 +1,81 @@
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use reqwest::Url;
+use std::path::{Path, PathBuf};
+
+/// `http+unix://%2Fvar%2Frun%2Fdocker.sock/v1.43/containers/json`: the host
+/// is the percent-encoded socket path, the rest is the request target.
+pub const SCHEME: &str = "http+unix";
+
+/// Characters escaped when a socket path is put back into a URL host.
+const SOCKET_PATH: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'~');
+
+/// What the rewritten URL uses as its host, and so what the Host header says.
+const HOST: &str = "localhost";
+
+/// A request addressed to a Unix domain socket rather than a TCP host.
+#[derive(Debug, Clone)]
+pub struct UnixTarget {
+    pub socket: PathBuf,
+}
+
+impl UnixTarget {
+    /// Rewrites an `http+unix` URL in place to the plain `http://localhost`
+    /// URL sent over the socket, returning the socket. Other URLs are left
+    /// untouched.
+    pub fn take_from(url: &mut Url) -> Result<Option<Self>, String> {
+        if url.scheme() != SCHEME {
+            return Ok(None);
+        }
+        let encoded = url.host_str().unwrap_or_default();
+        let socket = percent_decode_str(encoded)
+            .decode_utf8()
+            .map_err(|e| format!("Invalid Unix socket path '{}': {}", encoded, e))?;
+        if socket.is_empty() {
+            return Err(format!(
+                "Missing Unix socket path; use {}://%2Fpath%2Fto%2Fapp.sock/",
+                SCHEME
+            ));
+        }
+        let socket = PathBuf::from(socket.as_ref());
+
+        let mut rewritten = Url::parse(&format!("http://{}/", HOST)).map_err(|e| e.to_string())?;
+        rewritten.set_path(url.path());
+        rewritten.set_query(url.query());
+        rewritten.set_fragment(url.fragment());
+        *url = rewritten;
+        Ok(Some(UnixTarget { socket }))
+    }
+
+    /// Whether a hop's URL still goes over the socket; redirects elsewhere
+    /// leave it for TCP.
+    pub fn serves(&self, url: &Url) -> bool {
+        url.scheme() == "http" && url.host_str() == Some(HOST) && url.port().is_none()
+    }
+
+    pub fn socket_for(&self, url: &Url) -> Option<&Path> {
+        self.serves(url).then_some(self.socket.as_path())
+    }
+
+    /// A URL the socket serves, back in its `http+unix` form for display.
+    pub fn display(&self, url: &Url) -> String {
+        if !self.serves(url) {
+            return url.to_string();
+        }
+        let socket = self.socket.to_string_lossy();
+        let mut display = format!(
+            "{}://{}{}",
+            SCHEME,
+            utf8_percent_encode(&socket, SOCKET_PATH),
+            url.path()
+        );
+        if let Some(query) = url.query() {
+            display.push('?');
+            display.push_str(query);
+        }
+        display
+    }
+}
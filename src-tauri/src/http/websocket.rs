@@ -0,0 +1,347 @@
+use super::client::{build_header_map, HeaderEntry, ResponseHeader};
+use super::decode::base64_encode;
+use super::tls;
+use super::ws_frame::{self, Assembler, BINARY, CLOSE, PING, PONG, TEXT};
+use super::ClientSource;
+use reqwest::header::{
+    HeaderName, HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY,
+    SEC_WEBSOCKET_PROTOCOL, SEC_WEBSOCKET_VERSION, UPGRADE,
+};
+use reqwest::{StatusCode, Upgraded, Url};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// Appended to the key before hashing it into `Sec-WebSocket-Accept` (RFC 6455 §1.3).
+const ACCEPT_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+/// How long to wait for the other side's close frame before dropping the connection.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Error bodies longer than this are cut short in handshake errors.
+const MAX_ERROR_BODY_CHARS: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct WsRequest {
+    pub url: String,
+    /// Inherited default headers, one layer per ancestor, as for HTTP requests.
+    #[serde(default)]
+    pub default_headers: Vec<Vec<HeaderEntry>>,
+    pub headers: Vec<HeaderEntry>,
+    /// Offered in `Sec-WebSocket-Protocol`, in order of preference.
+    #[serde(default)]
+    pub subprotocols: Vec<String>,
+}
+
+/// The server's answer to the upgrade request.
+#[derive(Debug, Serialize)]
+pub struct Handshake {
+    pub headers: Vec<ResponseHeader>,
+    /// The subprotocol the server picked, if any.
+    pub protocol: Option<String>,
+    pub time_ms: u64,
+}
+
+/// A frame waiting to be written.
+pub enum Outgoing {
+    Text(String),
+    Binary(Vec<u8>),
+    Pong(Vec<u8>),
+    Close(Option<u16>, String),
+    /// The connection is gone; stop without writing anything.
+    Abort,
+}
+
+/// Open sessions, keyed by session ID, each with the queue its writer drains.
+#[derive(Default)]
+pub struct WsSessions(pub Mutex<HashMap<String, mpsc::UnboundedSender<Outgoing>>>);
+
+impl WsSessions {
+    pub fn send(&self, session_id: &str, message: Outgoing) -> Result<(), String> {
+        let sessions = self.0.lock().map_err(|e| e.to_string())?;
+        let queue = sessions
+            .get(session_id)
+            .ok_or_else(|| format!("WebSocket session '{}' is not open", session_id))?;
+        queue
+            .send(message)
+            .map_err(|_| format!("WebSocket session '{}' is closing", session_id))
+    }
+}
+
+/// One frame as sent or received, control frames included.
+#[derive(Debug, Serialize, Clone)]
+pub struct WsMessage {
+    /// `sent` or `received`.
+    pub direction: &'static str,
+    /// `text`, `binary`, `ping`, `pong` or `close`.
+    pub kind: &'static str,
+    /// Text and close frames (as the code and reason) as text, the rest as base64.
+    pub data: String,
+    pub size_bytes: usize,
+    /// Milliseconds since the connection opened.
+    pub at_ms: u64,
+}
+
+impl WsMessage {
+    fn new(direction: &'static str, opcode: u8, payload: &[u8], at_ms: u64) -> Self {
+        let (kind, data) = match opcode {
+            TEXT => ("text", String::from_utf8_lossy(payload).into_owned()),
+            CLOSE => {
+                let (code, reason) = ws_frame::parse_close(payload);
+                let data = match code {
+                    Some(code) => format!("{} {}", code, reason).trim_end().to_string(),
+                    None => String::new(),
+                };
+                ("close", data)
+            }
+            PING => ("ping", base64_encode(payload)),
+            PONG => ("pong", base64_encode(payload)),
+            _ => ("binary", base64_encode(payload)),
+        };
+        WsMessage {
+            direction,
+            kind,
+            data,
+            size_bytes: payload.len(),
+            at_ms,
+        }
+    }
+}
+
+/// How a session ended.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WsClose {
+    pub code: Option<u16>,
+    pub reason: String,
+    /// Why the connection ended without a clean close handshake.
+    pub error: Option<String>,
+}
+
+fn accept_key(key: &str) -> String {
+    base64_encode(&Sha1::digest(format!("{}{}", key, ACCEPT_GUID)))
+}
+
+/// Performs the opening handshake over a pooled HTTP/1.1 client, so proxies,
+/// TLS settings, DNS overrides and cookies apply as they do to requests.
+pub async fn connect(
+    clients: &ClientSource,
+    request: &WsRequest,
+) -> Result<(Upgraded, Handshake), String> {
+    let mut url =
+        Url::parse(&request.url).map_err(|e| format!("Invalid URL '{}': {}", request.url, e))?;
+    let scheme = match url.scheme() {
+        "ws" | "http" => "http",
+        "wss" | "https" => "https",
+        other => {
+            return Err(format!(
+                "Unsupported WebSocket scheme '{}'; use ws:// or wss://",
+                other
+            ))
+        }
+    };
+    url.set_scheme(scheme)
+        .map_err(|_| format!("Invalid WebSocket URL '{}'", request.url))?;
+
+    let mut headers = build_header_map(&request.default_headers, &request.headers)?;
+    let key = base64_encode(Uuid::new_v4().as_bytes());
+    headers.insert(CONNECTION, HeaderValue::from_static("Upgrade"));
+    headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+    headers.insert(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"));
+    headers.insert(
+        SEC_WEBSOCKET_KEY,
+        HeaderValue::from_str(&key).map_err(|e| e.to_string())?,
+    );
+    if !request.subprotocols.is_empty() {
+        let offered = request.subprotocols.join(", ");
+        let value = HeaderValue::from_str(&offered)
+            .map_err(|e| format!("Invalid subprotocols '{}': {}", offered, e))?;
+        headers.insert(SEC_WEBSOCKET_PROTOCOL, value);
+    }
+
+    let client = clients.client_for(&url, None)?;
+    let start = Instant::now();
+    let response = client
+        .get(url)
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|e| tls::describe_send_error(&e))?;
+    let time_ms = start.elapsed().as_millis() as u64;
+
+    let status = response.status();
+    if status != StatusCode::SWITCHING_PROTOCOLS {
+        let mut message = format!(
+            "Server refused the WebSocket upgrade: {} {}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or("")
+        )
+        .trim_end()
+        .to_string();
+        let body = response.text().await.unwrap_or_default();
+        let body = body.trim();
+        if !body.is_empty() {
+            let excerpt: String = body.chars().take(MAX_ERROR_BODY_CHARS).collect();
+            message = format!("{}: {}", message, excerpt);
+        }
+        return Err(message);
+    }
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    if header(SEC_WEBSOCKET_ACCEPT) != Some(accept_key(&key)) {
+        return Err(
+            "Server answered with a wrong Sec-WebSocket-Accept; it may not support WebSocket"
+                .to_string(),
+        );
+    }
+    let protocol = header(SEC_WEBSOCKET_PROTOCOL);
+    if let Some(ref protocol) = protocol {
+        if !request.subprotocols.contains(protocol) {
+            return Err(format!(
+                "Server picked subprotocol '{}', which was not offered",
+                protocol
+            ));
+        }
+    }
+    let handshake = Handshake {
+        headers: response
+            .headers()
+            .iter()
+            .map(|(name, value)| ResponseHeader::new(name, value))
+            .collect(),
+        protocol,
+        time_ms,
+    };
+
+    let stream = response
+        .upgrade()
+        .await
+        .map_err(|e| format!("WebSocket upgrade failed: {}", e))?;
+    Ok((stream, handshake))
+}
+
+/// Drives an open connection until either side closes it, passing every
+/// frame read or written to `on_message`. Pings are answered automatically.
+pub async fn run(
+    stream: Upgraded,
+    queue: mpsc::UnboundedSender<Outgoing>,
+    mut outgoing: mpsc::UnboundedReceiver<Outgoing>,
+    on_message: impl Fn(WsMessage),
+) -> WsClose {
+    let opened = Instant::now();
+    let at_ms = || opened.elapsed().as_millis() as u64;
+    let (mut read_half, mut write_half) = tokio::io::split(stream);
+
+    let reader = async {
+        let mut assembler = Assembler::default();
+        loop {
+            let message = match ws_frame::read_frame(&mut read_half).await {
+                Ok(frame) => assembler.push(frame),
+                Err(error) => {
+                    let _ = queue.send(Outgoing::Abort);
+                    return WsClose {
+                        error: Some(error),
+                        ..Default::default()
+                    };
+                }
+            };
+            let (opcode, payload) = match message {
+                Ok(Some(message)) => message,
+                Ok(None) => continue,
+                Err(error) => {
+                    let _ = queue.send(Outgoing::Close(Some(1002), String::new()));
+                    return WsClose {
+                        error: Some(error),
+                        ..Default::default()
+                    };
+                }
+            };
+            on_message(WsMessage::new("received", opcode, &payload, at_ms()));
+            match opcode {
+                TEXT if std::str::from_utf8(&payload).is_err() => {
+                    let _ = queue.send(Outgoing::Close(Some(1007), String::new()));
+                    return WsClose {
+                        error: Some(
+                            "Server sent a text message that is not valid UTF-8".to_string(),
+                        ),
+                        ..Default::default()
+                    };
+                }
+                PING => {
+                    let _ = queue.send(Outgoing::Pong(payload));
+                }
+                CLOSE => {
+                    let (code, reason) = ws_frame::parse_close(&payload);
+                    // Echoed only if the server spoke first; otherwise the writer is done
+                    let _ = queue.send(Outgoing::Close(code, reason.clone()));
+                    return WsClose {
+                        code,
+                        reason,
+                        error: None,
+                    };
+                }
+                _ => {}
+            }
+        }
+    };
+
+    let writer = async {
+        while let Some(next) = outgoing.recv().await {
+            let (opcode, payload) = match next {
+                Outgoing::Text(text) => (TEXT, text.into_bytes()),
+                Outgoing::Binary(bytes) => (BINARY, bytes),
+                Outgoing::Pong(payload) => (PONG, payload),
+                Outgoing::Close(code, reason) => (CLOSE, ws_frame::close_payload(code, &reason)),
+                Outgoing::Abort => break,
+            };
+            let frame = ws_frame::encode(opcode, &payload);
+            write_half
+                .write_all(&frame)
+                .await
+                .map_err(|e| format!("Failed to send frame: {}", e))?;
+            write_half
+                .flush()
+                .await
+                .map_err(|e| format!("Failed to send frame: {}", e))?;
+            on_message(WsMessage::new("sent", opcode, &payload, at_ms()));
+            if opcode == CLOSE {
+                break;
+            }
+        }
+        Ok::<_, String>(())
+    };
+
+    tokio::pin!(reader, writer);
+    tokio::select! {
+        close = &mut reader => {
+            // Let the writer flush the close reply or protocol error
+            let _ = tokio::time::timeout(CLOSE_TIMEOUT, &mut writer).await;
+            close
+        }
+        written = &mut writer => {
+            let failed = written.err();
+            match tokio::time::timeout(CLOSE_TIMEOUT, &mut reader).await {
+                Ok(close) => WsClose {
+                    error: failed.or(close.error),
+                    ..close
+                },
+                Err(_) => WsClose {
+                    error: Some(failed.unwrap_or_else(|| {
+                        "Server did not answer the close frame".to_string()
+                    })),
+                    ..Default::default()
+                },
+            }
+        }
+    }
+}
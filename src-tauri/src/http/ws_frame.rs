@@ -0,0 +1,183 @@
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+
+/// Frame opcodes from RFC 6455 §5.2.
+pub const CONTINUATION: u8 = 0x0;
+pub const TEXT: u8 = 0x1;
+pub const BINARY: u8 = 0x2;
+pub const CLOSE: u8 = 0x8;
+pub const PING: u8 = 0x9;
+pub const PONG: u8 = 0xA;
+
+/// Largest message accepted from a server, after reassembling fragments.
+pub const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Control frames may not carry more than this (RFC 6455 §5.5).
+const MAX_CONTROL_PAYLOAD: usize = 125;
+
+#[derive(Debug)]
+pub struct Frame {
+    pub fin: bool,
+    pub opcode: u8,
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    pub fn is_control(&self) -> bool {
+        self.opcode & 0x8 != 0
+    }
+}
+
+/// Encodes a single final frame. Client frames are always masked.
+pub fn encode(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut frame = Vec::with_capacity(payload.len() + 14);
+    frame.push(0x80 | opcode);
+    match payload.len() {
+        len @ 0..=125 => frame.push(0x80 | len as u8),
+        len @ 126..=0xFFFF => {
+            frame.push(0x80 | 126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(0x80 | 127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    let mut mask = [0u8; 4];
+    mask.copy_from_slice(&Uuid::new_v4().as_bytes()[..4]);
+    frame.extend_from_slice(&mask);
+    frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+    frame
+}
+
+/// A close frame's payload: the status code followed by a UTF-8 reason.
+pub fn close_payload(code: Option<u16>, reason: &str) -> Vec<u8> {
+    let Some(code) = code else {
+        return Vec::new();
+    };
+    let mut payload = code.to_be_bytes().to_vec();
+    // Keep within the control frame limit without splitting a character
+    let mut end = reason.len().min(MAX_CONTROL_PAYLOAD - 2);
+    while !reason.is_char_boundary(end) {
+        end -= 1;
+    }
+    payload.extend_from_slice(&reason.as_bytes()[..end]);
+    payload
+}
+
+/// The status code and reason of a received close frame.
+pub fn parse_close(payload: &[u8]) -> (Option<u16>, String) {
+    if payload.len() < 2 {
+        return (None, String::new());
+    }
+    let code = u16::from_be_bytes([payload[0], payload[1]]);
+    (
+        Some(code),
+        String::from_utf8_lossy(&payload[2..]).into_owned(),
+    )
+}
+
+/// Reads one frame. Servers must not mask, but masked frames are unmasked
+/// rather than rejected.
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Frame, String> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await.map_err(read_error)?;
+    let fin = head[0] & 0x80 != 0;
+    if head[0] & 0x70 != 0 {
+        return Err("Server set reserved frame bits without a negotiated extension".to_string());
+    }
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0u8; 2];
+            reader.read_exact(&mut len).await.map_err(read_error)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0u8; 8];
+            reader.read_exact(&mut len).await.map_err(read_error)?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_MESSAGE_BYTES as u64 {
+        return Err(format!(
+            "Server sent a {} byte frame; the limit is {} bytes",
+            len, MAX_MESSAGE_BYTES
+        ));
+    }
+    let frame = Frame {
+        fin,
+        opcode,
+        payload: Vec::new(),
+    };
+    if frame.is_control() && (!fin || len as usize > MAX_CONTROL_PAYLOAD) {
+        return Err("Server sent a fragmented or oversized control frame".to_string());
+    }
+    let mut mask = [0u8; 4];
+    if masked {
+        reader.read_exact(&mut mask).await.map_err(read_error)?;
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await.map_err(read_error)?;
+    if masked {
+        for (i, b) in payload.iter_mut().enumerate() {
+            *b ^= mask[i % 4];
+        }
+    }
+    Ok(Frame { payload, ..frame })
+}
+
+fn read_error(e: std::io::Error) -> String {
+    if e.kind() == std::io::ErrorKind::UnexpectedEof {
+        "Connection closed without a close frame".to_string()
+    } else {
+        format!("Connection failed: {}", e)
+    }
+}
+
+/// Reassembles fragmented data frames into messages, passing control frames
+/// through as they arrive between fragments.
+#[derive(Default)]
+pub struct Assembler {
+    opcode: Option<u8>,
+    buffer: Vec<u8>,
+}
+
+impl Assembler {
+    /// The complete message `frame` finishes, as its opcode and payload.
+    pub fn push(&mut self, frame: Frame) -> Result<Option<(u8, Vec<u8>)>, String> {
+        if frame.is_control() {
+            return match frame.opcode {
+                CLOSE | PING | PONG => Ok(Some((frame.opcode, frame.payload))),
+                opcode => Err(format!("Server sent unknown opcode {:#x}", opcode)),
+            };
+        }
+        match (frame.opcode, self.opcode) {
+            (CONTINUATION, None) => {
+                return Err(
+                    "Server sent a continuation frame with no message to continue".to_string(),
+                )
+            }
+            (CONTINUATION, Some(_)) => {}
+            (TEXT | BINARY, None) => self.opcode = Some(frame.opcode),
+            (TEXT | BINARY, Some(_)) => {
+                return Err("Server started a message before finishing the previous one".to_string())
+            }
+            (opcode, _) => return Err(format!("Server sent unknown opcode {:#x}", opcode)),
+        }
+        if self.buffer.len() + frame.payload.len() > MAX_MESSAGE_BYTES {
+            return Err(format!(
+                "Server sent a message over the {} byte limit",
+                MAX_MESSAGE_BYTES
+            ));
+        }
+        self.buffer.extend_from_slice(&frame.payload);
+        if !frame.fin {
+            return Ok(None);
+        }
+        let opcode = self.opcode.take().unwrap_or(BINARY);
+        Ok(Some((opcode, std::mem::take(&mut self.buffer))))
+    }
+}
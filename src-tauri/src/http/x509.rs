@@ -0,0 +1,395 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// One DER tag-length-value element. Only as much DER as is needed to pull
+/// fields out of a peer certificate, which the TLS layer hands over as raw bytes.
+struct Tlv<'a> {
+    tag: u8,
+    /// The whole element, header included.
+    raw: &'a [u8],
+    contents: &'a [u8],
+}
+
+/// Reads the element at the start of `input`, returning it and the rest.
+fn read_tlv(input: &[u8]) -> Option<(Tlv<'_>, &[u8])> {
+    let tag = *input.first()?;
+    let first = *input.get(1)? as usize;
+    let (len, header) = if first < 0x80 {
+        (first, 2)
+    } else {
+        let count = first & 0x7f;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let bytes = input.get(2..2 + count)?;
+        let len = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        (len, 2 + count)
+    };
+    let end = header.checked_add(len)?;
+    let raw = input.get(..end)?;
+    Some((
+        Tlv {
+            tag,
+            raw,
+            contents: &raw[header..],
+        },
+        &input[end..],
+    ))
+}
+
+const SEQUENCE: u8 = 0x30;
+const SET: u8 = 0x31;
+const OID: u8 = 0x06;
+const OCTET_STRING: u8 = 0x04;
+const INTEGER: u8 = 0x02;
+const UTC_TIME: u8 = 0x17;
+const GENERALIZED_TIME: u8 = 0x18;
+const VERSION: u8 = 0xa0;
+const EXTENSIONS: u8 = 0xa3;
+
+/// Every element in a constructed value's contents.
+fn children(contents: &[u8]) -> Vec<Tlv<'_>> {
+    let mut out = Vec::new();
+    let mut rest = contents;
+    while let Some((tlv, next)) = read_tlv(rest) {
+        out.push(tlv);
+        rest = next;
+    }
+    out
+}
+
+fn oid_to_string(bytes: &[u8]) -> String {
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut value = 0u64;
+    for b in bytes {
+        value = (value << 7) | (b & 0x7f) as u64;
+        if b & 0x80 == 0 {
+            // The first byte packs the first two arcs as 40 * a + b
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    arcs.iter()
+        .map(|a| a.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn attribute_name(oid: &str) -> String {
+    match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "2.5.4.5" => "serialNumber",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        other => other,
+    }
+    .to_string()
+}
+
+fn string_value(tlv: &Tlv) -> String {
+    match tlv.tag {
+        // BMPString
+        0x1e => {
+            let units: Vec<u16> = tlv
+                .contents
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        // T61String, effectively Latin-1 in the wild
+        0x14 => tlv.contents.iter().map(|&b| b as char).collect(),
+        _ => String::from_utf8_lossy(tlv.contents).into_owned(),
+    }
+}
+
+/// A distinguished name as `CN=example.com, O=Example`, in certificate order.
+fn name_to_string(name: &Tlv) -> String {
+    children(name.contents)
+        .iter()
+        .filter(|rdn| rdn.tag == SET)
+        .flat_map(|rdn| children(rdn.contents))
+        .filter_map(|attr| {
+            let parts = children(attr.contents);
+            let (oid, value) = (parts.first()?, parts.get(1)?);
+            (oid.tag == OID).then(|| {
+                format!(
+                    "{}={}",
+                    attribute_name(&oid_to_string(oid.contents)),
+                    string_value(value)
+                )
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// UTCTime or GeneralizedTime as RFC 3339 (`2026-01-31T12:00:00Z`).
+fn time_to_string(time: &Tlv) -> Option<String> {
+    let text = std::str::from_utf8(time.contents).ok()?;
+    // Sliced by byte below, which multibyte characters would split
+    if !text.is_ascii() {
+        return None;
+    }
+    let digits = text.strip_suffix('Z')?;
+    let full = match time.tag {
+        UTC_TIME if digits.len() == 12 => {
+            let year: u32 = digits[..2].parse().ok()?;
+            format!("{}{}", if year >= 50 { 19 } else { 20 }, digits)
+        }
+        GENERALIZED_TIME if digits.len() >= 14 => digits[..14].to_string(),
+        _ => return None,
+    };
+    if !full.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!(
+        "{}-{}-{}T{}:{}:{}Z",
+        &full[..4],
+        &full[4..6],
+        &full[6..8],
+        &full[8..10],
+        &full[10..12],
+        &full[12..14]
+    ))
+}
+
+const SUBJECT_ALT_NAME: &str = "2.5.29.17";
+
+/// DNS names, IP addresses, emails and URIs from the subjectAltName extension.
+fn subject_alt_names(extensions: &Tlv) -> Vec<String> {
+    let Some((list, _)) = read_tlv(extensions.contents) else {
+        return Vec::new();
+    };
+    for extension in children(list.contents) {
+        let parts = children(extension.contents);
+        let is_san = parts
+            .first()
+            .is_some_and(|oid| oid.tag == OID && oid_to_string(oid.contents) == SUBJECT_ALT_NAME);
+        let value = parts.iter().find(|p| p.tag == OCTET_STRING);
+        if let (true, Some(value)) = (is_san, value) {
+            let Some((names, _)) = read_tlv(value.contents) else {
+                return Vec::new();
+            };
+            return children(names.contents)
+                .iter()
+                .filter_map(|name| match name.tag {
+                    0x81 => Some(format!("email:{}", String::from_utf8_lossy(name.contents))),
+                    0x82 => Some(String::from_utf8_lossy(name.contents).into_owned()),
+                    0x86 => Some(format!("uri:{}", String::from_utf8_lossy(name.contents))),
+                    0x87 => match name.contents.len() {
+                        4 => <[u8; 4]>::try_from(name.contents)
+                            .ok()
+                            .map(|ip| std::net::Ipv4Addr::from(ip).to_string()),
+                        16 => <[u8; 16]>::try_from(name.contents)
+                            .ok()
+                            .map(|ip| std::net::Ipv6Addr::from(ip).to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+fn hex(bytes: &[u8], separator: &str) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// The TBSCertificate's fields in order, with the optional version skipped.
+fn tbs_fields(cert_der: &[u8]) -> Option<Vec<Tlv<'_>>> {
+    let (cert, _) = read_tlv(cert_der)?;
+    if cert.tag != SEQUENCE {
+        return None;
+    }
+    let (tbs, _) = read_tlv(cert.contents)?;
+    if tbs.tag != SEQUENCE {
+        return None;
+    }
+    let mut fields = Vec::new();
+    let mut rest = tbs.contents;
+    while !rest.is_empty() {
+        let (field, next) = read_tlv(rest)?;
+        if !(fields.is_empty() && field.tag == VERSION) {
+            fields.push(field);
+        }
+        rest = next;
+    }
+    Some(fields)
+}
+
+// serial, signature, issuer, validity, subject, subjectPublicKeyInfo, extensions...
+const SERIAL_INDEX: usize = 0;
+const ISSUER_INDEX: usize = 2;
+const VALIDITY_INDEX: usize = 3;
+const SUBJECT_INDEX: usize = 4;
+const SPKI_INDEX: usize = 5;
+
+/// What the response panel and history show about a server certificate.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CertificateSummary {
+    pub subject: String,
+    pub issuer: String,
+    pub subject_alt_names: Vec<String>,
+    /// RFC 3339.
+    pub not_before: String,
+    /// RFC 3339.
+    pub not_after: String,
+    /// Hex, as `openssl x509 -serial` prints it.
+    pub serial: String,
+    /// Colon-separated hex SHA-256 of the whole certificate.
+    pub sha256_fingerprint: String,
+    /// Base64 SPKI hash, ready to paste into a pin.
+    pub spki_sha256: String,
+}
+
+/// Parses the fields of a DER certificate worth showing; `None` if the
+/// certificate is too malformed to summarise.
+pub fn summarize(cert_der: &[u8]) -> Option<CertificateSummary> {
+    let fields = tbs_fields(cert_der)?;
+    let serial = fields.get(SERIAL_INDEX).filter(|f| f.tag == INTEGER)?;
+    let issuer = fields.get(ISSUER_INDEX).filter(|f| f.tag == SEQUENCE)?;
+    let validity = fields.get(VALIDITY_INDEX).filter(|f| f.tag == SEQUENCE)?;
+    let subject = fields.get(SUBJECT_INDEX).filter(|f| f.tag == SEQUENCE)?;
+    let times = children(validity.contents);
+    let extensions = fields.iter().find(|f| f.tag == EXTENSIONS);
+
+    // Positive serials may carry a leading zero byte for the sign bit
+    let serial = match serial.contents {
+        [0, rest @ ..] if !rest.is_empty() => rest,
+        bytes => bytes,
+    };
+
+    Some(CertificateSummary {
+        subject: name_to_string(subject),
+        issuer: name_to_string(issuer),
+        subject_alt_names: extensions.map(subject_alt_names).unwrap_or_default(),
+        not_before: time_to_string(times.first()?)?,
+        not_after: time_to_string(times.get(1)?)?,
+        serial: hex(serial, ""),
+        sha256_fingerprint: hex(&Sha256::digest(cert_der), ":"),
+        spki_sha256: super::decode::base64_encode(&spki_sha256(cert_der)?),
+    })
+}
+
+/// SHA-256 of the certificate's SubjectPublicKeyInfo, the value key pins are
+/// compared against.
+pub fn spki_sha256(cert_der: &[u8]) -> Option<[u8; 32]> {
+    let fields = tbs_fields(cert_der)?;
+    let spki = fields.get(SPKI_INDEX).filter(|f| f.tag == SEQUENCE)?;
+    Some(Sha256::digest(spki.raw).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// example.com, issued by the CA below, with DNS, IP, email and URI SANs.
+    const LEAF: &[u8] = include_bytes!("testdata/leaf.der");
+    /// Self-signed, valid from 2024 (UTCTime) to 2060 (GeneralizedTime).
+    const CA: &[u8] = include_bytes!("testdata/ca.der");
+
+    fn time(tag: u8, text: &str) -> Option<String> {
+        time_to_string(&Tlv {
+            tag,
+            raw: &[],
+            contents: text.as_bytes(),
+        })
+    }
+
+    #[test]
+    fn summarizes_leaf_with_alt_names() {
+        let summary = summarize(LEAF).unwrap();
+        assert_eq!(summary.subject, "C=US, O=Example, CN=example.com");
+        assert_eq!(summary.issuer, "C=US, O=Hermes Test, CN=Hermes Test CA");
+        assert_eq!(
+            summary.subject_alt_names,
+            [
+                "example.com",
+                "*.example.com",
+                "127.0.0.1",
+                "::1",
+                "email:admin@example.com",
+                "uri:https://example.com/",
+            ]
+        );
+        assert_eq!(summary.not_before, "2025-01-02T03:04:05Z");
+        assert_eq!(summary.not_after, "2030-01-02T03:04:05Z");
+        // Encoded as 00 C0 FF EE, the zero only there for the sign bit
+        assert_eq!(summary.serial, "C0FFEE");
+        assert_eq!(
+            summary.sha256_fingerprint,
+            "D2:71:79:3C:0F:62:91:C6:BF:A9:40:50:26:5F:31:9C:9A:17:D2:E1:9C:C7:A7:2D:A0:92:07:27:26:B7:4A:2F"
+        );
+        assert_eq!(summary.spki_sha256, "JR4OaQOMyXLIeIivqcYpHQCqBtnIALKtHXRJGOYhCzY=");
+    }
+
+    #[test]
+    fn reads_utc_and_generalized_validity() {
+        let summary = summarize(CA).unwrap();
+        assert_eq!(summary.subject, summary.issuer);
+        assert!(summary.subject_alt_names.is_empty());
+        assert_eq!(summary.not_before, "2024-01-01T00:00:00Z");
+        assert_eq!(summary.not_after, "2060-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn converts_times() {
+        let cases = [
+            (UTC_TIME, "240101000000Z", Some("2024-01-01T00:00:00Z")),
+            (UTC_TIME, "491231235959Z", Some("2049-12-31T23:59:59Z")),
+            (UTC_TIME, "500101000000Z", Some("1950-01-01T00:00:00Z")),
+            (GENERALIZED_TIME, "20600101000000Z", Some("2060-01-01T00:00:00Z")),
+            (GENERALIZED_TIME, "20600101000000.25Z", Some("2060-01-01T00:00:00Z")),
+            (UTC_TIME, "2401010000Z", None),
+            (UTC_TIME, "240101000000", None),
+            (UTC_TIME, "24O101000000Z", None),
+            (GENERALIZED_TIME, "2060Z", None),
+            (SEQUENCE, "240101000000Z", None),
+            // Multibyte characters where the year and time are sliced
+            (UTC_TIME, "2\u{e9}010100000Z", None),
+            (GENERALIZED_TIME, "2060010100000\u{e9}Z", None),
+        ];
+        for (tag, text, expected) in cases {
+            assert_eq!(time(tag, text).as_deref(), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated_certificates() {
+        for len in 0..LEAF.len() {
+            assert!(summarize(&LEAF[..len]).is_none(), "{} bytes", len);
+            assert!(spki_sha256(&LEAF[..len]).is_none(), "{} bytes", len);
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        let inputs: [&[u8]; 5] = [
+            &[0x30],
+            &[0x30, 0x80],
+            &[0x30, 0x84, 0xff, 0xff, 0xff, 0xff],
+            &[0x30, 0x03, 0x30, 0x01, 0x02],
+            &[0x04, 0x02, 0xc3, 0x28],
+        ];
+        for input in inputs {
+            assert!(summarize(input).is_none(), "{:02x?}", input);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//! Secrets kept in the OS credential store (Keychain, Credential Manager or
+//! the Secret Service) instead of the SQLite database.
+
+const SERVICE: &str = "com.hermes.app";
+
+/// Runs a credential store call off the async runtime, as the Secret Service
+/// backend blocks on D-Bus.
+async fn blocking<T: Send + 'static>(
+    call: impl FnOnce() -> Result<T, String> + Send + 'static,
+) -> Result<T, String> {
+    tokio::task::spawn_blocking(call)
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, name)
+        .map_err(|e| format!("The system keychain is unavailable: {}", e))
+}
+
+pub async fn get(name: &str) -> Result<Option<String>, String> {
+    let name = name.to_string();
+    blocking(move || match entry(&name)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Cannot read {} from the system keychain: {}", name, e)),
+    })
+    .await
+}
+
+/// Stores `secret` under `name`, or removes the entry when it is empty.
+pub async fn set(name: &str, secret: String) -> Result<(), String> {
+    let name = name.to_string();
+    blocking(move || {
+        let entry = entry(&name)?;
+        let result = if secret.is_empty() {
+            match entry.delete_credential() {
+                Err(keyring::Error::NoEntry) => Ok(()),
+                other => other,
+            }
+        } else {
+            entry.set_password(&secret)
+        };
+        result.map_err(|e| format!("Cannot save {} to the system keychain: {}", name, e))
+    })
+    .await
+}
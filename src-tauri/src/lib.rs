@@ -1,8 +1,15 @@
 mod commands;
 mod db;
+mod graphql;
+mod grpc;
 mod http;
+mod keychain;
 
 use db::AppDb;
+use grpc::{GrpcCalls, GrpcDescriptors};
+use http::cookies::CookieJars;
+use http::websocket::WsSessions;
+use http::{ClientPool, InFlight};
 use std::sync::Mutex;
 use tauri::Manager;
 
@@ -18,6 +25,12 @@ pub fn run() {
             let conn =
                 db::init_db(&data_dir).map_err(|e| -> Box<dyn std::error::Error> { e.into() })?;
             app.manage(AppDb(Mutex::new(conn)));
+            app.manage(InFlight::default());
+            app.manage(ClientPool::default());
+            app.manage(CookieJars::default());
+            app.manage(WsSessions::default());
+            app.manage(GrpcCalls::default());
+            app.manage(GrpcDescriptors::default());
 
             let window = app.get_webview_window("main").unwrap();
 
@@ -36,6 +49,23 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::send_request,
+            commands::cancel_request,
+            commands::flush_client_pool,
+            commands::ws_connect,
+            commands::ws_send,
+            commands::ws_close,
+            commands::list_ws_sessions,
+            commands::get_ws_messages,
+            commands::delete_ws_session,
+            commands::introspect_graphql,
+            commands::get_graphql_schema,
+            commands::delete_graphql_schema,
+            commands::validate_graphql,
+            commands::grpc_describe,
+            commands::grpc_start,
+            commands::grpc_send,
+            commands::grpc_close_send,
+            commands::grpc_cancel,
             commands::load_workspace,
             commands::create_collection,
             commands::update_collection,
@@ -53,8 +83,28 @@ pub fn run() {
             commands::create_environment,
             commands::update_environment,
             commands::delete_environment,
+            commands::list_cookies,
+            commands::create_cookie,
+            commands::update_cookie,
+            commands::delete_cookie,
+            commands::clear_cookies,
+            commands::list_client_certificates,
+            commands::create_client_certificate,
+            commands::update_client_certificate,
+            commands::delete_client_certificate,
+            commands::test_client_certificate,
+            commands::list_ca_certificates,
+            commands::create_ca_certificate,
+            commands::update_ca_certificate,
+            commands::delete_ca_certificate,
+            commands::list_certificate_pins,
+            commands::create_certificate_pin,
+            commands::update_certificate_pin,
+            commands::delete_certificate_pin,
             commands::get_setting,
             commands::set_setting,
+            commands::get_proxy_password,
+            commands::set_proxy_password,
             commands::log_history,
             commands::get_history_entry,
             commands::search_history,
@@ -70,6 +120,7 @@ pub fn run() {
             commands::remove_sync_queue_entry,
             commands::hard_delete_synced,
             commands::write_file,
+            commands::write_binary_file,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
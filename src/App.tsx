@@ -6,6 +6,9 @@ import { Titlebar } from "@/components/layout/Titlebar";
 import { UrlBar } from "@/components/request/UrlBar";
 import { RequestConfigTabs } from "@/components/request/RequestConfigTabs";
 import { ResponsePanel } from "@/components/response/ResponsePanel";
+import { WebSocketPanel } from "@/components/websocket/WebSocketPanel";
+import { EventStreamPanel } from "@/components/sse/EventStreamPanel";
+import { GrpcPanel } from "@/components/grpc/GrpcPanel";
 import { CreateCollectionDialog } from "@/components/collections/CreateCollectionDialog";
 import { SaveRequestDialog } from "@/components/collections/SaveRequestDialog";
 import { ImportDialog } from "@/components/import/ImportDialog";
@@ -17,7 +20,7 @@ import { ShareCollectionDialog } from "@/components/teams/ShareCollectionDialog"
 import { SettingsPanel } from "@/components/settings/SettingsPanel";
 import { EnvironmentsPanel } from "@/components/environments/EnvironmentsPanel";
 import { Toaster } from "@/components/ui/sonner";
-import { useTabStore, isRequestTab } from "@/stores/tabStore";
+import { useTabStore, isRequestTab, bodyToSend, type RequestTab } from "@/stores/tabStore";
 import { useCollectionStore } from "@/stores/collectionStore";
 import { useEnvironmentStore } from "@/stores/environmentStore";
 import { useSettingsStore } from "@/stores/settingsStore";
@@ -25,22 +28,30 @@ import { useHistoryStore } from "@/stores/historyStore";
 import { useAuthStore } from "@/stores/authStore";
 import { useTeamStore } from "@/stores/teamStore";
 import { useSyncStore } from "@/stores/syncStore";
+import { useWsStore } from "@/stores/wsStore";
+import { useGraphqlStore } from "@/stores/graphqlStore";
+import { useGrpcStore } from "@/stores/grpcStore";
 import { useKeyboard } from "@/hooks/useKeyboard";
 import { useAutoSave } from "@/hooks/useAutoSave";
 import {
   buildScopeForRequest,
   buildAttributedScopeForRequest,
   getFolderChain,
+  resolveString,
 } from "@/lib/variables";
+import { graphqlEndpoint } from "@/lib/graphql";
+import { EMPTY_GRPC, grpcSourceKey } from "@/lib/grpc";
 import { unshareCollection } from "@/lib/sync-utils";
 import { supabase } from "@/lib/supabase";
 import type { VariableCompletionItem } from "@/lib/codemirror/variable-extension";
 import type { CurlImport } from "@/lib/import/curl";
 import { useUndoStore } from "@/stores/undoStore";
 import { invoke } from "@tauri-apps/api/core";
-import { Plus, Zap } from "lucide-react";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Braces, Cable, Code, Network, Plus, Zap } from "lucide-react";
 import { Button } from "@/components/ui/button";
 import type { HistoryEntry } from "@/types/history";
+import type { HeaderEntry, RequestAuth } from "@/types/request";
 import { requestToCurl } from "@/lib/export-utils";
 import { toast } from "sonner";
 
@@ -56,14 +67,86 @@ function EmptyState() {
           Open a request from the sidebar or create a new one
         </p>
       </div>
-      <Button variant="outline" size="sm" onClick={openNewTab}>
-        <Plus className="size-3.5 mr-1.5" />
-        New Request
-      </Button>
+      <div className="flex gap-2">
+        <Button variant="outline" size="sm" onClick={() => openNewTab()}>
+          <Plus className="size-3.5 mr-1.5" />
+          New Request
+        </Button>
+        <Button variant="outline" size="sm" onClick={() => openNewTab("websocket")}>
+          <Cable className="size-3.5 mr-1.5" />
+          New WebSocket
+        </Button>
+        <Button variant="outline" size="sm" onClick={() => openNewTab("graphql")}>
+          <Braces className="size-3.5 mr-1.5" />
+          New GraphQL
+        </Button>
+        <Button variant="outline" size="sm" onClick={() => openNewTab("grpc")}>
+          <Network className="size-3.5 mr-1.5" />
+          New gRPC
+        </Button>
+        <Button variant="outline" size="sm" onClick={() => openNewTab("jsonrpc")}>
+          <Code className="size-3.5 mr-1.5" />
+          New JSON-RPC
+        </Button>
+      </div>
     </div>
   );
 }
 
+/**
+ * What a saved request inherits from its collection and folders: the auth
+ * used when its own is "none", and default header layers, collection first
+ * then folders root → leaf.
+ */
+function inheritedRequestContext(tab: RequestTab): {
+  resolveAuth: () => RequestAuth;
+  defaultHeaders: HeaderEntry[][];
+} {
+  const resolveAuth = () => {
+    const { auth } = tab.state;
+    if (auth.type !== "none") return auth;
+
+    if (tab.savedRequestId) {
+      const req = useCollectionStore
+        .getState()
+        .getRequest(tab.savedRequestId);
+      if (req) {
+        if (req.folderId) {
+          const folder = useCollectionStore
+            .getState()
+            .getFolder(req.folderId);
+          if (folder && folder.defaultAuth.type !== "none") {
+            return folder.defaultAuth;
+          }
+        }
+        const col = useCollectionStore
+          .getState()
+          .getCollection(req.collectionId);
+        if (col && col.defaultAuth.type !== "none") {
+          return col.defaultAuth;
+        }
+      }
+    }
+
+    return auth;
+  };
+
+  const defaultHeaders: HeaderEntry[][] = [];
+  if (tab.savedRequestId) {
+    const store = useCollectionStore.getState();
+    const req = store.getRequest(tab.savedRequestId);
+    if (req) {
+      const col = store.getCollection(req.collectionId);
+      if (col) defaultHeaders.push(col.defaultHeaders);
+      for (const folder of getFolderChain(req.folderId, store.folders)) {
+        defaultHeaders.push(folder.defaultHeaders);
+      }
+    }
+  }
+
+  return { resolveAuth, defaultHeaders };
+}
+
 function App() {
   const loadWorkspace = useCollectionStore((s) => s.loadWorkspace);
   const collections = useCollectionStore((s) => s.collections);
@@ -81,12 +164,31 @@ function App() {
   const setBodyConfig = useTabStore((s) => s.setBodyConfig);
   const setBodyType = useTabStore((s) => s.setBodyType);
   const setAuth = useTabStore((s) => s.setAuth);
+  const setSubprotocols = useTabStore((s) => s.setSubprotocols);
+  const setGraphql = useTabStore((s) => s.setGraphql);
+  const setGrpc = useTabStore((s) => s.setGrpc);
+  const setJsonRpc = useTabStore((s) => s.setJsonRpc);
+  const setKind = useTabStore((s) => s.setKind);
   const sendRequest = useTabStore((s) => s.sendRequest);
+  const cancelRequest = useTabStore((s) => s.cancelRequest);
   const openHistoryEntry = useTabStore((s) => s.openHistoryEntry);
   const restoreFromHistory = useTabStore((s) => s.restoreFromHistory);
   const updateSavedSnapshot = useTabStore((s) => s.updateSavedSnapshot);
   const tabs = useTabStore((s) => s.tabs);
 
+  const wsConnect = useWsStore((s) => s.connect);
+  const wsDisconnect = useWsStore((s) => s.disconnect);
+  const wsStatus = useWsStore((s) =>
+    activeTab ? s.connections[activeTab.id]?.status : undefined,
+  );
+  const introspect = useGraphqlStore((s) => s.introspect);
+  const grpcStart = useGrpcStore((s) => s.start);
+  const grpcCancel = useGrpcStore((s) => s.cancel);
+  const grpcDescribe = useGrpcStore((s) => s.describe);
+  const grpcRunning = useGrpcStore((s) =>
+    activeTab ? s.calls[activeTab.id]?.status === "running" : false,
+  );
+
   const environments = useEnvironmentStore((s) => s.environments);
   const activeEnvironmentId = useEnvironmentStore((s) => s.activeEnvironmentId);
 
@@ -235,41 +337,50 @@ function App() {
     return items;
   }, [variableScopeContext.attributed]);
 
-  const handleSend = useCallback(() => {
+  const dispatchSend = useCallback((saveTo?: string) => {
     const tab = useTabStore.getState().getActiveTab();
     if (!tab || !isRequestTab(tab)) return;
 
-    const resolveAuth = () => {
-      const { auth } = tab.state;
-      if (auth.type !== "none") return auth;
+    const { resolveAuth, defaultHeaders } = inheritedRequestContext(tab);
 
-      if (tab.savedRequestId) {
-        const req = useCollectionStore
-          .getState()
-          .getRequest(tab.savedRequestId);
-        if (req) {
-          if (req.folderId) {
-            const folder = useCollectionStore
-              .getState()
-              .getFolder(req.folderId);
-            if (folder && folder.defaultAuth.type !== "none") {
-              return folder.defaultAuth;
-            }
-          }
-          const col = useCollectionStore
-            .getState()
-            .getCollection(req.collectionId);
-          if (col && col.defaultAuth.type !== "none") {
-            return col.defaultAuth;
-          }
-        }
-      }
+    if (tab.state.kind === "websocket") {
+      wsConnect(
+        tab.id,
+        tab.state,
+        tab.savedRequestId,
+        resolveAuth,
+        variableScopeContext.scope,
+        defaultHeaders,
+      );
+      return;
+    }
+    if (tab.state.kind === "grpc") {
+      grpcStart(tab.id, tab.state, resolveAuth, variableScopeContext.scope, defaultHeaders);
+      return;
+    }
+    sendRequest(resolveAuth, variableScopeContext.scope, defaultHeaders, saveTo);
+  }, [sendRequest, wsConnect, grpcStart, variableScopeContext.scope]);
+
+  const handleSend = useCallback(() => dispatchSend(), [dispatchSend]);
 
-      return auth;
-    };
+  const handleFetchSchema = useCallback(() => {
+    const tab = useTabStore.getState().getActiveTab();
+    if (!tab || !isRequestTab(tab)) return;
+    const { resolveAuth, defaultHeaders } = inheritedRequestContext(tab);
+    introspect(tab.state, resolveAuth, variableScopeContext.scope, defaultHeaders);
+  }, [introspect, variableScopeContext.scope]);
+
+  const handleLoadGrpcServices = useCallback((refresh: boolean) => {
+    const tab = useTabStore.getState().getActiveTab();
+    if (!tab || !isRequestTab(tab)) return;
+    const { resolveAuth, defaultHeaders } = inheritedRequestContext(tab);
+    grpcDescribe(tab.state, resolveAuth, variableScopeContext.scope, defaultHeaders, refresh);
+  }, [grpcDescribe, variableScopeContext.scope]);
 
-    sendRequest(resolveAuth, variableScopeContext.scope);
-  }, [sendRequest, variableScopeContext.scope]);
+  const handleSendAndSave = useCallback(async () => {
+    const path = await save({ defaultPath: "response.bin" });
+    if (path) dispatchSend(path);
+  }, [dispatchSend]);
 
   const handleSave = useCallback(async () => {
     const tab = useTabStore.getState().getActiveTab();
@@ -284,6 +395,11 @@ function App() {
         params: tab.state.params,
         body: tab.state.bodyConfig,
         auth: tab.state.auth,
+        subprotocols: tab.state.subprotocols ?? [],
+        graphql: tab.state.graphql,
+        grpc: tab.state.grpc,
+        jsonrpc: tab.state.jsonrpc,
+        kind: tab.state.kind ?? "http",
       });
       updateSavedSnapshot(tab.id);
     } else {
@@ -291,6 +407,18 @@ function App() {
     }
   }, [updateSavedSnapshot]);
 
+  const handleDisconnect = useCallback(() => {
+    const tab = useTabStore.getState().getActiveTab();
+    if (tab) wsDisconnect(tab.id);
+  }, [wsDisconnect]);
+
+  const handleCancelGrpc = useCallback(() => {
+    const tab = useTabStore.getState().getActiveTab();
+    if (tab) grpcCancel(tab.id).catch(() => {});
+  }, [grpcCancel]);
+
+  const handleNewTab = useCallback(() => openNewTab(), [openNewTab]);
+
   const handleCloseTab = useCallback(() => {
     const tab = useTabStore.getState().getActiveTab();
     if (tab) closeTab(tab.id);
@@ -415,7 +543,7 @@ function App() {
     onSend: handleSend,
     onFocusUrl: focusUrl,
     onSave: handleSave,
-    onNewTab: openNewTab,
+    onNewTab: handleNewTab,
     onCloseTab: handleCloseTab,
     onPrevTab: handlePrevTab,
     onNextTab: handleNextTab,
@@ -456,13 +584,30 @@ function App() {
                 </div>
               )}
               <UrlBar
+                kind={activeTab.state.kind}
+                connected={wsStatus === "open" || wsStatus === "closing"}
                 method={activeTab.state.method}
                 url={activeTab.state.url}
-                loading={activeTab.state.loading}
+                loading={
+                  activeTab.state.kind === "websocket"
+                    ? wsStatus === "connecting" || wsStatus === "closing"
+                    : activeTab.state.kind === "grpc"
+                      ? grpcRunning
+                      : activeTab.state.loading
+                }
                 disabled={activeTab.readOnly}
                 onMethodChange={setMethod}
                 onUrlChange={setUrl}
                 onSend={handleSend}
+                onSendAndSave={handleSendAndSave}
+                onCancel={
+                  activeTab.state.kind === "websocket"
+                    ? handleDisconnect
+                    : activeTab.state.kind === "grpc"
+                      ? handleCancelGrpc
+                      : cancelRequest
+                }
+                onKindChange={setKind}
                 variableItems={getVariableItems}
                 isVariableResolved={isVariableResolved}
                 onCurlDetected={handleCurlDetected}
@@ -472,6 +617,8 @@ function App() {
             <div className="flex-1 min-h-0 flex">
               <div className="w-1/2 min-w-0 border-r border-border overflow-hidden">
                 <RequestConfigTabs
+                  kind={activeTab.state.kind}
+                  tabId={activeTab.id}
                   params={activeTab.state.params}
                   headers={activeTab.state.headers}
                   auth={activeTab.state.auth}
@@ -481,6 +628,23 @@ function App() {
                   onAuthChange={setAuth}
                   onBodyConfigChange={setBodyConfig}
                   onBodyTypeChange={setBodyType}
+                  subprotocols={activeTab.state.subprotocols}
+                  onSubprotocolsChange={setSubprotocols}
+                  graphql={activeTab.state.graphql}
+                  graphqlEndpoint={graphqlEndpoint(
+                    resolveString(activeTab.state.url, variableScopeContext.scope),
+                  )}
+                  onGraphqlChange={setGraphql}
+                  onFetchSchema={handleFetchSchema}
+                  grpc={activeTab.state.grpc}
+                  grpcSourceKey={grpcSourceKey(
+                    resolveString(activeTab.state.url, variableScopeContext.scope),
+                    activeTab.state.grpc ?? EMPTY_GRPC,
+                  )}
+                  onGrpcChange={setGrpc}
+                  onLoadGrpcServices={handleLoadGrpcServices}
+                  jsonrpc={activeTab.state.jsonrpc}
+                  onJsonRpcChange={setJsonRpc}
                   inheritedAuth={inheritedAuth}
                   variableItems={getVariableItems}
                   isVariableResolved={isVariableResolved}
@@ -488,22 +652,53 @@ function App() {
                 />
               </div>
               <div className="w-1/2 min-w-0 overflow-hidden">
-                <ResponsePanel
-                  response={activeTab.state.response}
-                  loading={activeTab.state.loading}
-                  error={activeTab.state.error}
-                  onCopyAsCurl={() => {
-                    const curl = requestToCurl(
-                      activeTab.state.method,
-                      activeTab.state.url,
-                      activeTab.state.headers,
-                      activeTab.state.bodyConfig,
-                      activeTab.state.auth,
-                    );
-                    navigator.clipboard.writeText(curl);
-                    toast.success("cURL copied to clipboard");
-                  }}
-                />
+                {activeTab.state.kind === "websocket" ? (
+                  <WebSocketPanel
+                    tabId={activeTab.id}
+                    savedRequestId={activeTab.savedRequestId}
+                  />
+                ) : activeTab.state.kind === "grpc" ? (
+                  <GrpcPanel
+                    tabId={activeTab.id}
+                    message={resolveString(
+                      (activeTab.state.grpc ?? EMPTY_GRPC).message,
+                      variableScopeContext.scope,
+                    )}
+                  />
+                ) : activeTab.state.kind === "sse" && activeTab.state.eventStream ? (
+                  <EventStreamPanel
+                    stream={activeTab.state.eventStream}
+                    loading={activeTab.state.loading}
+                    error={activeTab.state.error}
+                  />
+                ) : (
+                  <ResponsePanel
+                    response={activeTab.state.response}
+                    loading={activeTab.state.loading}
+                    downloadProgress={activeTab.state.downloadProgress ?? null}
+                    error={activeTab.state.error}
+                    jsonrpcSent={
+                      activeTab.state.kind === "jsonrpc" ? activeTab.state.jsonrpcSent : null
+                    }
+                    onCopyAsCurl={() => {
+                      let body = activeTab.state.bodyConfig;
+                      try {
+                        body = bodyToSend(activeTab.state);
+                      } catch {
+                        // Malformed GraphQL variables or JSON-RPC params: copy the request without them
+                      }
+                      const curl = requestToCurl(
+                        activeTab.state.method,
+                        activeTab.state.url,
+                        activeTab.state.headers,
+                        body,
+                        activeTab.state.auth,
+                      );
+                      navigator.clipboard.writeText(curl);
+                      toast.success("cURL copied to clipboard");
+                    }}
+                  />
+                )}
               </div>
             </div>
           </>
@@ -14,7 +14,7 @@ import {
   SidebarMenuSubItem,
   SidebarMenuSubButton,
 } from "@/components/ui/sidebar";
-import { MethodBadge } from "@/components/request/MethodBadge";
+import { KindBadge, MethodBadge } from "@/components/request/MethodBadge";
 import { RenameInput } from "./RenameInput";
 import {
   TreeContextMenuWrapper,
@@ -27,6 +27,8 @@ import { useTabStore, isRequestTab } from "@/stores/tabStore";
 import { useCollectionStore } from "@/stores/collectionStore";
 import { buildTree } from "@/lib/tree-utils";
 import { exportCollectionToJson, requestToCurl } from "@/lib/export-utils";
+import { graphqlBody } from "@/lib/graphql";
+import { EMPTY_JSONRPC_CALL, jsonrpcBody } from "@/lib/jsonrpc";
 import { save } from "@tauri-apps/plugin-dialog";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
@@ -135,6 +137,71 @@ function CollectionNode({
     });
   }, [saveRequest, node.data.id]);
 
+  const handleNewWebSocket = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.id,
+      folderId: null,
+      name: "New WebSocket",
+      method: "GET",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "websocket",
+    });
+  }, [saveRequest, node.data.id]);
+
+  const handleNewGraphql = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.id,
+      folderId: null,
+      name: "New GraphQL",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "graphql",
+    });
+  }, [saveRequest, node.data.id]);
+
+  const handleNewGrpc = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.id,
+      folderId: null,
+      name: "New gRPC",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "grpc",
+    });
+  }, [saveRequest, node.data.id]);
+
+  const handleNewJsonRpc = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.id,
+      folderId: null,
+      name: "New JSON-RPC",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "jsonrpc",
+      jsonrpc: [EMPTY_JSONRPC_CALL],
+    });
+  }, [saveRequest, node.data.id]);
+
   const handleExport = useCallback(async () => {
     const store = useCollectionStore.getState();
     const json = exportCollectionToJson(
@@ -156,6 +223,10 @@ function CollectionNode({
   const actions = collectionActions({
     onNewFolder: handleNewFolder,
     onNewRequest: handleNewRequest,
+    onNewWebSocket: handleNewWebSocket,
+    onNewGraphql: handleNewGraphql,
+    onNewGrpc: handleNewGrpc,
+    onNewJsonRpc: handleNewJsonRpc,
     onRename: () => setRenaming(true),
     onExport: handleExport,
     onShare: isAuthenticated && !isSynced && onShareCollection
@@ -254,12 +325,81 @@ function FolderNode({
     });
   }, [saveRequest, node.data.collectionId, node.data.id]);
 
+  const handleNewWebSocket = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.collectionId,
+      folderId: node.data.id,
+      name: "New WebSocket",
+      method: "GET",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "websocket",
+    });
+  }, [saveRequest, node.data.collectionId, node.data.id]);
+
+  const handleNewGraphql = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.collectionId,
+      folderId: node.data.id,
+      name: "New GraphQL",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "graphql",
+    });
+  }, [saveRequest, node.data.collectionId, node.data.id]);
+
+  const handleNewGrpc = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.collectionId,
+      folderId: node.data.id,
+      name: "New gRPC",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "grpc",
+    });
+  }, [saveRequest, node.data.collectionId, node.data.id]);
+
+  const handleNewJsonRpc = useCallback(async () => {
+    await saveRequest({
+      collectionId: node.data.collectionId,
+      folderId: node.data.id,
+      name: "New JSON-RPC",
+      method: "POST",
+      url: "",
+      headers: [],
+      params: [],
+      body: { type: "none" },
+      auth: { type: "none" },
+      variables: [],
+      kind: "jsonrpc",
+      jsonrpc: [EMPTY_JSONRPC_CALL],
+    });
+  }, [saveRequest, node.data.collectionId, node.data.id]);
+
   const handleNewSubfolder = useCallback(async () => {
     await createFolder(node.data.collectionId, "New Folder", node.data.id);
   }, [createFolder, node.data.collectionId, node.data.id]);
 
   const actions = folderActions({
     onNewRequest: handleNewRequest,
+    onNewWebSocket: handleNewWebSocket,
+    onNewGraphql: handleNewGraphql,
+    onNewGrpc: handleNewGrpc,
+    onNewJsonRpc: handleNewJsonRpc,
     onNewSubfolder: handleNewSubfolder,
     onRename: () => setRenaming(true),
   });
@@ -318,11 +458,26 @@ function RequestSubNode({
   const duplicateRequest = useCollectionStore((s) => s.duplicateRequest);
 
   const handleCopyAsCurl = useCallback(async () => {
+    let body = node.data.body;
+    if (node.data.kind === "graphql") {
+      try {
+        body = graphqlBody(node.data.graphql);
+      } catch {
+        // Malformed variables: copy the request without them
+      }
+    }
+    if (node.data.kind === "jsonrpc") {
+      try {
+        body = jsonrpcBody(node.data.jsonrpc);
+      } catch {
+        // No calls or malformed params: copy the request without a body
+      }
+    }
     const curl = requestToCurl(
       node.data.method,
       node.data.url,
       node.data.headers,
-      node.data.body,
+      body,
       node.data.auth,
     );
     await navigator.clipboard.writeText(curl);
@@ -333,7 +488,7 @@ function RequestSubNode({
     onRename: () => setRenaming(true),
     onDuplicate: () => duplicateRequest(node.data.id),
     onMove: () => onMoveRequest(node.data),
-    onCopyAsCurl: handleCopyAsCurl,
+    onCopyAsCurl: node.data.kind !== "websocket" ? handleCopyAsCurl : undefined,
   });
 
   return (
@@ -350,10 +505,17 @@ function RequestSubNode({
           className="cursor-pointer"
         >
           <GripVertical className="size-3 text-muted-foreground/40 shrink-0 cursor-grab" />
-          <MethodBadge
-            method={node.data.method}
-            className="text-[9px] px-1 py-0 rounded leading-tight"
-          />
+          {node.data.kind === "http" ? (
+            <MethodBadge
+              method={node.data.method}
+              className="text-[9px] px-1 py-0 rounded leading-tight"
+            />
+          ) : (
+            <KindBadge
+              kind={node.data.kind}
+              className="text-[9px] px-1 py-0 rounded leading-tight"
+            />
+          )}
           {renaming ? (
             <RenameInput
               value={node.data.name}
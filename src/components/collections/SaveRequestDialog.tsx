@@ -43,7 +43,13 @@ export function SaveRequestDialog({
   // Reset form when opened
   const handleOpenChange = (nextOpen: boolean) => {
     if (nextOpen) {
-      setName(activeTab?.title === "New Request" ? "" : (activeTab?.title ?? ""));
+      const untitled =
+        activeTab?.title === "New Request" ||
+        activeTab?.title === "New WebSocket" ||
+        activeTab?.title === "New GraphQL" ||
+        activeTab?.title === "New gRPC" ||
+        activeTab?.title === "New JSON-RPC";
+      setName(untitled ? "" : (activeTab?.title ?? ""));
       setCollectionId(collections[0]?.id ?? "");
       setFolderId("__none__");
     }
@@ -69,6 +75,11 @@ export function SaveRequestDialog({
         body: activeTab.state.bodyConfig,
         auth: activeTab.state.auth,
         variables: [],
+        kind: activeTab.state.kind ?? "http",
+        subprotocols: activeTab.state.subprotocols ?? [],
+        graphql: activeTab.state.graphql,
+        grpc: activeTab.state.grpc,
+        jsonrpc: activeTab.state.jsonrpc,
       });
       linkTabToSaved(activeTab.id, saved.id, saved.name);
       onOpenChange(false);
@@ -26,6 +26,10 @@ import {
   Terminal,
   Cloud,
   CloudOff,
+  Cable,
+  Braces,
+  Network,
+  Code,
 } from "lucide-react";
 import { useState } from "react";
 
@@ -114,6 +118,10 @@ export function TreeContextMenuWrapper({
 export function collectionActions({
   onNewFolder,
   onNewRequest,
+  onNewWebSocket,
+  onNewGraphql,
+  onNewGrpc,
+  onNewJsonRpc,
   onRename,
   onExport,
   onShare,
@@ -121,6 +129,10 @@ export function collectionActions({
 }: {
   onNewFolder: () => void;
   onNewRequest: () => void;
+  onNewWebSocket: () => void;
+  onNewGraphql: () => void;
+  onNewGrpc: () => void;
+  onNewJsonRpc: () => void;
   onRename: () => void;
   onExport?: () => void;
   onShare?: () => void;
@@ -137,6 +149,26 @@ export function collectionActions({
       icon: <FilePlus className="size-3.5" />,
       onClick: onNewRequest,
     },
+    {
+      label: "New WebSocket",
+      icon: <Cable className="size-3.5" />,
+      onClick: onNewWebSocket,
+    },
+    {
+      label: "New GraphQL",
+      icon: <Braces className="size-3.5" />,
+      onClick: onNewGraphql,
+    },
+    {
+      label: "New gRPC",
+      icon: <Network className="size-3.5" />,
+      onClick: onNewGrpc,
+    },
+    {
+      label: "New JSON-RPC",
+      icon: <Code className="size-3.5" />,
+      onClick: onNewJsonRpc,
+    },
     {
       label: "Rename",
       icon: <Pencil className="size-3.5" />,
@@ -169,10 +201,18 @@ export function collectionActions({
 
 export function folderActions({
   onNewRequest,
+  onNewWebSocket,
+  onNewGraphql,
+  onNewGrpc,
+  onNewJsonRpc,
   onNewSubfolder,
   onRename,
 }: {
   onNewRequest: () => void;
+  onNewWebSocket: () => void;
+  onNewGraphql: () => void;
+  onNewGrpc: () => void;
+  onNewJsonRpc: () => void;
   onNewSubfolder: () => void;
   onRename: () => void;
 }): ContextAction[] {
@@ -182,6 +222,26 @@ export function folderActions({
       icon: <FilePlus className="size-3.5" />,
       onClick: onNewRequest,
     },
+    {
+      label: "New WebSocket",
+      icon: <Cable className="size-3.5" />,
+      onClick: onNewWebSocket,
+    },
+    {
+      label: "New GraphQL",
+      icon: <Braces className="size-3.5" />,
+      onClick: onNewGraphql,
+    },
+    {
+      label: "New gRPC",
+      icon: <Network className="size-3.5" />,
+      onClick: onNewGrpc,
+    },
+    {
+      label: "New JSON-RPC",
+      icon: <Code className="size-3.5" />,
+      onClick: onNewJsonRpc,
+    },
     {
       label: "New Subfolder",
       icon: <FolderPlus className="size-3.5" />,
@@ -11,8 +11,9 @@ import {
   AlertDialogTitle,
 } from "@/components/ui/alert-dialog";
 import { useEnvironmentStore } from "@/stores/environmentStore";
+import { toast } from "sonner";
 import { Plus, Trash2, Eye, EyeOff } from "lucide-react";
-import type { Variable, Environment } from "@/types/environment";
+import type { Variable, Environment, DnsOverride } from "@/types/environment";
 import { ScrollArea } from "@/components/ui/scroll-area";
 
 interface GridRow {
@@ -68,6 +69,51 @@ function gridToEnvironments(
   return result;
 }
 
+interface DnsGridRow {
+  host: string;
+  values: Map<string, string>; // envId → address
+}
+
+function buildDnsGrid(environments: Environment[]): DnsGridRow[] {
+  const rows = new Map<string, DnsGridRow>();
+
+  for (const env of environments) {
+    for (const o of env.dnsOverrides) {
+      if (!rows.has(o.host)) {
+        rows.set(o.host, { host: o.host, values: new Map() });
+      }
+      rows.get(o.host)!.values.set(env.id, o.address);
+    }
+  }
+
+  return Array.from(rows.values());
+}
+
+// Unlike variables, an empty cell means "no override" and is not stored
+function dnsGridToEnvironments(
+  grid: DnsGridRow[],
+  environments: Environment[],
+): Map<string, DnsOverride[]> {
+  const result = new Map<string, DnsOverride[]>();
+
+  for (const env of environments) {
+    result.set(env.id, []);
+  }
+
+  for (const row of grid) {
+    const host = row.host.trim();
+    if (!host) continue;
+    for (const env of environments) {
+      const address = row.values.get(env.id)?.trim();
+      if (address) {
+        result.get(env.id)!.push({ host, address });
+      }
+    }
+  }
+
+  return result;
+}
+
 export function EnvironmentsPanel() {
   const environments = useEnvironmentStore((s) => s.environments);
   const createEnvironment = useEnvironmentStore((s) => s.createEnvironment);
@@ -75,6 +121,7 @@ export function EnvironmentsPanel() {
   const deleteEnvironment = useEnvironmentStore((s) => s.deleteEnvironment);
 
   const [grid, setGrid] = useState<GridRow[]>(() => buildGrid(environments));
+  const [dnsGrid, setDnsGrid] = useState<DnsGridRow[]>(() => buildDnsGrid(environments));
   const [deleteTarget, setDeleteTarget] = useState<Environment | null>(null);
   const [renamingEnvId, setRenamingEnvId] = useState<string | null>(null);
   const [renameValue, setRenameValue] = useState("");
@@ -132,6 +179,34 @@ export function EnvironmentsPanel() {
     setGrid((prev) => prev.filter((_, i) => i !== rowIdx));
   }, []);
 
+  const updateDnsCell = useCallback(
+    (rowIdx: number, envId: string, address: string) => {
+      setDnsGrid((prev) =>
+        prev.map((row, i) => {
+          if (i !== rowIdx) return row;
+          const newValues = new Map(row.values);
+          newValues.set(envId, address);
+          return { ...row, values: newValues };
+        }),
+      );
+    },
+    [],
+  );
+
+  const updateDnsHost = useCallback((rowIdx: number, host: string) => {
+    setDnsGrid((prev) =>
+      prev.map((row, i) => (i === rowIdx ? { ...row, host } : row)),
+    );
+  }, []);
+
+  const addDnsRow = useCallback(() => {
+    setDnsGrid((prev) => [...prev, { host: "", values: new Map() }]);
+  }, []);
+
+  const removeDnsRow = useCallback((rowIdx: number) => {
+    setDnsGrid((prev) => prev.filter((_, i) => i !== rowIdx));
+  }, []);
+
   const handleAddEnvironment = useCallback(async () => {
     const env = await createEnvironment("New Environment");
     setRenamingEnvId(env.id);
@@ -154,23 +229,30 @@ export function EnvironmentsPanel() {
     if (!deleteTarget) return;
     await deleteEnvironment(deleteTarget.id);
     setDeleteTarget(null);
-    setGrid((prev) =>
-      prev.map((row) => {
-        const newValues = new Map(row.values);
-        newValues.delete(deleteTarget.id);
-        return { ...row, values: newValues };
-      }),
-    );
+    const dropColumn = <T extends { values: Map<string, string> }>(row: T): T => {
+      const newValues = new Map(row.values);
+      newValues.delete(deleteTarget.id);
+      return { ...row, values: newValues };
+    };
+    setGrid((prev) => prev.map(dropColumn));
+    setDnsGrid((prev) => prev.map(dropColumn));
   }, [deleteTarget, deleteEnvironment]);
 
   const handleSave = useCallback(async () => {
     const envVars = gridToEnvironments(grid, environments);
+    const envDns = dnsGridToEnvironments(dnsGrid, environments);
     const promises: Promise<void>[] = [];
     for (const [envId, variables] of envVars) {
-      promises.push(updateEnvironment(envId, { variables }));
+      promises.push(
+        updateEnvironment(envId, { variables, dnsOverrides: envDns.get(envId) ?? [] }),
+      );
     }
-    await Promise.all(promises);
-  }, [grid, environments, updateEnvironment]);
+    try {
+      await Promise.all(promises);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  }, [grid, dnsGrid, environments, updateEnvironment]);
 
   return (
     <>
@@ -317,6 +399,80 @@ export function EnvironmentsPanel() {
               Add Variable
             </button>
           </div>
+
+          <div className="px-3 pt-4 pb-2 space-y-1">
+            <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+              DNS Overrides
+            </h3>
+            <p className="text-xs text-muted-foreground">
+              Connect to these addresses instead of resolving the host, like curl&apos;s
+              --resolve. Leave a cell empty to use normal DNS in that environment.
+            </p>
+          </div>
+          <div className="overflow-x-auto">
+            <table className="w-full text-xs border-collapse">
+              <thead>
+                <tr className="border-b border-border bg-muted/30">
+                  <th className="text-left font-medium text-muted-foreground px-3 py-2 w-[180px] min-w-[180px] sticky left-0 bg-muted/30 z-10">
+                    Host
+                  </th>
+                  {orderedEnvs.map((env) => (
+                    <th
+                      key={env.id}
+                      className="text-left font-medium text-muted-foreground px-3 py-2 min-w-[160px]"
+                    >
+                      {env.name}
+                    </th>
+                  ))}
+                </tr>
+              </thead>
+              <tbody>
+                {dnsGrid.map((row, rowIdx) => (
+                  <tr
+                    key={rowIdx}
+                    className="group border-b border-border/50 hover:bg-muted/20"
+                  >
+                    <td className="px-3 py-1 sticky left-0 bg-background z-10">
+                      <div className="flex items-center gap-1">
+                        <input
+                          value={row.host}
+                          onChange={(e) => updateDnsHost(rowIdx, e.target.value)}
+                          placeholder="api.example.com:443"
+                          className="bg-transparent border-0 border-b border-transparent focus:border-primary/50 font-mono text-xs px-0 py-0.5 outline-none placeholder:text-muted-foreground/40 w-full transition-colors"
+                        />
+                        <button
+                          onClick={() => removeDnsRow(rowIdx)}
+                          className="shrink-0 text-muted-foreground hover:text-destructive p-0.5 opacity-0 group-hover:opacity-100 transition-opacity"
+                        >
+                          <Trash2 className="size-3" />
+                        </button>
+                      </div>
+                    </td>
+                    {orderedEnvs.map((env) => (
+                      <td key={env.id} className="px-3 py-1">
+                        <input
+                          value={row.values.get(env.id) ?? ""}
+                          onChange={(e) => updateDnsCell(rowIdx, env.id, e.target.value)}
+                          placeholder="10.0.0.5"
+                          className="bg-transparent border-0 border-b border-transparent focus:border-primary/50 font-mono text-xs px-0 py-0.5 outline-none placeholder:text-muted-foreground/40 w-full transition-colors"
+                        />
+                      </td>
+                    ))}
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+
+          <div className="px-3 py-2">
+            <button
+              onClick={addDnsRow}
+              className="flex items-center gap-1 text-xs text-muted-foreground hover:text-foreground transition-colors"
+            >
+              <Plus className="size-3" />
+              Add Override
+            </button>
+          </div>
         </ScrollArea>
       </div>
 
@@ -0,0 +1,174 @@
+import { useState } from "react";
+import { toast } from "sonner";
+import { Eraser, Network, Send, SquareStop } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
+import { HeadersViewer } from "@/components/response/HeadersViewer";
+import { MessageLog } from "@/components/websocket/MessageLog";
+import { useGrpcStore, type GrpcCall } from "@/stores/grpcStore";
+import { cn } from "@/lib/utils";
+
+function statusLabel(call: GrpcCall): string {
+  if (call.result) return `${call.result.code} ${call.result.name}`;
+  if (call.status === "running") return "Running";
+  return "Failed";
+}
+
+/** Sends messages on a client or bidi streaming call until the stream is ended. */
+function Composer({ tabId, initialMessage }: { tabId: string; initialMessage: string }) {
+  const send = useGrpcStore((s) => s.send);
+  const closeSend = useGrpcStore((s) => s.closeSend);
+  const [data, setData] = useState(initialMessage);
+
+  const handleSend = async () => {
+    try {
+      await send(tabId, data);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  const handleEnd = async () => {
+    try {
+      await closeSend(tabId);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  return (
+    <div className="shrink-0 border-t border-border p-2 space-y-2">
+      <textarea
+        value={data}
+        onChange={(e) => setData(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && (e.metaKey || e.ctrlKey)) {
+            e.preventDefault();
+            e.stopPropagation();
+            handleSend();
+          }
+        }}
+        placeholder="{}"
+        rows={4}
+        className="w-full resize-none rounded-md border border-border bg-background px-3 py-2 font-mono text-xs outline-none focus:ring-2 focus:ring-ring/40"
+      />
+      <div className="flex items-center gap-2">
+        <Button variant="outline" size="xs" className="gap-1 text-xs" onClick={handleEnd}>
+          <SquareStop className="size-3" />
+          End Stream
+        </Button>
+        <Button size="xs" className="ml-auto gap-1 text-xs" onClick={handleSend}>
+          <Send className="size-3" />
+          Send
+        </Button>
+      </div>
+    </div>
+  );
+}
+
+/** Messages, response metadata, trailers and final status of a gRPC tab's call. */
+export function GrpcPanel({ tabId, message }: { tabId: string; message: string }) {
+  const call = useGrpcStore((s) => s.calls[tabId]);
+  const clearMessages = useGrpcStore((s) => s.clearMessages);
+
+  return (
+    <Tabs defaultValue="messages" className="flex h-full flex-col gap-0">
+      <div className="flex items-center gap-2 border-b border-border px-4 py-1.5 shrink-0">
+        {call && (
+          <span className="flex items-center gap-1.5 text-xs font-mono whitespace-nowrap">
+            <span
+              className={cn(
+                "size-2 rounded-full",
+                call.status === "running"
+                  ? "bg-method-put"
+                  : call.result?.code === 0
+                    ? "bg-status-2xx"
+                    : "bg-status-5xx",
+              )}
+            />
+            {statusLabel(call)}
+          </span>
+        )}
+        <TabsList variant="line" className="border-0 bg-transparent">
+          <TabsTrigger value="messages" className="text-xs gap-1">
+            Messages
+            {call && call.messages.length > 0 && (
+              <span className="text-[10px] font-semibold text-muted-foreground">
+                ({call.messages.length})
+              </span>
+            )}
+          </TabsTrigger>
+          <TabsTrigger value="headers" className="text-xs gap-1">
+            Headers
+            {call?.headers && call.headers.length > 0 && (
+              <span className="text-[10px] font-semibold text-muted-foreground">
+                ({call.headers.length})
+              </span>
+            )}
+          </TabsTrigger>
+          <TabsTrigger value="trailers" className="text-xs gap-1">
+            Trailers
+            {call && call.trailers.length > 0 && (
+              <span className="text-[10px] font-semibold text-muted-foreground">
+                ({call.trailers.length})
+              </span>
+            )}
+          </TabsTrigger>
+        </TabsList>
+        <div className="ml-auto flex items-center gap-2">
+          {call?.timeMs != null && (
+            <span className="text-xs text-muted-foreground whitespace-nowrap">
+              {call.timeMs}ms
+            </span>
+          )}
+          {call && call.messages.length > 0 && (
+            <Button
+              variant="ghost"
+              size="icon-xs"
+              onClick={() => clearMessages(tabId)}
+              aria-label="Clear messages"
+              title="Clear messages"
+            >
+              <Eraser className="size-3.5" />
+            </Button>
+          )}
+        </div>
+      </div>
+
+      <TabsContent value="messages" className="flex-1 min-h-0 flex flex-col">
+        {call?.error && (
+          <p className="shrink-0 border-b border-border/50 px-4 py-2 text-xs text-destructive">
+            {call.error}
+          </p>
+        )}
+        {call?.result && call.result.code !== 0 && call.result.message && (
+          <p className="shrink-0 border-b border-border/50 px-4 py-2 text-xs text-destructive">
+            {call.result.message}
+          </p>
+        )}
+        {call ? (
+          <div className="flex-1 min-h-0">
+            <MessageLog messages={call.messages} />
+          </div>
+        ) : (
+          <div className="flex flex-1 flex-col items-center justify-center gap-3 text-muted-foreground/50">
+            <Network className="h-8 w-8" />
+            <p className="text-sm">Invoke the method to see its responses</p>
+            <p className="text-xs">⌘ Enter</p>
+          </div>
+        )}
+        {call?.status === "running" && !call.halfClosed && (
+          <Composer tabId={tabId} initialMessage={message} />
+        )}
+      </TabsContent>
+
+      <TabsContent value="headers" className="flex-1 min-h-0">
+        <HeadersViewer headers={call?.headers ?? []} emptyText="No response metadata." />
+      </TabsContent>
+
+      <TabsContent value="trailers" className="flex-1 min-h-0">
+        <HeadersViewer headers={call?.trailers ?? []} emptyText="No trailers." />
+      </TabsContent>
+    </Tabs>
+  );
+}
@@ -105,6 +105,8 @@ function HistoryItem({
               {entry.error}
             </TooltipContent>
           </Tooltip>
+        ) : entry.cancelled ? (
+          <span className="text-[10px] text-muted-foreground">Cancelled</span>
         ) : entry.responseStatus ? (
           <span
             className={cn(
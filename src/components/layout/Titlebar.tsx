@@ -1,5 +1,5 @@
 import { useRef, useState, useEffect } from "react";
-import { X, Plus, MoreHorizontal, Minus, Copy, Settings, Globe } from "lucide-react";
+import { X, Plus, MoreHorizontal, Minus, Copy, Settings, Globe, Cable, Braces, Network, Code } from "lucide-react";
 import { cn } from "@/lib/utils";
 import { useTabStore, isRequestTab, type Tab } from "@/stores/tabStore";
 import type { HttpMethod } from "@/types/request";
@@ -27,7 +27,20 @@ function TabItem({ tab, isActive }: { tab: Tab; isActive: boolean }) {
     if (tab.type === "environments") return <Globe className="size-3" />;
     if (isRequestTab(tab)) {
       return (
-        <span className={cn("size-2 rounded-full shrink-0", methodDotColor[tab.state.method])} />
+        <span
+          className={cn(
+            "size-2 rounded-full shrink-0",
+            tab.state.kind === "websocket"
+              ? "bg-method-ws"
+              : tab.state.kind === "graphql"
+                ? "bg-method-graphql"
+                : tab.state.kind === "grpc"
+                  ? "bg-method-grpc"
+                  : tab.state.kind === "jsonrpc"
+                    ? "bg-method-jsonrpc"
+                    : methodDotColor[tab.state.method],
+          )}
+        />
       );
     }
     return null;
@@ -141,11 +154,39 @@ export function Titlebar() {
           ))}
           <button
             className="flex items-center justify-center px-2.5 text-muted-foreground hover:text-foreground hover:bg-muted/50 transition-colors shrink-0"
-            onClick={openNewTab}
+            onClick={() => openNewTab()}
             title="New Tab (Cmd+T)"
           >
             <Plus className="size-3.5" />
           </button>
+          <button
+            className="flex items-center justify-center px-2 text-muted-foreground hover:text-foreground hover:bg-muted/50 transition-colors shrink-0"
+            onClick={() => openNewTab("websocket")}
+            title="New WebSocket"
+          >
+            <Cable className="size-3.5" />
+          </button>
+          <button
+            className="flex items-center justify-center px-2 text-muted-foreground hover:text-foreground hover:bg-muted/50 transition-colors shrink-0"
+            onClick={() => openNewTab("graphql")}
+            title="New GraphQL"
+          >
+            <Braces className="size-3.5" />
+          </button>
+          <button
+            className="flex items-center justify-center px-2 text-muted-foreground hover:text-foreground hover:bg-muted/50 transition-colors shrink-0"
+            onClick={() => openNewTab("grpc")}
+            title="New gRPC"
+          >
+            <Network className="size-3.5" />
+          </button>
+          <button
+            className="flex items-center justify-center px-2 text-muted-foreground hover:text-foreground hover:bg-muted/50 transition-colors shrink-0"
+            onClick={() => openNewTab("jsonrpc")}
+            title="New JSON-RPC"
+          >
+            <Code className="size-3.5" />
+          </button>
           <div data-tauri-drag-region className="flex-1" />
         </div>
         <div
@@ -13,8 +13,9 @@ import {
 } from "@/components/ui/select";
 import { Button } from "@/components/ui/button";
 import { KeyValueEditor } from "./KeyValueEditor";
-import { Sparkles } from "lucide-react";
-import type { RequestBody, RawFormat } from "@/types/request";
+import { FileUp, Paperclip, Sparkles, X } from "lucide-react";
+import { open } from "@tauri-apps/plugin-dialog";
+import type { RequestBody, RawFormat, FormDataEntry } from "@/types/request";
 import {
   variableHighlight,
   variableAutocomplete,
@@ -161,7 +162,15 @@ export function BodyEditor({
         </div>
       )}
 
-      {(body.type === "form-data" || body.type === "x-www-form-urlencoded") && (
+      {body.type === "form-data" && (
+        <FormDataEditor
+          entries={body.entries}
+          onChange={(entries) => onChange({ ...body, entries })}
+          disabled={disabled}
+        />
+      )}
+
+      {body.type === "x-www-form-urlencoded" && (
         <KeyValueEditor
           entries={body.entries}
           onChange={(entries) => onChange({ ...body, entries })}
@@ -172,10 +181,113 @@ export function BodyEditor({
       )}
 
       {body.type === "binary" && (
-        <p className="text-xs text-muted-foreground">
-          Binary upload coming in a future update.
-        </p>
+        <div className="flex items-center gap-2">
+          <Button
+            variant="outline"
+            size="xs"
+            className="gap-1 text-xs"
+            disabled={disabled}
+            onClick={async () => {
+              const path = await open({ multiple: false, directory: false });
+              if (typeof path === "string") onChange({ ...body, filePath: path });
+            }}
+          >
+            <FileUp className="h-3.5 w-3.5" />
+            Choose File
+          </Button>
+          <span className="truncate text-xs font-mono text-muted-foreground">
+            {body.filePath || "No file selected"}
+          </span>
+        </div>
       )}
     </div>
   );
 }
+
+/** Text fields via the key-value editor, file parts listed beneath it. */
+function FormDataEditor({
+  entries,
+  onChange,
+  disabled,
+}: {
+  entries: FormDataEntry[];
+  onChange: (entries: FormDataEntry[]) => void;
+  disabled?: boolean;
+}) {
+  const textEntries = entries.filter((e) => !e.filePath);
+  const fileEntries = entries.filter((e) => e.filePath);
+
+  const addFile = async () => {
+    const path = await open({ multiple: false, directory: false });
+    if (typeof path !== "string") return;
+    const name = path.split(/[\\/]/).pop() ?? "file";
+    onChange([
+      ...entries,
+      { key: "file", value: name, enabled: true, filePath: path, fileName: name },
+    ]);
+  };
+
+  const updateFile = (index: number, patch: Partial<FormDataEntry>) => {
+    onChange([
+      ...textEntries,
+      ...fileEntries.map((e, i) => (i === index ? { ...e, ...patch } : e)),
+    ]);
+  };
+
+  return (
+    <div className="flex flex-col gap-2">
+      <KeyValueEditor
+        entries={textEntries}
+        onChange={(next) => onChange([...next, ...fileEntries])}
+        keyPlaceholder="Key"
+        valuePlaceholder="Value"
+        disabled={disabled}
+      />
+      {fileEntries.map((e, i) => (
+        <div key={i} className="flex items-center gap-2 text-xs">
+          <Paperclip className="h-3.5 w-3.5 shrink-0 text-muted-foreground" />
+          <input
+            className="w-32 rounded border bg-transparent px-2 py-1 font-mono"
+            value={e.key}
+            placeholder="Key"
+            disabled={disabled}
+            onChange={(ev) => updateFile(i, { key: ev.target.value })}
+          />
+          <span
+            className="flex-1 truncate font-mono text-muted-foreground"
+            title={e.filePath}
+          >
+            {e.filePath}
+          </span>
+          <input
+            className="w-40 rounded border bg-transparent px-2 py-1 font-mono"
+            value={e.contentType ?? ""}
+            placeholder="Auto content type"
+            disabled={disabled}
+            onChange={(ev) => updateFile(i, { contentType: ev.target.value })}
+          />
+          <Button
+            variant="ghost"
+            size="xs"
+            disabled={disabled}
+            onClick={() =>
+              onChange([...textEntries, ...fileEntries.filter((_, j) => j !== i)])
+            }
+          >
+            <X className="h-3.5 w-3.5" />
+          </Button>
+        </div>
+      ))}
+      <Button
+        variant="outline"
+        size="xs"
+        className="gap-1 self-start text-xs"
+        disabled={disabled}
+        onClick={addFile}
+      >
+        <FileUp className="h-3.5 w-3.5" />
+        Add File
+      </Button>
+    </div>
+  );
+}
@@ -0,0 +1,177 @@
+import { useEffect, useMemo } from "react";
+import CodeMirror from "@uiw/react-codemirror";
+import { json } from "@codemirror/lang-json";
+import { oneDark } from "@codemirror/theme-one-dark";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { AlertCircle, CheckCircle2, Loader2, RefreshCw } from "lucide-react";
+import { useGraphqlStore } from "@/stores/graphqlStore";
+import type { GraphqlRequest } from "@/types/graphql";
+import {
+  variableHighlight,
+  variableAutocomplete,
+  type VariableCompletionItem,
+} from "@/lib/codemirror/variable-extension";
+
+/** How long typing must pause before the query is checked again. */
+const VALIDATE_DELAY = 400;
+
+/** Query, variables and operation name of a GraphQL request, checked against the endpoint's cached schema. */
+export function GraphqlEditor({
+  tabId,
+  endpoint,
+  graphql,
+  onChange,
+  onFetchSchema,
+  variableItems,
+  isVariableResolved,
+  disabled,
+}: {
+  tabId: string;
+  /** The resolved URL the schema is cached under. */
+  endpoint: string;
+  graphql: GraphqlRequest;
+  onChange: (graphql: Partial<GraphqlRequest>) => void;
+  onFetchSchema?: () => void;
+  variableItems?: () => VariableCompletionItem[];
+  isVariableResolved?: (name: string) => boolean;
+  disabled?: boolean;
+}) {
+  const schema = useGraphqlStore((s) => s.schemas[endpoint]);
+  const fetching = useGraphqlStore((s) => s.fetching[endpoint] ?? false);
+  const fetchError = useGraphqlStore((s) => s.fetchErrors[endpoint]);
+  const validation = useGraphqlStore((s) => s.validations[tabId]);
+  const loadSchema = useGraphqlStore((s) => s.loadSchema);
+  const validate = useGraphqlStore((s) => s.validate);
+
+  useEffect(() => {
+    if (endpoint && schema === undefined) loadSchema(endpoint).catch(() => {});
+  }, [endpoint, schema, loadSchema]);
+
+  const { query, operationName } = graphql;
+  useEffect(() => {
+    if (!query.trim()) return;
+    const timer = setTimeout(() => {
+      // Variables play no part in validation
+      validate(tabId, endpoint, { query, operationName, variables: "" }).catch(() => {});
+    }, VALIDATE_DELAY);
+    return () => clearTimeout(timer);
+  }, [tabId, endpoint, query, operationName, schema, validate]);
+
+  const variablesExtensions = useMemo(() => {
+    const exts = [];
+    exts.push(json());
+    if (isVariableResolved) exts.push(variableHighlight(isVariableResolved));
+    if (variableItems) exts.push(variableAutocomplete(variableItems));
+    return exts;
+  }, [variableItems, isVariableResolved]);
+
+  const errors = query.trim() ? (validation?.errors ?? []) : [];
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2 text-xs">
+        {schema ? (
+          <span className="text-muted-foreground">
+            Schema: {schema.type_count} types, fetched {schema.fetched_at}
+          </span>
+        ) : (
+          <span className="text-muted-foreground">
+            No schema cached for this endpoint; only syntax is checked.
+          </span>
+        )}
+        {onFetchSchema && !disabled && (
+          <Button
+            variant="ghost"
+            size="xs"
+            onClick={onFetchSchema}
+            disabled={fetching || !endpoint}
+            className="gap-1 text-xs text-muted-foreground"
+          >
+            {fetching ? (
+              <Loader2 className="size-3 animate-spin" />
+            ) : (
+              <RefreshCw className="size-3" />
+            )}
+            {schema ? "Refresh Schema" : "Fetch Schema"}
+          </Button>
+        )}
+      </div>
+      {fetchError && <p className="text-xs text-destructive">{fetchError}</p>}
+
+      <div className="rounded-md border border-border overflow-hidden">
+        <CodeMirror
+          value={query}
+          onChange={(value) => onChange({ query: value })}
+          theme={oneDark}
+          readOnly={disabled}
+          editable={!disabled}
+          height="200px"
+          placeholder="query { ... }"
+          basicSetup={{
+            lineNumbers: true,
+            foldGutter: true,
+            bracketMatching: true,
+            closeBrackets: true,
+          }}
+        />
+      </div>
+
+      {query.trim() && validation && (
+        <div className="space-y-1 text-xs">
+          {errors.length === 0 ? (
+            <p className="flex items-center gap-1.5 text-muted-foreground">
+              <CheckCircle2 className="size-3 text-status-2xx" />
+              {validation.schema_cached ? "Valid against the cached schema" : "Syntax is valid"}
+            </p>
+          ) : (
+            errors.map((error, i) => (
+              <p key={i} className="flex items-start gap-1.5 text-destructive">
+                <AlertCircle className="size-3 mt-0.5 shrink-0" />
+                <span>
+                  <span className="font-mono text-muted-foreground">
+                    {error.line}:{error.column}
+                  </span>{" "}
+                  {error.message}
+                </span>
+              </p>
+            ))
+          )}
+        </div>
+      )}
+
+      <div className="space-y-1">
+        <label className="text-xs text-muted-foreground">Operation name</label>
+        <Input
+          value={operationName}
+          onChange={(e) => onChange({ operationName: e.target.value })}
+          placeholder="Needed when the query defines several operations"
+          readOnly={disabled}
+          className="h-8 font-mono text-xs"
+        />
+      </div>
+
+      <div className="space-y-1">
+        <label className="text-xs text-muted-foreground">Variables (JSON)</label>
+        <div className="rounded-md border border-border overflow-hidden">
+          <CodeMirror
+            value={graphql.variables}
+            onChange={(value) => onChange({ variables: value })}
+            extensions={variablesExtensions}
+            theme={oneDark}
+            readOnly={disabled}
+            editable={!disabled}
+            height="120px"
+            placeholder="{}"
+            basicSetup={{
+              lineNumbers: true,
+              foldGutter: false,
+              bracketMatching: true,
+              closeBrackets: true,
+            }}
+          />
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,284 @@
+import { useMemo } from "react";
+import CodeMirror from "@uiw/react-codemirror";
+import { json } from "@codemirror/lang-json";
+import { oneDark } from "@codemirror/theme-one-dark";
+import { open } from "@tauri-apps/plugin-dialog";
+import { Button } from "@/components/ui/button";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { FileUp, FolderPlus, Loader2, RefreshCw, Wand2, X } from "lucide-react";
+import { useGrpcStore } from "@/stores/grpcStore";
+import type { GrpcMethodInfo, GrpcRequest } from "@/types/grpc";
+import {
+  variableHighlight,
+  variableAutocomplete,
+  type VariableCompletionItem,
+} from "@/lib/codemirror/variable-extension";
+
+const SOURCES: { value: GrpcRequest["source"]; label: string }[] = [
+  { value: "reflection", label: "Server Reflection" },
+  { value: "proto_files", label: ".proto Files" },
+];
+
+/** How a method streams, as shown next to its name. */
+function grpcMethodKind(method: GrpcMethodInfo): string {
+  if (method.client_streaming && method.server_streaming) return "bidi streaming";
+  if (method.client_streaming) return "client streaming";
+  if (method.server_streaming) return "server streaming";
+  return "unary";
+}
+
+function PathList({
+  paths,
+  empty,
+  onChange,
+  disabled,
+}: {
+  paths: string[];
+  empty: string;
+  onChange: (paths: string[]) => void;
+  disabled?: boolean;
+}) {
+  if (paths.length === 0) {
+    return <p className="text-xs text-muted-foreground">{empty}</p>;
+  }
+  return (
+    <div className="space-y-1">
+      {paths.map((path) => (
+        <div key={path} className="group flex items-center gap-1.5">
+          <span className="flex-1 truncate text-xs font-mono text-muted-foreground" title={path}>
+            {path}
+          </span>
+          {!disabled && (
+            <button
+              onClick={() => onChange(paths.filter((p) => p !== path))}
+              className="text-muted-foreground/40 hover:text-destructive cursor-pointer"
+            >
+              <X className="size-3" />
+            </button>
+          )}
+        </div>
+      ))}
+    </div>
+  );
+}
+
+/** Where a gRPC request's definitions come from, the method it calls and the message it sends. */
+export function GrpcEditor({
+  sourceKey,
+  grpc,
+  onChange,
+  onLoadServices,
+  variableItems,
+  isVariableResolved,
+  disabled,
+}: {
+  /** What the source's services are kept under, see `grpcSourceKey`. */
+  sourceKey: string;
+  grpc: GrpcRequest;
+  onChange: (grpc: Partial<GrpcRequest>) => void;
+  onLoadServices?: (refresh: boolean) => void;
+  variableItems?: () => VariableCompletionItem[];
+  isVariableResolved?: (name: string) => boolean;
+  disabled?: boolean;
+}) {
+  const services = useGrpcStore((s) => s.services[sourceKey]);
+  const loading = useGrpcStore((s) => s.loading[sourceKey] ?? false);
+  const loadError = useGrpcStore((s) => s.loadErrors[sourceKey]);
+
+  const service = services?.find((s) => s.name === grpc.service);
+  const method = service?.methods.find((m) => m.name === grpc.method);
+
+  const extensions = useMemo(() => {
+    const exts = [];
+    exts.push(json());
+    if (isVariableResolved) exts.push(variableHighlight(isVariableResolved));
+    if (variableItems) exts.push(variableAutocomplete(variableItems));
+    return exts;
+  }, [variableItems, isVariableResolved]);
+
+  const addFiles = async () => {
+    const picked = await open({
+      multiple: true,
+      directory: false,
+      filters: [{ name: "Protocol Buffers", extensions: ["proto"] }],
+    });
+    if (!Array.isArray(picked)) return;
+    onChange({ protoFiles: [...new Set([...grpc.protoFiles, ...picked])] });
+  };
+
+  const addImportPath = async () => {
+    const picked = await open({ multiple: false, directory: true });
+    if (typeof picked !== "string" || grpc.importPaths.includes(picked)) return;
+    onChange({ importPaths: [...grpc.importPaths, picked] });
+  };
+
+  return (
+    <div className="space-y-3">
+      <div className="flex items-center gap-2 flex-wrap">
+        {SOURCES.map((s) => (
+          <button
+            key={s.value}
+            onClick={() => onChange({ source: s.value })}
+            disabled={disabled}
+            className={`px-2.5 py-1 rounded-md text-xs font-medium transition-colors ${disabled ? "" : "cursor-pointer"} ${
+              grpc.source === s.value
+                ? "bg-primary text-primary-foreground"
+                : "bg-muted text-muted-foreground hover:text-foreground"
+            }`}
+          >
+            {s.label}
+          </button>
+        ))}
+        {onLoadServices && !disabled && (
+          <Button
+            variant="ghost"
+            size="xs"
+            onClick={() => onLoadServices(services !== undefined)}
+            disabled={loading}
+            className="gap-1 text-xs text-muted-foreground"
+          >
+            {loading ? (
+              <Loader2 className="size-3 animate-spin" />
+            ) : (
+              <RefreshCw className="size-3" />
+            )}
+            {services ? "Reload Services" : "Load Services"}
+          </Button>
+        )}
+      </div>
+
+      {grpc.source === "proto_files" && (
+        <div className="grid grid-cols-2 gap-3">
+          <div className="space-y-1.5">
+            <div className="flex items-center justify-between">
+              <label className="text-xs text-muted-foreground">Files</label>
+              {!disabled && (
+                <Button variant="ghost" size="xs" className="gap-1 text-xs" onClick={addFiles}>
+                  <FileUp className="size-3" />
+                  Add
+                </Button>
+              )}
+            </div>
+            <PathList
+              paths={grpc.protoFiles}
+              empty="No .proto files selected"
+              onChange={(protoFiles) => onChange({ protoFiles })}
+              disabled={disabled}
+            />
+          </div>
+          <div className="space-y-1.5">
+            <div className="flex items-center justify-between">
+              <label className="text-xs text-muted-foreground">Import paths</label>
+              {!disabled && (
+                <Button variant="ghost" size="xs" className="gap-1 text-xs" onClick={addImportPath}>
+                  <FolderPlus className="size-3" />
+                  Add
+                </Button>
+              )}
+            </div>
+            <PathList
+              paths={grpc.importPaths}
+              empty="Imports resolve next to the files"
+              onChange={(importPaths) => onChange({ importPaths })}
+              disabled={disabled}
+            />
+          </div>
+        </div>
+      )}
+
+      {loadError && <p className="text-xs text-destructive">{loadError}</p>}
+
+      <div className="flex items-center gap-2">
+        <Select
+          value={grpc.service}
+          onValueChange={(name: string) => {
+            const first = services?.find((s) => s.name === name)?.methods[0];
+            onChange({ service: name, method: first?.name ?? "" });
+          }}
+          disabled={disabled || !services?.length}
+        >
+          <SelectTrigger size="sm" className="flex-1 min-w-0 font-mono text-xs">
+            <SelectValue placeholder={services ? "Service" : "Load services first"}>
+              {grpc.service}
+            </SelectValue>
+          </SelectTrigger>
+          <SelectContent>
+            {services?.map((s) => (
+              <SelectItem key={s.name} value={s.name} className="font-mono text-xs">
+                {s.name}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+        <Select
+          value={grpc.method}
+          onValueChange={(name: string) => onChange({ method: name })}
+          disabled={disabled || !service}
+        >
+          <SelectTrigger size="sm" className="flex-1 min-w-0 font-mono text-xs">
+            <SelectValue placeholder="Method">{grpc.method}</SelectValue>
+          </SelectTrigger>
+          <SelectContent>
+            {service?.methods.map((m) => (
+              <SelectItem key={m.name} value={m.name} className="font-mono text-xs">
+                {m.name}
+                <span className="text-muted-foreground">{grpcMethodKind(m)}</span>
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </div>
+      {method && (
+        <p className="text-xs text-muted-foreground font-mono truncate">
+          {method.input_type} → {method.output_type}
+          <span className="font-sans"> · {grpcMethodKind(method)}</span>
+        </p>
+      )}
+
+      <div className="space-y-1">
+        <div className="flex items-center justify-between">
+          <label className="text-xs text-muted-foreground">
+            {method?.client_streaming
+              ? "Message (sent from the response panel once the call starts)"
+              : "Message (JSON)"}
+          </label>
+          {method && !disabled && (
+            <Button
+              variant="ghost"
+              size="xs"
+              onClick={() => onChange({ message: method.template })}
+              className="gap-1 text-xs text-muted-foreground"
+            >
+              <Wand2 className="size-3" />
+              Template
+            </Button>
+          )}
+        </div>
+        <div className="rounded-md border border-border overflow-hidden">
+          <CodeMirror
+            value={grpc.message}
+            onChange={(value) => onChange({ message: value })}
+            extensions={extensions}
+            theme={oneDark}
+            readOnly={disabled}
+            editable={!disabled}
+            height="200px"
+            placeholder="{}"
+            basicSetup={{
+              lineNumbers: true,
+              foldGutter: true,
+              bracketMatching: true,
+              closeBrackets: true,
+            }}
+          />
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,42 @@
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import type { HeaderMode } from "@/types/request";
+
+/** How a header row combines with inherited and earlier values. */
+export function HeaderModeSelect({
+  mode,
+  onChange,
+  disabled,
+}: {
+  mode: HeaderMode | undefined;
+  onChange: (mode: HeaderMode | undefined) => void;
+  disabled?: boolean;
+}) {
+  return (
+    <Select
+      value={mode ?? "auto"}
+      onValueChange={(v) =>
+        onChange(v === "auto" ? undefined : (v as HeaderMode))
+      }
+      disabled={disabled}
+    >
+      <SelectTrigger
+        size="sm"
+        className="w-24 px-2 text-xs data-[size=sm]:h-6"
+        title="Auto replaces inherited values, then appends repeats"
+      >
+        <SelectValue />
+      </SelectTrigger>
+      <SelectContent>
+        <SelectItem value="auto">Auto</SelectItem>
+        <SelectItem value="replace">Replace</SelectItem>
+        <SelectItem value="append">Append</SelectItem>
+      </SelectContent>
+    </Select>
+  );
+}
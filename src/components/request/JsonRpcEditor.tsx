@@ -0,0 +1,132 @@
+import { useMemo } from "react";
+import CodeMirror from "@uiw/react-codemirror";
+import { json } from "@codemirror/lang-json";
+import { oneDark } from "@codemirror/theme-one-dark";
+import { Button } from "@/components/ui/button";
+import { Checkbox } from "@/components/ui/checkbox";
+import { Plus, X } from "lucide-react";
+import { EMPTY_JSONRPC_CALL } from "@/lib/jsonrpc";
+import type { JsonRpcCall } from "@/types/jsonrpc";
+import {
+  variableHighlight,
+  variableAutocomplete,
+  type VariableCompletionItem,
+} from "@/lib/codemirror/variable-extension";
+
+/** The calls of a JSON-RPC request; IDs are assigned by position when they are sent. */
+export function JsonRpcEditor({
+  calls,
+  onChange,
+  variableItems,
+  isVariableResolved,
+  disabled,
+}: {
+  calls: JsonRpcCall[];
+  onChange: (calls: JsonRpcCall[]) => void;
+  variableItems?: () => VariableCompletionItem[];
+  isVariableResolved?: (name: string) => boolean;
+  disabled?: boolean;
+}) {
+  const extensions = useMemo(() => {
+    const exts = [];
+    exts.push(json());
+    if (isVariableResolved) exts.push(variableHighlight(isVariableResolved));
+    if (variableItems) exts.push(variableAutocomplete(variableItems));
+    return exts;
+  }, [variableItems, isVariableResolved]);
+
+  const update = (index: number, patch: Partial<JsonRpcCall>) =>
+    onChange(calls.map((c, i) => (i === index ? { ...c, ...patch } : c)));
+
+  // The ID each call goes out with, matching `jsonrpcPayload`
+  let position = 0;
+  const ids = calls.map((c) => {
+    if (!c.enabled) return null;
+    position += 1;
+    return c.notification ? null : position;
+  });
+  const enabledCount = position;
+
+  return (
+    <div className="space-y-3">
+      {calls.map((call, i) => (
+        <div key={i} className="group space-y-1.5">
+          <div className="flex items-center gap-2">
+            <Checkbox
+              checked={call.enabled}
+              onCheckedChange={(checked) => update(i, { enabled: !!checked })}
+              className="size-3.5"
+              disabled={disabled}
+            />
+            <input
+              value={call.method}
+              onChange={(e) => update(i, { method: e.target.value })}
+              placeholder="Method, e.g. eth_blockNumber"
+              readOnly={disabled}
+              className="flex-1 bg-transparent border-0 border-b border-transparent focus:border-border/50 font-mono text-xs px-1 py-0.5 outline-none placeholder:text-muted-foreground/40 transition-colors"
+            />
+            <span className="w-10 text-right text-[10px] font-mono text-muted-foreground">
+              {ids[i] !== null ? `id ${ids[i]}` : ""}
+            </span>
+            <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+              <Checkbox
+                checked={call.notification}
+                onCheckedChange={(checked) => update(i, { notification: !!checked })}
+                className="size-3.5"
+                disabled={disabled}
+              />
+              Notification
+            </label>
+            {!disabled && (
+              <Button
+                variant="ghost"
+                size="icon-xs"
+                onClick={() => onChange(calls.filter((_, j) => j !== i))}
+                className="opacity-0 group-hover:opacity-100 transition-opacity text-muted-foreground hover:text-destructive"
+              >
+                <X className="size-3" />
+              </Button>
+            )}
+          </div>
+          <div
+            className={`rounded-md border border-border overflow-hidden ${call.enabled ? "" : "opacity-50"}`}
+          >
+            <CodeMirror
+              value={call.params}
+              onChange={(value) => update(i, { params: value })}
+              extensions={extensions}
+              theme={oneDark}
+              readOnly={disabled}
+              editable={!disabled}
+              minHeight="60px"
+              maxHeight="240px"
+              placeholder="Params: [ ] or { }, blank for none"
+              basicSetup={{
+                lineNumbers: true,
+                foldGutter: true,
+                bracketMatching: true,
+                closeBrackets: true,
+              }}
+            />
+          </div>
+        </div>
+      ))}
+
+      {!disabled && (
+        <button
+          onClick={() => onChange([...calls, EMPTY_JSONRPC_CALL])}
+          className="flex items-center gap-1 text-xs text-muted-foreground hover:text-foreground transition-colors cursor-pointer"
+        >
+          <Plus className="size-3" />
+          <span>Add Call</span>
+        </button>
+      )}
+
+      <p className="text-xs text-muted-foreground/70">
+        {enabledCount > 1
+          ? `Sent as a batch of ${enabledCount} calls; responses are matched back by ID.`
+          : "Notifications are sent without an ID and get no response."}
+      </p>
+    </div>
+  );
+}
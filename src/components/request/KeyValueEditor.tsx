@@ -1,4 +1,4 @@
-import { useCallback } from "react";
+import { useCallback, type ReactNode } from "react";
 import { Checkbox } from "@/components/ui/checkbox";
 import { Button } from "@/components/ui/button";
 import { Plus, X } from "lucide-react";
@@ -9,40 +9,42 @@ export interface KVEntry {
   enabled: boolean;
 }
 
-interface KeyValueEditorProps {
-  entries: KVEntry[];
-  onChange: (entries: KVEntry[]) => void;
+interface KeyValueEditorProps<T extends KVEntry> {
+  entries: T[];
+  onChange: (entries: T[]) => void;
   keyPlaceholder?: string;
   valuePlaceholder?: string;
   disabled?: boolean;
+  /** An extra control per row, in a column headed `rowControlLabel`. */
+  rowControl?: (entry: T, patch: (fields: Partial<T>) => void) => ReactNode;
+  rowControlLabel?: string;
 }
 
-export function KeyValueEditor({
+export function KeyValueEditor<T extends KVEntry>({
   entries,
   onChange,
   keyPlaceholder = "Key",
   valuePlaceholder = "Value",
   disabled,
-}: KeyValueEditorProps) {
-  const update = useCallback(
-    (index: number, field: keyof KVEntry, value: string | boolean) => {
-      const next = entries.map((e, i) =>
-        i === index ? { ...e, [field]: value } : e,
-      );
-      onChange(next);
+  rowControl,
+  rowControlLabel,
+}: KeyValueEditorProps<T>) {
+  const remove = useCallback(
+    (index: number) => {
+      onChange(entries.filter((_, i) => i !== index));
     },
     [entries, onChange],
   );
 
-  const remove = useCallback(
-    (index: number) => {
-      onChange(entries.filter((_, i) => i !== index));
+  const patch = useCallback(
+    (index: number, fields: Partial<T>) => {
+      onChange(entries.map((e, i) => (i === index ? { ...e, ...fields } : e)));
     },
     [entries, onChange],
   );
 
   const add = useCallback(() => {
-    onChange([...entries, { key: "", value: "", enabled: true }]);
+    onChange([...entries, { key: "", value: "", enabled: true } as T]);
   }, [entries, onChange]);
 
   const handleKeyInput = useCallback(
@@ -52,7 +54,7 @@ export function KeyValueEditor({
       );
       // Auto-add empty row when typing in the last row's key
       if (index === entries.length - 1 && value) {
-        next.push({ key: "", value: "", enabled: true });
+        next.push({ key: "", value: "", enabled: true } as T);
       }
       onChange(next);
     },
@@ -66,6 +68,7 @@ export function KeyValueEditor({
         <span className="w-5" />
         <span className="flex-1">{keyPlaceholder}</span>
         <span className="flex-1">{valuePlaceholder}</span>
+        {rowControl && <span className="w-24">{rowControlLabel}</span>}
         <span className="w-7" />
       </div>
 
@@ -77,7 +80,9 @@ export function KeyValueEditor({
         >
           <Checkbox
             checked={entry.enabled}
-            onCheckedChange={(checked) => update(i, "enabled", !!checked)}
+            onCheckedChange={(checked) =>
+              patch(i, { enabled: !!checked } as Partial<T>)
+            }
             className="size-3.5"
             disabled={disabled}
           />
@@ -90,11 +95,18 @@ export function KeyValueEditor({
           />
           <input
             value={entry.value}
-            onChange={(e) => update(i, "value", e.target.value)}
+            onChange={(e) =>
+              patch(i, { value: e.target.value } as Partial<T>)
+            }
             placeholder={valuePlaceholder}
             readOnly={disabled}
             className="flex-1 bg-transparent border-0 border-b border-transparent focus:border-border/50 font-mono text-xs px-1 py-0.5 outline-none placeholder:text-muted-foreground/40 transition-colors"
           />
+          {rowControl && (
+            <div className="w-24">
+              {rowControl(entry, (fields) => patch(i, fields))}
+            </div>
+          )}
           {!disabled && (
             <Button
               variant="ghost"
@@ -1,5 +1,6 @@
 import { cn } from "@/lib/utils";
 import type { HttpMethod } from "@/types/request";
+import type { RequestKind } from "@/types/collection";
 
 export type { HttpMethod };
 
@@ -13,6 +14,15 @@ const methodColorMap: Record<HttpMethod, string> = {
   OPTIONS: "bg-method-options",
 };
 
+/** Badges for saved requests that are not plain HTTP. */
+const kindBadgeMap: Partial<Record<RequestKind, { label: string; color: string }>> = {
+  sse: { label: "SSE", color: "bg-method-sse" },
+  websocket: { label: "WS", color: "bg-method-ws" },
+  graphql: { label: "GQL", color: "bg-method-graphql" },
+  grpc: { label: "gRPC", color: "bg-method-grpc" },
+  jsonrpc: { label: "RPC", color: "bg-method-jsonrpc" },
+};
+
 export function MethodBadge({
   method,
   className,
@@ -32,3 +42,26 @@ export function MethodBadge({
     </span>
   );
 }
+
+/** Stands in for the method badge on non-HTTP requests; renders nothing for HTTP. */
+export function KindBadge({
+  kind,
+  className,
+}: {
+  kind: RequestKind;
+  className?: string;
+}) {
+  const badge = kindBadgeMap[kind];
+  if (!badge) return null;
+  return (
+    <span
+      className={cn(
+        "inline-flex items-center rounded-md px-2.5 py-0.5 text-[11px] font-bold text-white tracking-wide",
+        badge.color,
+        className,
+      )}
+    >
+      {badge.label}
+    </span>
+  );
+}
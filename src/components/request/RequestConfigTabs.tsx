@@ -1,13 +1,24 @@
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
 import { KeyValueEditor } from "./KeyValueEditor";
+import { HeaderModeSelect } from "./HeaderModeSelect";
 import { AuthEditor } from "./AuthEditor";
 import { BodyEditor } from "./BodyEditor";
+import { SubprotocolsEditor } from "./SubprotocolsEditor";
+import { GraphqlEditor } from "./GraphqlEditor";
+import { GrpcEditor } from "./GrpcEditor";
+import { JsonRpcEditor } from "./JsonRpcEditor";
 import type {
   HeaderEntry,
   ParamEntry,
   RequestAuth,
   RequestBody,
 } from "@/types/request";
+import type { RequestKind } from "@/types/collection";
+import type { GraphqlRequest } from "@/types/graphql";
+import { EMPTY_GRAPHQL } from "@/lib/graphql";
+import type { GrpcRequest } from "@/types/grpc";
+import { EMPTY_GRPC } from "@/lib/grpc";
+import type { JsonRpcCall } from "@/types/jsonrpc";
 import type { VariableCompletionItem } from "@/lib/codemirror/variable-extension";
 
 interface InheritedAuth {
@@ -16,6 +27,8 @@ interface InheritedAuth {
 }
 
 interface RequestConfigTabsProps {
+  kind?: RequestKind;
+  tabId?: string;
   params: ParamEntry[];
   headers: HeaderEntry[];
   auth: RequestAuth;
@@ -25,6 +38,20 @@ interface RequestConfigTabsProps {
   onAuthChange: (auth: RequestAuth) => void;
   onBodyConfigChange: (body: RequestBody) => void;
   onBodyTypeChange: (type: RequestBody["type"]) => void;
+  subprotocols?: string[];
+  onSubprotocolsChange?: (subprotocols: string[]) => void;
+  graphql?: GraphqlRequest;
+  /** Resolved URL the GraphQL schema is cached under. */
+  graphqlEndpoint?: string;
+  onGraphqlChange?: (graphql: Partial<GraphqlRequest>) => void;
+  onFetchSchema?: () => void;
+  grpc?: GrpcRequest;
+  /** What the gRPC services are kept under, with the URL resolved. */
+  grpcSourceKey?: string;
+  onGrpcChange?: (grpc: Partial<GrpcRequest>) => void;
+  onLoadGrpcServices?: (refresh: boolean) => void;
+  jsonrpc?: JsonRpcCall[];
+  onJsonRpcChange?: (jsonrpc: JsonRpcCall[]) => void;
   inheritedAuth?: InheritedAuth | null;
   variableItems?: () => VariableCompletionItem[];
   isVariableResolved?: (name: string) => boolean;
@@ -36,6 +63,8 @@ function countActive(entries: { key: string; enabled: boolean }[]): number {
 }
 
 export function RequestConfigTabs({
+  kind = "http",
+  tabId = "",
   params,
   headers,
   auth,
@@ -45,6 +74,18 @@ export function RequestConfigTabs({
   onAuthChange,
   onBodyConfigChange,
   onBodyTypeChange,
+  subprotocols = [],
+  onSubprotocolsChange,
+  graphql = EMPTY_GRAPHQL,
+  graphqlEndpoint = "",
+  onGraphqlChange,
+  onFetchSchema,
+  grpc = EMPTY_GRPC,
+  grpcSourceKey = "",
+  onGrpcChange,
+  onLoadGrpcServices,
+  jsonrpc = [],
+  onJsonRpcChange,
   inheritedAuth,
   variableItems,
   isVariableResolved,
@@ -54,23 +95,39 @@ export function RequestConfigTabs({
   const headerCount = countActive(headers);
   const hasBody = bodyConfig.type !== "none";
   const hasAuth = auth.type !== "none";
+  const protocolCount = subprotocols.filter((p) => p.trim()).length;
+  const callCount = jsonrpc.filter((c) => c.enabled).length;
 
+  // gRPC has no params or body, so its tabs start over rather than keep another kind's selection
   return (
-    <Tabs defaultValue="params" className="h-full gap-0">
+    <Tabs
+      key={kind === "grpc" ? "grpc" : "request"}
+      defaultValue={kind === "grpc" ? "message" : "params"}
+      className="h-full gap-0"
+    >
       <TabsList
         variant="line"
         className="shrink-0 border-b border-border/50 px-2 w-full justify-start"
       >
-        <TabsTrigger value="params" className="text-xs gap-1">
-          Params
-          {paramCount > 0 && (
-            <span className="text-[10px] font-semibold bg-primary/15 text-primary rounded-full px-1.5 min-w-[18px] text-center">
-              {paramCount}
-            </span>
-          )}
-        </TabsTrigger>
+        {kind === "grpc" ? (
+          <TabsTrigger value="message" className="text-xs gap-1">
+            Message
+            {grpc.method && (
+              <span className="size-1.5 rounded-full bg-primary" />
+            )}
+          </TabsTrigger>
+        ) : (
+          <TabsTrigger value="params" className="text-xs gap-1">
+            Params
+            {paramCount > 0 && (
+              <span className="text-[10px] font-semibold bg-primary/15 text-primary rounded-full px-1.5 min-w-[18px] text-center">
+                {paramCount}
+              </span>
+            )}
+          </TabsTrigger>
+        )}
         <TabsTrigger value="headers" className="text-xs gap-1">
-          Headers
+          {kind === "grpc" ? "Metadata" : "Headers"}
           {headerCount > 0 && (
             <span className="text-[10px] font-semibold bg-primary/15 text-primary rounded-full px-1.5 min-w-[18px] text-center">
               {headerCount}
@@ -83,12 +140,39 @@ export function RequestConfigTabs({
             <span className="size-1.5 rounded-full bg-primary" />
           )}
         </TabsTrigger>
-        <TabsTrigger value="body" className="text-xs gap-1">
-          Body
-          {hasBody && (
-            <span className="size-1.5 rounded-full bg-primary" />
-          )}
-        </TabsTrigger>
+        {kind === "websocket" ? (
+          <TabsTrigger value="protocols" className="text-xs gap-1">
+            Protocols
+            {protocolCount > 0 && (
+              <span className="text-[10px] font-semibold bg-primary/15 text-primary rounded-full px-1.5 min-w-[18px] text-center">
+                {protocolCount}
+              </span>
+            )}
+          </TabsTrigger>
+        ) : kind === "graphql" ? (
+          <TabsTrigger value="query" className="text-xs gap-1">
+            Query
+            {graphql.query.trim() && (
+              <span className="size-1.5 rounded-full bg-primary" />
+            )}
+          </TabsTrigger>
+        ) : kind === "jsonrpc" ? (
+          <TabsTrigger value="calls" className="text-xs gap-1">
+            Calls
+            {callCount > 0 && (
+              <span className="text-[10px] font-semibold bg-primary/15 text-primary rounded-full px-1.5 min-w-[18px] text-center">
+                {callCount}
+              </span>
+            )}
+          </TabsTrigger>
+        ) : kind === "grpc" ? null : (
+          <TabsTrigger value="body" className="text-xs gap-1">
+            Body
+            {hasBody && (
+              <span className="size-1.5 rounded-full bg-primary" />
+            )}
+          </TabsTrigger>
+        )}
       </TabsList>
 
       <TabsContent value="params" className="overflow-y-auto">
@@ -105,9 +189,21 @@ export function RequestConfigTabs({
         <KeyValueEditor
           entries={headers}
           onChange={onHeadersChange}
-          keyPlaceholder="Header"
+          keyPlaceholder={kind === "grpc" ? "Key" : "Header"}
           valuePlaceholder="Value"
           disabled={disabled}
+          rowControl={
+            kind === "grpc"
+              ? undefined
+              : (entry, patch) => (
+                  <HeaderModeSelect
+                    mode={entry.mode}
+                    onChange={(mode) => patch({ mode })}
+                    disabled={disabled}
+                  />
+                )
+          }
+          rowControlLabel="Mode"
         />
       </TabsContent>
 
@@ -115,6 +211,49 @@ export function RequestConfigTabs({
         <AuthEditor auth={auth} onChange={onAuthChange} inheritedAuth={inheritedAuth} disabled={disabled} />
       </TabsContent>
 
+      <TabsContent value="protocols" className="overflow-y-auto">
+        <SubprotocolsEditor
+          subprotocols={subprotocols}
+          onChange={(next) => onSubprotocolsChange?.(next)}
+          disabled={disabled}
+        />
+      </TabsContent>
+
+      <TabsContent value="query" className="overflow-y-auto p-4">
+        <GraphqlEditor
+          tabId={tabId}
+          endpoint={graphqlEndpoint}
+          graphql={graphql}
+          onChange={(next) => onGraphqlChange?.(next)}
+          onFetchSchema={onFetchSchema}
+          variableItems={variableItems}
+          isVariableResolved={isVariableResolved}
+          disabled={disabled}
+        />
+      </TabsContent>
+
+      <TabsContent value="message" className="overflow-y-auto p-4">
+        <GrpcEditor
+          sourceKey={grpcSourceKey}
+          grpc={grpc}
+          onChange={(next) => onGrpcChange?.(next)}
+          onLoadServices={onLoadGrpcServices}
+          variableItems={variableItems}
+          isVariableResolved={isVariableResolved}
+          disabled={disabled}
+        />
+      </TabsContent>
+
+      <TabsContent value="calls" className="overflow-y-auto p-4">
+        <JsonRpcEditor
+          calls={jsonrpc}
+          onChange={(next) => onJsonRpcChange?.(next)}
+          variableItems={variableItems}
+          isVariableResolved={isVariableResolved}
+          disabled={disabled}
+        />
+      </TabsContent>
+
       <TabsContent value="body" className="overflow-y-auto p-4">
         <BodyEditor
           body={bodyConfig}
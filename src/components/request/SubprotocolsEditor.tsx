@@ -0,0 +1,63 @@
+import { Button } from "@/components/ui/button";
+import { Plus, X } from "lucide-react";
+
+/** WebSocket subprotocols to offer, most preferred first. */
+export function SubprotocolsEditor({
+  subprotocols,
+  onChange,
+  disabled,
+}: {
+  subprotocols: string[];
+  onChange: (subprotocols: string[]) => void;
+  disabled?: boolean;
+}) {
+  return (
+    <div className="text-xs">
+      <div className="flex items-center gap-2 px-2 py-1.5 text-muted-foreground font-medium border-b border-border/50">
+        <span className="flex-1">Subprotocol</span>
+        <span className="w-7" />
+      </div>
+
+      {subprotocols.map((protocol, i) => (
+        <div
+          key={i}
+          className="group flex items-center gap-2 px-2 py-1 hover:bg-muted/30 transition-colors"
+        >
+          <input
+            value={protocol}
+            onChange={(e) =>
+              onChange(subprotocols.map((p, j) => (j === i ? e.target.value : p)))
+            }
+            placeholder="e.g. graphql-transport-ws"
+            readOnly={disabled}
+            className="flex-1 bg-transparent border-0 border-b border-transparent focus:border-border/50 font-mono text-xs px-1 py-0.5 outline-none placeholder:text-muted-foreground/40 transition-colors"
+          />
+          {!disabled && (
+            <Button
+              variant="ghost"
+              size="icon-xs"
+              onClick={() => onChange(subprotocols.filter((_, j) => j !== i))}
+              className="opacity-0 group-hover:opacity-100 transition-opacity text-muted-foreground hover:text-destructive"
+            >
+              <X className="size-3" />
+            </Button>
+          )}
+        </div>
+      ))}
+
+      {!disabled && (
+        <button
+          onClick={() => onChange([...subprotocols, ""])}
+          className="flex items-center gap-1 px-2 py-1.5 text-muted-foreground hover:text-foreground transition-colors cursor-pointer"
+        >
+          <Plus className="size-3" />
+          <span>Add</span>
+        </button>
+      )}
+
+      <p className="px-2 py-2 text-muted-foreground/70">
+        Offered in Sec-WebSocket-Protocol in this order; the server picks at most one.
+      </p>
+    </div>
+  );
+}
@@ -3,14 +3,16 @@ import CodeMirror, { type ReactCodeMirrorRef } from "@uiw/react-codemirror";
 import { EditorView, keymap } from "@codemirror/view";
 import {
   DropdownMenu,
+  DropdownMenuCheckboxItem,
   DropdownMenuContent,
   DropdownMenuItem,
+  DropdownMenuSeparator,
   DropdownMenuTrigger,
 } from "@/components/ui/dropdown-menu";
 import { Button } from "@/components/ui/button";
 import { Tooltip, TooltipContent, TooltipProvider, TooltipTrigger } from "@/components/ui/tooltip";
-import { MethodBadge, type HttpMethod } from "./MethodBadge";
-import { ChevronDown, Send, Loader2 } from "lucide-react";
+import { KindBadge, MethodBadge, type HttpMethod } from "./MethodBadge";
+import { Cable, ChevronDown, Download, Send, Loader2, Unplug, X } from "lucide-react";
 import {
   variableHighlight,
   variableAutocomplete,
@@ -19,6 +21,7 @@ import {
 } from "@/lib/codemirror/variable-extension";
 import { parseCurl, type CurlImport } from "@/lib/import/curl";
 import { cn } from "@/lib/utils";
+import type { RequestKind } from "@/types/collection";
 
 const METHODS: HttpMethod[] = [
   "GET",
@@ -114,6 +117,8 @@ function isCompleteCurlCommand(input: string): boolean {
 }
 
 export function UrlBar({
+  kind = "http",
+  connected = false,
   method,
   url,
   loading,
@@ -121,11 +126,17 @@ export function UrlBar({
   onMethodChange,
   onUrlChange,
   onSend,
+  onSendAndSave,
+  onCancel,
+  onKindChange,
   variableItems,
   isVariableResolved,
   onCurlDetected,
   onCurlError,
 }: {
+  kind?: RequestKind;
+  /** WebSocket only: a connection is open, so the button disconnects. */
+  connected?: boolean;
   method: HttpMethod;
   url: string;
   loading: boolean;
@@ -133,6 +144,10 @@ export function UrlBar({
   onMethodChange: (method: HttpMethod) => void;
   onUrlChange: (url: string) => void;
   onSend: () => void;
+  onSendAndSave?: () => void;
+  onCancel?: () => void;
+  /** Switches between a plain request and an event stream. */
+  onKindChange?: (kind: RequestKind) => void;
   variableItems?: () => VariableCompletionItem[];
   isVariableResolved?: (name: string) => boolean;
   onCurlDetected?: (parsed: CurlImport) => void;
@@ -156,6 +171,7 @@ export function UrlBar({
       onUrlChange(value);
 
       if (debounceRef.current) clearTimeout(debounceRef.current);
+      if (kind !== "http") return;
 
       debounceRef.current = setTimeout(() => {
         const trimmed = value.trim();
@@ -176,7 +192,7 @@ export function UrlBar({
         }
       }, 300);
     },
-    [onUrlChange, onCurlDetected, onCurlError],
+    [kind, onUrlChange, onCurlDetected, onCurlError],
   );
 
   const extensions = useMemo(() => {
@@ -217,30 +233,37 @@ export function UrlBar({
 
   return (
     <div className="flex items-center gap-3">
-      <DropdownMenu>
-        <DropdownMenuTrigger asChild disabled={disabled}>
-          <Button
-            variant="ghost"
-            className="flex items-center gap-1.5 px-3 h-10 bg-background hover:bg-background/80 border border-border rounded-lg"
-            disabled={disabled}
-          >
-            <MethodBadge method={method} />
-            {!disabled && <ChevronDown className="h-3 w-3 text-muted-foreground" />}
-          </Button>
-        </DropdownMenuTrigger>
-        <DropdownMenuContent align="start" className="min-w-[140px]">
-          {METHODS.map((m) => (
-            <DropdownMenuItem
-              key={m}
-              onClick={() => onMethodChange(m)}
-              className="gap-2"
+      {kind === "websocket" || kind === "grpc" ? (
+        <div className="flex items-center px-3 h-10 bg-background border border-border rounded-lg">
+          <KindBadge kind={kind} />
+        </div>
+      ) : (
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild disabled={disabled}>
+            <Button
+              variant="ghost"
+              className="flex items-center gap-1.5 px-3 h-10 bg-background hover:bg-background/80 border border-border rounded-lg"
+              disabled={disabled}
             >
-              <MethodBadge method={m} />
-              <span className="text-muted-foreground text-xs">{m}</span>
-            </DropdownMenuItem>
-          ))}
-        </DropdownMenuContent>
-      </DropdownMenu>
+              <MethodBadge method={method} />
+              <KindBadge kind={kind} />
+              {!disabled && <ChevronDown className="h-3 w-3 text-muted-foreground" />}
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="start" className="min-w-[140px]">
+            {METHODS.map((m) => (
+              <DropdownMenuItem
+                key={m}
+                onClick={() => onMethodChange(m)}
+                className="gap-2"
+              >
+                <MethodBadge method={m} />
+                <span className="text-muted-foreground text-xs">{m}</span>
+              </DropdownMenuItem>
+            ))}
+          </DropdownMenuContent>
+        </DropdownMenu>
+      )}
 
       <div
         data-testid="url-input"
@@ -257,7 +280,17 @@ export function UrlBar({
           theme="none"
           readOnly={disabled}
           editable={!disabled}
-          placeholder="Enter request URL or paste a cURL command"
+          placeholder={
+            kind === "websocket"
+              ? "Enter a ws:// or wss:// URL"
+              : kind === "graphql"
+                ? "Enter a GraphQL endpoint URL"
+                : kind === "grpc"
+                  ? "Enter a grpc:// or grpcs:// server address"
+                  : kind === "jsonrpc"
+                    ? "Enter a JSON-RPC endpoint URL"
+                    : "Enter request URL or paste a cURL command"
+          }
           basicSetup={{
             lineNumbers: false,
             foldGutter: false,
@@ -284,19 +317,48 @@ export function UrlBar({
         <TooltipProvider delayDuration={300}>
           <Tooltip>
             <TooltipTrigger asChild>
-              <Button
-                onClick={onSend}
-                disabled={loading}
-                size="lg"
-                className="h-10 px-5 rounded-lg gap-2 font-semibold text-sm"
-              >
-                {loading ? (
-                  <Loader2 className="h-4 w-4 animate-spin" />
-                ) : (
-                  <Send className="h-4 w-4" />
-                )}
-                {loading ? "Sending…" : "Send"}
-              </Button>
+              {kind === "websocket" ? (
+                <Button
+                  onClick={connected ? onCancel : onSend}
+                  disabled={loading}
+                  variant={connected ? "outline" : "default"}
+                  size="lg"
+                  className="h-10 px-5 rounded-lg gap-2 font-semibold text-sm"
+                >
+                  {loading ? (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  ) : connected ? (
+                    <Unplug className="h-4 w-4" />
+                  ) : (
+                    <Cable className="h-4 w-4" />
+                  )}
+                  {loading ? "Connecting…" : connected ? "Disconnect" : "Connect"}
+                </Button>
+              ) : loading && onCancel ? (
+                <Button
+                  onClick={onCancel}
+                  variant="outline"
+                  size="lg"
+                  className="h-10 px-5 rounded-lg gap-2 font-semibold text-sm"
+                >
+                  <X className="h-4 w-4" />
+                  {kind === "sse" ? "Stop" : "Cancel"}
+                </Button>
+              ) : (
+                <Button
+                  onClick={onSend}
+                  disabled={loading}
+                  size="lg"
+                  className="h-10 px-5 rounded-lg gap-2 font-semibold text-sm"
+                >
+                  {loading ? (
+                    <Loader2 className="h-4 w-4 animate-spin" />
+                  ) : (
+                    <Send className="h-4 w-4" />
+                  )}
+                  {loading ? "Sending…" : kind === "grpc" ? "Invoke" : "Send"}
+                </Button>
+              )}
             </TooltipTrigger>
             <TooltipContent side="bottom">
               <span className="text-xs">⌘ Enter</span>
@@ -304,6 +366,39 @@ export function UrlBar({
           </Tooltip>
         </TooltipProvider>
       )}
+
+      {!disabled && !loading && (kind === "http" || kind === "sse") && (onSendAndSave || onKindChange) && (
+        <DropdownMenu>
+          <DropdownMenuTrigger asChild>
+            <Button
+              variant="outline"
+              size="icon"
+              className="h-10 w-8 rounded-lg -ml-2"
+              aria-label="More send options"
+            >
+              <ChevronDown className="h-3.5 w-3.5" />
+            </Button>
+          </DropdownMenuTrigger>
+          <DropdownMenuContent align="end">
+            {kind === "http" && onSendAndSave && (
+              <DropdownMenuItem onClick={onSendAndSave} className="gap-2 text-xs">
+                <Download className="h-3.5 w-3.5" />
+                Send and Save to File…
+              </DropdownMenuItem>
+            )}
+            {kind === "http" && onSendAndSave && onKindChange && <DropdownMenuSeparator />}
+            {onKindChange && (
+              <DropdownMenuCheckboxItem
+                checked={kind === "sse"}
+                onCheckedChange={(checked) => onKindChange(checked ? "sse" : "http")}
+                className="text-xs"
+              >
+                Read as Event Stream (SSE)
+              </DropdownMenuCheckboxItem>
+            )}
+          </DropdownMenuContent>
+        </DropdownMenu>
+      )}
     </div>
   );
 }
@@ -0,0 +1,42 @@
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { StatusBadge } from "./StatusBadge";
+import type { HttpResponse, RetryAttempt } from "@/types/request";
+
+interface AttemptsViewerProps {
+  attempts: RetryAttempt[];
+  response: HttpResponse;
+}
+
+export function AttemptsViewer({ attempts, response }: AttemptsViewerProps) {
+  return (
+    <ScrollArea className="h-full">
+      <ol className="p-4 space-y-2 text-xs font-mono">
+        {attempts.map((attempt, i) => (
+          <li key={i} className="border-b border-border/30 pb-2 space-y-1">
+            <div className="flex items-center gap-2">
+              <span className="text-muted-foreground">#{i + 1}</span>
+              {attempt.status != null ? (
+                <StatusBadge status={attempt.status} statusText={attempt.status_text} />
+              ) : (
+                <span className="text-destructive">Failed</span>
+              )}
+              <span className="flex-1 truncate" title={attempt.url}>
+                {attempt.url}
+              </span>
+              <span className="text-muted-foreground">{attempt.time_ms}ms</span>
+            </div>
+            {attempt.error && <p className="break-all text-destructive">{attempt.error}</p>}
+            <p className="text-muted-foreground">waited {attempt.delay_ms}ms before retrying</p>
+          </li>
+        ))}
+        <li className="flex items-center gap-2">
+          <span className="text-muted-foreground">#{attempts.length + 1}</span>
+          <StatusBadge status={response.status} statusText={response.status_text} />
+          <span className="flex-1 truncate text-muted-foreground" title={response.url}>
+            {response.url}
+          </span>
+        </li>
+      </ol>
+    </ScrollArea>
+  );
+}
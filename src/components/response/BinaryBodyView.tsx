@@ -0,0 +1,63 @@
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Download, FileQuestion } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { ScrollArea } from "@/components/ui/scroll-area";
+
+function extensionFor(mimeType: string | null): string {
+  const subtype = mimeType?.split("/")[1]?.split("+")[0];
+  return subtype && /^[a-z0-9.-]+$/i.test(subtype) ? subtype : "bin";
+}
+
+/** Preview (images only) and save for bodies returned as base64. */
+export function BinaryBodyView({
+  base64,
+  mimeType,
+  sizeBytes,
+}: {
+  base64: string;
+  mimeType: string | null;
+  sizeBytes: number;
+}) {
+  const isImage = mimeType?.startsWith("image/") ?? false;
+
+  const handleSave = async () => {
+    const path = await save({ defaultPath: `response.${extensionFor(mimeType)}` });
+    if (!path) return;
+    try {
+      await invoke("write_binary_file", { path, content: base64 });
+      toast.success("Response saved");
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  return (
+    <ScrollArea className="h-full">
+      <div className="flex flex-col items-center gap-3 p-6">
+        {isImage ? (
+          <img
+            src={`data:${mimeType};base64,${base64}`}
+            alt="Response body"
+            className="max-w-full rounded border border-border"
+          />
+        ) : (
+          <FileQuestion className="h-8 w-8 text-muted-foreground/50" />
+        )}
+        <p className="text-xs text-muted-foreground">
+          {mimeType ?? "Unknown type"} · {sizeBytes} bytes
+        </p>
+        <Button
+          variant="outline"
+          size="xs"
+          className="gap-1 text-xs"
+          onClick={handleSave}
+        >
+          <Download className="h-3.5 w-3.5" />
+          Save to File
+        </Button>
+      </div>
+    </ScrollArea>
+  );
+}
@@ -0,0 +1,99 @@
+import { ScrollArea } from "@/components/ui/scroll-area";
+import type { ConnectionInfo } from "@/types/request";
+import type { CertificateSummary } from "@/types/tls";
+
+/** Certificates expiring within this many days are flagged. */
+const EXPIRY_WARNING_DAYS = 30;
+
+function daysUntil(iso: string): number {
+  return Math.floor((new Date(iso).getTime() - Date.now()) / 86_400_000);
+}
+
+function Row({ label, children }: { label: string; children: React.ReactNode }) {
+  return (
+    <tr>
+      <td className="py-0.5 pr-4 font-semibold text-muted-foreground whitespace-nowrap align-top">
+        {label}
+      </td>
+      <td className="py-0.5 break-all">{children}</td>
+    </tr>
+  );
+}
+
+function CertificateRows({ cert }: { cert: CertificateSummary }) {
+  const days = daysUntil(cert.not_after);
+  return (
+    <table className="w-full">
+      <tbody>
+        <Row label="Subject">{cert.subject}</Row>
+        <Row label="Issuer">{cert.issuer}</Row>
+        <Row label="Alt names">{cert.subject_alt_names.join(", ") || "—"}</Row>
+        <Row label="Valid from">{cert.not_before}</Row>
+        <Row label="Expires">
+          {cert.not_after}
+          {days < 0 && <span className="ml-2 text-destructive">expired</span>}
+          {days >= 0 && days <= EXPIRY_WARNING_DAYS && (
+            <span className="ml-2 text-yellow-500">in {days} days</span>
+          )}
+        </Row>
+        <Row label="Serial">{cert.serial}</Row>
+        <Row label="SHA-256">{cert.sha256_fingerprint}</Row>
+        <Row label="SPKI pin">sha256/{cert.spki_sha256}</Row>
+      </tbody>
+    </table>
+  );
+}
+
+function SectionTitle({ children }: { children: React.ReactNode }) {
+  return (
+    <h4 className="text-[11px] font-sans font-medium text-muted-foreground uppercase tracking-wider">
+      {children}
+    </h4>
+  );
+}
+
+export function ConnectionViewer({ connection }: { connection: ConnectionInfo }) {
+  const cert = connection.peer_certificate;
+
+  return (
+    <ScrollArea className="h-full">
+      <div className="p-4 space-y-4 text-xs font-mono">
+        <table className="w-full">
+          <tbody>
+            <Row label="HTTP version">{connection.http_version}</Row>
+            {connection.unix_socket ? (
+              <Row label="Unix socket">{connection.unix_socket}</Row>
+            ) : (
+              <Row label="Remote address">{connection.remote_addr ?? "—"}</Row>
+            )}
+            {connection.resolved_addresses.length > 0 && (
+              <Row label="Resolved to">
+                {connection.resolved_addresses.join(", ")}
+                {connection.dns_override && (
+                  <span className="ml-2 text-muted-foreground">(DNS override)</span>
+                )}
+              </Row>
+            )}
+            <Row label="Connection">{connection.reused ? "Reused" : "New"}</Row>
+            {connection.tls_version && <Row label="TLS version">{connection.tls_version}</Row>}
+            {connection.tls_resumed && <Row label="TLS session">Resumed</Row>}
+          </tbody>
+        </table>
+
+        {cert && (
+          <div className="space-y-1">
+            <SectionTitle>Server Certificate</SectionTitle>
+            <CertificateRows cert={cert} />
+          </div>
+        )}
+
+        {connection.certificate_chain.map((issuer, i) => (
+          <div key={i} className="space-y-1">
+            <SectionTitle>Chain Certificate {i + 1}</SectionTitle>
+            <CertificateRows cert={issuer} />
+          </div>
+        ))}
+      </div>
+    </ScrollArea>
+  );
+}
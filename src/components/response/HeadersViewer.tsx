@@ -1,15 +1,15 @@
 import { ScrollArea } from "@/components/ui/scroll-area";
+import type { ResponseHeader } from "@/types/request";
 
 interface HeadersViewerProps {
-  headers: Record<string, string>;
+  headers: ResponseHeader[];
+  emptyText?: string;
 }
 
-export function HeadersViewer({ headers }: HeadersViewerProps) {
-  const entries = Object.entries(headers);
-
-  if (entries.length === 0) {
+export function HeadersViewer({ headers, emptyText = "No response headers." }: HeadersViewerProps) {
+  if (headers.length === 0) {
     return (
-      <p className="p-4 text-xs text-muted-foreground">No response headers.</p>
+      <p className="p-4 text-xs text-muted-foreground">{emptyText}</p>
     );
   }
 
@@ -18,12 +18,17 @@ export function HeadersViewer({ headers }: HeadersViewerProps) {
       <div className="p-4">
         <table className="w-full text-xs font-mono">
           <tbody>
-            {entries.map(([name, value]) => (
-              <tr key={name} className="border-b border-border/30 last:border-0">
+            {headers.map(({ name, value, raw }, i) => (
+              <tr key={`${name}-${i}`} className="border-b border-border/30 last:border-0">
                 <td className="py-1.5 pr-4 font-semibold text-muted-foreground whitespace-nowrap align-top">
                   {name}
                 </td>
-                <td className="py-1.5 text-foreground break-all">{value}</td>
+                <td
+                  className="py-1.5 text-foreground break-all"
+                  title={raw ? `Non-UTF-8 bytes: ${raw.join(" ")}` : undefined}
+                >
+                  {value}
+                </td>
               </tr>
             ))}
           </tbody>
@@ -0,0 +1,76 @@
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { JsonViewer } from "./JsonViewer";
+import { jsonrpcErrorName } from "@/lib/jsonrpc";
+import type { JsonRpcError, JsonRpcMatch } from "@/types/jsonrpc";
+
+function ErrorObject({ error }: { error: JsonRpcError }) {
+  const name = jsonrpcErrorName(error.code);
+  return (
+    <div className="rounded-md border border-destructive/40 bg-destructive/5 p-2 space-y-1">
+      <p className="text-destructive">
+        <span className="font-semibold">{error.code}</span>
+        {name && <span className="text-destructive/80"> {name}</span>}
+        <span className="font-sans">: {error.message}</span>
+      </p>
+      {error.data !== undefined && <JsonViewer data={JSON.stringify(error.data)} />}
+    </div>
+  );
+}
+
+/** Each sent call with its result or error object, in the order the calls were sent. */
+export function JsonRpcViewer({ match }: { match: JsonRpcMatch }) {
+  return (
+    <ScrollArea className="h-full">
+      <div className="p-4 space-y-3 text-xs font-mono">
+        {match.problem && <p className="font-sans text-destructive">{match.problem}</p>}
+
+        {match.outcomes.map((outcome, i) => (
+          <div key={i} className="border-b border-border/30 pb-3 space-y-1.5">
+            <div className="flex items-center gap-2">
+              <span className="text-muted-foreground w-10">
+                {outcome.call.id !== null ? `#${outcome.call.id}` : "—"}
+              </span>
+              <span className="flex-1 truncate font-semibold">{outcome.call.method}</span>
+              <span
+                className={
+                  outcome.kind === "result"
+                    ? "text-status-2xx"
+                    : outcome.kind === "error"
+                      ? "text-destructive"
+                      : "text-muted-foreground"
+                }
+              >
+                {outcome.kind === "result"
+                  ? "Result"
+                  : outcome.kind === "error"
+                    ? "Error"
+                    : outcome.kind === "missing"
+                      ? "No response"
+                      : "Notification"}
+              </span>
+            </div>
+            {outcome.kind === "result" && (
+              <JsonViewer data={JSON.stringify(outcome.result)} />
+            )}
+            {outcome.kind === "error" && <ErrorObject error={outcome.error} />}
+          </div>
+        ))}
+
+        {match.unmatched.length > 0 && (
+          <div className="space-y-2">
+            <p className="font-sans text-muted-foreground">
+              Responses that match no call ({match.unmatched.length})
+            </p>
+            {match.unmatched.map((response, i) => (
+              <div key={i} className="space-y-1.5">
+                <span className="text-muted-foreground">id {JSON.stringify(response.id)}</span>
+                {response.error && <ErrorObject error={response.error} />}
+                {"result" in response && <JsonViewer data={JSON.stringify(response.result)} />}
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
+    </ScrollArea>
+  );
+}
@@ -0,0 +1,45 @@
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { StatusBadge } from "./StatusBadge";
+import type { RedirectHop } from "@/types/request";
+
+interface RedirectsViewerProps {
+  redirects: RedirectHop[];
+  finalUrl: string;
+}
+
+export function RedirectsViewer({ redirects, finalUrl }: RedirectsViewerProps) {
+  return (
+    <ScrollArea className="h-full">
+      <ol className="p-4 space-y-2 text-xs font-mono">
+        {redirects.map((hop, i) => (
+          <li key={i} className="border-b border-border/30 pb-2">
+            <details>
+              <summary className="flex cursor-pointer items-center gap-2">
+                <StatusBadge status={hop.status} statusText={hop.status_text} />
+                <span className="flex-1 truncate" title={hop.url}>
+                  {hop.url}
+                </span>
+                <span className="text-muted-foreground">{hop.time_ms}ms</span>
+              </summary>
+              <table className="mt-2 w-full">
+                <tbody>
+                  {hop.headers.map(({ name, value }, j) => (
+                    <tr key={`${name}-${j}`}>
+                      <td className="py-0.5 pr-4 font-semibold text-muted-foreground whitespace-nowrap align-top">
+                        {name}
+                      </td>
+                      <td className="py-0.5 break-all">{value}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </details>
+          </li>
+        ))}
+        <li className="truncate text-muted-foreground" title={finalUrl}>
+          → {finalUrl}
+        </li>
+      </ol>
+    </ScrollArea>
+  );
+}
@@ -1,13 +1,31 @@
-import { useState } from "react";
+import { useMemo, useState } from "react";
 import { motion, AnimatePresence } from "motion/react";
 import { ArrowUp } from "lucide-react";
 import { StatusBadge } from "./StatusBadge";
 import { HeadersViewer } from "./HeadersViewer";
 import { ResponseToolbar } from "./ResponseToolbar";
 import { JsonViewer } from "./JsonViewer";
+import { BinaryBodyView } from "./BinaryBodyView";
+import { RedirectsViewer } from "./RedirectsViewer";
+import { AttemptsViewer } from "./AttemptsViewer";
+import { ConnectionViewer } from "./ConnectionViewer";
+import { WireViewer } from "./WireViewer";
+import { JsonRpcViewer } from "./JsonRpcViewer";
 import { ScrollArea } from "@/components/ui/scroll-area";
 import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
-import type { HttpResponse } from "@/types/request";
+import {
+  Tooltip,
+  TooltipContent,
+  TooltipProvider,
+  TooltipTrigger,
+} from "@/components/ui/tooltip";
+import type {
+  DownloadProgress,
+  HttpResponse,
+  TimingBreakdown,
+} from "@/types/request";
+import type { JsonRpcSentCall } from "@/types/jsonrpc";
+import { matchJsonRpcResponse } from "@/lib/jsonrpc";
 
 function formatSize(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
@@ -15,6 +33,33 @@ function formatSize(bytes: number): string {
   return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
 }
 
+function formatMs(ms: number | null): string {
+  if (ms == null) return "—";
+  return ms < 10 ? `${ms.toFixed(2)}ms` : `${Math.round(ms)}ms`;
+}
+
+function TimingTable({ timings }: { timings: TimingBreakdown }) {
+  const rows: [string, number | null][] = [
+    ["DNS", timings.dns_ms],
+    ["Connect", timings.connect_ms],
+    ["TLS handshake", timings.tls_ms],
+    ["Waiting (TTFB)", timings.ttfb_ms],
+    ["Download", timings.download_ms],
+  ];
+  return (
+    <table className="text-xs font-mono">
+      <tbody>
+        {rows.map(([label, ms]) => (
+          <tr key={label}>
+            <td className="pr-4">{label}</td>
+            <td className="text-right">{formatMs(ms)}</td>
+          </tr>
+        ))}
+      </tbody>
+    </table>
+  );
+}
+
 function LoadingShimmer() {
   return (
     <div className="flex h-full flex-col gap-3 p-6">
@@ -31,15 +76,39 @@ function LoadingShimmer() {
   );
 }
 
+function DownloadingView({ progress }: { progress: DownloadProgress }) {
+  const { downloaded_bytes: done, total_bytes: total } = progress;
+  const percent = total ? Math.min(100, (done / total) * 100) : null;
+  return (
+    <div className="flex h-full flex-col items-center justify-center gap-3 p-6">
+      <div className="h-1.5 w-64 overflow-hidden rounded bg-muted">
+        <div
+          className="h-full bg-primary transition-[width]"
+          style={{ width: percent != null ? `${percent}%` : "100%" }}
+        />
+      </div>
+      <p className="text-xs text-muted-foreground">
+        {formatSize(done)}
+        {total != null && ` of ${formatSize(total)}`}
+      </p>
+    </div>
+  );
+}
+
 export function ResponsePanel({
   response,
   loading,
+  downloadProgress = null,
   error,
+  jsonrpcSent = null,
   onCopyAsCurl,
 }: {
   response: HttpResponse | null;
   loading: boolean;
+  downloadProgress?: DownloadProgress | null;
   error: string | null;
+  /** The JSON-RPC calls the response answers, to match it back to them. */
+  jsonrpcSent?: JsonRpcSentCall[] | null;
   onCopyAsCurl?: () => void;
 }) {
   const [viewMode, setViewMode] = useState<"pretty" | "raw">("pretty");
@@ -47,8 +116,20 @@ export function ResponsePanel({
   const [searchQuery, setSearchQuery] = useState("");
   const [matchCount, setMatchCount] = useState(0);
 
+  const jsonrpcMatch = useMemo(
+    () =>
+      jsonrpcSent && response && response.body_encoding !== "base64" && !response.saved_to
+        ? matchJsonRpcResponse(response.body, jsonrpcSent)
+        : null,
+    [jsonrpcSent, response],
+  );
+
   if (loading) {
-    return <LoadingShimmer />;
+    return downloadProgress ? (
+      <DownloadingView progress={downloadProgress} />
+    ) : (
+      <LoadingShimmer />
+    );
   }
 
   if (error) {
@@ -74,7 +155,11 @@ export function ResponsePanel({
     );
   }
 
-  const headerCount = Object.keys(response.headers).length;
+  const headerCount = response.headers.length;
+  const jsonrpcErrors = jsonrpcMatch
+    ? jsonrpcMatch.outcomes.filter((o) => o.kind === "error").length +
+      jsonrpcMatch.unmatched.filter((u) => u.error).length
+    : 0;
 
   return (
     <AnimatePresence mode="wait">
@@ -85,17 +170,35 @@ export function ResponsePanel({
         transition={{ duration: 0.15 }}
         className="flex h-full flex-col"
       >
-        <Tabs defaultValue="body" className="flex h-full flex-col gap-0">
+        <Tabs defaultValue={jsonrpcMatch ? "calls" : "body"} className="flex h-full flex-col gap-0">
           {/* Status bar + tabs + toolbar */}
           <div className="flex items-center gap-2 border-b border-border px-4 py-1.5 shrink-0">
             <StatusBadge
               status={response.status}
               statusText={response.status_text}
             />
+            {response.connection && (
+              <span className="text-[10px] font-mono text-muted-foreground">
+                {response.connection.http_version}
+              </span>
+            )}
+            {jsonrpcErrors > 0 && (
+              <span className="text-xs font-mono text-destructive whitespace-nowrap">
+                {jsonrpcErrors} RPC {jsonrpcErrors === 1 ? "error" : "errors"}
+              </span>
+            )}
             <TabsList
               variant="line"
               className="border-0 bg-transparent"
             >
+              {jsonrpcMatch && (
+                <TabsTrigger value="calls" className="text-xs gap-1">
+                  Calls
+                  <span className="text-[10px] font-semibold text-muted-foreground">
+                    ({jsonrpcMatch.outcomes.length})
+                  </span>
+                </TabsTrigger>
+              )}
               <TabsTrigger value="body" className="text-xs">
                 Body
               </TabsTrigger>
@@ -107,6 +210,32 @@ export function ResponsePanel({
                   </span>
                 )}
               </TabsTrigger>
+              {response.redirects.length > 0 && (
+                <TabsTrigger value="redirects" className="text-xs gap-1">
+                  Redirects
+                  <span className="text-[10px] font-semibold text-muted-foreground">
+                    ({response.redirects.length})
+                  </span>
+                </TabsTrigger>
+              )}
+              {response.attempts.length > 0 && (
+                <TabsTrigger value="attempts" className="text-xs gap-1">
+                  Attempts
+                  <span className="text-[10px] font-semibold text-muted-foreground">
+                    ({response.attempts.length + 1})
+                  </span>
+                </TabsTrigger>
+              )}
+              {response.wire_body && (
+                <TabsTrigger value="wire" className="text-xs">
+                  Wire
+                </TabsTrigger>
+              )}
+              {response.connection && (
+                <TabsTrigger value="connection" className="text-xs">
+                  Connection
+                </TabsTrigger>
+              )}
             </TabsList>
 
             <div className="ml-auto flex items-center gap-2">
@@ -121,15 +250,58 @@ export function ResponsePanel({
                 matchCount={matchCount}
                 onCopyAsCurl={onCopyAsCurl}
               />
-              <span className="text-xs text-muted-foreground whitespace-nowrap">
-                {formatSize(response.size_bytes)} · {response.time_ms}ms
-              </span>
+              {response.decompress_error && (
+                <span
+                  className="text-xs text-destructive whitespace-nowrap cursor-default"
+                  title={response.decompress_error}
+                >
+                  Not decompressed
+                </span>
+              )}
+              <TooltipProvider delayDuration={300}>
+                <Tooltip>
+                  <TooltipTrigger asChild>
+                    <span className="text-xs text-muted-foreground whitespace-nowrap cursor-default">
+                      {response.request_size_bytes > 0 &&
+                        `↑ ${formatSize(response.request_size_bytes)} · `}
+                      {formatSize(response.size_bytes)}
+                      {response.wire_size_bytes !== response.size_bytes &&
+                        ` (${formatSize(response.wire_size_bytes)} ${response.content_encoding ?? "on the wire"})`}
+                      {" · "}
+                      {response.time_ms}ms
+                    </span>
+                  </TooltipTrigger>
+                  <TooltipContent side="bottom">
+                    <TimingTable timings={response.timings} />
+                  </TooltipContent>
+                </Tooltip>
+              </TooltipProvider>
             </div>
           </div>
 
+          {/* Calls tab */}
+          {jsonrpcMatch && (
+            <TabsContent value="calls" className="flex-1 min-h-0">
+              <JsonRpcViewer match={jsonrpcMatch} />
+            </TabsContent>
+          )}
+
           {/* Body tab */}
           <TabsContent value="body" className="flex-1 min-h-0">
-            {viewMode === "pretty" ? (
+            {response.saved_to ? (
+              <div className="flex h-full flex-col items-center justify-center gap-1 p-6">
+                <p className="text-sm">Saved to file</p>
+                <p className="text-xs font-mono text-muted-foreground break-all">
+                  {response.saved_to}
+                </p>
+              </div>
+            ) : response.body_encoding === "base64" ? (
+              <BinaryBodyView
+                base64={response.body}
+                mimeType={response.mime_type}
+                sizeBytes={response.size_bytes}
+              />
+            ) : viewMode === "pretty" ? (
               <ScrollArea className="h-full">
                 <div className="p-4">
                   <JsonViewer
@@ -152,6 +324,39 @@ export function ResponsePanel({
           <TabsContent value="headers" className="flex-1 min-h-0">
             <HeadersViewer headers={response.headers} />
           </TabsContent>
+
+          {/* Redirects tab */}
+          {response.redirects.length > 0 && (
+            <TabsContent value="redirects" className="flex-1 min-h-0">
+              <RedirectsViewer redirects={response.redirects} finalUrl={response.url} />
+            </TabsContent>
+          )}
+
+          {/* Attempts tab */}
+          {response.attempts.length > 0 && (
+            <TabsContent value="attempts" className="flex-1 min-h-0">
+              <AttemptsViewer attempts={response.attempts} response={response} />
+            </TabsContent>
+          )}
+
+          {/* Wire tab */}
+          {response.wire_body && (
+            <TabsContent value="wire" className="flex-1 min-h-0">
+              <WireViewer
+                base64={response.wire_body}
+                contentEncoding={response.content_encoding ?? ""}
+                wireSizeBytes={response.wire_size_bytes}
+                sizeBytes={response.size_bytes}
+              />
+            </TabsContent>
+          )}
+
+          {/* Connection tab */}
+          {response.connection && (
+            <TabsContent value="connection" className="flex-1 min-h-0">
+              <ConnectionViewer connection={response.connection} />
+            </TabsContent>
+          )}
         </Tabs>
       </motion.div>
     </AnimatePresence>
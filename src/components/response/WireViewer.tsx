@@ -0,0 +1,85 @@
+import { useMemo } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { Download } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { ScrollArea } from "@/components/ui/scroll-area";
+
+/** Bytes shown in the hex dump; the rest is only saved. */
+const PREVIEW_BYTES = 4096;
+
+function hexDump(bytes: Uint8Array): string {
+  const lines: string[] = [];
+  for (let offset = 0; offset < bytes.length; offset += 16) {
+    const row = bytes.subarray(offset, offset + 16);
+    const hex = Array.from(row, (b) => b.toString(16).padStart(2, "0")).join(" ");
+    const ascii = Array.from(row, (b) =>
+      b >= 0x20 && b < 0x7f ? String.fromCharCode(b) : ".",
+    ).join("");
+    lines.push(`${offset.toString(16).padStart(8, "0")}  ${hex.padEnd(47)}  ${ascii}`);
+  }
+  return lines.join("\n");
+}
+
+/** The compressed body exactly as received, before decompression. */
+export function WireViewer({
+  base64,
+  contentEncoding,
+  wireSizeBytes,
+  sizeBytes,
+}: {
+  base64: string;
+  contentEncoding: string;
+  wireSizeBytes: number;
+  sizeBytes: number;
+}) {
+  const preview = useMemo(() => {
+    const binary = atob(base64);
+    const length = Math.min(binary.length, PREVIEW_BYTES);
+    const bytes = new Uint8Array(length);
+    for (let i = 0; i < length; i++) bytes[i] = binary.charCodeAt(i);
+    return hexDump(bytes);
+  }, [base64]);
+
+  const ratio = sizeBytes > 0 ? Math.round((1 - wireSizeBytes / sizeBytes) * 100) : 0;
+
+  const handleSave = async () => {
+    const path = await save({ defaultPath: "response.wire" });
+    if (!path) return;
+    try {
+      await invoke("write_binary_file", { path, content: base64 });
+      toast.success("Response saved");
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  return (
+    <ScrollArea className="h-full">
+      <div className="p-4 space-y-3">
+        <div className="flex items-center gap-2 text-xs">
+          <span className="font-mono">{contentEncoding}</span>
+          <span className="text-muted-foreground">
+            {wireSizeBytes} bytes on the wire · {sizeBytes} decoded · {ratio}% smaller
+          </span>
+          <Button
+            variant="outline"
+            size="xs"
+            className="ml-auto gap-1 text-xs"
+            onClick={handleSave}
+          >
+            <Download className="h-3.5 w-3.5" />
+            Save Raw Bytes
+          </Button>
+        </div>
+        <pre className="font-mono text-xs text-foreground whitespace-pre">{preview}</pre>
+        {wireSizeBytes > PREVIEW_BYTES && (
+          <p className="text-xs text-muted-foreground">
+            Showing the first {PREVIEW_BYTES} bytes.
+          </p>
+        )}
+      </div>
+    </ScrollArea>
+  );
+}
@@ -0,0 +1,231 @@
+import { useCallback, useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Checkbox } from "@/components/ui/checkbox";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { FileUp, Plus, Trash2 } from "lucide-react";
+import type { ClientCertificate, ClientCertificateKind } from "@/types/tls";
+
+interface Draft {
+  host_pattern: string;
+  kind: ClientCertificateKind;
+  cert_path: string;
+  key_path: string;
+  passphrase: string;
+}
+
+const emptyDraft: Draft = {
+  host_pattern: "",
+  kind: "pem",
+  cert_path: "",
+  key_path: "",
+  passphrase: "",
+};
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() ?? path;
+}
+
+async function chooseFile(): Promise<string | null> {
+  const path = await open({ multiple: false, directory: false });
+  return typeof path === "string" ? path : null;
+}
+
+export function ClientCertificatesSection() {
+  const [certificates, setCertificates] = useState<ClientCertificate[]>([]);
+  const [draft, setDraft] = useState<Draft | null>(null);
+
+  const load = useCallback(async () => {
+    try {
+      setCertificates(await invoke<ClientCertificate[]>("list_client_certificates"));
+    } catch (err) {
+      toast.error(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  const handleAdd = useCallback(async () => {
+    if (!draft) return;
+    const data = {
+      host_pattern: draft.host_pattern,
+      kind: draft.kind,
+      cert_path: draft.cert_path,
+      key_path: draft.kind === "pem" && draft.key_path ? draft.key_path : null,
+      passphrase: draft.kind === "pkcs12" ? draft.passphrase : null,
+    };
+    try {
+      // Surface unreadable files and wrong passphrases before saving
+      await invoke("test_client_certificate", { data });
+      await invoke("create_client_certificate", { data });
+      setDraft(null);
+      load();
+    } catch (err) {
+      toast.error(String(err));
+    }
+  }, [draft, load]);
+
+  const handleToggle = useCallback(
+    async (cert: ClientCertificate, enabled: boolean) => {
+      try {
+        await invoke("update_client_certificate", { id: cert.id, data: { enabled } });
+        load();
+      } catch (err) {
+        toast.error(String(err));
+      }
+    },
+    [load]
+  );
+
+  const handleDelete = useCallback(
+    async (id: string) => {
+      try {
+        await invoke("delete_client_certificate", { id });
+        load();
+      } catch (err) {
+        toast.error(String(err));
+      }
+    },
+    [load]
+  );
+
+  return (
+    <section className="space-y-3">
+      <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+        Client Certificates
+      </h3>
+
+      {certificates.length === 0 && !draft && (
+        <p className="text-xs text-muted-foreground">
+          No client certificates. Add one to present it to matching hosts.
+        </p>
+      )}
+
+      {certificates.map((cert) => (
+        <div key={cert.id} className="flex items-center gap-2">
+          <Checkbox
+            checked={cert.enabled}
+            onCheckedChange={(checked) => handleToggle(cert, checked === true)}
+          />
+          <div className="flex-1 min-w-0">
+            <div className="text-xs font-mono truncate">{cert.host_pattern}</div>
+            <div className="text-[11px] text-muted-foreground truncate">
+              {cert.kind === "pkcs12" ? "PKCS#12" : "PEM"} · {fileName(cert.cert_path)}
+              {cert.key_path ? ` + ${fileName(cert.key_path)}` : ""}
+            </div>
+          </div>
+          <Button
+            variant="ghost"
+            size="icon-xs"
+            className="text-muted-foreground hover:text-destructive"
+            onClick={() => handleDelete(cert.id)}
+          >
+            <Trash2 className="size-3" />
+          </Button>
+        </div>
+      ))}
+
+      {draft ? (
+        <div className="space-y-2 rounded-md border border-border p-3">
+          <div className="space-y-2">
+            <Label htmlFor="cert-host" className="text-xs">
+              Host
+            </Label>
+            <Input
+              id="cert-host"
+              placeholder="api.example.com, *.example.com:8443"
+              value={draft.host_pattern}
+              onChange={(e) => setDraft({ ...draft, host_pattern: e.target.value })}
+              className="h-8 text-xs font-mono"
+            />
+          </div>
+          <Select
+            value={draft.kind}
+            onValueChange={(kind: ClientCertificateKind) => setDraft({ ...draft, kind })}
+          >
+            <SelectTrigger size="sm" className="w-48">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value="pem">PEM certificate + key</SelectItem>
+              <SelectItem value="pkcs12">PKCS#12 (.p12, .pfx)</SelectItem>
+            </SelectContent>
+          </Select>
+          <Button
+            variant="outline"
+            size="xs"
+            className="gap-1 text-xs w-full justify-start"
+            onClick={async () => {
+              const path = await chooseFile();
+              if (path) setDraft({ ...draft, cert_path: path });
+            }}
+          >
+            <FileUp className="h-3.5 w-3.5" />
+            {draft.cert_path
+              ? fileName(draft.cert_path)
+              : draft.kind === "pkcs12"
+                ? "Choose bundle"
+                : "Choose certificate"}
+          </Button>
+          {draft.kind === "pem" ? (
+            <Button
+              variant="outline"
+              size="xs"
+              className="gap-1 text-xs w-full justify-start"
+              onClick={async () => {
+                const path = await chooseFile();
+                if (path) setDraft({ ...draft, key_path: path });
+              }}
+            >
+              <FileUp className="h-3.5 w-3.5" />
+              {draft.key_path ? fileName(draft.key_path) : "Choose key (if separate)"}
+            </Button>
+          ) : (
+            <Input
+              type="password"
+              placeholder="Passphrase"
+              value={draft.passphrase}
+              onChange={(e) => setDraft({ ...draft, passphrase: e.target.value })}
+              className="h-8 text-xs"
+            />
+          )}
+          <div className="flex justify-end gap-1">
+            <Button variant="ghost" size="xs" className="text-xs" onClick={() => setDraft(null)}>
+              Cancel
+            </Button>
+            <Button
+              size="xs"
+              className="text-xs"
+              disabled={!draft.host_pattern.trim() || !draft.cert_path}
+              onClick={handleAdd}
+            >
+              Add
+            </Button>
+          </div>
+        </div>
+      ) : (
+        <Button
+          variant="outline"
+          size="xs"
+          className="gap-1 text-xs"
+          onClick={() => setDraft(emptyDraft)}
+        >
+          <Plus className="h-3.5 w-3.5" />
+          Add Certificate
+        </Button>
+      )}
+    </section>
+  );
+}
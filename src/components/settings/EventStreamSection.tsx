@@ -0,0 +1,51 @@
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Checkbox } from "@/components/ui/checkbox";
+import { useSettingsStore } from "@/stores/settingsStore";
+
+export function EventStreamSection() {
+  const reconnect = useSettingsStore((s) => s.sseReconnect);
+  const maxReconnects = useSettingsStore((s) => s.sseMaxReconnects);
+  const setReconnect = useSettingsStore((s) => s.setSseReconnect);
+  const setMaxReconnects = useSettingsStore((s) => s.setSseMaxReconnects);
+
+  return (
+    <section className="space-y-3">
+      <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+        Event Streams
+      </h3>
+      <div className="flex items-center gap-2">
+        <Checkbox
+          id="sse-reconnect"
+          checked={reconnect}
+          onCheckedChange={(checked) => setReconnect(checked === true)}
+        />
+        <Label htmlFor="sse-reconnect" className="text-xs cursor-pointer">
+          Reconnect when the stream ends, sending Last-Event-ID
+        </Label>
+      </div>
+      {reconnect && (
+        <div className="space-y-2">
+          <Label htmlFor="sse-max-reconnects" className="text-xs">
+            Failed reconnects before giving up
+          </Label>
+          <Input
+            id="sse-max-reconnects"
+            type="number"
+            min={1}
+            max={100}
+            value={maxReconnects}
+            onChange={(e) => {
+              const v = parseInt(e.target.value, 10);
+              if (v >= 1) setMaxReconnects(v);
+            }}
+            className="h-8 text-xs"
+          />
+          <p className="text-xs text-muted-foreground">
+            Waits as long as the server's retry field asks, or 3 seconds.
+          </p>
+        </div>
+      )}
+    </section>
+  );
+}
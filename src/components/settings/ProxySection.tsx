@@ -0,0 +1,95 @@
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Checkbox } from "@/components/ui/checkbox";
+import { useSettingsStore, type ProxySettings } from "@/stores/settingsStore";
+import { toast } from "sonner";
+
+const urlFields: {
+  key: "httpProxy" | "httpsProxy" | "socksProxy";
+  label: string;
+  placeholder: string;
+}[] = [
+  { key: "httpProxy", label: "HTTP proxy", placeholder: "http://proxy:8080" },
+  { key: "httpsProxy", label: "HTTPS proxy", placeholder: "http://proxy:8080" },
+  { key: "socksProxy", label: "SOCKS5 proxy", placeholder: "socks5h://127.0.0.1:1080" },
+];
+
+export function ProxySection() {
+  const httpProxy = useSettingsStore((s) => s.httpProxy);
+  const httpsProxy = useSettingsStore((s) => s.httpsProxy);
+  const socksProxy = useSettingsStore((s) => s.socksProxy);
+  const proxyUsername = useSettingsStore((s) => s.proxyUsername);
+  const proxyPassword = useSettingsStore((s) => s.proxyPassword);
+  const noProxy = useSettingsStore((s) => s.noProxy);
+  const useSystemProxy = useSettingsStore((s) => s.useSystemProxy);
+  const setProxy = useSettingsStore((s) => s.setProxy);
+
+  const values = { httpProxy, httpsProxy, socksProxy, proxyUsername, proxyPassword, noProxy };
+  const field = (key: keyof Omit<ProxySettings, "useSystemProxy">) => ({
+    value: values[key],
+    onChange: (e: React.ChangeEvent<HTMLInputElement>) =>
+      setProxy({ [key]: e.target.value }).catch((err) => toast.error(String(err))),
+  });
+
+  return (
+    <section className="space-y-3">
+      <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+        Proxy
+      </h3>
+      {urlFields.map(({ key, label, placeholder }) => (
+        <div key={key} className="space-y-2">
+          <Label htmlFor={key} className="text-xs">
+            {label}
+          </Label>
+          <Input
+            id={key}
+            type="text"
+            placeholder={placeholder}
+            {...field(key)}
+            className="h-8 text-xs font-mono"
+          />
+        </div>
+      ))}
+      <p className="text-xs text-muted-foreground">
+        SOCKS5 carries any request without a proxy of its own.
+      </p>
+      <div className="flex gap-1">
+        <Input
+          placeholder="Username"
+          autoComplete="off"
+          {...field("proxyUsername")}
+          className="h-8 text-xs"
+        />
+        <Input
+          type="password"
+          placeholder="Password or {{secret}}"
+          autoComplete="off"
+          {...field("proxyPassword")}
+          className="h-8 text-xs"
+        />
+      </div>
+      <div className="space-y-2">
+        <Label htmlFor="no-proxy" className="text-xs">
+          Bypass proxy for
+        </Label>
+        <Input
+          id="no-proxy"
+          type="text"
+          placeholder="localhost, .internal.example.com, 10.0.0.0/8"
+          {...field("noProxy")}
+          className="h-8 text-xs font-mono"
+        />
+      </div>
+      <div className="flex items-center gap-2">
+        <Checkbox
+          id="use-system-proxy"
+          checked={useSystemProxy}
+          onCheckedChange={(checked) => setProxy({ useSystemProxy: checked === true })}
+        />
+        <Label htmlFor="use-system-proxy" className="text-xs cursor-pointer">
+          Use HTTP_PROXY / NO_PROXY from the environment
+        </Label>
+      </div>
+    </section>
+  );
+}
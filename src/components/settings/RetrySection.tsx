@@ -0,0 +1,160 @@
+import { Input } from "@/components/ui/input";
+import { Label } from "@/components/ui/label";
+import { Checkbox } from "@/components/ui/checkbox";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { useSettingsStore, type RetryBackoff } from "@/stores/settingsStore";
+
+const backoffOptions: { value: RetryBackoff; label: string }[] = [
+  { value: "exponential", label: "Exponential (delay doubles)" },
+  { value: "linear", label: "Linear (delay × attempt)" },
+  { value: "fixed", label: "Fixed" },
+];
+
+export function RetrySection() {
+  const maxAttempts = useSettingsStore((s) => s.retryMaxAttempts);
+  const backoff = useSettingsStore((s) => s.retryBackoff);
+  const delayMs = useSettingsStore((s) => s.retryDelayMs);
+  const maxDelayMs = useSettingsStore((s) => s.retryMaxDelayMs);
+  const statuses = useSettingsStore((s) => s.retryStatuses);
+  const networkErrors = useSettingsStore((s) => s.retryNetworkErrors);
+  const respectRetryAfter = useSettingsStore((s) => s.retryRespectRetryAfter);
+  const nonIdempotent = useSettingsStore((s) => s.retryNonIdempotent);
+  const setRetry = useSettingsStore((s) => s.setRetry);
+
+  return (
+    <section className="space-y-3">
+      <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+        Retries
+      </h3>
+      <div className="space-y-2">
+        <Label htmlFor="retry-attempts" className="text-xs">
+          Attempts per request
+        </Label>
+        <Input
+          id="retry-attempts"
+          type="number"
+          min={1}
+          max={10}
+          value={maxAttempts}
+          onChange={(e) => {
+            const v = parseInt(e.target.value, 10);
+            if (v >= 1) setRetry({ retryMaxAttempts: v });
+          }}
+          className="h-8 text-xs"
+        />
+        <p className="text-xs text-muted-foreground">
+          1 sends once. Only GET, HEAD, PUT, DELETE and OPTIONS are retried unless allowed below.
+        </p>
+      </div>
+      {maxAttempts > 1 && (
+        <>
+          <div className="space-y-2">
+            <Label className="text-xs">Backoff</Label>
+            <Select
+              value={backoff}
+              onValueChange={(v: RetryBackoff) => setRetry({ retryBackoff: v })}
+            >
+              <SelectTrigger size="sm" className="w-full text-xs">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                {backoffOptions.map(({ value, label }) => (
+                  <SelectItem key={value} value={value} className="text-xs">
+                    {label}
+                  </SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="flex gap-1">
+            <div className="flex-1 space-y-2">
+              <Label htmlFor="retry-delay" className="text-xs">
+                Initial delay (ms)
+              </Label>
+              <Input
+                id="retry-delay"
+                type="number"
+                min={0}
+                step={100}
+                value={delayMs}
+                onChange={(e) => {
+                  const v = parseInt(e.target.value, 10);
+                  if (v >= 0) setRetry({ retryDelayMs: v });
+                }}
+                className="h-8 text-xs"
+              />
+            </div>
+            <div className="flex-1 space-y-2">
+              <Label htmlFor="retry-max-delay" className="text-xs">
+                Maximum delay (ms)
+              </Label>
+              <Input
+                id="retry-max-delay"
+                type="number"
+                min={0}
+                step={1000}
+                value={maxDelayMs}
+                onChange={(e) => {
+                  const v = parseInt(e.target.value, 10);
+                  if (v >= 0) setRetry({ retryMaxDelayMs: v });
+                }}
+                className="h-8 text-xs"
+              />
+            </div>
+          </div>
+          <div className="space-y-2">
+            <Label htmlFor="retry-statuses" className="text-xs">
+              Retry on status
+            </Label>
+            <Input
+              id="retry-statuses"
+              type="text"
+              placeholder="429, 500-504"
+              value={statuses}
+              onChange={(e) => setRetry({ retryStatuses: e.target.value })}
+              className="h-8 text-xs font-mono"
+            />
+          </div>
+          <div className="flex items-center gap-2">
+            <Checkbox
+              id="retry-network-errors"
+              checked={networkErrors}
+              onCheckedChange={(checked) => setRetry({ retryNetworkErrors: checked === true })}
+            />
+            <Label htmlFor="retry-network-errors" className="text-xs cursor-pointer">
+              Retry connection failures and timeouts
+            </Label>
+          </div>
+          <div className="flex items-center gap-2">
+            <Checkbox
+              id="retry-after"
+              checked={respectRetryAfter}
+              onCheckedChange={(checked) =>
+                setRetry({ retryRespectRetryAfter: checked === true })
+              }
+            />
+            <Label htmlFor="retry-after" className="text-xs cursor-pointer">
+              Wait as long as Retry-After asks (up to the maximum delay)
+            </Label>
+          </div>
+          <div className="flex items-center gap-2">
+            <Checkbox
+              id="retry-non-idempotent"
+              checked={nonIdempotent}
+              onCheckedChange={(checked) => setRetry({ retryNonIdempotent: checked === true })}
+            />
+            <Label htmlFor="retry-non-idempotent" className="text-xs cursor-pointer">
+              Also retry POST and PATCH
+            </Label>
+          </div>
+        </>
+      )}
+    </section>
+  );
+}
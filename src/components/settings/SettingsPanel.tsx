@@ -4,6 +4,13 @@ import { Input } from "@/components/ui/input";
 import { Label } from "@/components/ui/label";
 import { Checkbox } from "@/components/ui/checkbox";
 import { Separator } from "@/components/ui/separator";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
 import {
   AlertDialog,
   AlertDialogAction,
@@ -16,10 +23,32 @@ import {
 } from "@/components/ui/alert-dialog";
 import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
-import { useSettingsStore, type Theme } from "@/stores/settingsStore";
+import {
+  useSettingsStore,
+  type HttpVersionPref,
+  type IpFamily,
+  type Theme,
+} from "@/stores/settingsStore";
 import { useHistoryStore } from "@/stores/historyStore";
+import { ClientCertificatesSection } from "./ClientCertificatesSection";
+import { ProxySection } from "./ProxySection";
+import { RetrySection } from "./RetrySection";
+import { EventStreamSection } from "./EventStreamSection";
+import { TrustSection } from "./TrustSection";
 import { Sun, Moon, Monitor } from "lucide-react";
 
+const httpVersionOptions: { value: HttpVersionPref; label: string }[] = [
+  { value: "auto", label: "Auto (HTTP/2 via ALPN)" },
+  { value: "http1", label: "HTTP/1.1 only" },
+  { value: "http2", label: "HTTP/2 only (h2c prior knowledge)" },
+];
+
+const ipFamilyOptions: { value: IpFamily; label: string }[] = [
+  { value: "any", label: "IPv4 and IPv6" },
+  { value: "ipv4", label: "IPv4 only" },
+  { value: "ipv6", label: "IPv6 only" },
+];
+
 const themeOptions: { value: Theme; label: string; icon: typeof Sun }[] = [
   { value: "dark", label: "Dark", icon: Moon },
   { value: "light", label: "Light", icon: Sun },
@@ -30,14 +59,24 @@ export function SettingsPanel() {
   const theme = useSettingsStore((s) => s.theme);
   const historyRetentionDays = useSettingsStore((s) => s.historyRetentionDays);
   const timeoutMs = useSettingsStore((s) => s.timeoutMs);
-  const proxyUrl = useSettingsStore((s) => s.proxyUrl);
   const verifySsl = useSettingsStore((s) => s.verifySsl);
+  const httpVersion = useSettingsStore((s) => s.httpVersion);
+  const ipFamily = useSettingsStore((s) => s.ipFamily);
+  const followRedirects = useSettingsStore((s) => s.followRedirects);
+  const maxRedirects = useSettingsStore((s) => s.maxRedirects);
+  const preserveMethodOnRedirect = useSettingsStore((s) => s.preserveMethodOnRedirect);
+  const decompressResponses = useSettingsStore((s) => s.decompressResponses);
 
   const setTheme = useSettingsStore((s) => s.setTheme);
   const setHistoryRetentionDays = useSettingsStore((s) => s.setHistoryRetentionDays);
   const setTimeoutMs = useSettingsStore((s) => s.setTimeoutMs);
-  const setProxyUrl = useSettingsStore((s) => s.setProxyUrl);
   const setVerifySsl = useSettingsStore((s) => s.setVerifySsl);
+  const setHttpVersion = useSettingsStore((s) => s.setHttpVersion);
+  const setIpFamily = useSettingsStore((s) => s.setIpFamily);
+  const setFollowRedirects = useSettingsStore((s) => s.setFollowRedirects);
+  const setMaxRedirects = useSettingsStore((s) => s.setMaxRedirects);
+  const setPreserveMethodOnRedirect = useSettingsStore((s) => s.setPreserveMethodOnRedirect);
+  const setDecompressResponses = useSettingsStore((s) => s.setDecompressResponses);
 
   const [showClearConfirm, setShowClearConfirm] = useState(false);
 
@@ -141,17 +180,37 @@ export function SettingsPanel() {
               />
             </div>
             <div className="space-y-2">
-              <Label htmlFor="proxy" className="text-xs">
-                Proxy URL
-              </Label>
-              <Input
-                id="proxy"
-                type="text"
-                placeholder="http://proxy:8080"
-                value={proxyUrl}
-                onChange={(e) => setProxyUrl(e.target.value)}
-                className="h-8 text-xs font-mono"
-              />
+              <Label className="text-xs">HTTP version</Label>
+              <Select
+                value={httpVersion}
+                onValueChange={(v: HttpVersionPref) => setHttpVersion(v)}
+              >
+                <SelectTrigger size="sm" className="w-full text-xs">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {httpVersionOptions.map(({ value, label }) => (
+                    <SelectItem key={value} value={value} className="text-xs">
+                      {label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+            <div className="space-y-2">
+              <Label className="text-xs">Address family</Label>
+              <Select value={ipFamily} onValueChange={(v: IpFamily) => setIpFamily(v)}>
+                <SelectTrigger size="sm" className="w-full text-xs">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {ipFamilyOptions.map(({ value, label }) => (
+                    <SelectItem key={value} value={value} className="text-xs">
+                      {label}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
             </div>
             <div className="flex items-center gap-2">
               <Checkbox
@@ -163,7 +222,79 @@ export function SettingsPanel() {
                 Verify SSL certificates
               </Label>
             </div>
+            <div className="flex items-center gap-2">
+              <Checkbox
+                id="decompress"
+                checked={decompressResponses}
+                onCheckedChange={(checked) => setDecompressResponses(checked === true)}
+              />
+              <Label htmlFor="decompress" className="text-xs cursor-pointer">
+                Decompress responses (gzip, deflate, br, zstd)
+              </Label>
+            </div>
+            <div className="flex items-center gap-2">
+              <Checkbox
+                id="follow-redirects"
+                checked={followRedirects}
+                onCheckedChange={(checked) => setFollowRedirects(checked === true)}
+              />
+              <Label htmlFor="follow-redirects" className="text-xs cursor-pointer">
+                Follow redirects
+              </Label>
+            </div>
+            {followRedirects && (
+              <>
+                <div className="space-y-2">
+                  <Label htmlFor="max-redirects" className="text-xs">
+                    Maximum redirects
+                  </Label>
+                  <Input
+                    id="max-redirects"
+                    type="number"
+                    min={0}
+                    max={50}
+                    value={maxRedirects}
+                    onChange={(e) => {
+                      const v = parseInt(e.target.value, 10);
+                      if (v >= 0) setMaxRedirects(v);
+                    }}
+                    className="h-8 text-xs"
+                  />
+                </div>
+                <div className="flex items-center gap-2">
+                  <Checkbox
+                    id="preserve-method"
+                    checked={preserveMethodOnRedirect}
+                    onCheckedChange={(checked) =>
+                      setPreserveMethodOnRedirect(checked === true)
+                    }
+                  />
+                  <Label htmlFor="preserve-method" className="text-xs cursor-pointer">
+                    Keep method and body on 307/308
+                  </Label>
+                </div>
+              </>
+            )}
           </section>
+
+          <Separator />
+
+          <RetrySection />
+          <Separator />
+
+          <EventStreamSection />
+
+          <Separator />
+
+          <ProxySection />
+
+          <Separator />
+
+          <ClientCertificatesSection />
+
+          <Separator />
+
+          <TrustSection />
         </div>
       </div>
 
@@ -24,6 +24,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { toast } from "sonner";
 import { useSettingsStore, type Theme } from "@/stores/settingsStore";
 import { useHistoryStore } from "@/stores/historyStore";
+import { ProxySection } from "./ProxySection";
 import { Sun, Moon, Monitor } from "lucide-react";
 
 interface SettingsSheetProps {
@@ -41,13 +42,11 @@ export function SettingsSheet({ open, onOpenChange }: SettingsSheetProps) {
   const theme = useSettingsStore((s) => s.theme);
   const historyRetentionDays = useSettingsStore((s) => s.historyRetentionDays);
   const timeoutMs = useSettingsStore((s) => s.timeoutMs);
-  const proxyUrl = useSettingsStore((s) => s.proxyUrl);
   const verifySsl = useSettingsStore((s) => s.verifySsl);
 
   const setTheme = useSettingsStore((s) => s.setTheme);
   const setHistoryRetentionDays = useSettingsStore((s) => s.setHistoryRetentionDays);
   const setTimeoutMs = useSettingsStore((s) => s.setTimeoutMs);
-  const setProxyUrl = useSettingsStore((s) => s.setProxyUrl);
   const setVerifySsl = useSettingsStore((s) => s.setVerifySsl);
 
   const [showClearConfirm, setShowClearConfirm] = useState(false);
@@ -158,19 +157,6 @@ export function SettingsSheet({ open, onOpenChange }: SettingsSheetProps) {
                   className="h-8 text-xs"
                 />
               </div>
-              <div className="space-y-2">
-                <Label htmlFor="proxy" className="text-xs">
-                  Proxy URL
-                </Label>
-                <Input
-                  id="proxy"
-                  type="text"
-                  placeholder="http://proxy:8080"
-                  value={proxyUrl}
-                  onChange={(e) => setProxyUrl(e.target.value)}
-                  className="h-8 text-xs font-mono"
-                />
-              </div>
               <div className="flex items-center gap-2">
                 <Checkbox
                   id="ssl"
@@ -184,6 +170,10 @@ export function SettingsSheet({ open, onOpenChange }: SettingsSheetProps) {
                 </Label>
               </div>
             </section>
+
+            <Separator />
+
+            <ProxySection />
           </div>
         </SheetContent>
       </Sheet>
@@ -0,0 +1,186 @@
+import { useCallback, useEffect, useState } from "react";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { Checkbox } from "@/components/ui/checkbox";
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { FileUp, Plus, Trash2 } from "lucide-react";
+import type { CaCertificate, CertificatePin } from "@/types/tls";
+
+function fileName(path: string): string {
+  return path.split(/[\\/]/).pop() ?? path;
+}
+
+function EntryRow({
+  enabled,
+  title,
+  detail,
+  onToggle,
+  onDelete,
+}: {
+  enabled: boolean;
+  title: string;
+  detail: string;
+  onToggle: (enabled: boolean) => void;
+  onDelete: () => void;
+}) {
+  return (
+    <div className="flex items-center gap-2">
+      <Checkbox checked={enabled} onCheckedChange={(checked) => onToggle(checked === true)} />
+      <div className="flex-1 min-w-0">
+        <div className="text-xs font-mono truncate">{title}</div>
+        <div className="text-[11px] text-muted-foreground font-mono truncate">{detail}</div>
+      </div>
+      <Button
+        variant="ghost"
+        size="icon-xs"
+        className="text-muted-foreground hover:text-destructive"
+        onClick={onDelete}
+      >
+        <Trash2 className="size-3" />
+      </Button>
+    </div>
+  );
+}
+
+export function TrustSection() {
+  const [cas, setCas] = useState<CaCertificate[]>([]);
+  const [pins, setPins] = useState<CertificatePin[]>([]);
+  const [caHost, setCaHost] = useState("");
+  const [pinHost, setPinHost] = useState("");
+  const [pinHash, setPinHash] = useState("");
+
+  const load = useCallback(async () => {
+    try {
+      const [cas, pins] = await Promise.all([
+        invoke<CaCertificate[]>("list_ca_certificates"),
+        invoke<CertificatePin[]>("list_certificate_pins"),
+      ]);
+      setCas(cas);
+      setPins(pins);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  }, []);
+
+  useEffect(() => {
+    load();
+  }, [load]);
+
+  // Every mutation follows the same shape: invoke, reload, toast on failure
+  const run = useCallback(
+    async (command: string, args: Record<string, unknown>) => {
+      try {
+        await invoke(command, args);
+        await load();
+        return true;
+      } catch (err) {
+        toast.error(String(err));
+        return false;
+      }
+    },
+    [load]
+  );
+
+  const handleAddCa = useCallback(async () => {
+    const path = await open({ multiple: false, directory: false });
+    if (typeof path !== "string") return;
+    const added = await run("create_ca_certificate", {
+      data: { host_pattern: caHost.trim() || null, path },
+    });
+    if (added) setCaHost("");
+  }, [caHost, run]);
+
+  const handleAddPin = useCallback(async () => {
+    const added = await run("create_certificate_pin", {
+      data: { host_pattern: pinHost, spki_sha256: pinHash },
+    });
+    if (added) {
+      setPinHost("");
+      setPinHash("");
+    }
+  }, [pinHost, pinHash, run]);
+
+  return (
+    <>
+      <section className="space-y-3">
+        <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+          Trusted CAs
+        </h3>
+        <p className="text-xs text-muted-foreground">
+          Trusted in addition to the system roots, for every host or only a matching one.
+        </p>
+        {cas.map((ca) => (
+          <EntryRow
+            key={ca.id}
+            enabled={ca.enabled}
+            title={ca.host_pattern ?? "All hosts"}
+            detail={fileName(ca.path)}
+            onToggle={(enabled) => run("update_ca_certificate", { id: ca.id, data: { enabled } })}
+            onDelete={() => run("delete_ca_certificate", { id: ca.id })}
+          />
+        ))}
+        <div className="flex gap-1">
+          <Input
+            placeholder="Host (blank for all)"
+            value={caHost}
+            onChange={(e) => setCaHost(e.target.value)}
+            className="h-7 text-xs font-mono"
+          />
+          <Button variant="outline" size="xs" className="gap-1 text-xs h-7" onClick={handleAddCa}>
+            <FileUp className="h-3.5 w-3.5" />
+            Add CA
+          </Button>
+        </div>
+      </section>
+
+      <section className="space-y-3">
+        <h3 className="text-xs font-medium text-muted-foreground uppercase tracking-wider">
+          Certificate Pins
+        </h3>
+        <p className="text-xs text-muted-foreground">
+          Connections to a pinned host fail unless the server key matches one of its pins.
+        </p>
+        {pins.map((pin) => (
+          <EntryRow
+            key={pin.id}
+            enabled={pin.enabled}
+            title={pin.host_pattern}
+            detail={`sha256/${pin.spki_sha256}`}
+            onToggle={(enabled) =>
+              run("update_certificate_pin", { id: pin.id, data: { enabled } })
+            }
+            onDelete={() => run("delete_certificate_pin", { id: pin.id })}
+          />
+        ))}
+        <div className="space-y-1">
+          <Input
+            placeholder="api.example.com"
+            value={pinHost}
+            onChange={(e) => setPinHost(e.target.value)}
+            className="h-7 text-xs font-mono"
+          />
+          <div className="flex gap-1">
+            <Input
+              placeholder="sha256/base64 SPKI hash"
+              value={pinHash}
+              onChange={(e) => setPinHash(e.target.value)}
+              className="h-7 text-xs font-mono"
+            />
+            <Button
+              variant="outline"
+              size="xs"
+              className="gap-1 text-xs h-7"
+              disabled={!pinHost.trim() || !pinHash.trim()}
+              onClick={handleAddPin}
+            >
+              <Plus className="h-3.5 w-3.5" />
+              Add Pin
+            </Button>
+          </div>
+        </div>
+      </section>
+    </>
+  );
+}
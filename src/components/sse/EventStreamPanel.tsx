@@ -0,0 +1,145 @@
+import { useEffect, useMemo, useRef, useState } from "react";
+import { Loader2, RefreshCw } from "lucide-react";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
+import { HeadersViewer } from "@/components/response/HeadersViewer";
+import { StatusBadge } from "@/components/response/StatusBadge";
+import { cn } from "@/lib/utils";
+import type { EventStreamState, SseEvent } from "@/types/sse";
+
+/** Characters shown before an event's data is expanded. */
+const PREVIEW_CHARS = 500;
+
+function formatOffset(ms: number): string {
+  return ms < 1000 ? `${ms}ms` : `${(ms / 1000).toFixed(2)}s`;
+}
+
+function EventRow({ event }: { event: SseEvent }) {
+  const [expanded, setExpanded] = useState(false);
+  const long = event.data.length > PREVIEW_CHARS;
+  const shown = expanded || !long ? event.data : `${event.data.slice(0, PREVIEW_CHARS)}…`;
+
+  return (
+    <div className="px-4 py-1.5 border-b border-border/30 text-xs">
+      <div className="flex items-center gap-2 font-mono text-[10px] text-muted-foreground">
+        <span
+          className={cn(
+            "rounded px-1 py-px font-semibold",
+            event.event === "message" ? "bg-muted" : "bg-method-sse/20 text-foreground",
+          )}
+        >
+          {event.event}
+        </span>
+        {event.id != null && <span>id {event.id}</span>}
+        {event.retry != null && <span>retry {event.retry}ms</span>}
+        <span className="ml-auto">{formatOffset(event.at_ms)}</span>
+      </div>
+      <pre
+        className={cn("mt-0.5 font-mono whitespace-pre-wrap break-all", long && "cursor-pointer")}
+        onClick={() => long && setExpanded(!expanded)}
+      >
+        {shown || "(empty)"}
+      </pre>
+    </div>
+  );
+}
+
+/** Events of an SSE tab as they arrive, and the stream's response headers. */
+export function EventStreamPanel({
+  stream,
+  loading,
+  error,
+}: {
+  stream: EventStreamState;
+  /** The stream is still open or reconnecting. */
+  loading: boolean;
+  error: string | null;
+}) {
+  const [filter, setFilter] = useState("");
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  const visible = useMemo(() => {
+    const query = filter.trim().toLowerCase();
+    if (!query) return stream.events;
+    return stream.events.filter(
+      (e) => e.data.toLowerCase().includes(query) || e.event.toLowerCase().includes(query),
+    );
+  }, [stream.events, filter]);
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ block: "end" });
+  }, [visible.length]);
+
+  return (
+    <Tabs defaultValue="events" className="flex h-full flex-col gap-0">
+      <div className="flex items-center gap-2 border-b border-border px-4 py-1.5 shrink-0">
+        <StatusBadge status={stream.status} statusText={stream.status_text} />
+        <TabsList variant="line" className="border-0 bg-transparent">
+          <TabsTrigger value="events" className="text-xs gap-1">
+            Events
+            {stream.events.length > 0 && (
+              <span className="text-[10px] font-semibold text-muted-foreground">
+                ({stream.events.length + stream.dropped})
+              </span>
+            )}
+          </TabsTrigger>
+          <TabsTrigger value="headers" className="text-xs">
+            Headers
+          </TabsTrigger>
+        </TabsList>
+        <div className="ml-auto flex items-center gap-2 text-xs text-muted-foreground whitespace-nowrap">
+          {stream.reconnecting ? (
+            <span className="flex items-center gap-1">
+              <RefreshCw className="size-3 animate-spin" />
+              Reconnecting in {formatOffset(stream.reconnecting.delay_ms)}
+            </span>
+          ) : (
+            loading && (
+              <span className="flex items-center gap-1">
+                <Loader2 className="size-3 animate-spin" />
+                Streaming
+              </span>
+            )
+          )}
+          {stream.reconnects > 0 && <span>{stream.reconnects} reconnects</span>}
+          <span>{stream.http_version}</span>
+        </div>
+      </div>
+
+      <TabsContent value="events" className="flex-1 min-h-0 flex flex-col">
+        {(error || stream.reconnecting?.error) && (
+          <p className="shrink-0 border-b border-border/50 px-4 py-2 text-xs text-destructive">
+            {error ?? stream.reconnecting?.error}
+          </p>
+        )}
+        <div className="shrink-0 border-b border-border/50 px-4 py-1.5">
+          <input
+            value={filter}
+            onChange={(e) => setFilter(e.target.value)}
+            placeholder="Filter events"
+            className="w-full bg-transparent text-xs outline-none placeholder:text-muted-foreground/40"
+          />
+        </div>
+        <ScrollArea className="flex-1 min-h-0">
+          {stream.dropped > 0 && (
+            <p className="px-4 py-1.5 text-[10px] text-muted-foreground">
+              {stream.dropped} earlier events not shown.
+            </p>
+          )}
+          {visible.length === 0 ? (
+            <p className="p-4 text-xs text-muted-foreground">
+              {loading ? "Waiting for events…" : "No events."}
+            </p>
+          ) : (
+            visible.map((event, i) => <EventRow key={i} event={event} />)
+          )}
+          <div ref={bottomRef} />
+        </ScrollArea>
+      </TabsContent>
+
+      <TabsContent value="headers" className="flex-1 min-h-0">
+        <HeadersViewer headers={stream.headers} />
+      </TabsContent>
+    </Tabs>
+  );
+}
@@ -0,0 +1,99 @@
+import { useEffect, useMemo, useRef, useState } from "react";
+import { ArrowDown, ArrowUp } from "lucide-react";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { cn } from "@/lib/utils";
+import type { WsMessage } from "@/types/websocket";
+
+/** A logged message; gRPC messages have no frame kind and read as text. */
+export type LogMessage = Omit<WsMessage, "kind"> & Partial<Pick<WsMessage, "kind">>;
+
+/** Characters shown before a message is expanded. */
+const PREVIEW_CHARS = 500;
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+function formatOffset(ms: number): string {
+  return ms < 1000 ? `${ms}ms` : `${(ms / 1000).toFixed(2)}s`;
+}
+
+function MessageRow({ message }: { message: LogMessage }) {
+  const [expanded, setExpanded] = useState(false);
+  const kind = message.kind ?? "text";
+  const isControl = kind !== "text" && kind !== "binary";
+  const long = message.data.length > PREVIEW_CHARS;
+  const shown = expanded || !long ? message.data : `${message.data.slice(0, PREVIEW_CHARS)}…`;
+
+  return (
+    <div
+      className={cn(
+        "flex items-start gap-2 px-4 py-1.5 border-b border-border/30 text-xs",
+        isControl && "text-muted-foreground",
+      )}
+    >
+      {message.direction === "sent" ? (
+        <ArrowUp className="size-3.5 mt-0.5 shrink-0 text-primary" />
+      ) : (
+        <ArrowDown className="size-3.5 mt-0.5 shrink-0 text-method-get" />
+      )}
+      <div className="min-w-0 flex-1">
+        <pre
+          className={cn(
+            "font-mono whitespace-pre-wrap break-all",
+            long && "cursor-pointer",
+          )}
+          onClick={() => long && setExpanded(!expanded)}
+        >
+          {kind === "text" ? shown : shown || "(empty)"}
+        </pre>
+        {message.truncated && (
+          <p className="text-[10px] text-muted-foreground">Stored truncated at 1MB.</p>
+        )}
+      </div>
+      <span className="shrink-0 font-mono text-[10px] text-muted-foreground whitespace-nowrap">
+        {kind !== "text" && `${kind} · `}
+        {formatSize(message.size_bytes)} · {formatOffset(message.at_ms)}
+      </span>
+    </div>
+  );
+}
+
+/** Frames in the order they crossed the wire; binary payloads as base64. */
+export function MessageLog({ messages }: { messages: LogMessage[] }) {
+  const [filter, setFilter] = useState("");
+  const bottomRef = useRef<HTMLDivElement>(null);
+
+  const visible = useMemo(() => {
+    const query = filter.trim().toLowerCase();
+    if (!query) return messages;
+    return messages.filter((m) => m.data.toLowerCase().includes(query));
+  }, [messages, filter]);
+
+  useEffect(() => {
+    bottomRef.current?.scrollIntoView({ block: "end" });
+  }, [visible.length]);
+
+  return (
+    <div className="flex h-full flex-col">
+      <div className="shrink-0 border-b border-border/50 px-4 py-1.5">
+        <input
+          value={filter}
+          onChange={(e) => setFilter(e.target.value)}
+          placeholder="Filter messages"
+          className="w-full bg-transparent text-xs outline-none placeholder:text-muted-foreground/40"
+        />
+      </div>
+      <ScrollArea className="flex-1 min-h-0">
+        {visible.length === 0 ? (
+          <p className="p-4 text-xs text-muted-foreground">No messages.</p>
+        ) : (
+          visible.map((message, i) => <MessageRow key={i} message={message} />)
+        )}
+        <div ref={bottomRef} />
+      </ScrollArea>
+    </div>
+  );
+}
@@ -0,0 +1,122 @@
+import { useCallback, useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { toast } from "sonner";
+import { AlertCircle, ArrowLeft, Trash2 } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { ScrollArea } from "@/components/ui/scroll-area";
+import { MessageLog } from "./MessageLog";
+import type { WsMessage, WsSession } from "@/types/websocket";
+
+function describeEnd(session: WsSession): string {
+  if (!session.closed_at) return "Open or interrupted";
+  if (session.error) return session.error;
+  if (session.close_code == null) return "Closed";
+  return `Closed ${session.close_code}${session.close_reason ? ` ${session.close_reason}` : ""}`;
+}
+
+/** Stored transcripts of earlier sessions, for a saved request or all of them. */
+export function SessionsList({
+  savedRequestId,
+  refreshKey,
+}: {
+  savedRequestId: string | null;
+  /** Changes whenever a session of this tab opens or closes. */
+  refreshKey: string;
+}) {
+  const [sessions, setSessions] = useState<WsSession[]>([]);
+  const [selected, setSelected] = useState<WsSession | null>(null);
+  const [messages, setMessages] = useState<WsMessage[]>([]);
+
+  const load = useCallback(async () => {
+    try {
+      setSessions(await invoke<WsSession[]>("list_ws_sessions", { savedRequestId }));
+    } catch (err) {
+      toast.error(String(err));
+    }
+  }, [savedRequestId]);
+
+  useEffect(() => {
+    load();
+  }, [load, refreshKey]);
+
+  const open = async (session: WsSession) => {
+    try {
+      setMessages(await invoke<WsMessage[]>("get_ws_messages", { sessionId: session.id }));
+      setSelected(session);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  const remove = async (id: string) => {
+    try {
+      await invoke("delete_ws_session", { id });
+      setSessions((s) => s.filter((session) => session.id !== id));
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  if (selected) {
+    return (
+      <div className="flex h-full flex-col">
+        <div className="flex items-center gap-2 border-b border-border/50 px-2 py-1.5 text-xs">
+          <Button
+            variant="ghost"
+            size="icon-xs"
+            onClick={() => setSelected(null)}
+            aria-label="Back to sessions"
+          >
+            <ArrowLeft className="size-3.5" />
+          </Button>
+          <span className="truncate font-mono">{selected.url}</span>
+          <span className="ml-auto shrink-0 text-muted-foreground">
+            {new Date(selected.opened_at + "Z").toLocaleString()}
+          </span>
+        </div>
+        <div className="flex-1 min-h-0">
+          <MessageLog messages={messages} />
+        </div>
+      </div>
+    );
+  }
+
+  if (sessions.length === 0) {
+    return <p className="p-4 text-xs text-muted-foreground">No sessions yet.</p>;
+  }
+
+  return (
+    <ScrollArea className="h-full">
+      <div className="p-2">
+        {sessions.map((session) => (
+          <button
+            key={session.id}
+            onClick={() => open(session)}
+            className="group w-full text-left px-2 py-1.5 rounded-md hover:bg-muted/50 flex items-center gap-2 text-xs transition-colors"
+          >
+            {session.error && <AlertCircle className="size-3 shrink-0 text-destructive" />}
+            <span className="min-w-0 flex-1">
+              <span className="block truncate font-mono">{session.url}</span>
+              <span className="block truncate text-[10px] text-muted-foreground">
+                {new Date(session.opened_at + "Z").toLocaleString()} · {session.message_count}{" "}
+                messages
+                {session.protocol && ` · ${session.protocol}`} · {describeEnd(session)}
+              </span>
+            </span>
+            <Button
+              variant="ghost"
+              size="icon-xs"
+              className="opacity-0 group-hover:opacity-100 size-5 text-muted-foreground hover:text-destructive"
+              onClick={(e) => {
+                e.stopPropagation();
+                remove(session.id);
+              }}
+            >
+              <Trash2 className="size-3" />
+            </Button>
+          </button>
+        ))}
+      </div>
+    </ScrollArea>
+  );
+}
@@ -0,0 +1,190 @@
+import { useState } from "react";
+import { toast } from "sonner";
+import { Cable, Eraser, Send } from "lucide-react";
+import { Button } from "@/components/ui/button";
+import { Tabs, TabsContent, TabsList, TabsTrigger } from "@/components/ui/tabs";
+import { HeadersViewer } from "@/components/response/HeadersViewer";
+import { MessageLog } from "./MessageLog";
+import { SessionsList } from "./SessionsList";
+import { useWsStore, type WsConnection } from "@/stores/wsStore";
+import { cn } from "@/lib/utils";
+
+function statusLabel(connection: WsConnection): string {
+  switch (connection.status) {
+    case "connecting":
+      return "Connecting";
+    case "open":
+      return "Connected";
+    case "closing":
+      return "Closing";
+    case "closed":
+      if (connection.close?.code != null) {
+        const reason = connection.close.reason ? ` ${connection.close.reason}` : "";
+        return `Closed ${connection.close.code}${reason}`;
+      }
+      return connection.handshake ? "Closed" : "Not connected";
+  }
+}
+
+function Composer({ tabId }: { tabId: string }) {
+  const send = useWsStore((s) => s.send);
+  const [data, setData] = useState("");
+  const [binary, setBinary] = useState(false);
+
+  const handleSend = async () => {
+    if (!data) return;
+    try {
+      await send(tabId, data, binary);
+    } catch (err) {
+      toast.error(String(err));
+    }
+  };
+
+  return (
+    <div className="shrink-0 border-t border-border p-2 space-y-2">
+      <textarea
+        value={data}
+        onChange={(e) => setData(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && (e.metaKey || e.ctrlKey)) {
+            e.preventDefault();
+            e.stopPropagation();
+            handleSend();
+          }
+        }}
+        placeholder={binary ? "Base64-encoded bytes" : "Message"}
+        rows={3}
+        className="w-full resize-none rounded-md border border-border bg-background px-3 py-2 font-mono text-xs outline-none focus:ring-2 focus:ring-ring/40"
+      />
+      <div className="flex items-center gap-2">
+        <div className="flex rounded-md border border-border text-[11px]">
+          {(["text", "binary"] as const).map((mode) => (
+            <button
+              key={mode}
+              onClick={() => setBinary(mode === "binary")}
+              className={cn(
+                "px-2 py-0.5 capitalize transition-colors",
+                (mode === "binary") === binary
+                  ? "bg-muted text-foreground"
+                  : "text-muted-foreground hover:text-foreground",
+              )}
+            >
+              {mode}
+            </button>
+          ))}
+        </div>
+        <Button size="xs" className="ml-auto gap-1 text-xs" onClick={handleSend} disabled={!data}>
+          <Send className="size-3" />
+          Send
+        </Button>
+      </div>
+    </div>
+  );
+}
+
+/** Live messages of a WebSocket tab, its handshake, and earlier sessions. */
+export function WebSocketPanel({
+  tabId,
+  savedRequestId,
+}: {
+  tabId: string;
+  savedRequestId: string | null;
+}) {
+  const connection = useWsStore((s) => s.connections[tabId]);
+  const clearMessages = useWsStore((s) => s.clearMessages);
+
+  const refreshKey = `${connection?.sessionId ?? ""}-${connection?.status ?? ""}`;
+
+  return (
+    <Tabs defaultValue="messages" className="flex h-full flex-col gap-0">
+      <div className="flex items-center gap-2 border-b border-border px-4 py-1.5 shrink-0">
+        {connection && (
+          <span className="flex items-center gap-1.5 text-xs whitespace-nowrap">
+            <span
+              className={cn(
+                "size-2 rounded-full",
+                connection.status === "open"
+                  ? "bg-method-get"
+                  : connection.status === "closed"
+                    ? "bg-muted-foreground/40"
+                    : "bg-method-put",
+              )}
+            />
+            {statusLabel(connection)}
+          </span>
+        )}
+        {connection?.handshake?.protocol && (
+          <span className="text-[10px] font-mono text-muted-foreground">
+            {connection.handshake.protocol}
+          </span>
+        )}
+        <TabsList variant="line" className="border-0 bg-transparent">
+          <TabsTrigger value="messages" className="text-xs gap-1">
+            Messages
+            {connection && connection.messages.length > 0 && (
+              <span className="text-[10px] font-semibold text-muted-foreground">
+                ({connection.messages.length})
+              </span>
+            )}
+          </TabsTrigger>
+          {connection?.handshake && (
+            <TabsTrigger value="handshake" className="text-xs">
+              Handshake
+            </TabsTrigger>
+          )}
+          <TabsTrigger value="sessions" className="text-xs">
+            Sessions
+          </TabsTrigger>
+        </TabsList>
+        <div className="ml-auto flex items-center gap-2">
+          {connection?.handshake && (
+            <span className="text-xs text-muted-foreground whitespace-nowrap">
+              {connection.handshake.time_ms}ms
+            </span>
+          )}
+          {connection && connection.messages.length > 0 && (
+            <Button
+              variant="ghost"
+              size="icon-xs"
+              onClick={() => clearMessages(tabId)}
+              aria-label="Clear messages"
+              title="Clear messages"
+            >
+              <Eraser className="size-3.5" />
+            </Button>
+          )}
+        </div>
+      </div>
+
+      <TabsContent value="messages" className="flex-1 min-h-0 flex flex-col">
+        {connection?.error && (
+          <p className="shrink-0 border-b border-border/50 px-4 py-2 text-xs text-destructive">
+            {connection.error}
+          </p>
+        )}
+        {connection ? (
+          <div className="flex-1 min-h-0">
+            <MessageLog messages={connection.messages} />
+          </div>
+        ) : (
+          <div className="flex flex-1 flex-col items-center justify-center gap-3 text-muted-foreground/50">
+            <Cable className="h-8 w-8" />
+            <p className="text-sm">Connect to start exchanging messages</p>
+            <p className="text-xs">⌘ Enter</p>
+          </div>
+        )}
+        {connection?.status === "open" && <Composer tabId={tabId} />}
+      </TabsContent>
+
+      {connection?.handshake && (
+        <TabsContent value="handshake" className="flex-1 min-h-0">
+          <HeadersViewer headers={connection.handshake.headers} />
+        </TabsContent>
+      )}
+
+      <TabsContent value="sessions" className="flex-1 min-h-0">
+        <SessionsList savedRequestId={savedRequestId} refreshKey={refreshKey} />
+      </TabsContent>
+    </Tabs>
+  );
+}
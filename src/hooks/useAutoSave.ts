@@ -39,6 +39,11 @@ export function useAutoSave() {
           params: activeTab.state.params,
           body: activeTab.state.bodyConfig,
           auth: activeTab.state.auth,
+          subprotocols: activeTab.state.subprotocols ?? [],
+          graphql: activeTab.state.graphql,
+          grpc: activeTab.state.grpc,
+          jsonrpc: activeTab.state.jsonrpc,
+          kind: activeTab.state.kind ?? "http",
         });
         updateSavedSnapshot(activeTab.id);
       } catch (err) {
@@ -46,6 +46,18 @@ interface HermesExport {
     auth: string;
     variables: string;
     sortOrder: number;
+    kind: string;
+    subprotocols: string;
+    graphqlQuery: string;
+    graphqlVariables: string;
+    graphqlOperationName: string;
+    grpcService: string;
+    grpcMethod: string;
+    grpcMessage: string;
+    grpcDescriptorSource: string;
+    grpcProtoFiles: string;
+    grpcImportPaths: string;
+    jsonrpcCalls: string;
   }[];
 }
 
@@ -88,6 +100,18 @@ export function exportCollectionToJson(
       auth: serializeAuth(r.auth),
       variables: serializeVariables(r.variables),
       sortOrder: r.sortOrder,
+      kind: r.kind,
+      subprotocols: JSON.stringify(r.subprotocols),
+      graphqlQuery: r.graphql.query,
+      graphqlVariables: r.graphql.variables,
+      graphqlOperationName: r.graphql.operationName,
+      grpcService: r.grpc.service,
+      grpcMethod: r.grpc.method,
+      grpcMessage: r.grpc.message,
+      grpcDescriptorSource: r.grpc.source,
+      grpcProtoFiles: JSON.stringify(r.grpc.protoFiles),
+      grpcImportPaths: JSON.stringify(r.grpc.importPaths),
+      jsonrpcCalls: JSON.stringify(r.jsonrpc),
     })),
   };
 
@@ -159,8 +183,24 @@ export function requestToCurl(
   } else if (body.type === "form-data") {
     for (const e of body.entries) {
       if (!e.enabled || !e.key) continue;
-      parts.push(`-F '${escapeShell(e.key)}=${escapeShell(e.value)}'`);
+      const value = e.filePath ? `@${e.filePath}` : e.value;
+      parts.push(`-F '${escapeShell(e.key)}=${escapeShell(value)}'`);
     }
+  } else if (body.type === "binary" && body.filePath) {
+    parts.push(`--data-binary '@${escapeShell(body.filePath)}'`);
+  }
+
+  // Unix socket: curl takes the socket separately from an http://localhost URL
+  const unix = url.match(/^http\+unix:\/\/([^/?#]*)(.*)$/i);
+  if (unix) {
+    let socket = unix[1];
+    try {
+      socket = decodeURIComponent(socket);
+    } catch {
+      // Leave a malformed escape as typed
+    }
+    parts.push(`--unix-socket '${escapeShell(socket)}'`);
+    url = `http://localhost${unix[2] || "/"}`;
   }
 
   // URL (always last)
@@ -0,0 +1,42 @@
+import type { GraphqlRequest } from "@/types/graphql";
+import type { RequestBody } from "@/types/request";
+
+export const EMPTY_GRAPHQL: GraphqlRequest = { query: "", variables: "", operationName: "" };
+
+/**
+ * The key an endpoint's schema is cached under: the URL as written, with
+ * variables resolved but before auth adds any credentials to it.
+ */
+export function graphqlEndpoint(url: string): string {
+  return url.trim();
+}
+
+/** Parses the variables editor's text; empty text means no variables. */
+export function parseGraphqlVariables(text: string): Record<string, unknown> | null {
+  if (!text.trim()) return null;
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(text);
+  } catch (err) {
+    throw new Error(
+      `Variables are not valid JSON: ${err instanceof Error ? err.message : String(err)}`,
+    );
+  }
+  if (parsed === null || typeof parsed !== "object" || Array.isArray(parsed)) {
+    throw new Error("Variables must be a JSON object");
+  }
+  return parsed as Record<string, unknown>;
+}
+
+/**
+ * The JSON body a GraphQL operation is sent as. Throws when the variables
+ * are not a JSON object.
+ */
+export function graphqlBody(graphql: GraphqlRequest): RequestBody {
+  const payload: Record<string, unknown> = { query: graphql.query };
+  const variables = parseGraphqlVariables(graphql.variables);
+  if (variables) payload.variables = variables;
+  const operationName = graphql.operationName.trim();
+  if (operationName) payload.operationName = operationName;
+  return { type: "raw", format: "json", content: JSON.stringify(payload) };
+}
@@ -0,0 +1,27 @@
+import type { GrpcRequest } from "@/types/grpc";
+
+export const EMPTY_GRPC: GrpcRequest = {
+  service: "",
+  method: "",
+  message: "",
+  source: "reflection",
+  protoFiles: [],
+  importPaths: [],
+};
+
+/** The descriptor source as the backend takes it. */
+export function grpcDescriptorSource(grpc: GrpcRequest) {
+  return grpc.source === "proto_files"
+    ? { type: "proto_files", files: grpc.protoFiles, import_paths: grpc.importPaths }
+    : { type: "reflection" };
+}
+
+/**
+ * The key a source's services are kept under: the server for reflection,
+ * the file set otherwise, mirroring the backend's descriptor cache.
+ */
+export function grpcSourceKey(url: string, grpc: GrpcRequest): string {
+  return grpc.source === "proto_files"
+    ? `files ${grpc.protoFiles.join(",")} | ${grpc.importPaths.join(",")}`
+    : `reflection ${url.trim()}`;
+}
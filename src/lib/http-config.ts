@@ -0,0 +1,45 @@
+import { useSettingsStore, parseStatusList } from "@/stores/settingsStore";
+import { resolveString } from "@/lib/variables";
+
+/**
+ * The `config` passed to `send_request` and `ws_connect`, built from the
+ * current settings. Proxy credentials may reference variables in `variableScope`.
+ */
+export function buildHttpConfig(variableScope?: Map<string, string>) {
+  const settings = useSettingsStore.getState();
+  const resolveSetting = (value: string) =>
+    variableScope ? resolveString(value, variableScope) : value;
+  return {
+    timeout_ms: settings.timeoutMs,
+    proxy: {
+      http: settings.httpProxy || null,
+      https: settings.httpsProxy || null,
+      socks: settings.socksProxy || null,
+      username: resolveSetting(settings.proxyUsername) || null,
+      password: resolveSetting(settings.proxyPassword) || null,
+      no_proxy: settings.noProxy || null,
+      use_system: settings.useSystemProxy,
+    },
+    verify_ssl: settings.verifySsl,
+    http_version: settings.httpVersion,
+    ip_family: settings.ipFamily,
+    follow_redirects: settings.followRedirects,
+    max_redirects: settings.maxRedirects,
+    preserve_method_on_redirect: settings.preserveMethodOnRedirect,
+    decompress: settings.decompressResponses,
+    retry: {
+      max_attempts: settings.retryMaxAttempts,
+      backoff: settings.retryBackoff,
+      initial_delay_ms: settings.retryDelayMs,
+      max_delay_ms: settings.retryMaxDelayMs,
+      statuses: parseStatusList(settings.retryStatuses),
+      network_errors: settings.retryNetworkErrors,
+      respect_retry_after: settings.retryRespectRetryAfter,
+      non_idempotent: settings.retryNonIdempotent,
+    },
+    reconnect: {
+      enabled: settings.sseReconnect,
+      max_attempts: settings.sseMaxReconnects,
+    },
+  };
+}
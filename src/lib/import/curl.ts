@@ -74,6 +74,7 @@ export function parseCurl(input: string): CurlImport {
   let bodyStr: string | null = null;
   let auth: RequestAuth = { type: "none" };
   let methodExplicit = false;
+  let unixSocket: string | null = null;
 
   while (i < tokens.length) {
     const token = tokens[i];
@@ -115,6 +116,9 @@ export function parseCurl(input: string): CurlImport {
     } else if (token === "-b" || token === "--cookie") {
       i++;
       headers.push({ key: "Cookie", value: tokens[i] ?? "", enabled: true });
+    } else if (token === "--unix-socket") {
+      i++;
+      unixSocket = tokens[i] ?? null;
     } else if (
       token === "--compressed" ||
       token === "-s" ||
@@ -140,6 +144,12 @@ export function parseCurl(input: string): CurlImport {
     i++;
   }
 
+  // Keep the socket in the URL itself: http+unix://<encoded path>/path
+  if (unixSocket) {
+    const rest = url.replace(/^(https?:\/\/)?[^/?#]*/i, "");
+    url = `http+unix://${encodeURIComponent(unixSocket)}${rest || "/"}`;
+  }
+
   // If body is set but no explicit method, use POST
   if (bodyStr && !methodExplicit) {
     method = "POST";
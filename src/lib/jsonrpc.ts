@@ -0,0 +1,161 @@
+import type {
+  JsonRpcCall,
+  JsonRpcError,
+  JsonRpcMatch,
+  JsonRpcOutcome,
+  JsonRpcSentCall,
+} from "@/types/jsonrpc";
+import type { RequestBody } from "@/types/request";
+
+export const EMPTY_JSONRPC_CALL: JsonRpcCall = {
+  method: "",
+  params: "",
+  notification: false,
+  enabled: true,
+};
+
+const ERROR_NAMES: Record<number, string> = {
+  [-32700]: "Parse error",
+  [-32600]: "Invalid Request",
+  [-32601]: "Method not found",
+  [-32602]: "Invalid params",
+  [-32603]: "Internal error",
+};
+
+/** The spec's name for a reserved error code, or `null` for application codes. */
+export function jsonrpcErrorName(code: number): string | null {
+  if (ERROR_NAMES[code]) return ERROR_NAMES[code];
+  if (code >= -32099 && code <= -32000) return "Server error";
+  return null;
+}
+
+/** Parses a call's params; blank text means the call has none. */
+function parseParams(call: JsonRpcCall): unknown[] | Record<string, unknown> | undefined {
+  if (!call.params.trim()) return undefined;
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(call.params);
+  } catch (err) {
+    throw new Error(
+      `Params of ${call.method} are not valid JSON: ${err instanceof Error ? err.message : String(err)}`,
+    );
+  }
+  if (parsed === null || typeof parsed !== "object") {
+    throw new Error(`Params of ${call.method} must be a JSON array or object`);
+  }
+  return parsed as unknown[] | Record<string, unknown>;
+}
+
+/**
+ * The body the enabled calls are sent as: one call as a request object,
+ * several as a batch. Calls are numbered from 1 in order; notifications
+ * get no ID. Throws when a call has no method or malformed params.
+ */
+export function jsonrpcPayload(calls: JsonRpcCall[]): {
+  body: RequestBody;
+  sent: JsonRpcSentCall[];
+} {
+  const enabled = calls.filter((c) => c.enabled);
+  if (enabled.length === 0) throw new Error("Add a call to send");
+
+  const sent: JsonRpcSentCall[] = [];
+  const requests = enabled.map((call, i) => {
+    const method = call.method.trim();
+    if (!method) throw new Error(`Call ${i + 1} has no method`);
+    const request: Record<string, unknown> = { jsonrpc: "2.0", method };
+    const params = parseParams({ ...call, method });
+    if (params !== undefined) request.params = params;
+    const id = call.notification ? null : i + 1;
+    if (id !== null) request.id = id;
+    sent.push({ id, method });
+    return request;
+  });
+
+  const payload = requests.length === 1 ? requests[0] : requests;
+  return {
+    body: { type: "raw", format: "json", content: JSON.stringify(payload) },
+    sent,
+  };
+}
+
+export function jsonrpcBody(calls: JsonRpcCall[]): RequestBody {
+  return jsonrpcPayload(calls).body;
+}
+
+function asError(value: unknown): JsonRpcError | null {
+  if (value === null || typeof value !== "object") return null;
+  const { code, message, data } = value as Record<string, unknown>;
+  const error: JsonRpcError = {
+    code: typeof code === "number" ? code : 0,
+    message: typeof message === "string" ? message : JSON.stringify(value),
+  };
+  if (data !== undefined) error.data = data;
+  return error;
+}
+
+/**
+ * Pairs each sent call with its response by ID. Error objects are kept
+ * apart from results; responses nobody asked for are reported as unmatched.
+ */
+export function matchJsonRpcResponse(body: string, sent: JsonRpcSentCall[]): JsonRpcMatch {
+  const unanswered = (call: JsonRpcSentCall): JsonRpcOutcome =>
+    call.id === null ? { kind: "notification", call } : { kind: "missing", call };
+
+  // A server answers a batch of notifications with nothing at all
+  if (!body.trim()) {
+    const expected = sent.some((c) => c.id !== null);
+    return {
+      outcomes: sent.map(unanswered),
+      unmatched: [],
+      problem: expected ? "The response is empty" : null,
+    };
+  }
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(body);
+  } catch {
+    return { outcomes: sent.map(unanswered), unmatched: [], problem: "The response is not JSON" };
+  }
+
+  const items = Array.isArray(parsed) ? parsed : [parsed];
+  const byId = new Map<string, Record<string, unknown>>();
+  const unmatched: JsonRpcMatch["unmatched"] = [];
+  const expected = new Set(sent.filter((c) => c.id !== null).map((c) => String(c.id)));
+  for (const item of items) {
+    if (item === null || typeof item !== "object" || Array.isArray(item)) {
+      unmatched.push({ id: null, error: null });
+      continue;
+    }
+    const response = item as Record<string, unknown>;
+    const key = response.id == null ? null : String(response.id);
+    if (key !== null && expected.has(key) && !byId.has(key)) {
+      byId.set(key, response);
+    } else {
+      const entry: JsonRpcMatch["unmatched"][number] = {
+        id: response.id ?? null,
+        error: asError(response.error),
+      };
+      if ("result" in response) entry.result = response.result;
+      unmatched.push(entry);
+    }
+  }
+
+  const outcomes = sent.map((call): JsonRpcOutcome => {
+    const response = call.id === null ? undefined : byId.get(String(call.id));
+    if (!response) return unanswered(call);
+    if ("error" in response && response.error != null) {
+      return { kind: "error", call, error: asError(response.error)! };
+    }
+    return { kind: "result", call, result: response.result ?? null };
+  });
+
+  // Neither a result nor an error anywhere: likely not a JSON-RPC endpoint
+  const shapeless =
+    byId.size === 0 && unmatched.every((u) => u.error === null && !("result" in u));
+  return {
+    outcomes,
+    unmatched,
+    problem: shapeless ? "The response is not a JSON-RPC response" : null,
+  };
+}
@@ -3,6 +3,8 @@ import type {
   ParamEntry,
   RequestBody,
   RequestAuth,
+  SendBody,
+  FormPart,
 } from "@/types/request";
 
 export function parseQueryParams(url: string): ParamEntry[] {
@@ -32,8 +34,12 @@ export function buildUrlWithParams(url: string, params: ParamEntry[]): string {
   }
 }
 
+function text(content: string): SendBody | null {
+  return content ? { type: "text", content } : null;
+}
+
 export function serializeBody(bodyConfig: RequestBody): {
-  body: string | null;
+  body: SendBody | null;
   contentType: string | null;
 } {
   switch (bodyConfig.type) {
@@ -46,7 +52,7 @@ export function serializeBody(bodyConfig: RequestBody): {
         text: "text/plain",
       };
       return {
-        body: bodyConfig.content || null,
+        body: text(bodyConfig.content),
         contentType: formatToContentType[bodyConfig.format],
       };
     }
@@ -57,28 +63,40 @@ export function serializeBody(bodyConfig: RequestBody): {
       }
       const str = params.toString();
       return {
-        body: str || null,
+        body: text(str),
         contentType: "application/x-www-form-urlencoded",
       };
     }
     case "form-data": {
-      const boundary = "----HermesBoundary" + Date.now();
       const entries = bodyConfig.entries.filter((e) => e.enabled && e.key);
       if (entries.length === 0) return { body: null, contentType: null };
-      let str = "";
-      for (const e of entries) {
-        str += `--${boundary}\r\n`;
-        str += `Content-Disposition: form-data; name="${e.key}"\r\n\r\n`;
-        str += `${e.value}\r\n`;
-      }
-      str += `--${boundary}--\r\n`;
-      return {
-        body: str,
-        contentType: `multipart/form-data; boundary=${boundary}`,
-      };
+      // Built in Rust, which also sets the Content-Type with its boundary
+      const parts: FormPart[] = entries.map((e) =>
+        e.filePath
+          ? {
+              kind: "file",
+              name: e.key,
+              path: e.filePath,
+              filename: e.fileName || null,
+              content_type: e.contentType || null,
+            }
+          : {
+              kind: "text",
+              name: e.key,
+              value: e.value,
+              content_type: e.contentType || null,
+            },
+      );
+      return { body: { type: "multipart", parts }, contentType: null };
     }
     case "binary":
-      return { body: null, contentType: null };
+      // Content type is sniffed from the file on the Rust side
+      return {
+        body: bodyConfig.filePath
+          ? { type: "file", path: bodyConfig.filePath }
+          : null,
+        contentType: null,
+      };
   }
 }
 
@@ -1,6 +1,90 @@
 import { invoke } from "@tauri-apps/api/core";
 import { supabase } from "@/lib/supabase";
 import { useCollectionStore } from "@/stores/collectionStore";
+import type { SavedRequest } from "@/types/collection";
+
+let kindConfigSupported: Promise<boolean> | null = null;
+
+/**
+ * Whether the project has `requests.kind_config`, which carries the settings
+ * only some request kinds use as a JSON object keyed by local column name.
+ * Projects without it (see supabase/migrations) still sync everything else.
+ */
+function supportsKindConfig(): Promise<boolean> {
+  kindConfigSupported ??= (async () => {
+    const { error } = await supabase.from("requests").select("kind_config").limit(1);
+    // Only a missing column is remembered; other failures are retried next time
+    if (error && error.code !== "42703") kindConfigSupported = null;
+    return !error;
+  })();
+  return kindConfigSupported;
+}
+
+/** `config` as the `kind_config` field of a cloud write, if the project has the column. */
+export async function kindConfigField(
+  config: Record<string, unknown>,
+): Promise<{ kind_config?: Record<string, unknown> }> {
+  return (await supportsKindConfig()) ? { kind_config: config } : {};
+}
+
+/** A request's `kind_config`. */
+export function kindConfigForCloud(req: SavedRequest): Record<string, unknown> {
+  return {
+    kind: req.kind,
+    subprotocols: req.subprotocols,
+    graphql_query: req.graphql.query,
+    graphql_variables: req.graphql.variables,
+    graphql_operation_name: req.graphql.operationName,
+    grpc_service: req.grpc.service,
+    grpc_method: req.grpc.method,
+    grpc_message: req.grpc.message,
+    grpc_descriptor_source: req.grpc.source,
+    grpc_proto_files: req.grpc.protoFiles,
+    grpc_import_paths: req.grpc.importPaths,
+    jsonrpc_calls: req.jsonrpc,
+  };
+}
+
+const JSON_KIND_COLUMNS = [
+  "subprotocols",
+  "grpc_proto_files",
+  "grpc_import_paths",
+  "jsonrpc_calls",
+] as const;
+
+const TEXT_KIND_COLUMNS = [
+  "kind",
+  "graphql_query",
+  "graphql_variables",
+  "graphql_operation_name",
+  "grpc_service",
+  "grpc_method",
+  "grpc_message",
+  "grpc_descriptor_source",
+] as const;
+
+/** The same `kind_config` from a local row, where JSON columns are stored as text. */
+export function kindConfigFromRow(row: Record<string, unknown>): Record<string, unknown> {
+  const config: Record<string, unknown> = {};
+  for (const key of TEXT_KIND_COLUMNS) config[key] = row[key];
+  for (const key of JSON_KIND_COLUMNS) config[key] = JSON.parse(row[key] as string);
+  return config;
+}
+
+/**
+ * A cloud row's `kind_config` as the local columns `upsert_from_cloud` takes.
+ * Rows without one, or keys it lacks, are sent as null, which keeps the local
+ * request's value (or the default, for a new request).
+ */
+export function kindColumnsFromCloud(row: Record<string, unknown>): Record<string, string | null> {
+  const config = (row.kind_config ?? {}) as Record<string, unknown>;
+  const columns: Record<string, string | null> = {};
+  for (const key of TEXT_KIND_COLUMNS) columns[key] = (config[key] as string | undefined) ?? null;
+  for (const key of JSON_KIND_COLUMNS) {
+    columns[key] = config[key] == null ? null : JSON.stringify(config[key]);
+  }
+  return columns;
+}
 
 export async function shareCollectionToTeam(
   collectionId: string,
@@ -98,6 +182,7 @@ export async function shareCollectionToTeam(
         auth: req.auth,
         variables: req.variables,
         sort_order: req.sortOrder,
+        ...(await kindConfigField(kindConfigForCloud(req))),
         updated_by: userId,
       })
       .select()
@@ -127,6 +127,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.url).toBe("http://localhost:3000/api/users");
@@ -144,6 +145,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.headers[0].key).toBe("X-Key");
@@ -162,6 +164,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.params[0].value).toBe("abc123");
@@ -179,6 +182,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.bodyConfig).toEqual({
@@ -203,6 +207,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     if (resolved.bodyConfig.type === "form-data") {
@@ -223,6 +228,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.auth).toEqual({ type: "bearer", token: "abc123" });
@@ -240,6 +246,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.auth).toEqual({ type: "basic", username: "admin", password: "password" });
@@ -257,6 +264,7 @@ describe("resolveRequest", () => {
       response: null,
       loading: false,
       error: null,
+      requestId: null,
     };
     const resolved = resolveRequest(state, scope);
     expect(resolved.auth).toEqual({ type: "apikey", key: "X-Key", value: "secret", addTo: "header" });
@@ -311,8 +319,8 @@ describe("getFolderChain", () => {
 describe("buildScopeForRequest", () => {
   it("applies full priority chain: Request > Folder > Collection > Env > Global", () => {
     const scope = buildScopeForRequest({
-      globalEnv: { id: "global", name: "Global", variables: [{ key: "a", value: "global" }, { key: "g", value: "only-global" }], isGlobal: true, sortOrder: 0, updatedAt: "", createdAt: "" },
-      activeEnv: { id: "dev", name: "Dev", variables: [{ key: "a", value: "dev" }, { key: "e", value: "only-env" }], isGlobal: false, sortOrder: 1, updatedAt: "", createdAt: "" },
+      globalEnv: { id: "global", name: "Global", variables: [{ key: "a", value: "global" }, { key: "g", value: "only-global" }], dnsOverrides: [], isGlobal: true, sortOrder: 0, updatedAt: "", createdAt: "" },
+      activeEnv: { id: "dev", name: "Dev", variables: [{ key: "a", value: "dev" }, { key: "e", value: "only-env" }], dnsOverrides: [], isGlobal: false, sortOrder: 1, updatedAt: "", createdAt: "" },
       collection: { id: "c1", name: "API", description: "", defaultHeaders: [], defaultAuth: { type: "none" }, variables: [{ key: "a", value: "collection" }], sortOrder: 0, updatedAt: "", createdAt: "", teamId: null, cloudId: null, syncedAt: null, dirty: 0 },
       folderChain: [
         { id: "f1", collectionId: "c1", parentFolderId: null, name: "Users", defaultHeaders: [], defaultAuth: { type: "none" }, variables: [{ key: "a", value: "folder" }], sortOrder: 0, createdAt: "", cloudId: null, syncedAt: null, dirty: 0 },
@@ -330,7 +338,7 @@ describe("buildScopeForRequest", () => {
 
   it("works with no active environment", () => {
     const scope = buildScopeForRequest({
-      globalEnv: { id: "global", name: "Global", variables: [{ key: "x", value: "1" }], isGlobal: true, sortOrder: 0, updatedAt: "", createdAt: "" },
+      globalEnv: { id: "global", name: "Global", variables: [{ key: "x", value: "1" }], dnsOverrides: [], isGlobal: true, sortOrder: 0, updatedAt: "", createdAt: "" },
       activeEnv: undefined,
       collection: undefined,
       folderChain: [],
@@ -3,6 +3,9 @@ import type { TabRequestState } from "@/stores/tabStore";
 import type { Collection, Folder } from "@/types/collection";
 import type { Environment } from "@/types/environment";
 import type { HeaderEntry, ParamEntry, RequestBody, RequestAuth } from "@/types/request";
+import type { GraphqlRequest } from "@/types/graphql";
+import type { GrpcRequest } from "@/types/grpc";
+import type { JsonRpcCall } from "@/types/jsonrpc";
 
 const VARIABLE_PATTERN = /\{\{([^{}]+?)\}\}/g;
 
@@ -70,7 +73,7 @@ export function buildScopeWithAttribution(
   return scope;
 }
 
-function resolveHeaders(
+export function resolveHeaders(
   headers: HeaderEntry[],
   scope: Map<string, string>,
 ): HeaderEntry[] {
@@ -115,6 +118,26 @@ function resolveBody(
   }
 }
 
+/** Only the variables are resolved; `{{` has no place in a GraphQL document. */
+function resolveGraphql(
+  graphql: GraphqlRequest,
+  scope: Map<string, string>,
+): GraphqlRequest {
+  return { ...graphql, variables: resolveString(graphql.variables, scope) };
+}
+
+function resolveGrpc(grpc: GrpcRequest, scope: Map<string, string>): GrpcRequest {
+  return { ...grpc, message: resolveString(grpc.message, scope) };
+}
+
+function resolveJsonRpc(calls: JsonRpcCall[], scope: Map<string, string>): JsonRpcCall[] {
+  return calls.map((c) => ({
+    ...c,
+    method: resolveString(c.method, scope),
+    params: resolveString(c.params, scope),
+  }));
+}
+
 function resolveAuth(
   auth: RequestAuth,
   scope: Map<string, string>,
@@ -154,6 +177,10 @@ export function resolveRequest(
     params: resolveParams(state.params, scope),
     bodyConfig: resolveBody(state.bodyConfig, scope),
     auth: resolveAuth(state.auth, scope),
+    subprotocols: state.subprotocols?.map((p) => resolveString(p, scope)),
+    graphql: state.graphql && resolveGraphql(state.graphql, scope),
+    grpc: state.grpc && resolveGrpc(state.grpc, scope),
+    jsonrpc: state.jsonrpc && resolveJsonRpc(state.jsonrpc, scope),
   };
 }
 
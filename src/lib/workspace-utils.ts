@@ -2,6 +2,7 @@ import type {
   Collection,
   Folder,
   SavedRequest,
+  RequestKind,
 } from "@/types/collection";
 import type {
   HeaderEntry,
@@ -9,9 +10,16 @@ import type {
   RequestBody,
   RequestAuth,
   HttpMethod,
+  ResponseHeader,
+  BodyEncoding,
+  RedirectHop,
+  RetryAttempt,
 } from "@/types/request";
-import type { Variable, Environment } from "@/types/environment";
+import type { Variable, Environment, DnsOverride } from "@/types/environment";
 import type { HistoryEntry } from "@/types/history";
+import type { SseEvent } from "@/types/sse";
+import type { CertificateSummary } from "@/types/tls";
+import type { JsonRpcCall } from "@/types/jsonrpc";
 
 // Raw shapes from Rust IPC (snake_case, JSON strings for complex fields)
 interface RawCollection {
@@ -63,12 +71,25 @@ interface RawRequest {
   cloud_id: string | null;
   synced_at: string | null;
   dirty: number;
+  kind: string;
+  subprotocols: string;
+  graphql_query: string;
+  graphql_variables: string;
+  graphql_operation_name: string;
+  grpc_service: string;
+  grpc_method: string;
+  grpc_message: string;
+  grpc_descriptor_source: string;
+  grpc_proto_files: string;
+  grpc_import_paths: string;
+  jsonrpc_calls: string;
 }
 
 interface RawEnvironment {
   id: string;
   name: string;
   variables: string;
+  dns_overrides: string;
   is_global: boolean;
   sort_order: number;
   updated_at: string;
@@ -91,6 +112,12 @@ function parseJson<T>(json: string, fallback: T): T {
   }
 }
 
+const REQUEST_KINDS: RequestKind[] = ["http", "sse", "websocket", "graphql", "grpc", "jsonrpc"];
+
+function parseKind(kind: string): RequestKind {
+  return REQUEST_KINDS.includes(kind as RequestKind) ? (kind as RequestKind) : "http";
+}
+
 function parseCollection(raw: RawCollection): Collection {
   return {
     id: raw.id,
@@ -145,6 +172,22 @@ function parseRequest(raw: RawRequest): SavedRequest {
     cloudId: raw.cloud_id ?? null,
     syncedAt: raw.synced_at ?? null,
     dirty: raw.dirty ?? 0,
+    kind: parseKind(raw.kind),
+    subprotocols: parseJson<string[]>(raw.subprotocols, []),
+    graphql: {
+      query: raw.graphql_query ?? "",
+      variables: raw.graphql_variables ?? "",
+      operationName: raw.graphql_operation_name ?? "",
+    },
+    grpc: {
+      service: raw.grpc_service ?? "",
+      method: raw.grpc_method ?? "",
+      message: raw.grpc_message ?? "",
+      source: raw.grpc_descriptor_source === "proto_files" ? "proto_files" : "reflection",
+      protoFiles: parseJson<string[]>(raw.grpc_proto_files, []),
+      importPaths: parseJson<string[]>(raw.grpc_import_paths, []),
+    },
+    jsonrpc: parseJson<JsonRpcCall[]>(raw.jsonrpc_calls, []),
   };
 }
 
@@ -153,6 +196,7 @@ function parseEnvironment(raw: RawEnvironment): Environment {
     id: raw.id,
     name: raw.name,
     variables: parseJson<Variable[]>(raw.variables, []),
+    dnsOverrides: parseJson<DnsOverride[]>(raw.dns_overrides, []),
     isGlobal: raw.is_global,
     sortOrder: raw.sort_order,
     updatedAt: raw.updated_at,
@@ -196,8 +240,30 @@ export interface RawHistoryEntry {
   response_body: string | null;
   response_time_ms: number | null;
   response_size_bytes: number | null;
+  response_wire_size_bytes: number | null;
   response_body_truncated: boolean;
   error: string | null;
+  cancelled: boolean;
+  timing_dns_ms: number | null;
+  timing_connect_ms: number | null;
+  timing_tls_ms: number | null;
+  timing_ttfb_ms: number | null;
+  timing_download_ms: number | null;
+  response_body_encoding: BodyEncoding;
+  response_body_raw: string | null;
+  response_mime_type: string | null;
+  response_charset: string | null;
+  response_url: string | null;
+  redirect_chain: string | null;
+  http_version: string | null;
+  remote_addr: string | null;
+  tls_version: string | null;
+  tls_resumed: boolean;
+  peer_certificate: string | null;
+  certificate_chain: string | null;
+  certificate_expires_at: string | null;
+  retry_attempts: string | null;
+  sse_events: string | null;
   saved_request_id: string | null;
   timestamp: string;
 }
@@ -214,13 +280,43 @@ export function parseHistoryEntry(raw: RawHistoryEntry): HistoryEntry {
     responseStatus: raw.response_status,
     responseStatusText: raw.response_status_text,
     responseHeaders: raw.response_headers
-      ? parseJson<Record<string, string>>(raw.response_headers, {})
+      ? parseJson<ResponseHeader[]>(raw.response_headers, [])
       : null,
     responseBody: raw.response_body,
     responseTimeMs: raw.response_time_ms,
     responseSizeBytes: raw.response_size_bytes,
+    responseWireSizeBytes: raw.response_wire_size_bytes,
     responseBodyTruncated: raw.response_body_truncated,
     error: raw.error,
+    cancelled: raw.cancelled,
+    timingDnsMs: raw.timing_dns_ms,
+    timingConnectMs: raw.timing_connect_ms,
+    timingTlsMs: raw.timing_tls_ms,
+    timingTtfbMs: raw.timing_ttfb_ms,
+    timingDownloadMs: raw.timing_download_ms,
+    responseBodyEncoding: raw.response_body_encoding,
+    responseBodyRaw: raw.response_body_raw,
+    responseMimeType: raw.response_mime_type,
+    responseCharset: raw.response_charset,
+    responseUrl: raw.response_url,
+    redirectChain: raw.redirect_chain
+      ? parseJson<RedirectHop[]>(raw.redirect_chain, [])
+      : null,
+    httpVersion: raw.http_version,
+    remoteAddr: raw.remote_addr,
+    tlsVersion: raw.tls_version,
+    tlsResumed: raw.tls_resumed,
+    peerCertificate: raw.peer_certificate
+      ? parseJson<CertificateSummary | null>(raw.peer_certificate, null)
+      : null,
+    certificateChain: raw.certificate_chain
+      ? parseJson<CertificateSummary[]>(raw.certificate_chain, [])
+      : null,
+    certificateExpiresAt: raw.certificate_expires_at,
+    retryAttempts: raw.retry_attempts
+      ? parseJson<RetryAttempt[]>(raw.retry_attempts, [])
+      : null,
+    sseEvents: raw.sse_events ? parseJson<SseEvent[]>(raw.sse_events, []) : null,
     savedRequestId: raw.saved_request_id,
     timestamp: raw.timestamp,
   };
@@ -31,7 +31,7 @@ interface CollectionState {
   createFolder: (collectionId: string, name: string, parentFolderId?: string) => Promise<Folder>;
   updateFolder: (id: string, updates: Partial<Pick<Folder, "name" | "defaultHeaders" | "defaultAuth" | "variables">>) => Promise<void>;
   deleteFolder: (id: string) => Promise<void>;
-  saveRequest: (data: Omit<SavedRequest, "id" | "sortOrder" | "updatedAt" | "createdAt" | "cloudId" | "syncedAt" | "dirty">) => Promise<SavedRequest>;
+  saveRequest: (data: Omit<SavedRequest, "id" | "sortOrder" | "updatedAt" | "createdAt" | "cloudId" | "syncedAt" | "dirty" | "kind" | "subprotocols" | "graphql" | "grpc" | "jsonrpc"> & Partial<Pick<SavedRequest, "kind" | "subprotocols" | "graphql" | "grpc" | "jsonrpc">>) => Promise<SavedRequest>;
   updateSavedRequest: (id: string, data: Partial<Omit<SavedRequest, "id" | "collectionId" | "sortOrder" | "updatedAt" | "createdAt" | "cloudId" | "syncedAt" | "dirty">>) => Promise<void>;
   deleteSavedRequest: (id: string) => Promise<void>;
   duplicateRequest: (id: string) => Promise<SavedRequest>;
@@ -166,6 +166,18 @@ export const useCollectionStore = create<CollectionState>((set, get) => ({
         params: serializeParams(data.params),
         body: serializeBody(data.body),
         auth: serializeAuth(data.auth),
+        kind: data.kind ?? "http",
+        subprotocols: JSON.stringify(data.subprotocols ?? []),
+        graphql_query: data.graphql?.query ?? "",
+        graphql_variables: data.graphql?.variables ?? "",
+        graphql_operation_name: data.graphql?.operationName ?? "",
+        grpc_service: data.grpc?.service ?? "",
+        grpc_method: data.grpc?.method ?? "",
+        grpc_message: data.grpc?.message ?? "",
+        grpc_descriptor_source: data.grpc?.source ?? "reflection",
+        grpc_proto_files: JSON.stringify(data.grpc?.protoFiles ?? []),
+        grpc_import_paths: JSON.stringify(data.grpc?.importPaths ?? []),
+        jsonrpc_calls: JSON.stringify(data.jsonrpc ?? []),
       },
     };
     // eslint-disable-next-line @typescript-eslint/no-explicit-any
@@ -186,6 +198,22 @@ export const useCollectionStore = create<CollectionState>((set, get) => ({
     if (data.auth !== undefined) payload.auth = serializeAuth(data.auth);
     if (data.folderId !== undefined) payload.folder_id = data.folderId;
     if (data.variables !== undefined) payload.variables = serializeVariables(data.variables);
+    if (data.kind !== undefined) payload.kind = data.kind;
+    if (data.subprotocols !== undefined) payload.subprotocols = JSON.stringify(data.subprotocols);
+    if (data.graphql !== undefined) {
+      payload.graphql_query = data.graphql.query;
+      payload.graphql_variables = data.graphql.variables;
+      payload.graphql_operation_name = data.graphql.operationName;
+    }
+    if (data.grpc !== undefined) {
+      payload.grpc_service = data.grpc.service;
+      payload.grpc_method = data.grpc.method;
+      payload.grpc_message = data.grpc.message;
+      payload.grpc_descriptor_source = data.grpc.source;
+      payload.grpc_proto_files = JSON.stringify(data.grpc.protoFiles);
+      payload.grpc_import_paths = JSON.stringify(data.grpc.importPaths);
+    }
+    if (data.jsonrpc !== undefined) payload.jsonrpc_calls = JSON.stringify(data.jsonrpc);
     await invoke("update_request", { id, data: payload });
     set((s) => ({
       requests: s.requests.map((r) =>
@@ -12,7 +12,10 @@ interface EnvironmentState {
 
   // CRUD
   createEnvironment: (name: string) => Promise<Environment>;
-  updateEnvironment: (id: string, updates: Partial<Pick<Environment, "name" | "variables">>) => Promise<void>;
+  updateEnvironment: (
+    id: string,
+    updates: Partial<Pick<Environment, "name" | "variables" | "dnsOverrides">>,
+  ) => Promise<void>;
   deleteEnvironment: (id: string) => Promise<void>;
 
   // Active environment
@@ -44,6 +47,7 @@ export const useEnvironmentStore = create<EnvironmentState>((set, get) => ({
     const data: Record<string, string | undefined> = {};
     if (updates.name !== undefined) data.name = updates.name;
     if (updates.variables !== undefined) data.variables = serializeVariables(updates.variables);
+    if (updates.dnsOverrides !== undefined) data.dns_overrides = JSON.stringify(updates.dnsOverrides);
     await invoke("update_environment", { id, data });
     set((s) => ({
       environments: s.environments.map((e) =>
@@ -0,0 +1,121 @@
+import { create } from "zustand";
+import { invoke } from "@tauri-apps/api/core";
+import type { HeaderEntry, RequestAuth } from "@/types/request";
+import type {
+  GraphqlRequest,
+  GraphqlSchemaInfo,
+  GraphqlValidation,
+} from "@/types/graphql";
+import type { TabRequestState } from "@/stores/tabStore";
+import { buildUrlWithParams, injectAuth } from "@/lib/request-utils";
+import { resolveRequest, resolveHeaders } from "@/lib/variables";
+import { buildHttpConfig } from "@/lib/http-config";
+import { graphqlEndpoint } from "@/lib/graphql";
+import { useEnvironmentStore } from "@/stores/environmentStore";
+
+interface GraphqlState {
+  /** Keyed by endpoint; `null` once looked up and found not cached. */
+  schemas: Record<string, GraphqlSchemaInfo | null>;
+  /** Endpoints whose introspection is in flight. */
+  fetching: Record<string, boolean>;
+  /** Why the last introspection of an endpoint failed. */
+  fetchErrors: Record<string, string>;
+  /** The latest check of each tab's query, keyed by tab ID. */
+  validations: Record<string, GraphqlValidation>;
+
+  loadSchema: (endpoint: string) => Promise<void>;
+  introspect: (
+    state: TabRequestState,
+    resolveAuth?: () => RequestAuth,
+    variableScope?: Map<string, string>,
+    defaultHeaders?: HeaderEntry[][],
+  ) => Promise<void>;
+  forgetSchema: (endpoint: string) => Promise<void>;
+  validate: (
+    tabId: string,
+    endpoint: string,
+    graphql: GraphqlRequest,
+  ) => Promise<GraphqlValidation>;
+  release: (tabId: string) => void;
+}
+
+function without<T>(record: Record<string, T>, key: string): Record<string, T> {
+  const next = { ...record };
+  delete next[key];
+  return next;
+}
+
+export const useGraphqlStore = create<GraphqlState>((set) => ({
+  schemas: {},
+  fetching: {},
+  fetchErrors: {},
+  validations: {},
+
+  loadSchema: async (endpoint) => {
+    if (!endpoint) return;
+    const schema = await invoke<GraphqlSchemaInfo | null>("get_graphql_schema", { endpoint });
+    set((s) => ({ schemas: { ...s.schemas, [endpoint]: schema } }));
+  },
+
+  introspect: async (state, resolveAuth, variableScope, defaultHeaders = []) => {
+    const stateWithAuth = resolveAuth ? { ...state, auth: resolveAuth() } : state;
+    const resolved = variableScope
+      ? resolveRequest(stateWithAuth, variableScope)
+      : stateWithAuth;
+    const endpoint = graphqlEndpoint(resolved.url);
+    if (!endpoint) return;
+
+    set((s) => ({
+      fetching: { ...s.fetching, [endpoint]: true },
+      fetchErrors: without(s.fetchErrors, endpoint),
+    }));
+    try {
+      const injected = injectAuth(resolved.headers, resolved.params, resolved.auth);
+      const schema = await invoke<GraphqlSchemaInfo>("introspect_graphql", {
+        endpoint,
+        request: {
+          method: "POST",
+          url: buildUrlWithParams(endpoint, injected.params),
+          default_headers: defaultHeaders.map((layer) =>
+            (variableScope ? resolveHeaders(layer, variableScope) : layer).filter(
+              (h) => h.enabled && h.key,
+            ),
+          ),
+          headers: injected.headers.filter((h) => h.enabled && h.key),
+          body: null,
+        },
+        config: buildHttpConfig(variableScope),
+        environmentId: useEnvironmentStore.getState().activeEnvironmentId ?? "global",
+      });
+      set((s) => ({ schemas: { ...s.schemas, [endpoint]: schema } }));
+    } catch (err) {
+      set((s) => ({
+        fetchErrors: {
+          ...s.fetchErrors,
+          [endpoint]: err instanceof Error ? err.message : String(err),
+        },
+      }));
+    } finally {
+      set((s) => ({ fetching: without(s.fetching, endpoint) }));
+    }
+  },
+
+  forgetSchema: async (endpoint) => {
+    await invoke("delete_graphql_schema", { endpoint });
+    set((s) => ({ schemas: { ...s.schemas, [endpoint]: null } }));
+  },
+
+  validate: async (tabId, endpoint, graphql) => {
+    const validation = await invoke<GraphqlValidation>("validate_graphql", {
+      endpoint,
+      query: graphql.query,
+      operationName: graphql.operationName.trim() || null,
+    });
+    set((s) => ({ validations: { ...s.validations, [tabId]: validation } }));
+    return validation;
+  },
+
+  release: (tabId) => {
+    set((s) => ({ validations: without(s.validations, tabId) }));
+  },
+}));
@@ -0,0 +1,287 @@
+import { create } from "zustand";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { HeaderEntry, RequestAuth, ResponseHeader } from "@/types/request";
+import type {
+  GrpcClosedEvent,
+  GrpcHeadersEvent,
+  GrpcMessage,
+  GrpcMessageEvent,
+  GrpcMethodInfo,
+  GrpcServiceInfo,
+  GrpcStatus,
+} from "@/types/grpc";
+import type { TabRequestState } from "@/stores/tabStore";
+import { injectAuth } from "@/lib/request-utils";
+import { resolveRequest, resolveHeaders } from "@/lib/variables";
+import { buildHttpConfig } from "@/lib/http-config";
+import { EMPTY_GRPC, grpcDescriptorSource, grpcSourceKey } from "@/lib/grpc";
+import { useEnvironmentStore } from "@/stores/environmentStore";
+
+/** Older messages are dropped from the live log of long streams. */
+const MAX_LIVE_MESSAGES = 5000;
+
+export type GrpcCallStatus = "running" | "closed";
+
+export interface GrpcCall {
+  status: GrpcCallStatus;
+  callId: string;
+  /** The method being called, for whether it takes a request stream. */
+  method: GrpcMethodInfo | null;
+  /** Set once the request stream is closed; unary calls close it at once. */
+  halfClosed: boolean;
+  messages: GrpcMessage[];
+  /** Response metadata; `null` until the server answers. */
+  headers: ResponseHeader[] | null;
+  trailers: ResponseHeader[];
+  result: GrpcStatus | null;
+  timeMs: number | null;
+  /** Why the call could not be started. */
+  error: string | null;
+}
+
+interface GrpcState {
+  /** Keyed by descriptor source, see `grpcSourceKey`. */
+  services: Record<string, GrpcServiceInfo[]>;
+  /** Sources whose services are being loaded. */
+  loading: Record<string, boolean>;
+  /** Why the last load of a source failed. */
+  loadErrors: Record<string, string>;
+  /** Keyed by tab ID; a tab has at most one call. */
+  calls: Record<string, GrpcCall>;
+
+  describe: (
+    state: TabRequestState,
+    resolveAuth?: () => RequestAuth,
+    variableScope?: Map<string, string>,
+    defaultHeaders?: HeaderEntry[][],
+    refresh?: boolean,
+  ) => Promise<void>;
+  start: (
+    tabId: string,
+    state: TabRequestState,
+    resolveAuth?: () => RequestAuth,
+    variableScope?: Map<string, string>,
+    defaultHeaders?: HeaderEntry[][],
+  ) => Promise<void>;
+  send: (tabId: string, message: string) => Promise<void>;
+  closeSend: (tabId: string) => Promise<void>;
+  cancel: (tabId: string) => Promise<void>;
+  clearMessages: (tabId: string) => void;
+  /** Cancels the tab's call and forgets it, for when the tab goes away. */
+  release: (tabId: string) => void;
+}
+
+function without<T>(record: Record<string, T>, key: string): Record<string, T> {
+  const next = { ...record };
+  delete next[key];
+  return next;
+}
+
+function appendMessage(messages: GrpcMessage[], message: GrpcMessage): GrpcMessage[] {
+  const next = [...messages, message];
+  return next.length > MAX_LIVE_MESSAGES ? next.slice(-MAX_LIVE_MESSAGES) : next;
+}
+
+/** What `grpc_describe` and `grpc_start` take as the call's target. */
+function buildTarget(
+  resolved: TabRequestState,
+  variableScope?: Map<string, string>,
+  defaultHeaders: HeaderEntry[][] = [],
+) {
+  // Query parameters have no place in a call, so an API key only goes in metadata
+  const injected = injectAuth(resolved.headers, [], resolved.auth);
+  return {
+    url: resolved.url.trim(),
+    default_headers: defaultHeaders.map((layer) =>
+      (variableScope ? resolveHeaders(layer, variableScope) : layer).filter(
+        (h) => h.enabled && h.key,
+      ),
+    ),
+    headers: injected.headers.filter((h) => h.enabled && h.key),
+  };
+}
+
+function resolveState(
+  state: TabRequestState,
+  resolveAuth?: () => RequestAuth,
+  variableScope?: Map<string, string>,
+): TabRequestState {
+  const stateWithAuth = resolveAuth ? { ...state, auth: resolveAuth() } : state;
+  return variableScope ? resolveRequest(stateWithAuth, variableScope) : stateWithAuth;
+}
+
+let listening: Promise<void> | null = null;
+
+function ensureListening(): Promise<void> {
+  listening ??= Promise.all([
+    listen<GrpcHeadersEvent>("grpc-headers", (e) => {
+      updateCall(e.payload.call_id, () => ({ headers: e.payload.headers }));
+    }),
+    listen<GrpcMessageEvent>("grpc-message", (e) => {
+      const { call_id, ...message } = e.payload;
+      updateCall(call_id, (c) => ({ messages: appendMessage(c.messages, message) }));
+    }),
+    listen<GrpcClosedEvent>("grpc-closed", (e) => {
+      const { call_id, status, trailers, time_ms } = e.payload;
+      updateCall(call_id, () => ({
+        status: "closed",
+        halfClosed: true,
+        result: status,
+        trailers,
+        timeMs: time_ms,
+      }));
+    }),
+  ]).then(() => {});
+  return listening;
+}
+
+/** Applies an update to whichever tab owns the call; events of released calls go nowhere. */
+function updateCall(callId: string, updater: (call: GrpcCall) => Partial<GrpcCall>) {
+  useGrpcStore.setState((s) => {
+    const tabId = Object.keys(s.calls).find((id) => s.calls[id].callId === callId);
+    if (!tabId) return s;
+    const call = s.calls[tabId];
+    return { calls: { ...s.calls, [tabId]: { ...call, ...updater(call) } } };
+  });
+}
+
+export const useGrpcStore = create<GrpcState>((set, get) => ({
+  services: {},
+  loading: {},
+  loadErrors: {},
+  calls: {},
+
+  describe: async (state, resolveAuth, variableScope, defaultHeaders = [], refresh = false) => {
+    const resolved = resolveState(state, resolveAuth, variableScope);
+    const grpc = resolved.grpc ?? EMPTY_GRPC;
+    const key = grpcSourceKey(resolved.url, grpc);
+    if (grpc.source === "reflection" && !resolved.url.trim()) {
+      set((s) => ({ loadErrors: { ...s.loadErrors, [key]: "URL is required" } }));
+      return;
+    }
+
+    set((s) => ({
+      loading: { ...s.loading, [key]: true },
+      loadErrors: without(s.loadErrors, key),
+    }));
+    try {
+      const services = await invoke<GrpcServiceInfo[]>("grpc_describe", {
+        request: buildTarget(resolved, variableScope, defaultHeaders),
+        source: grpcDescriptorSource(grpc),
+        config: buildHttpConfig(variableScope),
+        environmentId: useEnvironmentStore.getState().activeEnvironmentId ?? "global",
+        refresh,
+      });
+      set((s) => ({ services: { ...s.services, [key]: services } }));
+    } catch (err) {
+      set((s) => ({
+        loadErrors: {
+          ...s.loadErrors,
+          [key]: err instanceof Error ? err.message : String(err),
+        },
+      }));
+    } finally {
+      set((s) => ({ loading: without(s.loading, key) }));
+    }
+  },
+
+  start: async (tabId, state, resolveAuth, variableScope, defaultHeaders = []) => {
+    const existing = get().calls[tabId];
+    if (existing?.status === "running") return;
+
+    const resolved = resolveState(state, resolveAuth, variableScope);
+    const grpc = resolved.grpc ?? EMPTY_GRPC;
+    const key = grpcSourceKey(resolved.url, grpc);
+    // Whether the method streams requests is only known from its descriptor
+    if (!get().services[key] && resolved.url.trim()) {
+      await get().describe(state, resolveAuth, variableScope, defaultHeaders);
+    }
+    const method =
+      get()
+        .services[key]
+        ?.find((s) => s.name === grpc.service)
+        ?.methods.find((m) => m.name === grpc.method) ?? null;
+    const callId = crypto.randomUUID();
+
+    set((s) => ({
+      calls: {
+        ...s.calls,
+        [tabId]: {
+          status: "running",
+          callId,
+          method,
+          halfClosed: !method?.client_streaming,
+          messages: [],
+          headers: null,
+          trailers: [],
+          result: null,
+          timeMs: null,
+          error: null,
+        },
+      },
+    }));
+
+    const fail = (error: string) =>
+      updateCall(callId, () => ({ status: "closed", halfClosed: true, error }));
+    if (!resolved.url.trim()) {
+      fail("URL is required");
+      return;
+    }
+    if (!grpc.service || !grpc.method) {
+      fail("Choose a service and method to call");
+      return;
+    }
+
+    try {
+      await ensureListening();
+      await invoke("grpc_start", {
+        callId,
+        request: {
+          ...buildTarget(resolved, variableScope, defaultHeaders),
+          source: grpcDescriptorSource(grpc),
+          service: grpc.service,
+          method: grpc.method,
+          message: grpc.message,
+        },
+        config: buildHttpConfig(variableScope),
+        environmentId: useEnvironmentStore.getState().activeEnvironmentId ?? "global",
+      });
+    } catch (err) {
+      fail(err instanceof Error ? err.message : String(err));
+    }
+  },
+
+  send: async (tabId, message) => {
+    const call = get().calls[tabId];
+    if (!call || call.status !== "running" || call.halfClosed) return;
+    await invoke("grpc_send", { callId: call.callId, message });
+  },
+
+  closeSend: async (tabId) => {
+    const call = get().calls[tabId];
+    if (!call || call.status !== "running" || call.halfClosed) return;
+    await invoke("grpc_close_send", { callId: call.callId });
+    updateCall(call.callId, () => ({ halfClosed: true }));
+  },
+
+  cancel: async (tabId) => {
+    const call = get().calls[tabId];
+    if (!call || call.status !== "running") return;
+    await invoke("grpc_cancel", { callId: call.callId });
+  },
+
+  clearMessages: (tabId) => {
+    const call = get().calls[tabId];
+    if (call) updateCall(call.callId, () => ({ messages: [] }));
+  },
+
+  release: (tabId) => {
+    const call = get().calls[tabId];
+    if (!call) return;
+    if (call.status === "running") {
+      invoke("grpc_cancel", { callId: call.callId }).catch(() => {});
+    }
+    set((s) => ({ calls: without(s.calls, tabId) }));
+  },
+}));
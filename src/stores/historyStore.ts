@@ -9,6 +9,8 @@ interface HistorySearchFilters {
   method?: string;
   statusMin?: number;
   statusMax?: number;
+  /** RFC 3339; only entries whose server certificate expires before it. */
+  certificateExpiresBefore?: string;
 }
 
 interface HistoryState {
@@ -60,6 +62,8 @@ export const useHistoryStore = create<HistoryState>((set, get) => ({
       if (filters.method) params.method = filters.method;
       if (filters.statusMin !== undefined) params.status_min = filters.statusMin;
       if (filters.statusMax !== undefined) params.status_max = filters.statusMax;
+      if (filters.certificateExpiresBefore)
+        params.certificate_expires_before = filters.certificateExpiresBefore;
 
       const raws = await invoke<RawHistoryEntry[]>("search_history", { params });
       const newEntries = raws.map(parseHistoryEntry);
@@ -91,6 +95,8 @@ export const useHistoryStore = create<HistoryState>((set, get) => ({
       if (filters.method) params.method = filters.method;
       if (filters.statusMin !== undefined) params.status_min = filters.statusMin;
       if (filters.statusMax !== undefined) params.status_max = filters.statusMax;
+      if (filters.certificateExpiresBefore)
+        params.certificate_expires_before = filters.certificateExpiresBefore;
 
       const raws = await invoke<RawHistoryEntry[]>("search_history", { params });
       const entries = raws.map(parseHistoryEntry);
@@ -2,21 +2,111 @@ import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
 
 export type Theme = "dark" | "light" | "system";
+export type HttpVersionPref = "auto" | "http1" | "http2";
+export type IpFamily = "any" | "ipv4" | "ipv6";
+export type RetryBackoff = "fixed" | "linear" | "exponential";
 
-interface Settings {
+export interface ProxySettings {
+  httpProxy: string;
+  httpsProxy: string;
+  socksProxy: string;
+  proxyUsername: string;
+  /**
+   * Kept in the system keychain rather than the settings table. May reference
+   * `{{variables}}`, so the password itself can live in a secret variable.
+   */
+  proxyPassword: string;
+  noProxy: string;
+  useSystemProxy: boolean;
+}
+
+const PROXY_SETTING_KEYS: Record<Exclude<keyof ProxySettings, "proxyPassword">, string> = {
+  httpProxy: "proxy_http",
+  httpsProxy: "proxy_https",
+  socksProxy: "proxy_socks",
+  proxyUsername: "proxy_username",
+  noProxy: "no_proxy",
+  useSystemProxy: "use_system_proxy",
+};
+
+export interface RetrySettings {
+  /** Attempts in total; 1 disables retries. */
+  retryMaxAttempts: number;
+  retryBackoff: RetryBackoff;
+  retryDelayMs: number;
+  retryMaxDelayMs: number;
+  /** Comma-separated statuses and ranges, e.g. "429, 500-504". */
+  retryStatuses: string;
+  retryNetworkErrors: boolean;
+  retryRespectRetryAfter: boolean;
+  retryNonIdempotent: boolean;
+}
+
+const RETRY_SETTING_KEYS: Record<keyof RetrySettings, string> = {
+  retryMaxAttempts: "retry_max_attempts",
+  retryBackoff: "retry_backoff",
+  retryDelayMs: "retry_delay_ms",
+  retryMaxDelayMs: "retry_max_delay_ms",
+  retryStatuses: "retry_statuses",
+  retryNetworkErrors: "retry_network_errors",
+  retryRespectRetryAfter: "retry_respect_retry_after",
+  retryNonIdempotent: "retry_non_idempotent",
+};
+
+/** Expands "429, 500-504" into status codes, ignoring anything malformed. */
+export function parseStatusList(value: string): number[] {
+  return value.split(",").flatMap((part) => {
+    const [from, to = from] = part.split("-").map((n) => parseInt(n.trim(), 10));
+    if (!(from >= 100 && to <= 599 && from <= to)) return [];
+    return Array.from({ length: to - from + 1 }, (_, i) => from + i);
+  });
+}
+
+interface Settings extends ProxySettings, RetrySettings {
   theme: Theme;
   historyRetentionDays: number;
   timeoutMs: number;
-  proxyUrl: string;
   verifySsl: boolean;
+  httpVersion: HttpVersionPref;
+  ipFamily: IpFamily;
+  followRedirects: boolean;
+  maxRedirects: number;
+  preserveMethodOnRedirect: boolean;
+  decompressResponses: boolean;
+  /** Reopen event streams that end, resuming from the last event ID. */
+  sseReconnect: boolean;
+  /** Reconnects in a row that may fail before an event stream gives up. */
+  sseMaxReconnects: number;
 }
 
 const DEFAULTS: Settings = {
   theme: "dark",
   historyRetentionDays: 30,
   timeoutMs: 30000,
-  proxyUrl: "",
+  httpProxy: "",
+  httpsProxy: "",
+  socksProxy: "",
+  proxyUsername: "",
+  proxyPassword: "",
+  noProxy: "",
+  useSystemProxy: true,
   verifySsl: true,
+  httpVersion: "auto",
+  ipFamily: "any",
+  followRedirects: true,
+  maxRedirects: 10,
+  preserveMethodOnRedirect: true,
+  decompressResponses: true,
+  sseReconnect: true,
+  sseMaxReconnects: 5,
+  retryMaxAttempts: 1,
+  retryBackoff: "exponential",
+  retryDelayMs: 500,
+  retryMaxDelayMs: 30000,
+  retryStatuses: "408, 429, 500, 502, 503, 504",
+  retryNetworkErrors: true,
+  retryRespectRetryAfter: true,
+  retryNonIdempotent: false,
 };
 
 const THEME_CACHE_KEY = "hermes-theme";
@@ -38,8 +128,17 @@ interface SettingsState extends Settings {
   setTheme: (theme: Theme) => Promise<void>;
   setHistoryRetentionDays: (days: number) => Promise<void>;
   setTimeoutMs: (ms: number) => Promise<void>;
-  setProxyUrl: (url: string) => Promise<void>;
+  setProxy: (patch: Partial<ProxySettings>) => Promise<void>;
   setVerifySsl: (verify: boolean) => Promise<void>;
+  setHttpVersion: (version: HttpVersionPref) => Promise<void>;
+  setIpFamily: (family: IpFamily) => Promise<void>;
+  setFollowRedirects: (follow: boolean) => Promise<void>;
+  setMaxRedirects: (max: number) => Promise<void>;
+  setPreserveMethodOnRedirect: (preserve: boolean) => Promise<void>;
+  setDecompressResponses: (decompress: boolean) => Promise<void>;
+  setSseReconnect: (reconnect: boolean) => Promise<void>;
+  setSseMaxReconnects: (max: number) => Promise<void>;
+  setRetry: (patch: Partial<RetrySettings>) => Promise<void>;
 }
 
 async function getSetting(key: string): Promise<string | null> {
@@ -50,25 +149,125 @@ async function setSetting(key: string, value: string): Promise<void> {
   await invoke("set_setting", { key, value });
 }
 
+// Drop pooled HTTP clients built from the previous network settings
+async function flushClientPool(): Promise<void> {
+  await invoke("flush_client_pool");
+}
+
 export const useSettingsStore = create<SettingsState>((set) => ({
   ...DEFAULTS,
   loaded: false,
 
   loadSettings: async () => {
-    const [theme, retention, timeout, proxy, ssl] = await Promise.all([
+    const [
+      theme,
+      retention,
+      timeout,
+      legacyProxy,
+      httpProxy,
+      httpsProxy,
+      socksProxy,
+      proxyUsername,
+      proxyPassword,
+      noProxy,
+      useSystemProxy,
+      ssl,
+      httpVersion,
+      ipFamily,
+      followRedirects,
+      maxRedirects,
+      preserveMethod,
+      decompress,
+      sseReconnect,
+      sseMaxReconnects,
+      retryMaxAttempts,
+      retryBackoff,
+      retryDelayMs,
+      retryMaxDelayMs,
+      retryStatuses,
+      retryNetworkErrors,
+      retryRespectRetryAfter,
+      retryNonIdempotent,
+    ] = await Promise.all([
       getSetting("theme"),
       getSetting("history_retention_days"),
       getSetting("timeout_ms"),
       getSetting("proxy_url"),
+      getSetting("proxy_http"),
+      getSetting("proxy_https"),
+      getSetting("proxy_socks"),
+      getSetting("proxy_username"),
+      // Without a usable keychain the rest of the settings still load
+      invoke<string>("get_proxy_password").catch(() => null),
+      getSetting("no_proxy"),
+      getSetting("use_system_proxy"),
       getSetting("verify_ssl"),
+      getSetting("http_version"),
+      getSetting("ip_family"),
+      getSetting("follow_redirects"),
+      getSetting("max_redirects"),
+      getSetting("preserve_method_on_redirect"),
+      getSetting("decompress_responses"),
+      getSetting("sse_reconnect"),
+      getSetting("sse_max_reconnects"),
+      getSetting("retry_max_attempts"),
+      getSetting("retry_backoff"),
+      getSetting("retry_delay_ms"),
+      getSetting("retry_max_delay_ms"),
+      getSetting("retry_statuses"),
+      getSetting("retry_network_errors"),
+      getSetting("retry_respect_retry_after"),
+      getSetting("retry_non_idempotent"),
     ]);
 
     const resolved: Settings = {
       theme: (theme as Theme) || DEFAULTS.theme,
       historyRetentionDays: retention ? parseInt(retention, 10) : DEFAULTS.historyRetentionDays,
       timeoutMs: timeout ? parseInt(timeout, 10) : DEFAULTS.timeoutMs,
-      proxyUrl: proxy ?? DEFAULTS.proxyUrl,
+      // The single proxy_url of older versions proxied both schemes
+      httpProxy: httpProxy ?? legacyProxy ?? DEFAULTS.httpProxy,
+      httpsProxy: httpsProxy ?? legacyProxy ?? DEFAULTS.httpsProxy,
+      socksProxy: socksProxy ?? DEFAULTS.socksProxy,
+      proxyUsername: proxyUsername ?? DEFAULTS.proxyUsername,
+      proxyPassword: proxyPassword ?? DEFAULTS.proxyPassword,
+      noProxy: noProxy ?? DEFAULTS.noProxy,
+      useSystemProxy:
+        useSystemProxy !== null ? useSystemProxy === "true" : DEFAULTS.useSystemProxy,
       verifySsl: ssl !== null ? ssl === "true" : DEFAULTS.verifySsl,
+      httpVersion: (httpVersion as HttpVersionPref) || DEFAULTS.httpVersion,
+      ipFamily: (ipFamily as IpFamily) || DEFAULTS.ipFamily,
+      followRedirects:
+        followRedirects !== null ? followRedirects === "true" : DEFAULTS.followRedirects,
+      maxRedirects: maxRedirects ? parseInt(maxRedirects, 10) : DEFAULTS.maxRedirects,
+      preserveMethodOnRedirect:
+        preserveMethod !== null ? preserveMethod === "true" : DEFAULTS.preserveMethodOnRedirect,
+      decompressResponses:
+        decompress !== null ? decompress === "true" : DEFAULTS.decompressResponses,
+      sseReconnect: sseReconnect !== null ? sseReconnect === "true" : DEFAULTS.sseReconnect,
+      sseMaxReconnects: sseMaxReconnects
+        ? parseInt(sseMaxReconnects, 10)
+        : DEFAULTS.sseMaxReconnects,
+      retryMaxAttempts: retryMaxAttempts
+        ? parseInt(retryMaxAttempts, 10)
+        : DEFAULTS.retryMaxAttempts,
+      retryBackoff: (retryBackoff as RetryBackoff) || DEFAULTS.retryBackoff,
+      retryDelayMs: retryDelayMs ? parseInt(retryDelayMs, 10) : DEFAULTS.retryDelayMs,
+      retryMaxDelayMs: retryMaxDelayMs
+        ? parseInt(retryMaxDelayMs, 10)
+        : DEFAULTS.retryMaxDelayMs,
+      retryStatuses: retryStatuses ?? DEFAULTS.retryStatuses,
+      retryNetworkErrors:
+        retryNetworkErrors !== null
+          ? retryNetworkErrors === "true"
+          : DEFAULTS.retryNetworkErrors,
+      retryRespectRetryAfter:
+        retryRespectRetryAfter !== null
+          ? retryRespectRetryAfter === "true"
+          : DEFAULTS.retryRespectRetryAfter,
+      retryNonIdempotent:
+        retryNonIdempotent !== null
+          ? retryNonIdempotent === "true"
+          : DEFAULTS.retryNonIdempotent,
     };
 
     applyThemeClass(resolved.theme);
@@ -88,18 +287,80 @@ export const useSettingsStore = create<SettingsState>((set) => ({
 
   setTimeoutMs: async (ms) => {
     await setSetting("timeout_ms", String(ms));
+    await flushClientPool();
     set({ timeoutMs: ms });
   },
 
-  setProxyUrl: async (url) => {
-    await setSetting("proxy_url", url);
-    set({ proxyUrl: url });
+  setProxy: async (patch) => {
+    const { proxyPassword, ...rest } = patch;
+    const entries = Object.entries(rest) as [keyof typeof PROXY_SETTING_KEYS, string | boolean][];
+    await Promise.all([
+      ...entries.map(([key, value]) => setSetting(PROXY_SETTING_KEYS[key], String(value))),
+      ...(proxyPassword !== undefined
+        ? [invoke("set_proxy_password", { password: proxyPassword })]
+        : []),
+    ]);
+    await flushClientPool();
+    set(patch);
   },
 
   setVerifySsl: async (verify) => {
     await setSetting("verify_ssl", String(verify));
+    await flushClientPool();
     set({ verifySsl: verify });
   },
+
+  setHttpVersion: async (version) => {
+    await setSetting("http_version", version);
+    await flushClientPool();
+    set({ httpVersion: version });
+  },
+
+  setIpFamily: async (family) => {
+    await setSetting("ip_family", family);
+    await flushClientPool();
+    set({ ipFamily: family });
+  },
+
+  // Redirects are followed per request, so the client pool is unaffected
+  setFollowRedirects: async (follow) => {
+    await setSetting("follow_redirects", String(follow));
+    set({ followRedirects: follow });
+  },
+
+  setMaxRedirects: async (max) => {
+    await setSetting("max_redirects", String(max));
+    set({ maxRedirects: max });
+  },
+
+  setPreserveMethodOnRedirect: async (preserve) => {
+    await setSetting("preserve_method_on_redirect", String(preserve));
+    set({ preserveMethodOnRedirect: preserve });
+  },
+
+  setDecompressResponses: async (decompress) => {
+    await setSetting("decompress_responses", String(decompress));
+    set({ decompressResponses: decompress });
+  },
+
+  setSseReconnect: async (reconnect) => {
+    await setSetting("sse_reconnect", String(reconnect));
+    set({ sseReconnect: reconnect });
+  },
+
+  setSseMaxReconnects: async (max) => {
+    await setSetting("sse_max_reconnects", String(max));
+    set({ sseMaxReconnects: max });
+  },
+
+  // Retries happen per request too
+  setRetry: async (patch) => {
+    const entries = Object.entries(patch) as [keyof RetrySettings, string | number | boolean][];
+    await Promise.all(
+      entries.map(([key, value]) => setSetting(RETRY_SETTING_KEYS[key], String(value))),
+    );
+    set(patch);
+  },
 }));
 
 // Listen for system theme changes when in "system" mode
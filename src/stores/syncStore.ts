@@ -4,6 +4,7 @@ import { supabase } from "@/lib/supabase";
 import { invoke } from "@tauri-apps/api/core";
 import { useCollectionStore } from "@/stores/collectionStore";
 import { useTeamStore } from "@/stores/teamStore";
+import { kindColumnsFromCloud, kindConfigField, kindConfigFromRow } from "@/lib/sync-utils";
 
 type SyncStatus = "offline" | "syncing" | "synced" | "error";
 
@@ -174,6 +175,7 @@ export const useSyncStore = create<SyncState>((set, get) => ({
               auth: JSON.stringify(r.auth),
               variables: JSON.stringify(r.variables),
               sort_order: r.sort_order,
+              ...kindColumnsFromCloud(r),
             },
           });
         }
@@ -269,6 +271,7 @@ export const useSyncStore = create<SyncState>((set, get) => ({
               auth: JSON.parse(r.auth as string),
               variables: JSON.parse(r.variables as string),
               sort_order: r.sort_order,
+              ...(await kindConfigField(kindConfigFromRow(r))),
             })
             .eq("id", cloudId);
 
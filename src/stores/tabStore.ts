@@ -1,5 +1,6 @@
 import { create } from "zustand";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import type {
   HttpMethod,
   HeaderEntry,
@@ -7,6 +8,8 @@ import type {
   RequestBody,
   RequestAuth,
   HttpResponse,
+  SendOutcome,
+  DownloadProgress,
 } from "@/types/request";
 
 type BodyType = RequestBody["type"];
@@ -17,17 +20,28 @@ interface BodyCache {
   "x-www-form-urlencoded"?: Extract<RequestBody, { type: "x-www-form-urlencoded" }>;
   binary?: Extract<RequestBody, { type: "binary" }>;
 }
-import type { SavedRequest } from "@/types/collection";
+import type { RequestKind, SavedRequest } from "@/types/collection";
 import type { HistoryEntry } from "@/types/history";
+import type { EventStreamState, SseUpdate } from "@/types/sse";
+import type { GraphqlRequest } from "@/types/graphql";
+import type { GrpcRequest } from "@/types/grpc";
+import type { JsonRpcCall, JsonRpcSentCall } from "@/types/jsonrpc";
 import {
   parseQueryParams,
   buildUrlWithParams,
   serializeBody,
   injectAuth,
 } from "@/lib/request-utils";
-import { resolveRequest } from "@/lib/variables";
+import { resolveRequest, resolveHeaders } from "@/lib/variables";
+import { buildHttpConfig } from "@/lib/http-config";
+import { EMPTY_GRAPHQL, graphqlBody, graphqlEndpoint } from "@/lib/graphql";
+import { EMPTY_GRPC } from "@/lib/grpc";
+import { EMPTY_JSONRPC_CALL, jsonrpcBody, jsonrpcPayload } from "@/lib/jsonrpc";
 import { useHistoryStore } from "@/stores/historyStore";
-import { useSettingsStore } from "@/stores/settingsStore";
+import { useEnvironmentStore } from "@/stores/environmentStore";
+import { useWsStore } from "@/stores/wsStore";
+import { useGraphqlStore } from "@/stores/graphqlStore";
+import { useGrpcStore } from "@/stores/grpcStore";
 import {
   serializeHeaders,
   serializeParams,
@@ -46,6 +60,23 @@ export interface TabRequestState {
   response: HttpResponse | null;
   loading: boolean;
   error: string | null;
+  requestId: string | null;
+  /** Progress of an in-flight "send and save" request. */
+  downloadProgress?: DownloadProgress | null;
+  /** Absent means a plain HTTP request. */
+  kind?: RequestKind;
+  /** WebSocket subprotocols to offer, in order of preference. */
+  subprotocols?: string[];
+  /** Status, headers and events of an event stream tab's last send. */
+  eventStream?: EventStreamState | null;
+  /** The operation a GraphQL tab sends in place of `bodyConfig`. */
+  graphql?: GraphqlRequest;
+  /** The method and message a gRPC tab calls. */
+  grpc?: GrpcRequest;
+  /** The calls a JSON-RPC tab sends in place of `bodyConfig`. */
+  jsonrpc?: JsonRpcCall[];
+  /** The calls as last sent, with their IDs, for matching the response. */
+  jsonrpcSent?: JsonRpcSentCall[] | null;
 }
 
 export type Tab = RequestTab | SettingsTab | EnvironmentsTab;
@@ -76,9 +107,14 @@ export function isRequestTab(tab: Tab): tab is RequestTab {
   return tab.type === "request";
 }
 
-function defaultTabState(): TabRequestState {
+function defaultTabState(kind: RequestKind = "http"): TabRequestState {
   return {
-    method: "GET",
+    kind,
+    subprotocols: [],
+    graphql: EMPTY_GRAPHQL,
+    grpc: EMPTY_GRPC,
+    jsonrpc: [EMPTY_JSONRPC_CALL],
+    method: kind === "graphql" || kind === "grpc" || kind === "jsonrpc" ? "POST" : "GET",
     url: "",
     headers: [],
     params: [],
@@ -88,6 +124,7 @@ function defaultTabState(): TabRequestState {
     response: null,
     loading: false,
     error: null,
+    requestId: null,
   };
 }
 
@@ -97,6 +134,11 @@ function stateFromSavedRequest(req: SavedRequest): TabRequestState {
     cache[req.body.type] = req.body as never;
   }
   return {
+    kind: req.kind,
+    subprotocols: req.subprotocols,
+    graphql: req.graphql,
+    grpc: req.grpc,
+    jsonrpc: req.jsonrpc,
     method: req.method,
     url: req.url,
     headers: req.headers,
@@ -107,6 +149,71 @@ function stateFromSavedRequest(req: SavedRequest): TabRequestState {
     response: null,
     loading: false,
     error: null,
+    requestId: null,
+  };
+}
+
+/** Events kept in memory per tab; older ones are dropped first. */
+const MAX_STREAM_EVENTS = 5000;
+
+function applyStreamUpdate(
+  stream: EventStreamState | null | undefined,
+  update: SseUpdate,
+): EventStreamState | null {
+  switch (update.type) {
+    case "open":
+      return {
+        status: update.status,
+        status_text: update.status_text,
+        headers: update.headers,
+        http_version: update.http_version,
+        events: stream?.events ?? [],
+        reconnects: stream?.reconnects ?? 0,
+        reconnecting: null,
+        dropped: stream?.dropped ?? 0,
+      };
+    case "event": {
+      if (!stream) return null;
+      const event = {
+        id: update.id,
+        event: update.event,
+        data: update.data,
+        retry: update.retry,
+        at_ms: update.at_ms,
+      };
+      const overflow = stream.events.length + 1 - MAX_STREAM_EVENTS;
+      return {
+        ...stream,
+        events: [...stream.events.slice(Math.max(overflow, 0)), event],
+        dropped: stream.dropped + Math.max(overflow, 0),
+      };
+    }
+    case "reconnecting":
+      if (!stream) return null;
+      return {
+        ...stream,
+        reconnects: stream.reconnects + 1,
+        reconnecting: {
+          attempt: update.attempt,
+          delay_ms: update.delay_ms,
+          error: update.error,
+        },
+      };
+  }
+}
+
+/** History fields describing an event stream, whether it ended or was stopped. */
+function streamHistoryFields(stream: EventStreamState, timeMs: number) {
+  return {
+    response_status: stream.status,
+    response_status_text: stream.status_text,
+    response_headers: JSON.stringify(stream.headers),
+    response_body: null,
+    response_time_ms: timeMs,
+    response_size_bytes: null,
+    response_mime_type: "text/event-stream",
+    http_version: stream.http_version,
+    sse_events: JSON.stringify(stream.events),
   };
 }
 
@@ -116,6 +223,11 @@ function generateId(): string {
 
 function stateEqual(a: TabRequestState, b: TabRequestState): boolean {
   return (
+    (a.kind ?? "http") === (b.kind ?? "http") &&
+    JSON.stringify(a.subprotocols ?? []) === JSON.stringify(b.subprotocols ?? []) &&
+    JSON.stringify(a.graphql ?? EMPTY_GRAPHQL) === JSON.stringify(b.graphql ?? EMPTY_GRAPHQL) &&
+    JSON.stringify(a.grpc ?? EMPTY_GRPC) === JSON.stringify(b.grpc ?? EMPTY_GRPC) &&
+    JSON.stringify(a.jsonrpc ?? []) === JSON.stringify(b.jsonrpc ?? []) &&
     a.method === b.method &&
     a.url === b.url &&
     JSON.stringify(a.headers) === JSON.stringify(b.headers) &&
@@ -129,7 +241,7 @@ interface TabState {
   tabs: Tab[];
   activeTabId: string | null;
 
-  openNewTab: () => void;
+  openNewTab: (kind?: RequestKind) => void;
   openSavedRequest: (request: SavedRequest) => void;
   openHistoryEntry: (entry: HistoryEntry) => void;
   openSettingsTab: () => void;
@@ -145,7 +257,18 @@ interface TabState {
   setBodyConfig: (body: RequestBody) => void;
   setBodyType: (type: BodyType) => void;
   setAuth: (auth: RequestAuth) => void;
-  sendRequest: (resolveAuth?: () => RequestAuth, variableScope?: Map<string, string>) => Promise<void>;
+  setSubprotocols: (subprotocols: string[]) => void;
+  setGraphql: (graphql: Partial<GraphqlRequest>) => void;
+  setGrpc: (grpc: Partial<GrpcRequest>) => void;
+  setJsonRpc: (jsonrpc: JsonRpcCall[]) => void;
+  setKind: (kind: RequestKind) => void;
+  sendRequest: (
+    resolveAuth?: () => RequestAuth,
+    variableScope?: Map<string, string>,
+    defaultHeaders?: HeaderEntry[][],
+    saveTo?: string,
+  ) => Promise<void>;
+  cancelRequest: () => Promise<void>;
 
   linkTabToSaved: (tabId: string, savedRequestId: string, title: string) => void;
   updateSavedSnapshot: (tabId: string) => void;
@@ -167,6 +290,36 @@ function updateActiveRequestTab(
   });
 }
 
+const NEW_TAB_TITLES: Record<RequestKind, string> = {
+  http: "New Request",
+  sse: "New Request",
+  websocket: "New WebSocket",
+  graphql: "New GraphQL",
+  grpc: "New gRPC",
+  jsonrpc: "New JSON-RPC",
+};
+
+/** The body a tab sends: a GraphQL tab's is built from its operation, a JSON-RPC tab's from its calls. */
+export function bodyToSend(state: TabRequestState): RequestBody {
+  switch (state.kind) {
+    case "graphql":
+      return graphqlBody(state.graphql ?? EMPTY_GRAPHQL);
+    case "jsonrpc":
+      return jsonrpcBody(state.jsonrpc ?? []);
+    default:
+      return state.bodyConfig;
+  }
+}
+
+/** `bodyToSend` for history, which keeps a malformed operation as it was typed. */
+function historyBody(state: TabRequestState): string {
+  try {
+    return serializeBodyJson(bodyToSend(state));
+  } catch {
+    return serializeBodyJson(state.bodyConfig);
+  }
+}
+
 const SETTINGS_TAB_ID = "settings-tab";
 const ENVIRONMENTS_TAB_ID = "environments-tab";
 
@@ -174,7 +327,7 @@ export const useTabStore = create<TabState>((set, get) => ({
   tabs: [],
   activeTabId: null,
 
-  openNewTab: () => {
+  openNewTab: (kind = "http") => {
     const id = generateId();
     const tab: RequestTab = {
       id,
@@ -182,8 +335,8 @@ export const useTabStore = create<TabState>((set, get) => ({
       savedRequestId: null,
       historyEntryId: null,
       readOnly: false,
-      title: "New Request",
-      state: defaultTabState(),
+      title: NEW_TAB_TITLES[kind],
+      state: defaultTabState(kind),
       savedSnapshot: null,
     };
     set((s) => ({
@@ -234,10 +387,40 @@ export const useTabStore = create<TabState>((set, get) => ({
         ? {
             status: entry.responseStatus,
             status_text: entry.responseStatusText ?? "",
-            headers: entry.responseHeaders ?? {},
+            url: entry.responseUrl ?? entry.url,
+            headers: entry.responseHeaders ?? [],
             body: entry.responseBody ?? "",
+            body_encoding: entry.responseBodyEncoding,
+            body_raw: entry.responseBodyRaw ?? undefined,
+            mime_type: entry.responseMimeType,
+            charset: entry.responseCharset,
             time_ms: entry.responseTimeMs ?? 0,
             size_bytes: entry.responseSizeBytes ?? 0,
+            wire_size_bytes: entry.responseWireSizeBytes ?? entry.responseSizeBytes ?? 0,
+            request_size_bytes: 0,
+            timings: {
+              dns_ms: entry.timingDnsMs,
+              connect_ms: entry.timingConnectMs,
+              tls_ms: entry.timingTlsMs,
+              ttfb_ms: entry.timingTtfbMs ?? 0,
+              download_ms: entry.timingDownloadMs ?? 0,
+            },
+            redirects: entry.redirectChain ?? [],
+            attempts: entry.retryAttempts ?? [],
+            connection: entry.httpVersion
+              ? {
+                  http_version: entry.httpVersion,
+                  remote_addr: entry.remoteAddr,
+                  reused: false,
+                  resolved_addresses: [],
+                  dns_override: false,
+                  unix_socket: null,
+                  tls_version: entry.tlsVersion,
+                  tls_resumed: entry.tlsResumed,
+                  peer_certificate: entry.peerCertificate,
+                  certificate_chain: entry.certificateChain ?? [],
+                }
+              : undefined,
           }
         : null;
 
@@ -249,6 +432,7 @@ export const useTabStore = create<TabState>((set, get) => ({
       readOnly: true,
       title,
       state: {
+        kind: entry.sseEvents ? "sse" : "http",
         method: entry.method,
         url: entry.url,
         headers: entry.headers,
@@ -262,6 +446,20 @@ export const useTabStore = create<TabState>((set, get) => ({
         response,
         loading: false,
         error: entry.error,
+        requestId: null,
+        eventStream:
+          entry.sseEvents && entry.responseStatus != null
+            ? {
+                status: entry.responseStatus,
+                status_text: entry.responseStatusText ?? "",
+                headers: entry.responseHeaders ?? [],
+                http_version: entry.httpVersion ?? "",
+                events: entry.sseEvents,
+                reconnects: 0,
+                reconnecting: null,
+                dropped: 0,
+              }
+            : null,
       },
       savedSnapshot: null,
     };
@@ -326,6 +524,14 @@ export const useTabStore = create<TabState>((set, get) => ({
   },
 
   closeTab: (tabId) => {
+    useWsStore.getState().release(tabId);
+    useGraphqlStore.getState().release(tabId);
+    useGrpcStore.getState().release(tabId);
+    // Event streams would otherwise run on with nowhere to show their events
+    const closing = get().tabs.find((t) => t.id === tabId);
+    if (closing && isRequestTab(closing) && closing.state.requestId) {
+      invoke("cancel_request", { requestId: closing.state.requestId }).catch(() => {});
+    }
     set((s) => {
       const idx = s.tabs.findIndex((t) => t.id === tabId);
       const newTabs = s.tabs.filter((t) => t.id !== tabId);
@@ -439,7 +645,41 @@ export const useTabStore = create<TabState>((set, get) => ({
     }));
   },
 
-  sendRequest: async (resolveAuth, variableScope) => {
+  setSubprotocols: (subprotocols) => {
+    set((s) => ({
+      tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({ subprotocols })),
+    }));
+  },
+
+  setGraphql: (graphql) => {
+    set((s) => ({
+      tabs: updateActiveRequestTab(s.tabs, s.activeTabId, (state) => ({
+        graphql: { ...(state.graphql ?? EMPTY_GRAPHQL), ...graphql },
+      })),
+    }));
+  },
+
+  setGrpc: (grpc) => {
+    set((s) => ({
+      tabs: updateActiveRequestTab(s.tabs, s.activeTabId, (state) => ({
+        grpc: { ...(state.grpc ?? EMPTY_GRPC), ...grpc },
+      })),
+    }));
+  },
+
+  setJsonRpc: (jsonrpc) => {
+    set((s) => ({
+      tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({ jsonrpc })),
+    }));
+  },
+
+  setKind: (kind) => {
+    set((s) => ({
+      tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({ kind, eventStream: null })),
+    }));
+  },
+
+  sendRequest: async (resolveAuth, variableScope, defaultHeaders = [], saveTo) => {
     const tab = get().getActiveTab();
     if (!tab || !isRequestTab(tab) || tab.readOnly) return;
 
@@ -451,30 +691,65 @@ export const useTabStore = create<TabState>((set, get) => ({
       ? resolveRequest(stateWithAuth, variableScope)
       : stateWithAuth;
 
-    const { method, url, headers, params, bodyConfig, auth } = resolved;
+    const { method, url, headers, params, auth } = resolved;
 
-    if (!url.trim()) {
+    const fail = (error: string) =>
       set((s) => ({
-        tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({
-          error: "URL is required",
-        })),
+        tabs: updateActiveRequestTab(s.tabs, tab.id, () => ({ error })),
       }));
+
+    if (!url.trim()) {
+      fail("URL is required");
       return;
     }
 
+    let bodyConfig: RequestBody;
+    try {
+      bodyConfig = bodyToSend(resolved);
+    } catch (err) {
+      fail(err instanceof Error ? err.message : String(err));
+      return;
+    }
+
+    // Checked against the endpoint's cached schema; without one, only the syntax
+    if (tab.state.kind === "graphql") {
+      try {
+        const { errors } = await useGraphqlStore
+          .getState()
+          .validate(tab.id, graphqlEndpoint(url), resolved.graphql ?? EMPTY_GRAPHQL);
+        if (errors.length > 0) {
+          const [first] = errors;
+          const more = errors.length > 1 ? ` (and ${errors.length - 1} more)` : "";
+          fail(`Query is invalid: ${first.message} [${first.line}:${first.column}]${more}`);
+          return;
+        }
+      } catch (err) {
+        fail(err instanceof Error ? err.message : String(err));
+        return;
+      }
+    }
+
+    const requestId = generateId();
+    const streaming = tab.state.kind === "sse";
+    const startedAt = Date.now();
+    // The body was built without error above, so this cannot throw
+    const jsonrpcSent =
+      tab.state.kind === "jsonrpc" ? jsonrpcPayload(resolved.jsonrpc ?? []).sent : null;
     set((s) => ({
       tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({
         loading: true,
         error: null,
+        requestId,
+        downloadProgress: null,
+        eventStream: null,
+        jsonrpcSent,
       })),
     }));
 
-    const settings = useSettingsStore.getState();
-    const config = {
-      timeout_ms: settings.timeoutMs,
-      proxy_url: settings.proxyUrl || null,
-      verify_ssl: settings.verifySsl,
-    };
+    // Kept here too, so closing the tab mid-stream still leaves the events for history
+    const live: { stream: EventStreamState | null } = { stream: null };
+
+    const config = buildHttpConfig(variableScope);
 
     try {
       const injected = injectAuth(headers, params, auth);
@@ -482,6 +757,11 @@ export const useTabStore = create<TabState>((set, get) => ({
       const { body, contentType } = serializeBody(bodyConfig);
 
       const finalHeaders = injected.headers.filter((h) => h.enabled && h.key);
+      const finalDefaultHeaders = defaultHeaders.map((layer) =>
+        (variableScope ? resolveHeaders(layer, variableScope) : layer).filter(
+          (h) => h.enabled && h.key,
+        ),
+      );
       if (
         contentType &&
         !finalHeaders.some((h) => h.key.toLowerCase() === "content-type")
@@ -493,54 +773,181 @@ export const useTabStore = create<TabState>((set, get) => ({
         });
       }
 
-      const response = await invoke<HttpResponse>("send_request", {
+      const unlisten = saveTo
+        ? await listen<DownloadProgress>("download-progress", (e) => {
+            if (e.payload.request_id !== requestId) return;
+            set((s) => ({
+              tabs: updateActiveRequestTab(s.tabs, tab.id, () => ({
+                downloadProgress: e.payload,
+              })),
+            }));
+          })
+        : streaming
+          ? await listen<SseUpdate>("sse-update", (e) => {
+              if (e.payload.request_id !== requestId) return;
+              const stream = applyStreamUpdate(live.stream, e.payload);
+              live.stream = stream;
+              set((s) => ({
+                tabs: updateActiveRequestTab(s.tabs, tab.id, () => ({ eventStream: stream })),
+              }));
+            })
+          : null;
+
+      const outcome = await invoke<SendOutcome>("send_request", {
+        requestId,
         request: {
           method,
           url: finalUrl,
+          default_headers: finalDefaultHeaders,
           headers: finalHeaders,
-          body: body || null,
+          body,
         },
         config,
-      });
+        environmentId:
+          useEnvironmentStore.getState().activeEnvironmentId ?? "global",
+        saveTo: saveTo ?? null,
+        streamEvents: streaming,
+      }).finally(() => unlisten?.());
+
+      if (outcome.outcome === "cancelled") {
+        set((s) => ({
+          tabs: updateActiveRequestTab(s.tabs, tab.id, (state) => ({
+            loading: false,
+            requestId: null,
+            downloadProgress: null,
+            eventStream: state.eventStream && { ...state.eventStream, reconnecting: null },
+          })),
+        }));
+
+        // Stopping is how an event stream normally ends, so keep what it received
+        const stream = live.stream;
+        if (stream) {
+          useHistoryStore.getState().logEntry({
+            method: tab.state.method,
+            url: tab.state.url,
+            headers: serializeHeaders(tab.state.headers),
+            params: serializeParams(tab.state.params),
+            body: historyBody(tab.state),
+            auth: serializeAuth(tab.state.auth),
+            ...streamHistoryFields(stream, Date.now() - startedAt),
+            error: null,
+            saved_request_id: tab.savedRequestId,
+          }).catch(() => {});
+          return;
+        }
+
+        useHistoryStore.getState().logEntry({
+          method: tab.state.method,
+          url: tab.state.url,
+          headers: serializeHeaders(tab.state.headers),
+          params: serializeParams(tab.state.params),
+          body: historyBody(tab.state),
+          auth: serializeAuth(tab.state.auth),
+          response_status: null,
+          response_status_text: null,
+          response_headers: null,
+          response_body: null,
+          response_time_ms: null,
+          response_size_bytes: null,
+          error: null,
+          cancelled: true,
+          saved_request_id: tab.savedRequestId,
+        }).catch(() => {});
+        return;
+      }
 
+      const response: HttpResponse = outcome;
+      const stream = live.stream;
+      // A reconnect answered with something other than a stream ends it
+      const streamError =
+        stream && !response.event_stream
+          ? `Reconnect answered ${response.status} ${response.status_text}`.trim()
+          : (response.event_stream?.error ?? null);
       set((s) => ({
-        tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({
+        tabs: updateActiveRequestTab(s.tabs, tab.id, (state) => ({
           response,
           loading: false,
+          requestId: null,
+          downloadProgress: null,
+          eventStream: state.eventStream && { ...state.eventStream, reconnecting: null },
+          error: stream ? streamError : null,
         })),
       }));
 
+      if (stream) {
+        useHistoryStore.getState().logEntry({
+          method: tab.state.method,
+          url: tab.state.url,
+          headers: serializeHeaders(tab.state.headers),
+          params: serializeParams(tab.state.params),
+          body: historyBody(tab.state),
+          auth: serializeAuth(tab.state.auth),
+          ...streamHistoryFields(stream, Date.now() - startedAt),
+          error: streamError,
+          saved_request_id: tab.savedRequestId,
+        }).catch(() => {});
+        return;
+      }
+
       useHistoryStore.getState().logEntry({
         method: tab.state.method,
         url: tab.state.url,
         headers: serializeHeaders(tab.state.headers),
         params: serializeParams(tab.state.params),
-        body: serializeBodyJson(tab.state.bodyConfig),
+        body: historyBody(tab.state),
         auth: serializeAuth(tab.state.auth),
         response_status: response.status,
         response_status_text: response.status_text,
         response_headers: JSON.stringify(response.headers),
-        response_body: response.body,
+        response_body: response.saved_to ? null : response.body,
         response_time_ms: response.time_ms,
         response_size_bytes: response.size_bytes,
+        response_wire_size_bytes: response.wire_size_bytes,
+        timing_dns_ms: response.timings.dns_ms,
+        timing_connect_ms: response.timings.connect_ms,
+        timing_tls_ms: response.timings.tls_ms,
+        timing_ttfb_ms: response.timings.ttfb_ms,
+        timing_download_ms: response.timings.download_ms,
+        response_body_encoding: response.body_encoding,
+        response_body_raw: response.body_raw ?? null,
+        response_mime_type: response.mime_type,
+        response_charset: response.charset,
+        response_url: response.url,
+        redirect_chain: JSON.stringify(response.redirects),
+        retry_attempts: response.attempts.length ? JSON.stringify(response.attempts) : null,
+        http_version: response.connection?.http_version ?? null,
+        remote_addr: response.connection?.remote_addr ?? null,
+        tls_version: response.connection?.tls_version ?? null,
+        tls_resumed: response.connection?.tls_resumed ?? false,
+        peer_certificate: response.connection?.peer_certificate
+          ? JSON.stringify(response.connection.peer_certificate)
+          : null,
+        certificate_chain: response.connection?.certificate_chain.length
+          ? JSON.stringify(response.connection.certificate_chain)
+          : null,
         error: null,
         saved_request_id: tab.savedRequestId,
       }).catch(() => {});
     } catch (err) {
       const errorMsg = err instanceof Error ? err.message : String(err);
       set((s) => ({
-        tabs: updateActiveRequestTab(s.tabs, s.activeTabId, () => ({
+        tabs: updateActiveRequestTab(s.tabs, tab.id, (state) => ({
           error: errorMsg,
           loading: false,
+          requestId: null,
+          downloadProgress: null,
+          eventStream: state.eventStream && { ...state.eventStream, reconnecting: null },
         })),
       }));
 
+      // Reconnects that kept failing still leave the events received before them
+      const stream = live.stream;
       useHistoryStore.getState().logEntry({
         method: tab.state.method,
         url: tab.state.url,
         headers: serializeHeaders(tab.state.headers),
         params: serializeParams(tab.state.params),
-        body: serializeBodyJson(tab.state.bodyConfig),
+        body: historyBody(tab.state),
         auth: serializeAuth(tab.state.auth),
         response_status: null,
         response_status_text: null,
@@ -548,12 +955,19 @@ export const useTabStore = create<TabState>((set, get) => ({
         response_body: null,
         response_time_ms: null,
         response_size_bytes: null,
+        ...(stream ? streamHistoryFields(stream, Date.now() - startedAt) : {}),
         error: errorMsg,
         saved_request_id: tab.savedRequestId,
       }).catch(() => {});
     }
   },
 
+  cancelRequest: async () => {
+    const tab = get().getActiveTab();
+    if (!tab || !isRequestTab(tab) || !tab.state.requestId) return;
+    await invoke("cancel_request", { requestId: tab.state.requestId });
+  },
+
   linkTabToSaved: (tabId, savedRequestId, title) => {
     set((s) => ({
       tabs: s.tabs.map((t) =>
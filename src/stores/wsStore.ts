@@ -0,0 +1,235 @@
+import { create } from "zustand";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import type { HeaderEntry, RequestAuth } from "@/types/request";
+import type {
+  WsClose,
+  WsClosedEvent,
+  WsConnected,
+  WsHandshake,
+  WsMessage,
+  WsMessageEvent,
+} from "@/types/websocket";
+import type { TabRequestState } from "@/stores/tabStore";
+import { buildUrlWithParams, injectAuth } from "@/lib/request-utils";
+import { resolveRequest, resolveHeaders } from "@/lib/variables";
+import { buildHttpConfig } from "@/lib/http-config";
+import { useEnvironmentStore } from "@/stores/environmentStore";
+
+/** Older messages are dropped from the live log; the transcript keeps them all. */
+const MAX_LIVE_MESSAGES = 5000;
+
+export type WsStatus = "connecting" | "open" | "closing" | "closed";
+
+export interface WsConnection {
+  status: WsStatus;
+  sessionId: string | null;
+  messages: WsMessage[];
+  handshake: WsHandshake | null;
+  close: WsClose | null;
+  error: string | null;
+}
+
+interface WsState {
+  /** Keyed by tab ID; a tab has at most one connection. */
+  connections: Record<string, WsConnection>;
+
+  connect: (
+    tabId: string,
+    state: TabRequestState,
+    savedRequestId: string | null,
+    resolveAuth?: () => RequestAuth,
+    variableScope?: Map<string, string>,
+    defaultHeaders?: HeaderEntry[][],
+  ) => Promise<void>;
+  send: (tabId: string, data: string, binary?: boolean) => Promise<void>;
+  disconnect: (tabId: string, code?: number, reason?: string) => Promise<void>;
+  clearMessages: (tabId: string) => void;
+  /** Closes the tab's connection and forgets it, for when the tab goes away. */
+  release: (tabId: string) => void;
+}
+
+function appendMessage(messages: WsMessage[], message: WsMessage): WsMessage[] {
+  const next = [...messages, message];
+  return next.length > MAX_LIVE_MESSAGES ? next.slice(-MAX_LIVE_MESSAGES) : next;
+}
+
+// Frames can arrive before `ws_connect` returns the session ID
+const early = new Map<string, { messages: WsMessage[]; close: WsClose | null }>();
+
+// Sessions of closed tabs, whose remaining events are ignored
+const released = new Set<string>();
+
+let listening: Promise<void> | null = null;
+
+function ensureListening(): Promise<void> {
+  listening ??= Promise.all([
+    listen<WsMessageEvent>("ws-message", (e) => {
+      const { session_id, ...message } = e.payload;
+      if (released.has(session_id)) return;
+      const tabId = tabForSession(session_id);
+      if (!tabId) {
+        const pending = early.get(session_id) ?? { messages: [], close: null };
+        pending.messages.push(message);
+        early.set(session_id, pending);
+        return;
+      }
+      updateConnection(tabId, (c) => ({ messages: appendMessage(c.messages, message) }));
+    }),
+    listen<WsClosedEvent>("ws-closed", (e) => {
+      const { session_id, ...close } = e.payload;
+      if (released.delete(session_id)) return;
+      const tabId = tabForSession(session_id);
+      if (!tabId) {
+        const pending = early.get(session_id) ?? { messages: [], close: null };
+        pending.close = close;
+        early.set(session_id, pending);
+        return;
+      }
+      updateConnection(tabId, () => ({ status: "closed", close, error: close.error }));
+    }),
+  ]).then(() => {});
+  return listening;
+}
+
+function tabForSession(sessionId: string): string | undefined {
+  const { connections } = useWsStore.getState();
+  return Object.keys(connections).find((id) => connections[id].sessionId === sessionId);
+}
+
+function updateConnection(
+  tabId: string,
+  updater: (connection: WsConnection) => Partial<WsConnection>,
+) {
+  useWsStore.setState((s) => {
+    const connection = s.connections[tabId];
+    if (!connection) return s;
+    return {
+      connections: {
+        ...s.connections,
+        [tabId]: { ...connection, ...updater(connection) },
+      },
+    };
+  });
+}
+
+export const useWsStore = create<WsState>((set, get) => ({
+  connections: {},
+
+  connect: async (tabId, state, savedRequestId, resolveAuth, variableScope, defaultHeaders = []) => {
+    const existing = get().connections[tabId];
+    if (existing && existing.status !== "closed") return;
+
+    const stateWithAuth = resolveAuth ? { ...state, auth: resolveAuth() } : state;
+    const resolved = variableScope
+      ? resolveRequest(stateWithAuth, variableScope)
+      : stateWithAuth;
+
+    set((s) => ({
+      connections: {
+        ...s.connections,
+        [tabId]: {
+          status: "connecting",
+          sessionId: null,
+          messages: [],
+          handshake: null,
+          close: null,
+          error: null,
+        },
+      },
+    }));
+
+    if (!resolved.url.trim()) {
+      updateConnection(tabId, () => ({ status: "closed", error: "URL is required" }));
+      return;
+    }
+
+    try {
+      await ensureListening();
+      const injected = injectAuth(resolved.headers, resolved.params, resolved.auth);
+      const { session, handshake } = await invoke<WsConnected>("ws_connect", {
+        request: {
+          url: buildUrlWithParams(resolved.url.trim(), injected.params),
+          default_headers: defaultHeaders.map((layer) =>
+            (variableScope ? resolveHeaders(layer, variableScope) : layer).filter(
+              (h) => h.enabled && h.key,
+            ),
+          ),
+          headers: injected.headers.filter((h) => h.enabled && h.key),
+          subprotocols: (resolved.subprotocols ?? []).map((p) => p.trim()).filter(Boolean),
+        },
+        config: buildHttpConfig(variableScope),
+        environmentId: useEnvironmentStore.getState().activeEnvironmentId ?? "global",
+        savedRequestId,
+      });
+
+      const pending = early.get(session.id);
+      early.delete(session.id);
+      if (!get().connections[tabId]) {
+        // The tab closed while the handshake was in flight
+        if (!pending?.close) {
+          released.add(session.id);
+          invoke("ws_close", { sessionId: session.id, code: null, reason: null }).catch(() => {});
+        }
+        return;
+      }
+      updateConnection(tabId, (c) => ({
+        status: pending?.close ? "closed" : "open",
+        sessionId: session.id,
+        handshake,
+        messages: pending ? [...c.messages, ...pending.messages] : c.messages,
+        close: pending?.close ?? null,
+        error: pending?.close?.error ?? null,
+      }));
+    } catch (err) {
+      updateConnection(tabId, () => ({
+        status: "closed",
+        error: err instanceof Error ? err.message : String(err),
+      }));
+    }
+  },
+
+  send: async (tabId, data, binary = false) => {
+    const connection = get().connections[tabId];
+    if (!connection?.sessionId || connection.status !== "open") return;
+    await invoke("ws_send", { sessionId: connection.sessionId, data, binary });
+  },
+
+  disconnect: async (tabId, code, reason) => {
+    const connection = get().connections[tabId];
+    if (!connection?.sessionId || connection.status !== "open") return;
+    updateConnection(tabId, () => ({ status: "closing" }));
+    try {
+      await invoke("ws_close", {
+        sessionId: connection.sessionId,
+        code: code ?? null,
+        reason: reason ?? null,
+      });
+    } catch (err) {
+      updateConnection(tabId, () => ({
+        status: "open",
+        error: err instanceof Error ? err.message : String(err),
+      }));
+    }
+  },
+
+  clearMessages: (tabId) => {
+    updateConnection(tabId, () => ({ messages: [] }));
+  },
+
+  release: (tabId) => {
+    const connection = get().connections[tabId];
+    if (!connection) return;
+    if (connection.sessionId && connection.status !== "closed") {
+      released.add(connection.sessionId);
+      invoke("ws_close", { sessionId: connection.sessionId, code: null, reason: null }).catch(
+        () => {},
+      );
+    }
+    set((s) => {
+      const connections = { ...s.connections };
+      delete connections[tabId];
+      return { connections };
+    });
+  },
+}));
@@ -6,6 +6,9 @@ import type {
   RequestAuth,
 } from "./request";
 import type { Variable } from "./environment";
+import type { GraphqlRequest } from "./graphql";
+import type { GrpcRequest } from "./grpc";
+import type { JsonRpcCall } from "./jsonrpc";
 
 export interface Collection {
   id: string;
@@ -38,6 +41,12 @@ export interface Folder {
   dirty: number;
 }
 
+/**
+ * What a saved request opens as: an HTTP request, an event stream, a
+ * WebSocket session, a GraphQL operation, a gRPC call or JSON-RPC calls.
+ */
+export type RequestKind = "http" | "sse" | "websocket" | "graphql" | "grpc" | "jsonrpc";
+
 export interface SavedRequest {
   id: string;
   collectionId: string;
@@ -56,6 +65,12 @@ export interface SavedRequest {
   cloudId: string | null;
   syncedAt: string | null;
   dirty: number;
+  kind: RequestKind;
+  /** WebSocket subprotocols to offer, in order of preference. */
+  subprotocols: string[];
+  graphql: GraphqlRequest;
+  grpc: GrpcRequest;
+  jsonrpc: JsonRpcCall[];
 }
 
 export interface Workspace {
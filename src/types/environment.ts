@@ -4,12 +4,36 @@ export interface Variable {
   secret?: boolean;
 }
 
+/** Like curl's `--resolve`: requests to `host` connect to `address` instead. */
+export interface DnsOverride {
+  /** `api.example.com`, `*.example.com`, optionally with `:port`. */
+  host: string;
+  /** One or more comma-separated IP addresses. */
+  address: string;
+}
+
 export interface Environment {
   id: string;
   name: string;
   variables: Variable[];
+  dnsOverrides: DnsOverride[];
   isGlobal: boolean;
   sortOrder: number;
   updatedAt: string;
   createdAt: string;
 }
+
+export interface Cookie {
+  id: string;
+  environment_id: string;
+  domain: string;
+  path: string;
+  name: string;
+  value: string;
+  expires_at: number | null;
+  secure: boolean;
+  http_only: boolean;
+  host_only: boolean;
+  same_site: string | null;
+  created_at: string;
+}
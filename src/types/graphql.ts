@@ -0,0 +1,30 @@
+/** A GraphQL operation, kept apart from the JSON body it is sent as. */
+export interface GraphqlRequest {
+  query: string;
+  /** A JSON object, as typed; may reference environment variables. */
+  variables: string;
+  /** Which operation to run when the document defines several. */
+  operationName: string;
+}
+
+export interface GraphqlSchemaInfo {
+  endpoint: string;
+  fetched_at: string;
+  query_type: string | null;
+  mutation_type: string | null;
+  subscription_type: string | null;
+  type_count: number;
+}
+
+/** A syntax or validation error, located in the query text. */
+export interface GraphqlError {
+  message: string;
+  line: number;
+  column: number;
+}
+
+export interface GraphqlValidation {
+  /** Without a cached schema only the syntax is checked. */
+  schema_cached: boolean;
+  errors: GraphqlError[];
+}
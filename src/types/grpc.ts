@@ -0,0 +1,70 @@
+import type { ResponseHeader } from "./request";
+
+/** Where a request's service definitions come from. */
+export type GrpcDescriptorSourceKind = "reflection" | "proto_files";
+
+/** A gRPC call: the method to invoke and the message to send it. */
+export interface GrpcRequest {
+  /** Fully qualified, e.g. `helloworld.Greeter`. */
+  service: string;
+  method: string;
+  /** The request message in its JSON mapping; may reference environment variables. */
+  message: string;
+  source: GrpcDescriptorSourceKind;
+  /** `.proto` files to load when the source is `proto_files`. */
+  protoFiles: string[];
+  /** Directories imports are resolved against, before the files' own. */
+  importPaths: string[];
+}
+
+export interface GrpcMethodInfo {
+  name: string;
+  /** `package.Service/Method`. */
+  full_name: string;
+  input_type: string;
+  output_type: string;
+  client_streaming: boolean;
+  server_streaming: boolean;
+  /** A JSON input message with every field at its default. */
+  template: string;
+}
+
+export interface GrpcServiceInfo {
+  name: string;
+  methods: GrpcMethodInfo[];
+}
+
+export interface GrpcStatus {
+  code: number;
+  /** `OK`, `NOT_FOUND`, ... */
+  name: string;
+  message: string;
+}
+
+export type GrpcDirection = "sent" | "received";
+
+export interface GrpcMessage {
+  direction: GrpcDirection;
+  /** The message in its JSON mapping. */
+  data: string;
+  /** Encoded size, without the frame prefix. */
+  size_bytes: number;
+  /** Milliseconds since the call started. */
+  at_ms: number;
+}
+
+export interface GrpcMessageEvent extends GrpcMessage {
+  call_id: string;
+}
+
+export interface GrpcHeadersEvent {
+  call_id: string;
+  headers: ResponseHeader[];
+}
+
+export interface GrpcClosedEvent {
+  call_id: string;
+  status: GrpcStatus;
+  trailers: ResponseHeader[];
+  time_ms: number;
+}
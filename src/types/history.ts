@@ -4,7 +4,13 @@ import type {
   ParamEntry,
   RequestBody,
   RequestAuth,
+  ResponseHeader,
+  BodyEncoding,
+  RedirectHop,
+  RetryAttempt,
 } from "./request";
+import type { SseEvent } from "./sse";
+import type { CertificateSummary } from "./tls";
 
 export interface HistoryEntry {
   id: string;
@@ -16,12 +22,35 @@ export interface HistoryEntry {
   auth: RequestAuth;
   responseStatus: number | null;
   responseStatusText: string | null;
-  responseHeaders: Record<string, string> | null;
+  responseHeaders: ResponseHeader[] | null;
   responseBody: string | null;
   responseTimeMs: number | null;
   responseSizeBytes: number | null;
+  responseWireSizeBytes: number | null;
   responseBodyTruncated: boolean;
   error: string | null;
+  cancelled: boolean;
+  timingDnsMs: number | null;
+  timingConnectMs: number | null;
+  timingTlsMs: number | null;
+  timingTtfbMs: number | null;
+  timingDownloadMs: number | null;
+  responseBodyEncoding: BodyEncoding;
+  responseBodyRaw: string | null;
+  responseMimeType: string | null;
+  responseCharset: string | null;
+  responseUrl: string | null;
+  redirectChain: RedirectHop[] | null;
+  httpVersion: string | null;
+  remoteAddr: string | null;
+  tlsVersion: string | null;
+  tlsResumed: boolean;
+  peerCertificate: CertificateSummary | null;
+  certificateChain: CertificateSummary[] | null;
+  certificateExpiresAt: string | null;
+  retryAttempts: RetryAttempt[] | null;
+  /** Events received, when the response was an event stream. */
+  sseEvents: SseEvent[] | null;
   savedRequestId: string | null;
   timestamp: string;
 }
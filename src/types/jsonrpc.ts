@@ -0,0 +1,38 @@
+/** One call of a JSON-RPC request; several are sent together as a batch. */
+export interface JsonRpcCall {
+  method: string;
+  /** A JSON array or object, as typed; may reference environment variables. Blank sends none. */
+  params: string;
+  /** Sent without an ID, so the server does not answer it. */
+  notification: boolean;
+  enabled: boolean;
+}
+
+/** A call as it was sent, kept to match the response against. */
+export interface JsonRpcSentCall {
+  /** `null` for notifications. */
+  id: number | null;
+  method: string;
+}
+
+export interface JsonRpcError {
+  code: number;
+  message: string;
+  data?: unknown;
+}
+
+/** What became of one sent call. */
+export type JsonRpcOutcome =
+  | { kind: "result"; call: JsonRpcSentCall; result: unknown }
+  | { kind: "error"; call: JsonRpcSentCall; error: JsonRpcError }
+  /** The server sent nothing back for this ID. */
+  | { kind: "missing"; call: JsonRpcSentCall }
+  | { kind: "notification"; call: JsonRpcSentCall };
+
+export interface JsonRpcMatch {
+  outcomes: JsonRpcOutcome[];
+  /** Responses no call was waiting for, such as an error with a `null` ID. */
+  unmatched: { id: unknown; error: JsonRpcError | null; result?: unknown }[];
+  /** Why the body could not be read as JSON-RPC at all. */
+  problem: string | null;
+}
@@ -1,9 +1,19 @@
+import type { CertificateSummary } from "./tls";
+import type { EventStreamEnd } from "./sse";
+
 export type HttpMethod = "GET" | "POST" | "PUT" | "PATCH" | "DELETE" | "HEAD" | "OPTIONS";
 
+/**
+ * How a header combines with earlier values of the same name. When unset,
+ * the first occurrence replaces inherited defaults and repeats append.
+ */
+export type HeaderMode = "append" | "replace";
+
 export interface HeaderEntry {
   key: string;
   value: string;
   enabled: boolean;
+  mode?: HeaderMode;
 }
 
 export interface ParamEntry {
@@ -12,26 +22,150 @@ export interface ParamEntry {
   enabled: boolean;
 }
 
+/** A form-data field; setting `filePath` makes it a file part. */
+export interface FormDataEntry extends ParamEntry {
+  filePath?: string;
+  fileName?: string;
+  contentType?: string;
+}
+
 export type RawFormat = "json" | "xml" | "text";
 
 export type RequestBody =
   | { type: "none" }
   | { type: "raw"; format: RawFormat; content: string }
-  | { type: "form-data"; entries: ParamEntry[] }
+  | { type: "form-data"; entries: FormDataEntry[] }
   | { type: "x-www-form-urlencoded"; entries: ParamEntry[] }
   | { type: "binary"; filePath: string };
 
+/** Request body as sent to `send_request`. */
+export type SendBody =
+  | { type: "text"; content: string }
+  | { type: "file"; path: string; content_type?: string | null }
+  | { type: "multipart"; parts: FormPart[] };
+
+export type FormPart =
+  | { kind: "text"; name: string; value: string; content_type?: string | null }
+  | {
+      kind: "file";
+      name: string;
+      path: string;
+      filename?: string | null;
+      content_type?: string | null;
+    };
+
 export type RequestAuth =
   | { type: "none" }
   | { type: "bearer"; token: string }
   | { type: "basic"; username: string; password: string }
   | { type: "apikey"; key: string; value: string; addTo: "header" | "query" };
 
+export interface TimingBreakdown {
+  dns_ms: number | null;
+  connect_ms: number | null;
+  /** TLS handshake; null when reused or over plain HTTP. */
+  tls_ms: number | null;
+  ttfb_ms: number;
+  download_ms: number;
+}
+
+export interface ResponseHeader {
+  name: string;
+  value: string;
+  /** Original bytes when the value was not valid UTF-8. */
+  raw?: number[];
+}
+
+export type BodyEncoding = "text" | "base64";
+
+/** An intermediate 3xx response that was followed. */
+export interface RedirectHop {
+  url: string;
+  status: number;
+  status_text: string;
+  headers: ResponseHeader[];
+  time_ms: number;
+  timings: TimingBreakdown;
+}
+
+export interface ConnectionInfo {
+  /** "HTTP/1.1", "HTTP/2.0", ... */
+  http_version: string;
+  remote_addr: string | null;
+  /** Whether an idle pooled connection carried the request. */
+  reused: boolean;
+  /** Every address the host resolved to; empty when reused or an IP literal. */
+  resolved_addresses: string[];
+  /** Whether the addresses came from an environment's DNS override. */
+  dns_override: boolean;
+  /** The Unix domain socket an `http+unix://` request went over. */
+  unix_socket: string | null;
+  /** "TLSv1.3" or "TLSv1.2", for https on a new, fully negotiated connection. */
+  tls_version: string | null;
+  /** Whether the TLS session was resumed, so only the leaf certificate is known. */
+  tls_resumed: boolean;
+  /** The server's leaf certificate, for https. */
+  peer_certificate: CertificateSummary | null;
+  /** The rest of the chain the server sent, nearest issuer first. */
+  certificate_chain: CertificateSummary[];
+}
+
+/** A failed attempt that was retried. */
+export interface RetryAttempt {
+  url: string;
+  /** The retried status; null when no response arrived. */
+  status: number | null;
+  status_text: string;
+  /** Why no response arrived. */
+  error: string | null;
+  time_ms: number;
+  /** How long was waited before the next attempt. */
+  delay_ms: number;
+}
+
 export interface HttpResponse {
   status: number;
   status_text: string;
-  headers: Record<string, string>;
+  /** Final URL after any redirects. */
+  url: string;
+  headers: ResponseHeader[];
+  /** Decoded text, or base64 when `body_encoding` is "base64". */
   body: string;
+  body_encoding: BodyEncoding;
+  /** Base64 of the original bytes when `body` was transcoded from another charset or had invalid bytes replaced. */
+  body_raw?: string;
+  mime_type: string | null;
+  charset: string | null;
+  /** Content codings removed from the body, e.g. "gzip". */
+  content_encoding?: string;
+  /** Exactly the bytes received, as base64, present only when they were decompressed. */
+  wire_body?: string;
+  /** Why a compressed body could not be decoded and was returned as received. */
+  decompress_error?: string;
+  /** Set when the body was streamed to this path instead of returned. */
+  saved_to?: string;
+  /** Set when the body was read as an event stream; its events arrived as `sse-update`s. */
+  event_stream?: EventStreamEnd;
   time_ms: number;
+  /** Size of the body after decompression. */
   size_bytes: number;
+  /** Size of the body as received on the wire. */
+  wire_size_bytes: number;
+  request_size_bytes: number;
+  timings: TimingBreakdown;
+  redirects: RedirectHop[];
+  /** Failed attempts retried before this response, in order. */
+  attempts: RetryAttempt[];
+  /** Missing on responses restored from history saved before it was recorded. */
+  connection?: ConnectionInfo;
 }
+
+export interface DownloadProgress {
+  request_id: string;
+  downloaded_bytes: number;
+  total_bytes: number | null;
+}
+
+export type SendOutcome =
+  | ({ outcome: "completed" } & HttpResponse)
+  | { outcome: "cancelled" };
@@ -0,0 +1,56 @@
+import type { ResponseHeader } from "./request";
+
+export interface SseEvent {
+  /** The last event ID seen so far, as a browser's `lastEventId`. */
+  id: string | null;
+  /** "message" unless the server named it. */
+  event: string;
+  data: string;
+  /** Reconnection delay set in this event's block, if any. */
+  retry: number | null;
+  /** Milliseconds since the first connection was opened. */
+  at_ms: number;
+}
+
+export type StreamUpdate =
+  | {
+      type: "open";
+      status: number;
+      status_text: string;
+      headers: ResponseHeader[];
+      http_version: string;
+    }
+  | ({ type: "event" } & SseEvent)
+  | {
+      type: "reconnecting";
+      attempt: number;
+      delay_ms: number;
+      /** Why the previous connection ended, if it did not end cleanly. */
+      error: string | null;
+      last_event_id: string | null;
+    };
+
+export type SseUpdate = { request_id: string } & StreamUpdate;
+
+/** How an event stream response ended. */
+export interface EventStreamEnd {
+  /** Events dispatched on that connection. */
+  events: number;
+  error: string | null;
+}
+
+/** What an event stream tab shows while it runs and after it ends. */
+export interface EventStreamState {
+  status: number;
+  status_text: string;
+  headers: ResponseHeader[];
+  http_version: string;
+  /** Every connection's events, oldest first. */
+  events: SseEvent[];
+  /** Times the stream was reopened. */
+  reconnects: number;
+  /** Set while waiting to reconnect. */
+  reconnecting: { attempt: number; delay_ms: number; error: string | null } | null;
+  /** Events dropped from the front to stay under the cap. */
+  dropped: number;
+}
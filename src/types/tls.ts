@@ -0,0 +1,44 @@
+export type ClientCertificateKind = "pem" | "pkcs12";
+
+export interface ClientCertificate {
+  id: string;
+  host_pattern: string;
+  kind: ClientCertificateKind;
+  cert_path: string;
+  key_path: string | null;
+  passphrase: string | null;
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface CaCertificate {
+  id: string;
+  /** `null` trusts the CA for every host. */
+  host_pattern: string | null;
+  path: string;
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface CertificatePin {
+  id: string;
+  host_pattern: string;
+  /** Base64 SHA-256 of the server's SubjectPublicKeyInfo. */
+  spki_sha256: string;
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface CertificateSummary {
+  subject: string;
+  issuer: string;
+  subject_alt_names: string[];
+  /** RFC 3339. */
+  not_before: string;
+  /** RFC 3339. */
+  not_after: string;
+  serial: string;
+  sha256_fingerprint: string;
+  /** Base64 SPKI hash, usable as a certificate pin. */
+  spki_sha256: string;
+}
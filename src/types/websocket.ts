@@ -0,0 +1,57 @@
+import type { ResponseHeader } from "./request";
+
+export type WsDirection = "sent" | "received";
+export type WsMessageKind = "text" | "binary" | "ping" | "pong" | "close";
+
+export interface WsMessage {
+  direction: WsDirection;
+  kind: WsMessageKind;
+  /** Text and close frames as text ("1000 reason"), the rest as base64. */
+  data: string;
+  size_bytes: number;
+  /** Milliseconds since the connection opened. */
+  at_ms: number;
+  /** Only stored messages over 1MB are cut short. */
+  truncated?: boolean;
+}
+
+export interface WsMessageEvent extends WsMessage {
+  session_id: string;
+}
+
+export interface WsClose {
+  code: number | null;
+  reason: string;
+  /** Why the connection ended without a clean close handshake. */
+  error: string | null;
+}
+
+export interface WsClosedEvent extends WsClose {
+  session_id: string;
+}
+
+export interface WsHandshake {
+  headers: ResponseHeader[];
+  protocol: string | null;
+  time_ms: number;
+}
+
+export interface WsSession {
+  id: string;
+  url: string;
+  /** JSON array of the subprotocols offered. */
+  subprotocols: string;
+  protocol: string | null;
+  saved_request_id: string | null;
+  error: string | null;
+  close_code: number | null;
+  close_reason: string | null;
+  opened_at: string;
+  closed_at: string | null;
+  message_count: number;
+}
+
+export interface WsConnected {
+  session: WsSession;
+  handshake: WsHandshake;
+}
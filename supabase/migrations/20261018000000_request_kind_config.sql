@@ -0,0 +1,5 @@
+-- Request kinds (HTTP, WebSocket, GraphQL, gRPC, JSON-RPC) and the settings
+-- only those kinds use, as one object per request so a new kind needs no
+-- schema change. Rows without it sync as they always have, and the app only
+-- sends it once this column exists.
+alter table public.requests add column if not exists kind_config jsonb;